@@ -0,0 +1,8 @@
+class_name QuoteFact
+extends Fact
+
+
+var author: String
+var source: Fact
+var _cached_length: int
+var on_quoted: Callable
@@ -7,6 +7,120 @@ func _ready():
 
 func run_tests():
 	test_other_types()
+	test_nested_arrays()
+	
+	# Web exports build with `wasm_compat`, which creates typed arrays without raw FFI calls.
+	print("Testing typed arrays%s" % (" (web export fallback)" if OS.has_feature("web") else ""))
+	test_typed_arrays()
+	
+	print("Testing fixed-length arrays round trip")
+	test_fixed_arrays()
+	test_compact_math()
+	test_hex_colors()
+	test_friendly_transforms()
+	test_euler_quaternions()
+	test_schema_hash()
+	test_backward_compatibility()
+	test_migrations()
+	test_save_manager()
+	test_json_patch()
+	test_merge_patch()
+	test_diff()
+	test_pointers()
+	test_value_keywords()
+	test_script_defaults()
+	test_example_json()
+	test_unique_items()
+	test_unique_placeholders()
+	test_self_test()
+	test_self_test_constraints()
+	test_random_shapes()
+	test_validation_only()
+	test_message_registry()
+	test_repair_prompt()
+	test_lenient_repair()
+	test_prompt_text()
+	test_minified_json()
+	test_schema_id()
+	test_shared_definitions()
+	test_schema_bundle()
+	test_class_inheritance()
+	test_private_properties()
+	test_class_bundle()
+	test_property_naming()
+	test_property_alias()
+	test_unwrapped_root()
+	test_streaming()
+	test_dictionary_schema()
+	test_gbnf()
+	test_schema_dict()
+	test_introspection()
+	test_dependency_graph()
+	test_headless_export()
+	test_custom_format()
+	test_custom_keyword()
+	test_validator_options()
+	test_log_level()
+	test_generation_report()
+	test_unsupported_property_policy()
+	test_validator_backends()
+	test_ollama_format()
+	test_validator_release()
+	test_method_tools()
+	test_schema_console()
+	test_tool_catalog()
+	test_confidence()
+	test_scene_serializer()
+	test_dictionary_input()
+	test_finetune_examples()
+	test_instantiate_array()
+	test_output_evaluator()
+	test_array_length_constant()
+	test_schema_result()
+	test_error_codes()
+	test_schema_errors()
+	test_failure_hook()
+	test_library_management()
+	test_project_schemas()
+	test_instantiation_plan()
+	await test_async_generation()
+	test_undo_record()
+	test_strict_numbers()
+	test_library_file()
+	test_verify_all()
+	test_export_schemas()
+	test_variant_encoding()
+	test_json_schema_import()
+	test_round_trip_annotations()
+	test_type_annotations()
+	test_json_hooks()
+	test_init_parameters()
+	test_setter_check()
+	test_resource_paths()
+	test_scene_paths()
+	test_instantiate_resource()
+	test_infer_from_value()
+	test_shapes()
+	test_property_editing()
+	test_property_projections()
+	test_merge_schemas()
+	test_schema_config()
+	test_exclude_constant()
+	test_usage_filter()
+	test_property_order()
+	test_global_enums()
+	test_enum_variant_descriptions()
+	test_integer_enums()
+	test_array_constraints()
+	test_global_unsupported_policy()
+	test_untyped_properties()
+	await test_reload_signal()
+	test_breaking_changes()
+	test_conditional_schemas()
+	test_llm_client()
+	test_schema_preview()
+	await test_async_failure()
+	await test_hot_reload()
 	
 	# Run this first since the second is a coroutine, otherwise the logs will mix.
 	print("Testing person class schema round trip")
@@ -44,6 +158,57 @@ func test_other_types():
 	test_type_info(TYPE_ARRAY, &"", PROPERTY_HINT_ARRAY_TYPE, "Fact")
 
 
+func test_nested_arrays():
+	var hint_string := "%d/%d:int" % [TYPE_ARRAY, PROPERTY_HINT_ARRAY_TYPE]
+	var schema: GodotSchema = GodotSchema.from_type_info(TYPE_ARRAY, &"", PROPERTY_HINT_ARRAY_TYPE, hint_string, PROPERTY_USAGE_NONE)
+	var result = schema.instantiate('{"value": [[1, 2], [3]]}')
+	
+	if result is String:
+		printerr(result)
+		return
+	
+	if result[0].get_typed_builtin() != TYPE_INT:
+		printerr("Expected the rows of a nested array to be Array[int], got: %s" % type_string(result[0].get_typed_builtin()))
+	
+	if not schema.instantiate('{"value": [[1, "two"]]}') is String:
+		printerr("Expected a string in an Array[Array[int]] to be rejected.")
+
+
+func test_typed_arrays():
+	var builtin_types := {
+		"int": [TYPE_INT, "[1, 2]"],
+		"float": [TYPE_FLOAT, "[0.5]"],
+		"String": [TYPE_STRING, "[\"a\"]"],
+		"Dictionary": [TYPE_DICTIONARY, "[{}]"],
+		"Vector2": [TYPE_VECTOR2, "[{\"x\": 1, \"y\": 2}]"],
+	}
+	
+	for type_name in builtin_types:
+		var expected: Variant.Type = builtin_types[type_name][0]
+		var schema: GodotSchema = GodotSchema.from_type_info(TYPE_ARRAY, &"", PROPERTY_HINT_ARRAY_TYPE, type_name, PROPERTY_USAGE_NONE)
+		var result = schema.instantiate('{"value": %s}' % builtin_types[type_name][1])
+		
+		if result is String:
+			printerr("Expected an Array[%s] to instantiate. Got: %s" % [type_name, result])
+		elif result.get_typed_builtin() != expected:
+			printerr("Expected an Array[%s], got: Array[%s]" % [type_name, type_string(result.get_typed_builtin())])
+	
+	var facts = GodotSchema.from_type_info(TYPE_ARRAY, &"", PROPERTY_HINT_ARRAY_TYPE, "Fact", PROPERTY_USAGE_NONE) \
+		.instantiate('{"value": [{"text": "a", "salient_word": "b", "is_password_related": true}]}')
+	
+	if facts is String or facts.size() != 1 or not facts[0] is Fact:
+		printerr("Expected an array of Fact to instantiate. Got: %s" % [facts])
+	
+	# Arrays assigned to class properties keep the property's element type, with or without the fallback.
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Person")
+	var person = schema.instantiate(schema.example_json(false))
+	
+	if not person is Person:
+		printerr("Expected the Person example to instantiate. Got: %s" % [person])
+	elif person.facts.get_typed_script() != Fact or person.facts.is_empty() or not person.facts[0] is Fact:
+		printerr("Expected Person.facts to stay an Array[Fact]. Got: %s" % [person.facts])
+
+
 func test_type_info(
 	variant_type: Variant.Type, 
 	_class_name: StringName = "", 
@@ -56,6 +221,2246 @@ func test_type_info(
 		printerr(result)
 
 
+# Basis and Projection are objects of fixed-length arrays (tuples), which must reject any other length.
+func test_fixed_arrays():
+	test_fixed_array_round_trip(TYPE_BASIS, Basis(Vector3(1, 2, 3), Vector3(4, 5, 6), Vector3(7, 8, 9)), "rows")
+	test_fixed_array_round_trip(TYPE_PROJECTION, Projection(Vector4(1, 2, 3, 4), Vector4(5, 6, 7, 8), Vector4(9, 10, 11, 12), Vector4(13, 14, 15, 16)), "cols")
+
+
+func test_fixed_array_round_trip(variant_type: Variant.Type, value: Variant, tuple_key: String):
+	var schema_res = GodotSchema.from_type_info(variant_type, &"", PROPERTY_HINT_NONE, "", PROPERTY_USAGE_NONE)
+	if schema_res is String:
+		printerr(schema_res)
+		return
+	
+	var schema: GodotSchema = schema_res
+	
+	if not schema.json.contains("\"prefixItems\"") or not schema.json.contains("\"minItems\""):
+		printerr("Expected tuple of %s to declare `prefixItems` and `minItems`:\n%s" % [type_string(variant_type), schema.json])
+	
+	var json = schema.serialize_instance(value)
+	var result = schema.instantiate(json)
+	
+	if result != value:
+		printerr("Round trip of %s failed. Expected: %s, got: %s" % [type_string(variant_type), value, result])
+	
+	# Drop the last element of the tuple, instantiation must fail.
+	var parsed: Dictionary = JSON.parse_string(json)
+	parsed["value"][tuple_key].pop_back()
+	
+	if not schema.instantiate(JSON.stringify(parsed)) is String:
+		printerr("Expected %s with a missing `%s` element to be rejected." % [type_string(variant_type), tuple_key])
+
+
+func test_compact_math():
+	var schema: GodotSchema = GodotSchema.from_type_info(TYPE_RECT2, &"", PROPERTY_HINT_NONE, "", PROPERTY_USAGE_NONE)
+	schema.set_compact_math(true)
+	
+	var value := Rect2(1, 2, 3, 4)
+	var json = schema.serialize_instance(value)
+	var parsed: Dictionary = JSON.parse_string(json)
+	
+	if not parsed["value"]["position"] is Array:
+		printerr("Expected compact Vector2 to be serialized as an array, got: %s" % json)
+	
+	var result = schema.instantiate(json)
+	
+	if result != value:
+		printerr("Round trip of compact Rect2 failed. Expected: %s, got: %s" % [value, result])
+
+
+func test_hex_colors():
+	var schema: GodotSchema = GodotSchema.from_type_info(TYPE_PACKED_COLOR_ARRAY, &"", PROPERTY_HINT_NONE, "", PROPERTY_USAGE_NONE)
+	schema.set_hex_colors(true)
+	
+	if not schema.json.contains("\"pattern\""):
+		printerr("Expected hex colors to declare a `pattern`:\n%s" % schema.json)
+	
+	var result = schema.instantiate('{"value": ["#ff8000", "00ff0080"]}')
+	var expected := PackedColorArray([Color.html("#ff8000"), Color.html("00ff0080")])
+	
+	if result != expected:
+		printerr("Expected hex colors to be parsed. Expected: %s, got: %s" % [expected, result])
+	
+	if not schema.instantiate('{"value": ["orange"]}') is String:
+		printerr("Expected a color that is not hex to be rejected.")
+
+
+func test_friendly_transforms():
+	var schema: GodotSchema = GodotSchema.from_type_info(TYPE_ARRAY, &"", PROPERTY_HINT_ARRAY_TYPE, "Transform3D", PROPERTY_USAGE_NONE)
+	schema.set_friendly_transforms(true)
+	
+	var result = schema.instantiate('{"value": [{"position": {"x": 1, "y": 2, "z": 3}, "rotation_degrees": {"x": 0, "y": 90, "z": 0}, "scale": {"x": 2, "y": 2, "z": 2}}]}')
+	
+	if result is String:
+		printerr(result)
+		return
+	
+	var expected := Transform3D(Basis.from_euler(Vector3(0, deg_to_rad(90), 0)) * Basis.from_scale(Vector3(2, 2, 2)), Vector3(1, 2, 3))
+	
+	if not result[0].is_equal_approx(expected):
+		printerr("Expected transform components to be converted. Expected: %s, got: %s" % [expected, result[0]])
+	
+	var round_trip = schema.instantiate(schema.serialize_instance(result))
+	
+	if round_trip is String or not round_trip[0].is_equal_approx(expected):
+		printerr("Round trip of friendly Transform3D failed. Expected: %s, got: %s" % [expected, round_trip])
+
+
+func test_euler_quaternions():
+	var schema: GodotSchema = GodotSchema.from_type_info(TYPE_ARRAY, &"", PROPERTY_HINT_ARRAY_TYPE, "Quaternion", PROPERTY_USAGE_NONE)
+	schema.set_euler_quaternions(true)
+	
+	var result = schema.instantiate('{"value": [{"yaw": 90, "pitch": 0, "roll": 0}]}')
+	
+	if result is String:
+		printerr(result)
+		return
+	
+	var expected := Quaternion.from_euler(Vector3(0, deg_to_rad(90), 0))
+	
+	if not result[0].is_equal_approx(expected):
+		printerr("Expected Euler angles to be converted. Expected: %s, got: %s" % [expected, result[0]])
+
+
+func test_schema_hash():
+	var first: GodotSchema = GodotSchema.from_type_info(TYPE_OBJECT, &"Fact", PROPERTY_HINT_NONE, "", PROPERTY_USAGE_NONE)
+	var second: GodotSchema = GodotSchema.from_type_info(TYPE_OBJECT, &"Fact", PROPERTY_HINT_NONE, "", PROPERTY_USAGE_NONE)
+	
+	if first.get_hash().length() != 64 or first.get_hash() != second.get_hash():
+		printerr("Expected identical schemas to have the same hash. Got: %s, %s" % [first.get_hash(), second.get_hash()])
+	
+	second.set_integer_enums(true)
+	
+	if first.get_hash() == second.get_hash():
+		printerr("Expected the hash to change along with the schema's JSON.")
+	
+	var ordered: GodotSchema = GodotSchema.from_shape({"name": TYPE_STRING, "hp": TYPE_INT})
+	var reordered: GodotSchema = GodotSchema.from_shape({"hp": TYPE_INT, "name": TYPE_STRING})
+	
+	if ordered.get_hash() != reordered.get_hash():
+		printerr("Expected the hash to ignore the declaration order of properties.")
+
+
+func test_backward_compatibility():
+	var old: GodotSchema = GodotSchema.from_shape({"name": TYPE_STRING, "tags": [TYPE_STRING]})
+	var constrained: GodotSchema = GodotSchema.from_shape({"name": TYPE_STRING, "tags": [TYPE_STRING]})
+	constrained.set_array_constraints("tags", 1, 3, false)
+	constrained.rebuild()
+	var changes = constrained.is_backward_compatible_with(old)
+	
+	if changes is Array and changes.any(func(change): return change["change"] == "type_changed"):
+		printerr("Expected constraint changes to not be type changes. Got: %s" % [changes])
+	
+	var person: GodotSchema = GodotSchema.from_class_name(&"Person")
+	var integer_person: GodotSchema = GodotSchema.from_class_name(&"Person")
+	integer_person.set_integer_enums(true)
+	changes = integer_person.is_backward_compatible_with(person)
+	
+	if not changes is Array or not changes.any(func(change): return change["change"] == "encoding_changed"):
+		printerr("Expected switching to integer enums to be an encoding change. Got: %s" % [changes])
+
+
+func test_migrations():
+	var schema: GodotSchema = GodotSchema.from_shape({"name": TYPE_STRING, "hp": TYPE_INT})
+	var migrator := SchemaMigrator.new()
+	migrator.register_schema(schema, 2)
+	migrator.register_migration("object", 1, 2, func(save: Dictionary):
+		save["hp"] = save["health"]
+		save.erase("health")
+		return save)
+	
+	var result = migrator.instantiate_versioned('{"$version": 1, "name": "Charlie", "health": 5}')
+	
+	if result is String:
+		printerr(result)
+		return
+	
+	if result["hp"] != 5:
+		printerr("Expected \"health\" to be migrated into \"hp\". Got: %s" % result)
+
+
+func test_save_manager():
+	var manager := SchemaSaveManager.new()
+	manager.get_migrator().register_schema(GodotSchema.from_class_name(&"Fact"), 1)
+	
+	var fact := Fact.new()
+	fact.text = "Charlie has a pet cat."
+	fact.salient_word = "cat"
+	
+	if not manager.save("user://schema_tester_save.json", {"fact": fact}):
+		printerr(manager.get_last_report())
+	
+	var loaded := manager.load("user://schema_tester_save.json")
+	
+	if not loaded.has("fact") or loaded["fact"].salient_word != "cat":
+		printerr("Expected the saved fact to be loaded. Report: %s" % manager.get_last_report())
+	
+	# Saving over an existing file replaces it through a temporary file.
+	fact.salient_word = "pet"
+	manager.save("user://schema_tester_save.json", {"fact": fact})
+	
+	if FileAccess.file_exists("user://schema_tester_save.json.tmp"):
+		printerr("Expected the temporary save file to be renamed.")
+	
+	if manager.load("user://schema_tester_save.json").get("fact").salient_word != "pet":
+		printerr("Expected the second save to replace the first. Report: %s" % manager.get_last_report())
+	
+	manager.free()
+
+
+func test_json_patch():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Fact")
+	var fact := Fact.new()
+	fact.text = "Charlie has a pet cat."
+	
+	var result = schema.apply_patch(fact, '[{"op": "replace", "path": "/salient_word", "value": "cat"}, {"op": "test", "path": "/text", "value": "Charlie has a pet cat."}]')
+	
+	if result is String:
+		printerr(result)
+	elif fact.salient_word != "cat":
+		printerr("Expected the patch to be applied. Got: %s" % fact.salient_word)
+	
+	schema.set_property_optional("salient_word", true)
+	result = schema.apply_patch(fact, '[{"op": "remove", "path": "/salient_word"}]')
+	
+	if result is String:
+		printerr(result)
+	elif fact.salient_word != "":
+		printerr("Expected removing an optional property to reset it. Got: %s" % fact.salient_word)
+
+
+func test_merge_patch():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Fact")
+	var fact := Fact.new()
+	fact.text = "Charlie has a pet cat."
+	fact.salient_word = "cat"
+	
+	var result = schema.merge_patch(fact, '{"salient_word": null, "is_password_related": true}')
+	
+	if result is String:
+		printerr(result)
+	elif fact.salient_word != "" or not fact.is_password_related or fact.text != "Charlie has a pet cat.":
+		printerr("Expected the merge patch to be applied. Got: %s, %s, %s" % [fact.text, fact.salient_word, fact.is_password_related])
+
+
+func test_diff():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Fact")
+	var fact := Fact.new()
+	fact.text = "Charlie has a pet cat."
+	
+	var diff := schema.diff(fact, '{"text": "Charlie has a pet cat.", "salient_word": "cat", "is_password_related": false}')
+	
+	if diff.keys() != ["/salient_word"] or diff["/salient_word"]["new"] != "cat":
+		printerr("Expected only \"salient_word\" to differ. Got: %s" % diff)
+
+
+func test_pointers():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Fact")
+	var fact := Fact.new()
+	fact.salient_word = "cat"
+	
+	if schema.get_at_pointer(fact, "/salient_word") != "cat":
+		printerr("Expected pointer to resolve into the instance.")
+	
+	if schema.get_at_pointer('{"text": "", "salient_word": "dog", "is_password_related": true}', "/is_password_related") != true:
+		printerr("Expected pointer to resolve into the JSON payload.")
+
+
+func test_value_keywords():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Fact")
+	schema.set_property_optional("salient_word", true)
+	schema.set_property_default("salient_word", "cat")
+	schema.set_property_const("is_password_related", false)
+	schema.rebuild()
+	
+	var fact = schema.instantiate('{"text": "Charlie has a pet cat.", "is_password_related": false}')
+	
+	if fact is String:
+		printerr(fact)
+	elif fact.salient_word != "cat":
+		printerr("Expected the absent property to take its default. Got: %s" % fact.salient_word)
+	
+	if not schema.instantiate('{"text": "", "is_password_related": true}') is String:
+		printerr("Expected a value other than the property's const to be rejected.")
+	
+	var shape: GodotSchema = GodotSchema.from_shape({"speed": TYPE_FLOAT})
+	shape.set_property_const("speed", 1.0)
+	shape.rebuild()
+	
+	for backend in [0, 1]:
+		shape.set_validator_backend(backend)
+		
+		if shape.instantiate('{"speed": 1}') is String:
+			printerr("Expected 1 to match the const 1.0 with validator backend %d." % backend)
+	
+	var counts: GodotSchema = GodotSchema.from_shape({"count/total": TYPE_INT})
+	
+	for backend in [0, 1]:
+		counts.set_validator_backend(backend)
+		
+		# Whole floats are integers in JSON Schema.
+		if not counts.is_valid('{"count/total": 2.0}'):
+			printerr("Expected 2.0 to be a valid integer with validator backend %d." % backend)
+		
+		var issues: Array[Dictionary] = counts.validate_verbose('{"count/total": "two"}')
+		
+		if issues.is_empty() or issues[0].get("instance_path") != "/count~1total":
+			printerr("Expected the property's name to be escaped in the issue's path with validator backend %d. Got: %s" % [backend, issues])
+
+
+func test_script_defaults():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Fact")
+	
+	if schema.json.contains("\"default\""):
+		printerr("Expected script defaults to be off by default:\n%s" % schema.json)
+	
+	var lib := SchemaLibrary.new()
+	lib.script_defaults = true
+	schema = lib.generate_named_class_schema(&"Fact")
+	
+	if not schema.json.contains("\"default\""):
+		printerr("Expected script properties to declare their initial value as `default`:\n%s" % schema.json)
+	
+	lib.free()
+
+
+func test_example_json():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Person")
+	var example := schema.example_json(true)
+	
+	if not schema.instantiate(example) is Person:
+		printerr("Expected the example to instantiate:\n%s" % example)
+
+
+func test_unique_items():
+	var schema: GodotSchema = GodotSchema.from_shape({"tags": [TYPE_STRING], "scores": [TYPE_INT]})
+	schema.set_array_constraints("tags", 3, -1, true)
+	schema.set_array_constraints("scores", 3, 5, true)
+	schema.rebuild()
+	var example := schema.example_json(false)
+	
+	if not schema.is_valid(example):
+		printerr("Expected the example of unique arrays to be valid:\n%s" % example)
+	
+	var failures = schema.self_test(10)
+	
+	if failures is String or not failures.is_empty():
+		printerr("Expected fuzzing unique arrays to find no failures. Got: %s" % [failures])
+	
+	# The structural backend.
+	schema.set_validator_backend(1)
+	
+	if schema.is_valid('{"tags": ["a", "b", "c"], "scores": [1, 2, 1.0]}'):
+		printerr("Expected 1 and 1.0 to be duplicates.")
+
+
+func test_unique_placeholders():
+	var schema: GodotSchema = GodotSchema.from_shape({"tags": [TYPE_STRING]})
+	schema.set_array_constraints("tags", 3, -1, true)
+	schema.rebuild()
+	var tags = JSON.parse_string(schema.example_json(false))["tags"]
+	
+	if tags != ["tags", "tags 2", "tags 3"]:
+		printerr("Expected the placeholders of unique elements to be numbered. Got: %s" % [tags])
+
+
+func test_self_test():
+	var failures = GodotSchema.from_class_name(&"Person").self_test(20)
+	
+	if failures is String or not failures.is_empty():
+		printerr("Expected fuzzing Person to find no failures. Got: %s" % [failures])
+
+
+func test_self_test_constraints():
+	var nodes_before := Performance.get_monitor(Performance.OBJECT_NODE_COUNT)
+	var failures = GodotSchema.from_class_name(&"StatBlock").self_test(20)
+	
+	if failures is String or not failures.is_empty():
+		printerr("Expected fuzzing StatBlock within its constraints to find no failures. Got: %s" % [failures])
+	
+	var leaked := Performance.get_monitor(Performance.OBJECT_NODE_COUNT) - nodes_before
+	
+	if leaked != 0:
+		printerr("Expected self_test to free the StatBlock nodes and their minions. Got %d leaked nodes." % leaked)
+
+
+const FUZZED_TYPES := [
+	TYPE_BOOL, TYPE_INT, TYPE_FLOAT, TYPE_STRING, TYPE_VECTOR2, TYPE_VECTOR3I, TYPE_RECT2, TYPE_COLOR,
+	TYPE_QUATERNION, TYPE_TRANSFORM3D, TYPE_PACKED_INT32_ARRAY, TYPE_PACKED_STRING_ARRAY, TYPE_ARRAY,
+]
+
+
+func test_random_shapes():
+	var rng := RandomNumberGenerator.new()
+	rng.seed = 1
+	
+	for i in 20:
+		var shape := random_object_shape(rng, 0)
+		var schema = GodotSchema.from_shape(shape)
+		
+		if schema is String:
+			printerr("Expected random shape %s to build a schema. Got: %s" % [shape, schema])
+			continue
+		
+		var failures = schema.self_test(10)
+		
+		if failures is String or not failures.is_empty():
+			printerr("Expected fuzzing random shape %s to find no failures. Got: %s" % [shape, failures])
+
+
+func random_object_shape(rng: RandomNumberGenerator, depth: int) -> Dictionary:
+	var shape := {}
+	
+	for i in rng.randi_range(1, 5):
+		shape["field_%d" % i] = random_shape(rng, depth + 1)
+	
+	return shape
+
+
+func random_shape(rng: RandomNumberGenerator, depth: int) -> Variant:
+	# Containers stop nesting after a few levels.
+	var kinds := FUZZED_TYPES.size() + (0 if depth >= 3 else 2)
+	var kind := rng.randi_range(0, kinds - 1)
+	
+	if kind < FUZZED_TYPES.size():
+		return FUZZED_TYPES[kind]
+	elif kind == FUZZED_TYPES.size():
+		return [random_shape(rng, depth + 1)]
+	else:
+		return random_object_shape(rng, depth)
+
+
+func test_validation_only():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Fact")
+	
+	if not schema.is_valid('{"text": "", "salient_word": "", "is_password_related": false}'):
+		printerr("Expected the message to be valid.")
+	
+	var issues := schema.validate_verbose('{"text": 3, "salient_word": ""}')
+	
+	if issues.size() < 2 or issues.any(func(issue): return issue["keyword"].is_empty()):
+		printerr("Expected the wrong type and the missing property to be reported. Got: %s" % issues)
+
+
+func test_message_registry():
+	var registry := MessageSchemaRegistry.new()
+	registry.register("fact", GodotSchema.from_class_name(&"Fact"))
+	
+	var result := registry.decode('{"type": "fact", "payload": {"text": "Hello!", "salient_word": "", "is_password_related": false}}')
+	
+	if not result.is_ok() or result.value.text != "Hello!":
+		printerr("Expected the packet to be decoded. Got: %s" % result.error_message)
+	
+	if registry.decode('{"type": "unknown", "payload": {}}').error_code != SchemaResult.VALIDATION_FAILED:
+		printerr("Expected a packet of an unknown type to be rejected.")
+	
+	if not registry.decode(registry.encode("fact", result.value)).is_ok():
+		printerr("Expected an encoded packet to be decoded.")
+	
+	registry.free()
+
+
+func test_repair_prompt():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Fact")
+	var invalid := '{"text": 3, "salient_word": ""}'
+	var prompt := schema.build_repair_prompt(invalid)
+	
+	if not prompt.contains(invalid) or not prompt.contains("salient_word"):
+		printerr("Expected the repair prompt to contain the response and the schema. Got: %s" % prompt)
+	
+	if prompt.contains("$schema"):
+		printerr("Expected the repair prompt to contain the schema as sent to providers. Got: %s" % prompt)
+	
+	var response_format: Dictionary = JSON.parse_string(schema.open_ai_response_format("fact"))
+	
+	if response_format["json_schema"].get("strict") != true:
+		printerr("Expected the response format of a schema without optional properties to be strict. Got: %s" % response_format)
+	
+	if not schema.build_repair_prompt('{"text": "", "salient_word": "", "is_password_related": false}').is_empty():
+		printerr("Expected no repair prompt for a valid response.")
+	
+	var result: Dictionary = schema.instantiate_or_repair(invalid)
+	
+	if result["instance"] != null or result["repair_prompt"] != prompt or result["error"].is_empty():
+		printerr("Expected the repair prompt. Got: %s" % result)
+	
+	result = schema.instantiate_or_repair('{"text": "", "salient_word": "", "is_password_related": false}')
+	
+	if not result["instance"] is Fact or not result["repair_prompt"].is_empty():
+		printerr("Expected the instance without a repair prompt. Got: %s" % result)
+
+
+func test_lenient_repair():
+	var numbers = GodotSchema.from_json_schema('{"type": "object", "properties": {"big": {"type": "number"}, "small": {"type": "number"}}}')
+	var result = numbers.instantiate_lenient('{"big": 1e5, "small": -2.5E-3,}')
+	
+	if result is String or result["instance"]["big"] != 100000.0 or not is_equal_approx(result["instance"]["small"], -0.0025):
+		printerr("Expected exponents to be kept as numbers. Got: %s" % result)
+	
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Fact")
+	result = schema.instantiate_lenient("{'text': '\\uD83D\\uDE00', 'salient_word': '', 'is_password_related': false}")
+	
+	if result is String or result["instance"].text != "😀":
+		printerr("Expected surrogate pairs to be decoded into one character. Got: %s" % result)
+	
+	result = schema.instantiate_lenient('Here: {"text": "a} b]", "salient_word": "", "is_password_related": false} (see [docs])')
+	
+	if result is String or result["instance"].text != "a} b]":
+		printerr("Expected brackets inside strings and after the document to be ignored. Got: %s" % result)
+	
+	result = schema.instantiate_lenient('{"text": "cut} here", "salient_word": "", "is_password_related": false')
+	
+	if result is String or result["instance"].text != "cut} here":
+		printerr("Expected a truncated document to be kept whole. Got: %s" % result)
+
+
+func test_prompt_text():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Fact")
+	var text := schema.to_prompt_text(0, true)
+	
+	if not text.contains("salient_word") or text.contains("$schema"):
+		printerr("Expected a compact rendering of the properties. Got: %s" % text)
+	
+	if schema.to_prompt_text(20, true).length() > 20:
+		printerr("Expected the rendering to fit in 20 characters.")
+
+
+func test_minified_json():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Person")
+	var minified := schema.to_json_ex(false, false, false)
+	
+	if minified.contains("\"description\"") or minified.contains("x-godot") or minified.contains("\n"):
+		printerr("Expected descriptions, vendor keys and whitespace to be stripped. Got: %s" % minified)
+	
+	if minified.length() >= schema.json.length():
+		printerr("Expected the minified schema to be smaller.")
+
+
+func test_schema_id():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Fact")
+	schema.set_schema_id("https://example.com/schemas/fact/v2.json")
+	schema.set_emit_meta_schema(false)
+	
+	var json: Dictionary = JSON.parse_string(schema.json)
+	
+	if json.get("$id") != "https://example.com/schemas/fact/v2.json" or json.has("$schema"):
+		printerr("Expected the schema to have an `$id` and no `$schema`. Got: %s" % schema.json)
+
+
+func test_shared_definitions():
+	var lib := SchemaLibrary.new()
+	var fact_schema: GodotSchema = lib.generate_named_class_schema(&"Fact")
+	var person_schema: GodotSchema = lib.generate_named_class_schema(&"Person")
+	fact_schema.set_schema_id("https://example.com/schemas/fact.json")
+	
+	if lib.link_shared_definitions() != 1 or not person_schema.json.contains("https://example.com/schemas/fact.json"):
+		printerr("Expected Person to reference Fact by `$id`. Got: %s" % person_schema.json)
+	
+	if not person_schema.is_valid(person_schema.example_json(false)):
+		printerr("Expected the reference to Fact to be resolved by the library.")
+	
+	if person_schema.open_ai_response_format("person").contains("https://example.com/schemas/fact.json"):
+		printerr("Expected provider payloads to bundle the referenced definitions.")
+	
+	fact_schema.set_schema_id("fact.json")
+	lib.link_shared_definitions()
+	
+	if not person_schema.is_valid(person_schema.example_json(false)):
+		printerr("Expected a relative `$id` to be resolved by the library.")
+	
+	lib.free()
+
+
+func test_class_inheritance():
+	var lib := SchemaLibrary.new()
+	lib.class_inheritance = true
+	lib.skip_private_properties = true
+	var schema: GodotSchema = lib.generate_named_class_schema(&"QuoteFact")
+	
+	if not schema.json.contains("allOf"):
+		printerr("Expected QuoteFact to reference Fact with `allOf`. Got: %s" % schema.json)
+	
+	var valid := '{"text": "Hi", "salient_word": "Hi", "is_password_related": false, "author": "Ann", "source": {"text": "Hey", "salient_word": "Hey", "is_password_related": false}}'
+	
+	if not schema.is_valid(valid):
+		printerr("Expected the inherited properties to be accepted.")
+	
+	if schema.is_valid(valid.replace('"Hey", "is_password_related"', '"Hey", "extra": 1, "is_password_related"')):
+		printerr("Expected references to the base class to reject unknown properties.")
+	
+	var provider := schema.open_ai_response_format("quote")
+	
+	if provider.contains("allOf") or provider.contains("unevaluatedProperties"):
+		printerr("Expected provider payloads to repeat the inherited properties. Got: %s" % provider)
+	
+	if GodotSchema.from_class_name(&"QuoteFact").json.contains("allOf"):
+		printerr("Expected class inheritance to only apply to the library's schemas.")
+	
+	lib.free()
+
+
+func test_private_properties():
+	if not GodotSchema.from_class_name(&"QuoteFact").json.contains("_cached_length"):
+		printerr("Expected private properties to be included by default.")
+	
+	var lib := SchemaLibrary.new()
+	lib.skip_private_properties = true
+	var schema: GodotSchema = lib.generate_named_class_schema(&"QuoteFact")
+	
+	if schema.json.contains("_cached_length"):
+		printerr("Expected the library to skip private properties. Got: %s" % schema.json)
+	
+	var other := SchemaLibrary.new()
+	
+	if not other.generate_named_class_schema(&"QuoteFact").json.contains("_cached_length"):
+		printerr("Expected skipping private properties to only apply to the library that enabled it.")
+	
+	other.free()
+	lib.free()
+
+
+func test_schema_bundle():
+	var lib := SchemaLibrary.new()
+	lib.generate_named_class_schema(&"Fact")
+	lib.generate_named_class_schema(&"Person")
+	
+	var bundle: Dictionary = JSON.parse_string(lib.export_bundle(false))
+	var defs: Dictionary = bundle.get("$defs", {})
+	
+	if not defs.has("Person") or not defs.has("Fact"):
+		printerr("Expected both classes in the bundle's `$defs`. Got: %s" % defs.keys())
+	
+	lib.free()
+
+
+func test_class_bundle():
+	var lib := SchemaLibrary.new()
+	var bundle = lib.bundle(PackedStringArray(["Person", "Fact"]), "")
+	
+	if bundle is String:
+		printerr(bundle)
+	else:
+		var fact = bundle.instantiate('{"value": {"text": "Hello!", "salient_word": "", "is_password_related": false}}')
+		
+		if not fact is Fact:
+			printerr("Expected the bundle to instantiate the matching class. Got: %s" % fact)
+	
+	if not lib.bundle(PackedStringArray(["Fact"]), "Person") is String:
+		printerr("Expected a root that is not bundled to be rejected.")
+	
+	var person: GodotSchema = lib.get_or_generate(&"Person")
+	person.set_integer_enums(true)
+	var integer_bundle = lib.bundle(PackedStringArray(["Person"]), "Person")
+	
+	if integer_bundle is String or not integer_bundle.get_integer_enums():
+		printerr("Expected the bundle to keep the classes' integer_enums. Got: %s" % integer_bundle)
+	
+	if not lib.bundle(PackedStringArray(["Person", "Fact"]), "") is String:
+		printerr("Expected classes with different integer_enums to be rejected.")
+	
+	lib.free()
+
+
+func test_property_naming():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Fact")
+	var error = schema.set_property_naming(GodotSchema.NAMING_CAMEL_CASE)
+	
+	if error != null or not schema.json.contains("salientWord") or schema.json.contains("salient_word"):
+		printerr("Expected camelCase property names. Got: %s %s" % [error, schema.json])
+	
+	if schema.open_ai_response_format("fact").contains("x-godot"):
+		printerr("Expected the rename map to stay out of provider payloads. Got: %s" % schema.open_ai_response_format("fact"))
+	
+	var fact = schema.instantiate('{"text": "Hello!", "salientWord": "Hello", "isPasswordRelated": true}')
+	
+	if not fact is Fact or fact.salient_word != "Hello" or not fact.is_password_related:
+		printerr("Expected camelCase names to be translated back. Got: %s" % fact)
+	
+	var applied := Fact.new()
+	var record = schema.apply_to(applied, '{"text": "Bye!", "salientWord": "Bye", "isPasswordRelated": true}', true)
+	
+	if not record is UndoRecord or applied.salient_word != "Bye":
+		printerr("Expected apply_to to assign camelCase properties. Got: %s" % [record])
+	else:
+		record.revert()
+		
+		if applied.salient_word != "" or applied.is_password_related:
+			printerr("Expected the undo record to revert the Godot properties.")
+	
+	schema.set_property_naming_callable(func(property: String) -> String: return property.to_upper())
+	
+	if schema.get_json_property_name("salient_word") != "SALIENT_WORD":
+		printerr("Expected the callable to name properties. Got: %s" % schema.json)
+
+
+func test_property_alias():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Fact")
+	var error = schema.set_property_alias("salient_word", "keyword")
+	
+	if error != null or not schema.json.contains("keyword"):
+		printerr("Expected property \"salient_word\" to be named \"keyword\". Got: %s %s" % [error, schema.json])
+	
+	var fact = schema.instantiate('{"text": "Hello!", "keyword": "Hello", "is_password_related": false}')
+	
+	if not fact is Fact or fact.salient_word != "Hello":
+		printerr("Expected the alias to assign property \"salient_word\". Got: %s" % fact)
+	
+	var applied := Fact.new()
+	var error_message = schema.apply_to(applied, '{"text": "Bye!", "keyword": "Bye", "is_password_related": false}', false)
+	
+	if error_message != null or applied.salient_word != "Bye":
+		printerr("Expected apply_to to assign the aliased property. Got: %s" % [error_message])
+	
+	var original = '{"text": "Hello!", "salient_word": "Hello", "is_password_related": false}'
+	
+	if schema.is_valid(original):
+		printerr("Expected the Godot name to be rejected by default.")
+	
+	schema.set_accept_godot_names(true)
+	
+	if not schema.instantiate(original) is Fact:
+		printerr("Expected the Godot name to be accepted.")
+
+
+func test_unwrapped_root():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Fact").get_array_schema("Fact")
+	schema.set_root_wrapper("")
+	
+	var json: Dictionary = JSON.parse_string(schema.json)
+	
+	if json.get("type") != "array":
+		printerr("Expected an array root. Got: %s" % schema.json)
+	
+	var facts = schema.instantiate('[{"text": "Hello!", "salient_word": "Hello", "is_password_related": false}]')
+	
+	if not facts is Array or facts.size() != 1:
+		printerr("Expected a plain array to be instantiated. Got: %s" % facts)
+	
+	schema.set_root_wrapper("facts")
+	
+	if not schema.is_valid('{"facts": []}'):
+		printerr("Expected the root to be wrapped in \"facts\". Got: %s" % schema.json)
+
+
+func test_streaming():
+	var fact_json := '{"text": "Hi", "salient_word": "Hi", "is_password_related": true}'
+	var expected := [["text", "Hi"], ["salient_word", "Hi"], ["is_password_related", "true"], ["completed"]]
+	var events := stream_events(GodotSchema.from_class_name(&"Fact"), "```json\n%s\n```" % fact_json)
+	
+	if events != expected:
+		printerr("Expected each property, then the instance, to be emitted. Got: %s" % [events])
+	
+	var renamed: GodotSchema = GodotSchema.from_class_name(&"Fact")
+	renamed.set_property_naming(GodotSchema.NAMING_CAMEL_CASE)
+	events = stream_events(renamed, '{"text": "Hi", "salientWord": "Hi", "isPasswordRelated": true}')
+	
+	if events != expected:
+		printerr("Expected renamed properties to be emitted by their Godot name. Got: %s" % [events])
+	
+	var confident: GodotSchema = GodotSchema.from_class_name(&"Fact").with_confidence("")
+	events = stream_events(confident, '{"text": "Hi", "text_confidence": 0.5, "salient_word": "Hi", "salient_word_confidence": 1, '
+		+ '"is_password_related": true, "is_password_related_confidence": 0}')
+	
+	if events != expected:
+		printerr("Expected confidences to not be emitted. Got: %s" % [events])
+	
+	var numbers: GodotSchema = GodotSchema.from_type_info(TYPE_ARRAY, &"", PROPERTY_HINT_ARRAY_TYPE, "int", PROPERTY_USAGE_NONE)
+	numbers.set_root_wrapper("numbers")
+	events = stream_events(numbers, '{"numbers": [1, 2]}')
+	
+	if events != [["numbers", "[1, 2]"], ["completed"]]:
+		printerr("Expected the wrapper property to be emitted. Got: %s" % [events])
+	
+	numbers.set_root_wrapper("")
+	events = stream_events(numbers, '[1, 2]')
+	
+	if events != [["completed"]]:
+		printerr("Expected an unwrapped root to only complete. Got: %s" % [events])
+	
+	events = stream_events(GodotSchema.from_class_name(&"Fact"), '{"text": "Hi", "salient_word": Hi, "is_password_related": true}')
+	
+	if events.front() != ["text", "Hi"] or events.count(["failed"]) != 2:
+		printerr("Expected the malformed property and root to fail. Got: %s" % [events])
+	
+	var streaming := StreamingInstantiator.create(GodotSchema.from_class_name(&"Fact"))
+	streaming.push_chunk(fact_json.substr(0, 10))
+	
+	if streaming.is_completed() or streaming.get_buffer() != fact_json.substr(0, 10):
+		printerr("Expected a partial root to be buffered. Got: %s" % streaming.get_buffer())
+	
+	streaming.push_chunk(fact_json.substr(10))
+	
+	if not streaming.is_completed():
+		printerr("Expected the root to be completed.")
+	
+	streaming.reset()
+	
+	if streaming.is_completed() or streaming.get_buffer() != "":
+		printerr("Expected reset to clear the received JSON.")
+
+
+# Pushes `json` to a `StreamingInstantiator` of `schema` in chunks of 4 characters.
+# Returns its events: `[name, str(value)]` per property, then `["completed"]` or `["failed"]`.
+func stream_events(schema: GodotSchema, json: String) -> Array:
+	var events := []
+	var streaming := StreamingInstantiator.create(schema)
+	streaming.property_completed.connect(func(name: String, value: Variant): events.append([name, str(value)]))
+	streaming.completed.connect(func(_instance: Variant): events.append(["completed"]))
+	streaming.failed.connect(func(_error_message: String): events.append(["failed"]))
+	
+	for i in range(0, json.length(), 4):
+		streaming.push_chunk(json.substr(i, 4))
+	
+	return events
+
+
+func test_dictionary_schema():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Fact").get_dictionary_schema("Fact")
+	var facts = schema.instantiate('{"value": {"greeting": {"text": "Hello!", "salient_word": "Hello", "is_password_related": false}}}')
+	
+	if not facts is Dictionary or not facts.get("greeting") is Fact:
+		printerr("Expected a Dictionary of facts. Got: %s" % facts)
+	
+	if schema.is_valid('{"value": {"greeting": 1}}'):
+		printerr("Expected the map's values to be validated.")
+
+
+func test_gbnf():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Fact")
+	schema.set_property_optional("salient_word", true)
+	var grammar := schema.to_gbnf()
+	
+	# Required members come first and are mandatory, the optional one may be skipped.
+	if not grammar.contains('"{" space "\\"text\\"" space ":" space'):
+		printerr("Expected \"text\" to be the first, mandatory member. Got: %s" % grammar)
+	
+	if not grammar.contains('("," space "\\"salient_word\\"" space ":" space string)?'):
+		printerr("Expected \"salient_word\" to be skippable. Got: %s" % grammar)
+	
+	var map_grammar := GodotSchema.from_class_name(&"Fact").get_dictionary_schema("Fact").to_gbnf()
+	
+	# A map repeats its key/value pairs instead of falling back to an untyped object.
+	if not map_grammar.contains('("," space string ":" space def-Fact)*'):
+		printerr("Expected the map's members to be repeated. Got: %s" % map_grammar)
+
+
+func test_schema_dict():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Fact")
+	var dict: Dictionary = schema.get_schema_dict()
+	
+	if not dict.get("properties", {}).has("salient_word"):
+		printerr("Expected the schema's Dictionary to list its properties. Got: %s" % dict)
+
+
+func test_introspection():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Person")
+	
+	if not schema.get_property_names().has("main_fact"):
+		printerr("Expected \"main_fact\" to be listed. Got: %s" % schema.get_property_names())
+	
+	var main_fact: Dictionary = schema.get_property_type("main_fact")
+	
+	if main_fact.get("kind") != "class" or main_fact.get("ref") != "Fact":
+		printerr("Expected \"main_fact\" to reference class \"Fact\". Got: %s" % main_fact)
+	
+	if not schema.get_dependencies().has("Fact"):
+		printerr("Expected \"Fact\" to be a dependency. Got: %s" % schema.get_dependencies())
+
+
+func test_dependency_graph():
+	var lib := SchemaLibrary.new()
+	lib.generate_named_class_schema(&"Person")
+	lib.generate_named_class_schema(&"Fact")
+	
+	var graph: Dictionary = lib.get_dependency_graph()
+	
+	if not graph.get("Person", PackedStringArray()).has("Fact") or not graph.get("Fact", PackedStringArray()).is_empty():
+		printerr("Expected Person to depend on Fact. Got: %s" % graph)
+	
+	lib.free()
+
+
+func test_headless_export():
+	var report: Dictionary = SchemaLibrary.export_project_schemas_headless("user://headless_export")
+	
+	if report["error"] != "" or report["written"] == 0:
+		printerr("Expected the project's schemas to be written. Got: %s" % report)
+	
+	if not FileAccess.file_exists("user://headless_export/Fact.schema.json"):
+		printerr("Expected \"Fact.schema.json\" to be written.")
+
+
+func test_custom_format():
+	var lib := SchemaLibrary.new()
+	var error = lib.register_format("item_id", func(value: String): return value.begins_with("item_"))
+	
+	if error != null:
+		printerr(error)
+	
+	lib.register_format("broken", func(_value: String): return "not a bool")
+	
+	var json := '{"type": "object", "properties": {"id": {"type": "string", "format": "item_id"}, "at": {"type": "string", "format": "date-time"}, "other": {"type": "string", "format": "broken"}}}'
+	var schema = GodotSchema.from_json_schema(json)
+	var outside = GodotSchema.from_json_schema(json)
+	lib.apply_custom_validators(schema)
+	
+	if not schema.is_valid('{"id": "item_sword"}') or schema.is_valid('{"id": "sword"}'):
+		printerr("Expected format \"item_id\" to be checked.")
+	
+	if not schema.is_valid('{"at": "yesterday"}') or schema.get_validate_formats():
+		printerr("Expected standard formats to stay annotations when custom ones are registered.")
+	
+	if schema.is_valid('{"other": "anything"}'):
+		printerr("Expected values to be rejected when their format's validator fails.")
+	
+	if not outside.is_valid('{"id": "sword"}'):
+		printerr("Expected formats to only be checked by schemas given the library's validators.")
+	
+	lib.unregister_format("item_id")
+	
+	if not schema.is_valid('{"id": "sword"}'):
+		printerr("Expected an unregistered format to not be checked.")
+	
+	lib.free()
+
+
+func test_custom_keyword():
+	var lib := SchemaLibrary.new()
+	var error = lib.register_keyword("x-max-node-count", func(value, argument): return value.size() <= argument)
+	
+	if error != null:
+		printerr(error)
+	
+	var schema = GodotSchema.from_json_schema('{"type": "object", "properties": {"nodes": {"type": "array", "x-max-node-count": 2}}}')
+	lib.apply_custom_validators(schema)
+	
+	if not schema.is_valid('{"nodes": [1, 2]}') or schema.is_valid('{"nodes": [1, 2, 3]}'):
+		printerr("Expected keyword \"x-max-node-count\" to be checked.")
+	
+	var other := SchemaLibrary.new()
+	var outside = GodotSchema.from_json_schema('{"type": "object", "properties": {"nodes": {"type": "array", "x-max-node-count": 2}}}')
+	other.apply_custom_validators(outside)
+	
+	if not outside.is_valid('{"nodes": [1, 2, 3]}'):
+		printerr("Expected keywords to only be checked by the schemas of the library that registered them.")
+	
+	other.free()
+	lib.register_keyword("x-broken", func(_value, _argument): return null)
+	var broken = GodotSchema.from_json_schema('{"type": "object", "x-broken": true}')
+	lib.apply_custom_validators(broken)
+	
+	if broken.is_valid('{}'):
+		printerr("Expected values to be rejected when their keyword's validator fails.")
+	
+	lib.unregister_keyword("x-max-node-count")
+	lib.free()
+
+
+func test_validator_options():
+	var schema = GodotSchema.from_json_schema('{"type": "object", "properties": {"at": {"type": "string", "format": "date-time", "pattern": "^[0-9T:-]+Z$"}}}')
+	
+	if not schema.is_valid('{"at": "99-99Z"}'):
+		printerr("Expected formats to be annotations by default.")
+	
+	schema.set_validate_formats(true)
+	
+	if schema.is_valid('{"at": "99-99Z"}') or not schema.is_valid('{"at": "2024-01-01T00:00:00Z"}'):
+		printerr("Expected format \"date-time\" to be asserted.")
+	
+	if schema.set_max_pattern_length(4) == null or schema.get_max_pattern_length() != 0:
+		printerr("Expected a longer pattern to be rejected.")
+
+
+func test_log_level():
+	SchemaLibrary.set_log_level(SchemaLibrary.LOG_DEBUG)
+	
+	if SchemaLibrary.get_log_level() != SchemaLibrary.LOG_DEBUG:
+		printerr("Expected the log level to be set.")
+	
+	GodotSchema.from_class_name(&"Fact")
+	SchemaLibrary.set_log_level(SchemaLibrary.LOG_OFF)
+
+
+func test_generation_report():
+	var report: Array = GodotSchema.from_class_name(&"QuoteFact").get_generation_report()
+	var skipped := report.filter(func(issue): return issue["property"] == "on_quoted")
+	
+	if skipped.size() != 1 or skipped[0]["kind"] != "skipped_property" or skipped[0]["class"] != "QuoteFact":
+		printerr("Expected the Callable property QuoteFact.on_quoted to be reported as skipped. Got: %s" % [report])
+	
+	var lib := SchemaLibrary.new()
+	lib.unsupported_property_policy = GodotSchema.UNSUPPORTED_STUB_AS_NULL
+	report = lib.generate_named_class_schema(&"QuoteFact").get_generation_report()
+	var stubbed := report.filter(func(issue): return issue["property"] == "on_quoted")
+	
+	if stubbed.size() != 1 or stubbed[0]["kind"] != "stubbed_property" or stubbed[0]["class"] != "QuoteFact":
+		printerr("Expected the library's policy to report QuoteFact.on_quoted as stubbed. Got: %s" % [report])
+	
+	if not GodotSchema.from_class_name(&"Fact").get_generation_report().is_empty():
+		printerr("Expected Fact to generate without issues.")
+	
+	lib.free()
+
+
+func test_unsupported_property_policy():
+	var lib := SchemaLibrary.new()
+	lib.unsupported_property_policy = GodotSchema.UNSUPPORTED_ERROR
+	
+	if lib.generate_named_class_schema(&"QuoteFact") is GodotSchema:
+		printerr("Expected the library's policy to fail the generation of a class with a `Callable` property.")
+	
+	if GodotSchema.from_class_name(&"QuoteFact").get_generation_warnings().is_empty():
+		printerr("Expected other schemas to keep skipping unsupported properties.")
+	
+	var shared := SchemaLibrary.new()
+	shared.generate_all_project_schemas("QuoteFact")
+	
+	for schema in shared.schemas:
+		if schema.get_generation_warnings().is_empty():
+			printerr("Expected schemas generated with shared definitions to report their warnings.")
+	
+	shared.free()
+	lib.free()
+
+
+func test_validator_backends():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Fact")
+	var wrong_type := '{"text": 1, "salient_word": "cat", "is_password_related": false}'
+	
+	if schema.get_validator_backend() != GodotSchema.VALIDATOR_JSONSCHEMA:
+		printerr("Expected the jsonschema backend to be the default. Got: %s" % schema.get_validator_backend())
+	
+	schema.set_validator_backend(GodotSchema.VALIDATOR_STRUCTURAL)
+	
+	if schema.get_validator_backend() != GodotSchema.VALIDATOR_STRUCTURAL or schema.is_valid(wrong_type):
+		printerr("Expected the structural backend to reject a property of the wrong type.")
+	
+	schema.set_validator_backend(GodotSchema.VALIDATOR_TRUST)
+	
+	if not schema.is_valid(wrong_type):
+		printerr("Expected the trust backend to accept any input.")
+
+
+func test_ollama_format():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Fact")
+	var format = JSON.parse_string(schema.ollama_format())
+	
+	if not format is Dictionary or not format.get("properties", {}).has("salient_word"):
+		printerr("Expected the Ollama format to be the schema's JSON. Got: %s" % format)
+	elif format.has("$schema"):
+		printerr("Expected the Ollama format to omit `$schema`. Got: %s" % format)
+
+
+func test_validator_release():
+	var lib := SchemaLibrary.new()
+	var schema: GodotSchema = lib.generate_named_class_schema(&"Fact")
+	
+	if lib.on_app_pause() != 1 or schema.is_validator_compiled():
+		printerr("Expected pausing the app to release the cached schema's validator.")
+	
+	var fact = schema.instantiate('{"text": "Charlie has a pet cat.", "salient_word": "cat", "is_password_related": false}')
+	
+	if not fact is Fact:
+		printerr("Expected the released validator to be recompiled on use. Got: %s" % fact)
+	elif not schema.is_validator_compiled():
+		printerr("Expected the validator to be compiled again after instantiating.")
+	
+	if lib.on_low_memory() != 1 or lib.on_low_memory() != 0:
+		printerr("Expected low memory to release the validator once.")
+	
+	schema.release_validator()
+	
+	if schema.is_validator_compiled():
+		printerr("Expected release_validator to drop the compiled validator.")
+	
+	lib.free()
+
+
+func test_method_tools():
+	var schema = GodotSchema.from_method(self, &"repeat_text")
+	
+	if not schema is GodotSchema or not schema.get_property_names().has("times"):
+		printerr("Expected a schema with one property per parameter. Got: %s" % schema)
+	
+	var dispatcher := ToolDispatcher.new()
+	dispatcher.register_method(self, &"repeat_text")
+	
+	if not dispatcher.get_method_schema("repeat_text") is GodotSchema:
+		printerr("Expected the registered method's schema to be found.")
+	
+	var result = dispatcher.dispatch('{"name": "repeat_text", "arguments": {"text": "ab", "times": 2}}')
+	
+	if result != "abab":
+		printerr("Expected the tool call to invoke the method. Got: %s" % result)
+	
+	result = dispatcher.dispatch('{"name": "repeat_text", "arguments": {"text": "ab", "times": "twice"}}')
+	
+	if result == "abab" or result == "":
+		printerr("Expected arguments of the wrong type to be rejected. Got: %s" % result)
+	
+	dispatcher.free()
+
+
+func repeat_text(text: String, times: int) -> String:
+	return text.repeat(times)
+
+
+func test_schema_console():
+	var console := SchemaConsole.new()
+	var schema: GodotSchema = GodotSchema.from_shape({"text": TYPE_STRING, "times": TYPE_INT})
+	console.register_command("say", schema, func(args: Dictionary): return args.text.repeat(args.times))
+	
+	if console.get_command_names() != PackedStringArray(["say"]) or console.get_command_usage("say") != "say <text> <times>":
+		printerr("Expected \"say <text> <times>\" to be registered. Got: %s" % console.get_command_usage("say"))
+	
+	for input in ["say text=ab times=2", "say ab 2", 'say {"text": "ab", "times": 2}']:
+		var result = console.execute(input)
+		
+		if result != "abab":
+			printerr("Expected \"%s\" to invoke the command. Got: %s" % [input, result])
+	
+	if console.execute("say ab twice") == "abab":
+		printerr("Expected arguments of the wrong type to be rejected.")
+	
+	if not console.unregister_command("say") or console.execute("say ab 2") == "abab":
+		printerr("Expected the unregistered command to be unknown.")
+
+
+func test_tool_catalog():
+	var lib := SchemaLibrary.new()
+	var error = lib.register_tools(self, PackedStringArray(["repeat_text", "not_a_method"]))
+	
+	if not error is String or not error.contains("not_a_method"):
+		printerr("Expected the missing method to be reported. Got: %s" % error)
+	
+	var definitions = JSON.parse_string(lib.get_tool_definitions("Anthropic"))
+	
+	if not definitions is Array or definitions.size() != 1 or definitions[0].get("name") != "repeat_text":
+		printerr("Expected one Anthropic tool definition for \"repeat_text\". Got: %s" % definitions)
+	elif not definitions[0].get("input_schema", {}).get("properties", {}).has("times"):
+		printerr("Expected the tool's input schema to list the method's parameters. Got: %s" % definitions)
+	
+	var result = lib.dispatch('{"function": {"name": "repeat_text", "arguments": "{\\"text\\": \\"ab\\", \\"times\\": 3}"}}')
+	
+	if result != "ababab":
+		printerr("Expected an OpenAI tool call to invoke the method. Got: %s" % result)
+	
+	lib.free()
+
+
+func test_confidence():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Fact").with_confidence("_certainty")
+	var json := '{"text": "Charlie has a pet cat.", "text_certainty": 0.5, "salient_word": "cat", "salient_word_certainty": 1, ' \
+		+ '"is_password_related": false, "is_password_related_certainty": 0.25}'
+	var result = schema.instantiate_with_confidence(json)
+	
+	if not result is Dictionary or not result.get("instance") is Fact:
+		printerr("Expected an instance along with its confidences. Got: %s" % result)
+	elif result.get("confidence") != {"text": 0.5, "salient_word": 1.0, "is_password_related": 0.25}:
+		printerr("Expected the confidences to be keyed by property name. Got: %s" % result.get("confidence"))
+	
+	if not schema.instantiate(json) is Fact:
+		printerr("Expected instantiate to ignore the confidences.")
+	
+	if not schema.instantiate('{"text": "", "salient_word": "", "is_password_related": false}') is String:
+		printerr("Expected the confidences to be required.")
+
+
+func test_scene_serializer():
+	var serializer := SceneSerializer.new()
+	add_child(serializer)
+	
+	var stats := StatBlock.new()
+	stats.hp = 5
+	stats.speed = 2.5
+	stats.nickname = "Sir Reginald"
+	stats.rank = "A"
+	add_child(stats)
+	stats.add_to_group(&"schema_tester_stats")
+	
+	var json := serializer.serialize_group(&"schema_tester_stats")
+	var document = JSON.parse_string(json)
+	
+	if not document is Dictionary or document.get(str(stats.get_path()), {}).get("hp") != 5.0:
+		printerr("Expected the node's properties to be keyed by its path. Got: %s" % json)
+	
+	stats.hp = 9
+	serializer.clear_cache()
+	var error = serializer.restore_group(&"schema_tester_stats", json)
+	
+	if error != null:
+		printerr(error)
+	elif stats.hp != 5:
+		printerr("Expected the node to be restored. Got: %d" % stats.hp)
+	
+	stats.free()
+	serializer.free()
+
+
+func test_dictionary_input():
+	var schema: GodotSchema = GodotSchema.from_shape({"count": TYPE_INT, "label": TYPE_STRING})
+	var input: Dictionary = JSON.parse_string('{"count": 3, "label": "apples"}')
+	var result = schema.instantiate_dict(input)
+	
+	if not result is Dictionary or typeof(result.get("count")) != TYPE_INT or result.get("count") != 3:
+		printerr("Expected the parsed float to be instantiated as an integer. Got: %s" % result)
+	
+	if schema.validate_dict(input) != null:
+		printerr("Expected the parsed Dictionary to be valid. Got: %s" % schema.validate_dict(input))
+	
+	if not schema.validate_dict({"count": "three", "label": "apples"}) is String:
+		printerr("Expected a property of the wrong type to be rejected.")
+
+
+func test_finetune_examples():
+	var lib := SchemaLibrary.new()
+	var schema: GodotSchema = lib.generate_named_class_schema(&"Fact")
+	var path := "user://schema_tester_finetune.jsonl"
+	var count = lib.export_finetune_examples(3, path)
+	
+	if count != 3:
+		printerr("Expected 3 examples to be written. Got: %s" % count)
+	
+	var lines := FileAccess.get_file_as_string(path).strip_edges().split("\n")
+	
+	for line in lines:
+		var example = JSON.parse_string(line)
+		
+		if not example is Dictionary or not example.get("prompt") is String:
+			printerr("Expected each line to contain a prompt. Got: %s" % line)
+		elif not schema.is_valid(example.get("response", "")):
+			printerr("Expected each response to be valid according to the schema. Got: %s" % example.get("response"))
+	
+	if lib.export_finetune_examples(3, path) != 3 or FileAccess.get_file_as_string(path).strip_edges().split("\n") != lines:
+		printerr("Expected exporting the same library twice to produce the same file.")
+	
+	DirAccess.remove_absolute(path)
+	lib.free()
+
+
+func test_instantiate_array():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Fact")
+	var valid := '{"text": "Charlie has a pet cat.", "salient_word": "cat", "is_password_related": false}'
+	
+	for json in ["[%s, 1, %s]" % [valid, valid], '{"value": [%s, 1, %s]}' % [valid, valid]]:
+		var result = schema.instantiate_array(json)
+		
+		if not result is Dictionary or result.get("instances", []).size() != 2 or not result["instances"][1] is Fact:
+			printerr("Expected the valid elements to be instantiated. Got: %s" % result)
+		elif result.get("errors", {}).keys() != [1]:
+			printerr("Expected the invalid element's error to be keyed by its index. Got: %s" % result.get("errors"))
+	
+	if not schema.instantiate_array(valid) is String:
+		printerr("Expected a single object to be rejected.")
+
+
+func test_output_evaluator():
+	var valid := '{"text": "Charlie has a pet cat.", "salient_word": "cat", "is_password_related": false}'
+	var evaluator := OutputEvaluator.create(GodotSchema.from_class_name(&"Fact"))
+	
+	if evaluator.evaluate(valid) != null or not evaluator.evaluate('{"text": 1, "salient_word": "", "is_password_related": false}') is String:
+		printerr("Expected evaluate to report the validation errors.")
+	
+	evaluator.evaluate("not json")
+	var metrics := evaluator.get_metrics()
+	
+	if metrics.get("total") != 3 or metrics.get("valid") != 1 or metrics.get("parse_errors") != 1:
+		printerr("Expected 3 outputs, 1 valid and 1 unparsable. Got: %s" % metrics)
+	elif metrics.get("field_errors") != {"/text": 1} or metrics.get("most_violated_constraints") != PackedStringArray(["type"]):
+		printerr("Expected the wrong type of \"text\" to be counted. Got: %s" % metrics)
+	
+	if not evaluator.to_csv().begins_with("metric,key,value\ntotal,,3\n"):
+		printerr("Expected the metrics as CSV. Got: %s" % evaluator.to_csv())
+	
+	var dir := "user://schema_tester_outputs"
+	DirAccess.make_dir_recursive_absolute(dir)
+	
+	for i in 2:
+		var file := FileAccess.open("%s/output_%d.json" % [dir, i], FileAccess.WRITE)
+		file.store_string(valid)
+		file.close()
+	
+	evaluator.reset()
+	metrics = evaluator.evaluate_directory(dir)
+	
+	if metrics.get("total") != 2 or metrics.get("validity_rate") != 1.0:
+		printerr("Expected every file of the directory to be evaluated. Got: %s" % metrics)
+	
+	for i in 2:
+		DirAccess.remove_absolute("%s/output_%d.json" % [dir, i])
+
+
+func test_array_length_constant():
+	var path := "user://schema_tester_array_lengths.gd"
+	write_script(path, "extends RefCounted\n\nconst JSON_SCHEMA_ARRAY_LENGTHS := { \"slots\": 2 }\n\nvar slots: Array[int]\n")
+	var schema = GodotSchema.from_class_script(load(path))
+	
+	if not schema is GodotSchema:
+		printerr(schema)
+		return
+	
+	if not schema.json.contains("\"minItems\": 2") or not schema.json.contains("\"maxItems\": 2"):
+		printerr("Expected \"slots\" to hold exactly 2 elements:\n%s" % schema.json)
+	
+	if not schema.instantiate('{"slots": [1]}') is String or schema.instantiate('{"slots": [1, 2]}') is String:
+		printerr("Expected only arrays of 2 elements to be accepted.")
+	
+	DirAccess.remove_absolute(path)
+
+
+func test_schema_result():
+	var result := GodotSchema.from_class_name_ex(&"Fact")
+	
+	if not result.is_ok() or not result.value is GodotSchema or not result.error_message.is_empty():
+		printerr("Expected a successful result holding the schema. Got: %s" % result.error_message)
+		return
+	
+	var schema: GodotSchema = result.value
+	var instance := schema.instantiate_ex('{"text": "Charlie has a pet cat.", "salient_word": "cat", "is_password_related": false}')
+	
+	if not instance.is_ok() or not instance.value is Fact:
+		printerr("Expected instantiate_ex to hold the instance. Got: %s" % instance.error_message)
+	
+	if not schema.get_array_schema_ex("Fact").value is GodotSchema:
+		printerr("Expected get_array_schema_ex to hold the array schema.")
+	
+	var lib := SchemaLibrary.new()
+	var failed := lib.generate_named_class_schema_ex(&"NotAClass")
+	
+	if failed.is_ok() or failed.value != null or failed.error_message.is_empty():
+		printerr("Expected a failed result holding the error message.")
+	
+	lib.free()
+
+
+func test_error_codes():
+	if GodotSchema.from_class_name_ex(&"NotAClass").error_code != SchemaResult.CLASS_NOT_FOUND:
+		printerr("Expected a missing class to be reported as CLASS_NOT_FOUND.")
+	
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Fact")
+	var codes := {
+		"not json": SchemaResult.JSON_PARSE_ERROR,
+		'{"text": 1, "salient_word": "", "is_password_related": false}': SchemaResult.VALIDATION_FAILED,
+		'{"text": "", "salient_word": "", "is_password_related": false}': SchemaResult.OK,
+	}
+	
+	for json in codes:
+		var result := schema.instantiate_ex(json)
+		
+		if result.error_code != codes[json]:
+			printerr("Expected error code %d for %s. Got: %d" % [codes[json], json, result.error_code])
+
+
+func test_schema_errors():
+	var lib := SchemaLibrary.new()
+	lib.unsupported_property_policy = GodotSchema.UNSUPPORTED_ERROR
+	var result := lib.generate_named_class_schema_ex(&"QuoteFact")
+	
+	if result.error_code != SchemaResult.UNSUPPORTED_PROPERTY_TYPE or not result.error_message.contains("on_quoted"):
+		printerr("Expected the unsupported property to be named. Got: %s" % result.error_message)
+	
+	# Without validation, the conversion itself reports where it failed.
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Person")
+	schema.set_validator_backend(GodotSchema.VALIDATOR_TRUST)
+	result = schema.instantiate_ex('{"gender": "Female", "first_name": "", "last_name": "", "password": "", '
+		+ '"main_fact": {"text": "", "salient_word": "", "is_password_related": false}, "facts": [{"text": 1, "salient_word": "", "is_password_related": false}]}')
+	
+	if result.error_code != SchemaResult.INSTANTIATION_FAILED or not result.error_message.contains("/facts/0/text"):
+		printerr("Expected the conversion error to point at the offending value. Got: %s" % result.error_message)
+	
+	lib.free()
+
+
+func test_failure_hook():
+	var failures := []
+	GodotSchema.set_failure_hook(func(schema_name: String, _error_summary: String, input_hash: int): failures.append([schema_name, input_hash]))
+	
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Fact")
+	var invalid := '{"text": 1, "salient_word": "", "is_password_related": false}'
+	schema.instantiate(invalid)
+	schema.instantiate(invalid)
+	schema.is_valid(invalid)
+	
+	if failures.size() != 2 or failures[0] != failures[1] or failures[0][0] != "Fact":
+		printerr("Expected both failed instantiations of \"Fact\" to be reported with the same input hash. Got: %s" % [failures])
+	
+	GodotSchema.clear_failure_hook()
+	schema.instantiate(invalid)
+	
+	if failures.size() != 2:
+		printerr("Expected failures to no longer be reported after clearing the hook.")
+
+
+func test_library_management():
+	var lib := SchemaLibrary.new()
+	lib.generate_named_class_schema(&"Person")
+	lib.generate_named_class_schema(&"Fact")
+	
+	if lib.get_class_names() != PackedStringArray(["Fact", "Person"]) or not lib.has_schema(&"Fact"):
+		printerr("Expected both classes to be cached, sorted by name. Got: %s" % lib.get_class_names())
+	
+	if not lib.remove_schema(&"Fact") or lib.remove_schema(&"Fact") or lib.has_schema(&"Fact"):
+		printerr("Expected \"Fact\" to be removed once.")
+	
+	lib.clear()
+	
+	if not lib.get_class_names().is_empty() or not lib.schemas.is_empty():
+		printerr("Expected clear to remove every cached schema.")
+	
+	lib.free()
+
+
+func test_project_schemas():
+	var lib := SchemaLibrary.new()
+	var report := lib.generate_all_project_schemas("")
+	
+	for class_name_ in ["Fact", "Person", "StatBlock"]:
+		if not report.get("succeeded", PackedStringArray()).has(class_name_) or not lib.has_schema(class_name_):
+			printerr("Expected \"%s\" to be generated and cached. Got: %s" % [class_name_, report])
+	
+	if not report.get("failed", {}).is_empty():
+		printerr("Expected every project class to be generated. Got: %s" % report.get("failed"))
+	
+	lib.clear()
+	report = lib.generate_all_project_schemas("Fa")
+	
+	if report.get("succeeded") != PackedStringArray(["Fact"]) or lib.get_class_names() != PackedStringArray(["Fact"]):
+		printerr("Expected only the classes starting with the prefix to be generated. Got: %s" % report)
+	
+	lib.free()
+
+
+func test_instantiation_plan():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Person")
+	var plan = schema.plan('{"gender": "Male", "first_name": "Charlie", "last_name": "Whimsby", "password": "", '
+		+ '"facts": [{"text": "Charlie has a pet cat.", "salient_word": "cat", "is_password_related": false}], '
+		+ '"main_fact": {"text": "Charlie is tall.", "salient_word": "tall", "is_password_related": false}}')
+	
+	if not plan is InstantiationPlan:
+		printerr(plan)
+		return
+	
+	if plan.get_classes().count("Fact") != 2 or not plan.get_classes().has("Person"):
+		printerr("Expected the plan to construct a Person and 2 facts. Got: %s" % plan.get_classes())
+	
+	var description = plan.to_dictionary()
+	
+	if description.get("class") != "Person" or description.get("properties", {}).get("first_name") != "Charlie":
+		printerr("Expected the plan to describe the Person's properties. Got: %s" % description)
+	
+	var person = schema.apply(plan)
+	
+	if not person is Person or person.main_fact.salient_word != "tall" or person.facts.size() != 1:
+		printerr("Expected applying the plan to construct the Person. Got: %s" % person)
+	
+	if not schema.plan('{"first_name": 1}') is String:
+		printerr("Expected invalid input to be rejected before planning.")
+
+
+func test_async_generation():
+	var lib := SchemaLibrary.new()
+	add_child(lib)
+	
+	# Class name => whether its validator was compiled.
+	var ready_classes := {}
+	lib.schema_ready.connect(func(class_name_: StringName, schema: GodotSchema): ready_classes[class_name_] = schema.is_validator_compiled())
+	lib.generate_async(PackedStringArray(["Fact", "Person"]))
+	
+	while lib.is_generating():
+		await get_tree().process_frame
+	
+	if ready_classes != {&"Fact": true, &"Person": true}:
+		printerr("Expected both schemas to be emitted with their validators compiled. Got: %s" % [ready_classes])
+	elif not lib.has_schema(&"Fact") or not lib.has_schema(&"Person"):
+		printerr("Expected the generated schemas to be cached.")
+	
+	lib.queue_free()
+
+
+func test_undo_record():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Fact")
+	var fact := Fact.new()
+	fact.text = "Charlie has a pet dog."
+	fact.salient_word = "dog"
+	
+	var record = schema.apply_to(fact, '{"text": "Charlie has a pet cat.", "salient_word": "cat", "is_password_related": false}', true)
+	
+	if not record is UndoRecord:
+		printerr(record)
+		return
+	
+	var changes: Array[Dictionary] = record.get_changes()
+	var salient_word := changes.filter(func(change: Dictionary): return change["property"] == "salient_word")
+	
+	if changes.size() != 3 or salient_word.size() != 1 or salient_word[0]["before"] != "dog" or salient_word[0]["after"] != "cat":
+		printerr("Expected one change per assigned property. Got: %s" % [changes])
+	
+	if record.revert() != 3 or fact.salient_word != "dog":
+		printerr("Expected revert to assign the previous values. Got: %s" % fact.salient_word)
+	
+	if record.reapply() != 3 or fact.salient_word != "cat":
+		printerr("Expected reapply to assign the new values. Got: %s" % fact.salient_word)
+	
+	var undo_redo := UndoRedo.new()
+	record.add_to_undo_redo(undo_redo, "Apply fact")
+	
+	if fact.salient_word != "cat" or not undo_redo.undo() or fact.salient_word != "dog":
+		printerr("Expected the change to be undone with the UndoRedo history. Got: %s" % fact.salient_word)
+	elif not undo_redo.redo() or fact.salient_word != "cat":
+		printerr("Expected the change to be redone with the UndoRedo history. Got: %s" % fact.salient_word)
+	
+	undo_redo.free()
+
+
+func test_strict_numbers():
+	var schema: GodotSchema = GodotSchema.from_shape({"speed": TYPE_FLOAT})
+	var strict := {"strict_numbers": true}
+	
+	if not schema.instantiate_with_options('{"speed": 1}', {}) is Dictionary:
+		printerr("Expected integers to be accepted for float properties by default.")
+	
+	var result = schema.instantiate_with_options('{"speed": 1}', strict)
+	
+	if not result is String or not result.contains("/speed"):
+		printerr("Expected strict numbers to reject the integer at \"/speed\". Got: %s" % result)
+	
+	if not schema.instantiate_with_options('{"speed": 1.0}', strict) is Dictionary:
+		printerr("Expected strict numbers to accept floats.")
+	
+	if not schema.instantiate_with_options('{"speed": 1.0}', {"strict": true}) is String:
+		printerr("Expected unknown options to be rejected.")
+
+
+func test_library_file():
+	var path := "user://schema_tester_library.json"
+	var lib := SchemaLibrary.new()
+	var saved: GodotSchema = lib.generate_named_class_schema(&"Person")
+	
+	var count = lib.save_to_file(path)
+	
+	if count != lib.schemas.size():
+		printerr("Expected every cached schema to be saved. Got: %s" % count)
+	
+	var loaded_lib := SchemaLibrary.new()
+	
+	if loaded_lib.load_from_file(path) != count or not loaded_lib.has_schema(&"Person"):
+		printerr("Expected the saved schema to be loaded.")
+	else:
+		var loaded: GodotSchema = loaded_lib.get_named_class_schema(&"Person")
+		
+		if loaded.json != saved.json or loaded.is_validator_compiled():
+			printerr("Expected the loaded schema to match the saved one, with its validator compiled lazily.")
+	
+	if not loaded_lib.load_from_file("user://schema_tester_missing.json") is String:
+		printerr("Expected loading a missing file to fail.")
+	
+	DirAccess.remove_absolute(path)
+	loaded_lib.free()
+	lib.free()
+
+
+func test_verify_all():
+	var lib := SchemaLibrary.new()
+	var schema: GodotSchema = lib.generate_named_class_schema(&"Fact")
+	
+	if not lib.verify_all().is_empty():
+		printerr("Expected a freshly generated schema to match its class. Got: %s" % [lib.verify_all()])
+	
+	schema.remove_property("salient_word")
+	var mismatches := lib.verify_all()
+	
+	if mismatches.size() != 1 or mismatches[0].get("property") != "salient_word" or mismatches[0].get("change") != "added":
+		printerr("Expected the property missing from the cached schema to be reported as added. Got: %s" % [mismatches])
+	
+	lib.free()
+
+
+func test_export_schemas():
+	var dir := "user://schema_tester_export"
+	var lib := SchemaLibrary.new()
+	lib.generate_named_class_schema(&"Fact")
+	lib.generate_named_class_schema(&"Person")
+	
+	if lib.export_schemas(dir, false) != 2:
+		printerr("Expected one file per cached schema.")
+	
+	for class_name_ in ["Fact", "Person"]:
+		var path := "%s/%s.schema.json" % [dir, class_name_]
+		var schema = JSON.parse_string(FileAccess.get_file_as_string(path))
+		
+		if not schema is Dictionary or not schema.has("properties"):
+			printerr("Expected \"%s\" to contain the schema of %s. Got: %s" % [path, class_name_, schema])
+		
+		DirAccess.remove_absolute(path)
+	
+	lib.free()
+
+
+func test_variant_encoding():
+	var schema: GodotSchema = GodotSchema.from_shape({"extra": TYPE_DICTIONARY})
+	var instance := {"extra": {"position": Vector3(1, 2, 3)}}
+	
+	if schema.get_variant_encoding() != GodotSchema.ENCODING_PLAIN:
+		printerr("Expected plain encoding to be the default.")
+	
+	var restored = schema.instantiate(schema.serialize_instance(instance))
+	
+	if not restored is Dictionary or not restored["extra"]["position"] is Dictionary:
+		printerr("Expected the plain Vector3 to be instantiated as a Dictionary. Got: %s" % [restored])
+	
+	schema.set_variant_encoding(GodotSchema.ENCODING_TAGGED)
+	var json := schema.serialize_instance(instance)
+	restored = schema.instantiate(json)
+	
+	if not json.contains("\"$type\":\"Vector3\"") and not json.contains("\"$type\": \"Vector3\""):
+		printerr("Expected the Vector3 to be tagged with its type. Got: %s" % json)
+	elif not restored is Dictionary or restored["extra"]["position"] != Vector3(1, 2, 3):
+		printerr("Expected the tagged Vector3 to be instantiated back. Got: %s" % [restored])
+
+
+func test_json_schema_import():
+	var json := '{"type": "object", "required": ["name", "items"], "properties": {"name": {"type": "string", "enum": ["sword", "shield"]}, ' \
+		+ '"items": {"type": "array", "items": {"$ref": "#/$defs/Item"}}}, ' \
+		+ '"$defs": {"Item": {"type": "object", "properties": {"count": {"type": "integer"}}}}}'
+	var schema = GodotSchema.from_json_schema(json)
+	
+	if not schema is GodotSchema:
+		printerr(schema)
+		return
+	
+	var result = schema.instantiate('{"name": "sword", "items": [{"count": 2}]}')
+	
+	if not result is Dictionary or result["items"][0]["count"] != 2 or typeof(result["items"][0]["count"]) != TYPE_INT:
+		printerr("Expected the referenced definition to be instantiated. Got: %s" % [result])
+	
+	# The enum has no Godot equivalent, but the original document still validates it.
+	if not schema.instantiate('{"name": "axe", "items": []}') is String:
+		printerr("Expected the original document's enum to be validated.")
+	
+	if not GodotSchema.from_json_schema("not json") is String:
+		printerr("Expected an invalid document to be rejected.")
+
+
+func test_round_trip_annotations():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Person")
+	
+	if schema.json.contains("x-godot"):
+		printerr("Expected round-trip annotations to be disabled by default:\n%s" % schema.json)
+	
+	schema.set_round_trip_annotations(true)
+	
+	if not schema.json.contains("\"x-godot-class\": \"Person\"") or not schema.json.contains("\"x-godot-enum\""):
+		printerr("Expected the class and its enum to be annotated:\n%s" % schema.json)
+	
+	if schema.open_ai_response_format("person").contains("x-godot"):
+		printerr("Expected provider payloads to never include the annotations.")
+
+
+func test_type_annotations():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Person")
+	
+	if schema.get_type_annotations() or schema.json.contains("x-godot-type"):
+		printerr("Expected type annotations to be disabled by default.")
+	
+	schema.set_type_annotations(true)
+	var facts: Dictionary = JSON.parse_string(schema.json)["properties"]["facts"]
+	
+	if facts.get("x-godot-type") != TYPE_ARRAY or facts.get("x-godot-hint") != PROPERTY_HINT_ARRAY_TYPE:
+		printerr("Expected \"facts\" to be annotated as a typed Array. Got: %s" % facts)
+	
+	if not schema.instantiate(schema.example_json(false)) is Person:
+		printerr("Expected validation to ignore the annotations.")
+
+
+func test_json_hooks():
+	var path := "user://schema_tester_json_hooks.gd"
+	write_script(path, "extends RefCounted\n\nvar label: String\n\n"
+		+ "func _from_json(data: Dictionary):\n\tlabel = data.label.to_upper()\n\n"
+		+ "func _to_json() -> Dictionary:\n\treturn {\"label\": label.to_lower()}\n")
+	var schema: GodotSchema = GodotSchema.from_class_script(load(path))
+	var instance = schema.instantiate('{"label": "sword"}')
+	
+	if instance is String or instance.label != "SWORD":
+		printerr("Expected `_from_json` to receive the converted values. Got: %s" % instance)
+	elif JSON.parse_string(schema.serialize_instance(instance)) != {"label": "sword"}:
+		printerr("Expected `_to_json` to provide the serialized values. Got: %s" % schema.serialize_instance(instance))
+	
+	DirAccess.remove_absolute(path)
+
+
+func test_init_parameters():
+	var path := "user://schema_tester_init_parameters.gd"
+	write_script(path, "extends RefCounted\n\nvar title: String\nvar level: int\n\n"
+		+ "func _init(title: String, level: int = 1):\n\tself.title = title + \"!\"\n\tself.level = level\n")
+	var schema: GodotSchema = GodotSchema.from_class_script(load(path))
+	var instance = schema.instantiate('{"title": "hero", "level": 3}')
+	
+	# Values passed to `_init` are not assigned again afterward.
+	if instance is String or instance.title != "hero!" or instance.level != 3:
+		printerr("Expected the matching values to be passed to `_init`. Got: %s" % instance)
+	
+	DirAccess.remove_absolute(path)
+
+
+func test_setter_check():
+	var path := "user://schema_tester_setter_check.gd"
+	write_script(path, "extends RefCounted\n\nvar hp: int:\n\tset(value):\n\t\thp = clampi(value, 0, 10)\n")
+	var schema: GodotSchema = GodotSchema.from_class_script(load(path))
+	var instance = schema.instantiate_with_options('{"hp": 50}', {"setter_check": GodotSchema.SETTERS_UNCHECKED})
+	
+	if instance is String or instance.hp != 10:
+		printerr("Expected the clamped value to be kept without setter checks. Got: %s" % instance)
+	
+	var result = schema.instantiate_with_options('{"hp": 50}', {"setter_check": GodotSchema.SETTERS_ERROR})
+	
+	if not result is String or not result.contains("hp"):
+		printerr("Expected the clamping setter to fail the instantiation. Got: %s" % result)
+	
+	if schema.instantiate_with_options('{"hp": 5}', {"setter_check": GodotSchema.SETTERS_ERROR}) is String:
+		printerr("Expected values kept by the setter to pass the check.")
+	
+	DirAccess.remove_absolute(path)
+
+
+func test_resource_paths():
+	var path := "user://schema_tester_resource_paths.gd"
+	write_script(path, "extends RefCounted\n\nvar source: Script\n")
+	var script: Script = load(path)
+	
+	var lib := SchemaLibrary.new()
+	lib.enable_resource_paths(PackedStringArray())
+	var schema: GodotSchema = lib.generate_unnamed_class_schema(script)
+	var instance = schema.instantiate('{"source": "res://fact.gd"}')
+	
+	if instance is String or instance.source != load("res://fact.gd"):
+		printerr("Expected the path to be loaded into the property. Got: %s" % instance)
+	
+	if not schema.instantiate('{"source": "res://schema_tester.tscn"}') is String:
+		printerr("Expected a resource of another type to be rejected.")
+	
+	var restricted := SchemaLibrary.new()
+	restricted.enable_resource_paths(PackedStringArray(["res://scripts"]))
+	
+	if not restricted.generate_unnamed_class_schema(script).instantiate('{"source": "res://fact.gd"}') is String:
+		printerr("Expected a path outside of the allowed directories to be rejected.")
+	
+	restricted.free()
+	lib.free()
+	DirAccess.remove_absolute(path)
+
+
+func test_scene_paths():
+	var path := "user://schema_tester_scene_paths.gd"
+	write_script(path, "extends RefCounted\n\nvar scene: PackedScene\nvar printer: Node\n")
+	
+	var lib := SchemaLibrary.new()
+	lib.scene_instancing = true
+	var schema: GodotSchema = lib.generate_unnamed_class_schema(load(path))
+	var instance = schema.instantiate('{"scene": "res://properties_printer.tscn", "printer": "res://properties_printer.tscn"}')
+	
+	if instance is String:
+		printerr(instance)
+	else:
+		if not instance.scene is PackedScene or instance.scene.resource_path != "res://properties_printer.tscn":
+			printerr("Expected the PackedScene property to hold the loaded scene. Got: %s" % instance.scene)
+		
+		if not instance.printer is Node or instance.printer.name != &"PropertiesPrinter":
+			printerr("Expected the Node property to hold the scene's instantiated root. Got: %s" % instance.printer)
+		else:
+			instance.printer.free()
+	
+	if not schema.instantiate('{"scene": "res://fact.gd", "printer": "res://properties_printer.tscn"}') is String:
+		printerr("Expected paths without a scene extension to be rejected.")
+	
+	lib.free()
+	DirAccess.remove_absolute(path)
+
+
+func test_instantiate_resource():
+	var path := "user://schema_tester_item.gd"
+	var save_path := "user://schema_tester_item.tres"
+	write_script(path, "extends Resource\n\n@export var title: String\n")
+	var schema: GodotSchema = GodotSchema.from_class_script(load(path))
+	var item = schema.instantiate_resource('{"title": "Sword"}', save_path)
+	
+	if not item is Resource or item.resource_path != save_path:
+		printerr("Expected the saved resource to take the save path. Got: %s" % item)
+	elif ResourceLoader.load(save_path, "", ResourceLoader.CACHE_MODE_IGNORE).title != "Sword":
+		printerr("Expected the resource to be saved with its properties.")
+	
+	var fact_schema: GodotSchema = GodotSchema.from_class_name(&"Fact")
+	
+	if not fact_schema.instantiate_resource('{"text": "", "salient_word": "", "is_password_related": false}', save_path) is String:
+		printerr("Expected classes not extending Resource to be rejected.")
+	
+	DirAccess.remove_absolute(save_path)
+	DirAccess.remove_absolute(path)
+
+
+func test_infer_from_value():
+	var sample := {"name": "Charlie", "level": 3, "scores": [1.5, 2.5], "position": Vector2(1, 2)}
+	var schema = GodotSchema.infer_from_value(sample)
+	
+	if not schema is GodotSchema:
+		printerr(schema)
+		return
+	
+	var result = schema.instantiate(schema.serialize_instance(sample))
+	
+	if not result is Dictionary or result.get("position") != Vector2(1, 2) or typeof(result.get("level")) != TYPE_INT:
+		printerr("Expected the sample to round trip through its inferred schema. Got: %s" % [result])
+	
+	if schema.is_valid('{"name": "Charlie", "level": 3, "scores": ["high"], "position": {"x": 1, "y": 2}}'):
+		printerr("Expected the inferred array to be typed after its elements.")
+
+
+func test_shapes():
+	var schema = GodotSchema.from_shape({"name": "String", "stats": {"hp": TYPE_INT}, "tags": [TYPE_STRING], "fact": &"Fact"})
+	
+	if not schema is GodotSchema:
+		printerr(schema)
+		return
+	
+	var result = schema.instantiate('{"name": "Charlie", "stats": {"hp": 3}, "tags": ["brave"], '
+		+ '"fact": {"text": "Charlie has a pet cat.", "salient_word": "cat", "is_password_related": false}}')
+	
+	if not result is Dictionary or not result["fact"] is Fact or result["stats"]["hp"] != 3 or not result["tags"] is Array[String]:
+		printerr("Expected the shape's nested object, typed array and class to be instantiated. Got: %s" % [result])
+	
+	if not GodotSchema.from_shape({"name": "NotAType"}) is String:
+		printerr("Expected unknown type names to be rejected.")
+
+
+func test_property_editing():
+	var schema: GodotSchema = GodotSchema.from_shape({"name": TYPE_STRING})
+	var error = schema.add_property("hp", GodotSchema.from_shape(TYPE_INT))
+	schema.rebuild()
+	
+	if error != null or not schema.is_valid('{"name": "Charlie", "hp": 3}') or schema.is_valid('{"name": "Charlie"}'):
+		printerr("Expected the added property to be required. Got: %s" % error)
+	
+	schema.rename_property("hp", "health")
+	schema.remove_property("name")
+	
+	if schema.is_valid('{"health": 3}'):
+		printerr("Expected edits to only take effect once rebuilt.")
+	
+	schema.rebuild()
+	
+	if not schema.is_valid('{"health": 3}') or schema.is_valid('{"name": "Charlie", "health": 3}'):
+		printerr("Expected the property to be renamed and the other removed:\n%s" % schema.json)
+	
+	if not schema.remove_property("name") is String:
+		printerr("Expected removing a missing property to fail.")
+
+
+func test_property_projections():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Person")
+	var picked = schema.pick_properties(PackedStringArray(["first_name", "main_fact"]))
+	
+	if not picked is GodotSchema or picked.get_property_names() != PackedStringArray(["first_name", "main_fact"]):
+		printerr("Expected only the picked properties. Got: %s" % picked)
+	
+	var omitted = schema.omit_properties(PackedStringArray(["facts", "main_fact"]))
+	
+	if not omitted is GodotSchema or omitted.json.contains("\"Fact\""):
+		printerr("Expected the definitions of the omitted properties to be dropped:\n%s" % omitted.json)
+	else:
+		var person = omitted.instantiate('{"gender": "Male", "first_name": "Charlie", "last_name": "Whimsby", "password": ""}')
+		
+		if not person is Person or person.first_name != "Charlie" or not person.facts.is_empty():
+			printerr("Expected the omitted properties to keep their default values. Got: %s" % person)
+
+
+func test_merge_schemas():
+	var left: GodotSchema = GodotSchema.from_shape({"name": TYPE_STRING, "hp": TYPE_INT})
+	var right: GodotSchema = GodotSchema.from_shape({"hp": TYPE_FLOAT, "mana": TYPE_INT})
+	var json := '{"name": "Charlie", "hp": 1.5, "mana": 2}'
+	
+	if not left.merge_with(right, GodotSchema.MERGE_ERROR) is String:
+		printerr("Expected \"hp\", typed differently by both schemas, to fail the merge.")
+	
+	var merged = left.merge_with(right, GodotSchema.MERGE_PREFER_LEFT)
+	
+	if not merged is GodotSchema or merged.get_property_names().size() != 3 or merged.is_valid(json):
+		printerr("Expected the left schema's integer \"hp\" to be kept.")
+	
+	merged = left.merge_with(right, GodotSchema.MERGE_PREFER_RIGHT)
+	
+	if not merged is GodotSchema or not merged.is_valid(json):
+		printerr("Expected the right schema's float \"hp\" to be kept.")
+
+
+func test_schema_config():
+	var path := "user://schema_tester_config.gd"
+	write_script(path, "extends RefCounted\n\nvar hp: int\nvar nickname: String\nvar cached_path: String\n\n"
+		+ "static func _json_schema_config() -> Dictionary:\n\treturn {\n"
+		+ "\t\t\"exclude\": [\"cached_path\"],\n\t\t\"optional\": [\"nickname\"],\n"
+		+ "\t\t\"descriptions\": {\"hp\": \"Current hit points.\"},\n\t\t\"constraints\": {\"hp\": {\"minimum\": 0, \"maximum\": 100}},\n\t}\n")
+	var schema: GodotSchema = GodotSchema.from_class_script(load(path))
+	
+	if schema.get_property_names() != PackedStringArray(["hp", "nickname"]):
+		printerr("Expected \"cached_path\" to be excluded. Got: %s" % schema.get_property_names())
+	
+	if not schema.json.contains("Current hit points."):
+		printerr("Expected \"hp\" to be described:\n%s" % schema.json)
+	
+	if not schema.is_valid('{"hp": 50}') or schema.is_valid('{"hp": 150}'):
+		printerr("Expected \"nickname\" to be optional and \"hp\" to be constrained.")
+	
+	DirAccess.remove_absolute(path)
+
+
+func test_exclude_constant():
+	var path := "user://schema_tester_exclude.gd"
+	write_script(path, "extends RefCounted\n\nconst JSON_SCHEMA_EXCLUDE := [\"cache\"]\n\nvar hp: int\nvar cache: Dictionary\n")
+	var schema: GodotSchema = GodotSchema.from_class_script(load(path))
+	
+	if schema.get_property_names() != PackedStringArray(["hp"]):
+		printerr("Expected the properties listed in `JSON_SCHEMA_EXCLUDE` to be left out. Got: %s" % schema.get_property_names())
+	
+	DirAccess.remove_absolute(path)
+
+
+func test_usage_filter():
+	var path := "user://schema_tester_usage_filter.gd"
+	write_script(path, "extends RefCounted\n\n@export var hp: int\nvar secret: int\n")
+	var script: Script = load(path)
+	
+	var lib := SchemaLibrary.new()
+	
+	if lib.get_usage_filter() != PROPERTY_USAGE_NONE or lib.generate_unnamed_class_schema(script).get_property_names().size() != 2:
+		printerr("Expected every property to be included by default.")
+	
+	var exported := SchemaLibrary.new()
+	exported.set_usage_filter(PROPERTY_USAGE_SCRIPT_VARIABLE | PROPERTY_USAGE_EDITOR)
+	var names: PackedStringArray = exported.generate_unnamed_class_schema(script).get_property_names()
+	
+	if names != PackedStringArray(["hp"]):
+		printerr("Expected only the exported script variable to be included. Got: %s" % names)
+	
+	exported.free()
+	lib.free()
+	DirAccess.remove_absolute(path)
+
+
+func test_property_order():
+	var expected := PackedStringArray(["gender", "first_name", "last_name", "password", "facts", "main_fact"])
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Person")
+	var properties: Dictionary = JSON.parse_string(schema.json)["properties"]
+	
+	if schema.get_property_names() != expected or PackedStringArray(properties.keys()) != expected:
+		printerr("Expected properties in declaration order. Got: %s" % [properties.keys()])
+	
+	var shape: GodotSchema = GodotSchema.from_shape({"zeta": TYPE_INT, "alpha": TYPE_INT})
+	
+	if shape.get_property_names() != PackedStringArray(["zeta", "alpha"]):
+		printerr("Expected shape properties in declaration order. Got: %s" % shape.get_property_names())
+
+
+func test_global_enums():
+	var path := "user://schema_tester_global_enums.gd"
+	write_script(path, "extends RefCounted\n\nvar side: Side\n")
+	var schema: GodotSchema = GodotSchema.from_class_script(load(path))
+	
+	if not schema.json.contains("SIDE_BOTTOM"):
+		printerr("Expected the global enum's variants to be listed:\n%s" % schema.json)
+	
+	var instance = schema.instantiate('{"side": "SIDE_BOTTOM"}')
+	
+	if instance is String or instance.side != SIDE_BOTTOM:
+		printerr("Expected the variant's name to be instantiated as its value. Got: %s" % instance)
+	
+	DirAccess.remove_absolute(path)
+
+
+func test_enum_variant_descriptions():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Person")
+	var error = schema.set_enum_variant_description("Gender", "Female", "Identifies as a woman.")
+	schema.rebuild()
+	
+	if error != null or not schema.json.contains('"oneOf"') or not schema.json.contains("Identifies as a woman."):
+		printerr("Expected the enum variants to be listed with their descriptions. Got: %s\n%s" % [error, schema.json])
+	
+	if not schema.set_enum_variant_description("Gender", "Other", "Unknown.") is String:
+		printerr("Expected describing a missing variant to fail.")
+	
+	if not schema.set_enum_variant_description("Fact", "Female", "Not an enum.") is String:
+		printerr("Expected describing a variant of a non-enum definition to fail.")
+
+
+func test_integer_enums():
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Person").omit_properties(PackedStringArray(["facts", "main_fact"]))
+	schema.set_integer_enums(true)
+	
+	var gender: Dictionary = JSON.parse_string(schema.json)["$defs"].get("Gender", {})
+	
+	if not schema.get_integer_enums() or gender.get("type") != "integer" or gender.get("enum") != [0.0, 1.0]:
+		printerr("Expected the enum to be described by its values:\n%s" % schema.json)
+	
+	var person = schema.instantiate('{"gender": 1, "first_name": "Charlie", "last_name": "Whimsby", "password": ""}')
+	
+	if not person is Person or person.gender != Person.Gender.Female:
+		printerr("Expected the enum to be instantiated from its value. Got: %s" % person)
+	else:
+		var serialized = JSON.parse_string(schema.serialize_instance(person))
+		
+		if not serialized is Dictionary or serialized.get("gender") != 1.0:
+			printerr("Expected the enum to be serialized as its value. Got: %s" % serialized)
+	
+	schema.set_validator_backend(GodotSchema.VALIDATOR_STRUCTURAL)
+	person = schema.instantiate('{"gender": "Female", "first_name": "Charlie", "last_name": "Whimsby", "password": ""}')
+	
+	if not person is Person or person.gender != Person.Gender.Female:
+		printerr("Expected the enum to also be instantiated from its name. Got: %s" % person)
+
+
+func test_array_constraints():
+	var schema: GodotSchema = GodotSchema.from_shape({"name": TYPE_STRING, "tags": [TYPE_STRING]})
+	var error = schema.set_array_constraints("tags", 1, 2, false)
+	schema.rebuild()
+	
+	if error != null or not schema.is_valid('{"name": "Charlie", "tags": ["a", "a"]}'):
+		printerr("Expected an array within its bounds to be valid. Got: %s" % error)
+	
+	if schema.is_valid('{"name": "Charlie", "tags": []}') or schema.is_valid('{"name": "Charlie", "tags": ["a", "b", "c"]}'):
+		printerr("Expected arrays outside of their bounds to be rejected:\n%s" % schema.json)
+	
+	if not schema.set_array_constraints("tags", 3, 2, false) is String:
+		printerr("Expected `min_items` above `max_items` to be rejected.")
+	
+	if not schema.set_array_constraints("name", 1, -1, false) is String:
+		printerr("Expected constraining a property that is not an array to fail.")
+
+
+func test_global_unsupported_policy():
+	GodotSchema.set_unsupported_property_policy(GodotSchema.UNSUPPORTED_STUB_AS_NULL)
+	var stubbed = GodotSchema.from_class_name(&"QuoteFact")
+	
+	if not stubbed is GodotSchema or not stubbed.json.contains('"on_quoted"') or stubbed.get_generation_warnings().is_empty():
+		printerr("Expected the `Callable` property to be stubbed and reported. Got: %s" % stubbed)
+	
+	GodotSchema.set_unsupported_property_policy(GodotSchema.UNSUPPORTED_ERROR)
+	
+	if GodotSchema.from_class_name(&"QuoteFact") is GodotSchema:
+		printerr("Expected the `Callable` property to fail the generation.")
+	
+	GodotSchema.set_unsupported_property_policy(GodotSchema.UNSUPPORTED_SKIP)
+	var skipped = GodotSchema.from_class_name(&"QuoteFact")
+	
+	if not skipped is GodotSchema or skipped.json.contains('"on_quoted"') or skipped.get_generation_warnings().is_empty():
+		printerr("Expected the `Callable` property to be skipped and reported. Got: %s" % skipped)
+
+
+func test_untyped_properties():
+	var path := "user://schema_tester_untyped.gd"
+	write_script(path, "extends RefCounted\n\nvar label: String\nvar data\n")
+	var schema: GodotSchema = GodotSchema.from_class_script(load(path))
+	var data: Dictionary = JSON.parse_string(schema.json)["properties"].get("data", {})
+	
+	if data.get("anyOf", []).size() != 7:
+		printerr("Expected the untyped property to accept every JSON type:\n%s" % schema.json)
+	
+	for value in ['null', 'true', '3', '1.5', '"sword"', '["a", "b"]', '{"name": "Charlie"}']:
+		var instance = schema.instantiate('{"label": "loot", "data": %s}' % value)
+		
+		if instance is String or instance.data != JSON.parse_string(value):
+			printerr("Expected the untyped property to hold %s. Got: %s" % [value, instance])
+
+
+func test_reload_signal():
+	var path := "user://schema_tester_reload_signal.gd"
+	write_script(path, "extends RefCounted\n\nvar health: int\n")
+	
+	var lib := SchemaLibrary.new()
+	lib.generate_unnamed_class_schema(load(path))
+	var reloaded := []
+	lib.schema_reloaded.connect(func(reloaded_class): reloaded.append(reloaded_class))
+	
+	if not lib.reload_changed().is_empty() or not reloaded.is_empty():
+		printerr("Expected an unmodified script to not be reloaded. Got: %s" % [reloaded])
+	
+	# Modification times are in seconds.
+	await get_tree().create_timer(1.1).timeout
+	write_script(path, "extends RefCounted\n\nvar health: int\nvar mana: int\n")
+	var names := lib.reload_changed()
+	
+	if names.size() != 1 or reloaded.size() != 1 or String(reloaded[0]) != names[0]:
+		printerr("Expected `schema_reloaded` to be emitted once for the modified script. Got: %s, %s" % [names, reloaded])
+	
+	lib.free()
+
+
+func test_breaking_changes():
+	var old: GodotSchema = GodotSchema.from_shape({"name": TYPE_STRING, "hp": TYPE_INT})
+	var same: GodotSchema = GodotSchema.from_shape({"name": TYPE_STRING, "hp": TYPE_INT})
+	var compatible = same.is_backward_compatible_with(old)
+	
+	if not compatible is bool or not compatible:
+		printerr("Expected identical schemas to be compatible. Got: %s" % [compatible])
+	
+	var new: GodotSchema = GodotSchema.from_shape({"name": TYPE_INT, "mana": TYPE_INT})
+	var changes = new.is_backward_compatible_with(old)
+	var by_property := {}
+	
+	if changes is Array:
+		for change in changes:
+			by_property[change["property"]] = change["change"]
+	
+	if by_property != {"hp": "removed", "name": "type_changed", "mana": "required_added"}:
+		printerr("Expected the removed, retyped and new required properties to be reported. Got: %s" % [changes])
+
+
+func test_conditional_schemas():
+	var json := '{"type": "object", "required": ["kind"], "properties": {"kind": {"type": "string"}, "range": {"type": "integer"}, ' \
+		+ '"damage": {"if": {"type": "integer"}, "then": {"type": "integer", "minimum": 0}, "else": {"type": "string"}}}, ' \
+		+ '"dependentRequired": {"range": ["damage"]}}'
+	var schema = GodotSchema.from_json_schema(json)
+	
+	if not schema is GodotSchema:
+		printerr(schema)
+		return
+	
+	if not schema.json.contains("dependentRequired") or not schema.json.contains("\"else\""):
+		printerr("Expected the conditional keywords to be kept:\n%s" % schema.json)
+	
+	var result = schema.instantiate('{"kind": "bow", "damage": 3}')
+	
+	if not result is Dictionary or typeof(result["damage"]) != TYPE_INT:
+		printerr("Expected the `then` branch to be instantiated. Got: %s" % [result])
+	
+	result = schema.instantiate('{"kind": "bow", "damage": "high"}')
+	
+	if not result is Dictionary or result["damage"] != "high":
+		printerr("Expected the `else` branch to be instantiated. Got: %s" % [result])
+	
+	if not schema.instantiate('{"kind": "bow", "damage": true}') is String:
+		printerr("Expected a value matching neither branch to be rejected.")
+	
+	if not schema.instantiate('{"kind": "bow", "range": 5}') is String:
+		printerr("Expected a property missing its dependent required property to be rejected.")
+
+
+func test_llm_client():
+	# Only registered when built with the `llm_client` feature.
+	if not ClassDB.class_exists(&"LlmStructuredClient"):
+		return
+	
+	var client = ClassDB.instantiate(&"LlmStructuredClient")
+	# Responses are simulated, the actual request fails to connect and is ignored.
+	client.endpoint = "http://127.0.0.1:9/v1/chat/completions"
+	add_child(client)
+	
+	var results := []
+	client.connect("result", func(instance): results.append(instance))
+	client.connect("failed", func(error): results.append(error))
+	var schema: GodotSchema = GodotSchema.from_class_name(&"Fact")
+	var content := '{"text": "Charlie grows carrots.", "salient_word": "carrot", "is_password_related": false}'
+	var body := JSON.stringify({"choices": [{"message": {"role": "assistant", "content": content}}]}).to_utf8_buffer()
+	
+	if client.is_busy() or client.request("Describe a fact.", schema) != null or not client.is_busy():
+		printerr("Expected the client to be busy only once a request is sent.")
+	
+	if not client.request("Describe another fact.", schema) is String:
+		printerr("Expected a second request to be rejected while the first is pending.")
+	
+	client.on_request_completed(HTTPRequest.RESULT_SUCCESS, 200, PackedStringArray(), body)
+	
+	if client.is_busy() or results.size() != 1 or not results[0] is Fact or results[0].salient_word != "carrot":
+		printerr("Expected the response to be instantiated and emitted with `result`. Got: %s" % [results])
+	
+	var retries := []
+	client.connect("retrying", func(attempt, _error): retries.append(attempt))
+	var invalid := JSON.stringify({"choices": [{"message": {"role": "assistant", "content": '{"text": 1}'}}]}).to_utf8_buffer()
+	client.request("Describe a fact.", schema)
+	client.on_request_completed(HTTPRequest.RESULT_SUCCESS, 200, PackedStringArray(), invalid)
+	
+	if not client.is_busy() or retries != [1] or results.size() != 1:
+		printerr("Expected an invalid response to be sent back to the model. Got: %s, %s" % [retries, results])
+	
+	client.cancel()
+	client.on_request_completed(HTTPRequest.RESULT_SUCCESS, 200, PackedStringArray(), body)
+	
+	if client.is_busy() or results.size() != 1:
+		printerr("Expected a cancelled request to emit nothing. Got: %s" % [results])
+	
+	client.free()
+
+
+func test_schema_preview():
+	# Only registered in the editor, when built with the `editor` feature.
+	if not ClassDB.class_exists(&"SchemaPreviewPlugin"):
+		return
+	
+	var plugin = ClassDB.instantiate(&"SchemaPreviewPlugin")
+	add_child(plugin)
+	DisplayServer.clipboard_set("")
+	plugin.on_script_changed(load("res://person.gd"))
+	plugin.copy_open_ai_format()
+	var format = JSON.parse_string(DisplayServer.clipboard_get())
+	
+	if not format is Dictionary or format["json_schema"]["name"] != "Person":
+		printerr("Expected the previewed script's OpenAI format to be copied. Got: %s" % [format])
+	
+	DisplayServer.clipboard_set("")
+	plugin.on_script_changed(null)
+	plugin.copy_open_ai_format()
+	
+	if not DisplayServer.clipboard_get().is_empty():
+		printerr("Expected nothing to be copied without a previewed script.")
+	
+	plugin.free()
+
+
+func test_async_failure():
+	var lib := SchemaLibrary.new()
+	add_child(lib)
+	lib.generate_async(PackedStringArray(["NotAClass"]))
+	
+	# Connected after the call, the failure must still be emitted.
+	var failed_classes := []
+	lib.schema_failed.connect(func(failed_class: StringName, _error_message: String): failed_classes.append(failed_class))
+	
+	while lib.is_generating():
+		await get_tree().process_frame
+	
+	if failed_classes != [&"NotAClass"]:
+		printerr("Expected generate_async to emit schema_failed for \"NotAClass\" on a later frame. Got: %s" % [failed_classes])
+	
+	lib.queue_free()
+
+
+func test_hot_reload():
+	var path := "user://schema_tester_hot_reload.gd"
+	write_script(path, "extends RefCounted\n\nvar health: int\nvar tags: Array[String]\n")
+	
+	var lib := SchemaLibrary.new()
+	var schema: GodotSchema = lib.generate_unnamed_class_schema(load(path))
+	schema.set_schema_id("https://example.com/schemas/hot_reload.json")
+	schema.set_array_constraints("tags", -1, 2, false)
+	schema.rebuild()
+	
+	# Modification times are in seconds.
+	await get_tree().create_timer(1.1).timeout
+	write_script(path, "extends RefCounted\n\nvar health: int\nvar mana: int\nvar tags: Array[String]\n")
+	
+	if lib.reload_changed().is_empty():
+		printerr("Expected the modified script to be reloaded.")
+	elif not schema.json.contains("mana"):
+		printerr("Expected the cached schema to be reloaded in place:\n%s" % schema.json)
+	elif not schema.json.contains("hot_reload.json") or not schema.json.contains("\"maxItems\": 2"):
+		printerr("Expected the reloaded schema to keep its options and edits:\n%s" % schema.json)
+	
+	lib.free()
+
+
+func write_script(path: String, source: String):
+	var file := FileAccess.open(path, FileAccess.WRITE)
+	file.store_string(source)
+	file.close()
+
+
 func test_person():
 	var json = """
 	{
@@ -0,0 +1,21 @@
+class_name StatBlock
+extends Node
+
+
+var hp: int
+var speed: float
+var nickname: String
+var rank: String
+var minions: Array[StatBlock]
+
+
+static func _json_schema_config() -> Dictionary:
+	return {
+		"constraints": {
+			"hp": { "minimum": 1, "maximum": 10 },
+			"speed": { "exclusiveMinimum": 0, "maximum": 20, "multipleOf": 0.5 },
+			"nickname": { "minLength": 12, "maxLength": 16 },
+			"rank": { "enum": ["S", "A", "B"] },
+			"minions": { "maxItems": 2 },
+		},
+	}
@@ -11,11 +11,117 @@ pub mod schema;
 #[derive(GodotClass)]
 #[class(init, base = Node)]
 pub struct SchemaLibrary {
-	#[var] pub schemas: Array<Gd<GodotSchema>>,
+	base: Base<Node>,
+	/// Every cached schema, in generation order. Read-only view, use the library's methods to modify it.
+	#[var(get)] pub schemas: Array<Gd<GodotSchema>>,
+	/// Cached class schemas, keyed by the class they were generated from.
+	pub classes: HashMap<ClassSource, Gd<GodotSchema>>,
+	/// The validator compiled by schemas generated from this library, see [`ValidatorBackend`].
+	#[var] pub validator_backend: ValidatorBackend,
+	/// See [`Self::set_usage_filter()`].
+	#[init(val = PropertyUsageFlags::NONE)]
+	pub usage_filter: PropertyUsageFlags,
+	/// Whether classes generated afterward reference the script class they extend with `allOf`, see [`SchemaOptions::class_inheritance`].
+	///
+	/// Provider payloads (e.g. [`GodotSchema::open_ai_response_format()`]) always repeat the inherited properties.
+	#[var] pub class_inheritance: bool,
+	/// Whether properties prefixed with `_` are left out of classes generated afterward, keeping internal bookkeeping variables out of prompts.
+	/// Properties can also be excluded with a `JSON_SCHEMA_EXCLUDE` script constant.
+	#[var] pub skip_private_properties: bool,
+	/// How properties of types that cannot be represented by a schema (`Callable`, `Signal`, `RID` or `Object` without a class)
+	/// are handled by classes generated afterward, one of:
+	/// - [`GodotSchema::UNSUPPORTED_ERROR`]: Fails the generation.
+	/// - [`GodotSchema::UNSUPPORTED_SKIP`] (the default): Leaves the property out of the schema.
+	/// - [`GodotSchema::UNSUPPORTED_STUB_AS_NULL`]: Describes the property as `null`, instantiation never assigns it.
+	///
+	/// Skipped and stubbed properties are listed by [`GodotSchema::get_generation_warnings()`].
+	#[var] pub unsupported_property_policy: UnsupportedPropertyPolicy,
+	/// See [`Self::enable_resource_paths()`].
+	pub resource_path_dirs: Option<Vec<String>>,
+	/// Whether `Node` properties of classes generated afterward are described as scene paths (`.tscn`/`.scn`),
+	/// instantiation loads the scene and assigns its instantiated root node, which must be of the property's type.
+	///
+	/// `PackedScene` properties are always described as scene paths, and hold the loaded scene itself.
+	#[var] pub scene_instancing: bool,
+	/// Whether classes generated afterward declare the initial value of their primitive (and `Dictionary`) script properties
+	/// as `default`, see [`SchemaOptions::script_defaults`]. Off by default, since it lengthens every schema.
+	#[var] pub script_defaults: bool,
+	/// Methods registered with [`Self::register_tools()`], keyed by method name.
+	pub tools: BTreeMap<String, MethodTool>,
+	/// Schemas started by [`Self::generate_async()`] whose validators are still being compiled.
+	pending: Vec<PendingSchema>,
+	/// Classes [`Self::generate_async()`] could not generate, with the error message, emitted along with [`Self::pending`]
+	/// so callers can connect to `schema_failed` after the call.
+	failed: Vec<(StringName, String)>,
+	/// Whether [`Self::reload_changed()`] is called automatically (about once per second) while the library is in the scene tree.
+	#[var] pub hot_reload: bool,
+	/// Modification time of the script of each cached GDScript class, when its schema was generated.
+	modified_times: HashMap<ClassSource, u64>,
+	/// Seconds since [`Self::hot_reload`] last checked for changes.
+	reload_timer: f64,
+	/// Formats and keywords registered with [`Self::register_format()`] and [`Self::register_keyword()`], given to every cached schema.
+	callables: CallableRegistry,
+}
+
+/// Seconds between the checks of [`SchemaLibrary::hot_reload`].
+const HOT_RELOAD_INTERVAL: f64 = 1.0;
+
+struct PendingSchema {
+	class_name: StringName,
+	schema: RootSchema,
+	json: String,
+	receiver: std::sync::mpsc::Receiver<Result<CompiledSchema>>,
 }
 
 #[godot_api]
 impl SchemaLibrary {
+	#[constant] pub const LOG_OFF: i64 = LogLevel::Off as i64;
+	#[constant] pub const LOG_WARN: i64 = LogLevel::Warn as i64;
+	#[constant] pub const LOG_INFO: i64 = LogLevel::Info as i64;
+	#[constant] pub const LOG_DEBUG: i64 = LogLevel::Debug as i64;
+
+	/// Emitted by [`Self::generate_async()`] once the schema of `class_name` is cached.
+	#[signal]
+	fn schema_ready(class_name: StringName, schema: Gd<GodotSchema>);
+
+	/// Emitted by [`Self::generate_async()`] if the schema of `class_name` could not be generated.
+	#[signal]
+	fn schema_failed(class_name: StringName, error_message: GString);
+
+	/// Emitted by [`Self::reload_changed()`] once the schema of `class_name` is regenerated.
+	#[signal]
+	fn schema_reloaded(class_name: StringName);
+
+	/// Sets the `PropertyUsageFlags` each property must have to be included in the schemas generated afterward by this library,
+	/// e.g. `PROPERTY_USAGE_STORAGE` or `PROPERTY_USAGE_SCRIPT_VARIABLE | PROPERTY_USAGE_EDITOR` (only `@export` script variables).
+	///
+	/// Defaults to `PROPERTY_USAGE_NONE`, which includes every property.
+	#[func]
+	pub fn set_usage_filter(&mut self, usage_filter: PropertyUsageFlags) {
+		self.usage_filter = usage_filter;
+	}
+
+	#[func]
+	pub fn get_usage_filter(&self) -> PropertyUsageFlags {
+		self.usage_filter
+	}
+
+	/// Sets how much of the crate's internal work is printed (with `godot_print!`/`godot_warn!`), for every schema and library:
+	/// - [`Self::LOG_OFF`] (the default): Nothing.
+	/// - [`Self::LOG_WARN`]: Problems that do not fail the operation, e.g. properties skipped by the generation.
+	/// - [`Self::LOG_INFO`]: Also the classes generated, and the inputs rejected by validation or instantiation.
+	/// - [`Self::LOG_DEBUG`]: Also how each property was resolved (or why it was excluded),
+	///   e.g. to find out why a property became `null` in the schema.
+	#[func]
+	pub fn set_log_level(level: LogLevel) {
+		set_log_level(level);
+	}
+
+	#[func]
+	pub fn get_log_level() -> LogLevel {
+		log_level()
+	}
+
 	/// Generates a schema for class named `class_name`.
 	///
 	/// If it is a GDScript class, it must be registered in [`ProjectSettings::get_global_class_list()`]. 
@@ -27,13 +133,15 @@ impl SchemaLibrary {
 	/// - Otherwise a `String` containing the error message.
 	#[func]
 	pub fn generate_named_class_schema(&mut self, class_name: StringName) -> Variant {
-		let variant = GodotSchema::from_class_name(class_name.clone());
-		
-		if let Ok(schema) = variant.try_to::<Gd<GodotSchema>>() {
-			self.schemas.push(&schema);
-		}
-		
-		variant
+		let result = self.filtered(|| ClassSource::from_class_name(class_name).and_then(RootSchema::from_class));
+		into_variant(self.register(result))
+	}
+
+	/// Same as [`Self::generate_named_class_schema()`], but returns a [`SchemaResult`].
+	#[func]
+	pub fn generate_named_class_schema_ex(&mut self, class_name: StringName) -> Gd<SchemaResult> {
+		let result = self.filtered(|| ClassSource::from_class_name(class_name).and_then(RootSchema::from_class));
+		SchemaResult::from_result(self.register(result))
 	}
 
 	/// Generates a schema for a GdScript class defined in `script`.
@@ -46,13 +154,15 @@ impl SchemaLibrary {
 	/// - Otherwise a `String` containing the error message.
 	#[func]
 	pub fn generate_unnamed_class_schema(&mut self, script: Gd<Script>) -> Variant {
-		let variant = GodotSchema::from_class_script(script);
-
-		if let Ok(schema) = variant.try_to::<Gd<GodotSchema>>() {
-			self.schemas.push(&schema);
-		}
+		let result = self.filtered(|| RootSchema::from_class(ClassSource::from_script(script)));
+		into_variant(self.register(result))
+	}
 
-		variant
+	/// Same as [`Self::generate_unnamed_class_schema()`], but returns a [`SchemaResult`].
+	#[func]
+	pub fn generate_unnamed_class_schema_ex(&mut self, script: Gd<Script>) -> Gd<SchemaResult> {
+		let result = self.filtered(|| RootSchema::from_class(ClassSource::from_script(script)));
+		SchemaResult::from_result(self.register(result))
 	}
 	
 	/// See [`GodotSchema::from_type_info()`]
@@ -69,19 +179,815 @@ impl SchemaLibrary {
 		hint_string: String,
 		usage: PropertyUsageFlags,
 	) -> Variant {
-		let variant = GodotSchema::from_type_info(
-			variant_type,
-			class_name,
-			hint,
-			hint_string,
-			usage,
-		);
-		
-		if let Ok(schema) = variant.try_to::<Gd<GodotSchema>>() {
-			self.schemas.push(&schema);
+		let info = PropertyTypeInfo::new(variant_type, class_name, hint, hint_string, usage);
+		let result = self.filtered(|| RootSchema::from_type_info(info));
+		into_variant(self.register(result))
+	}
+
+	/// Same as [`Self::generate_type_info_schema()`], but returns a [`SchemaResult`].
+	#[func]
+	pub fn generate_type_info_schema_ex(
+		&mut self,
+		variant_type: VariantType,
+		class_name: StringName,
+		hint: PropertyHint,
+		hint_string: String,
+		usage: PropertyUsageFlags,
+	) -> Gd<SchemaResult> {
+		let info = PropertyTypeInfo::new(variant_type, class_name, hint, hint_string, usage);
+		let result = self.filtered(|| RootSchema::from_type_info(info));
+		SchemaResult::from_result(self.register(result))
+	}
+
+	/// Generates a schema for every class in [`ProjectSettings::get_global_class_list()`] whose name starts with `filter_prefix`,
+	/// replacing previously cached schemas of the same classes.
+	///
+	/// Dependencies shared between classes are generated only once.
+	///
+	/// # Returns
+	/// A `Dictionary` with the keys "succeeded" (a `PackedStringArray` of class names)
+	/// and "failed" (class name => error message).
+	#[func]
+	pub fn generate_all_project_schemas(&mut self, filter_prefix: String) -> Dictionary {
+		let mut shared_defs = BTreeMap::new();
+		let mut shared_issues = Vec::new();
+		let mut succeeded = Vec::new();
+		let mut failed = Dictionary::new();
+
+		for dict in ProjectSettings::singleton().get_global_class_list().iter_shared() {
+			let Ok(class_name) = try_get::<StringName>(&dict, "class")
+			else { continue };
+
+			if !class_name.to_string().starts_with(&filter_prefix) {
+				continue;
+			}
+
+			let result = self.filtered(|| {
+				ClassSource::from_class_name(class_name.clone())
+					.and_then(|source| RootSchema::from_class_shared(source, &mut shared_defs, &mut shared_issues))
+			});
+
+			match self.register(result) {
+				Ok(_) => succeeded.push(GString::from(&class_name)),
+				Err(err) => { failed.set(class_name, format!("{err:?}")); }
+			}
+		}
+
+		let mut report = Dictionary::new();
+		report.set("succeeded", succeeded.into_iter().collect::<PackedStringArray>());
+		report.set("failed", failed);
+		report
+	}
+
+	/// Same as [`Self::generate_named_class_schema()`] for each class in `class_names`, without blocking the main thread
+	/// on the expensive part: compiling the schemas' validators.
+	///
+	/// Property lists are fetched on the main thread (calling into Godot is not thread-safe),
+	/// then the validators are compiled on a pool of worker threads.
+	/// Without the `threads` feature, validators are compiled immediately instead.
+	///
+	/// Results are collected on the next frames, emitting `schema_ready` (or `schema_failed`) once per class,
+	/// the library must be in the scene tree for that to happen. Classes that fail before compiling are also reported then,
+	/// so signals connected right after the call are not missed.
+	#[func]
+	pub fn generate_async(&mut self, class_names: PackedStringArray) {
+		let mut started = Vec::new();
+
+		for class_name in class_names.as_slice() {
+			let class_name = StringName::from(class_name);
+
+			let result = self
+				.filtered(|| ClassSource::from_class_name(class_name.clone()).and_then(RootSchema::from_class))
+				.and_then(|mut schema| {
+					schema.options.validator.callables = Some(self.callables.clone());
+					Ok((schema.to_json_pretty()?, schema))
+				});
+
+			match result {
+				Ok((json, schema)) => started.push((class_name, schema, json)),
+				Err(err) => self.failed.push((class_name, format!("{err:?}"))),
+			}
+		}
+
+		let jobs = started
+			.iter()
+			.map(|(_, schema, json)| (json.clone(), self.validator_backend, CompileOptions::of(schema)))
+			.collect();
+		let receivers = compile_in_background(jobs);
+
+		for ((class_name, schema, json), receiver) in started.into_iter().zip(receivers) {
+			self.pending.push(PendingSchema { class_name, schema, json, receiver });
+		}
+	}
+
+	/// # Returns
+	/// Whether any schema started by [`Self::generate_async()`] has not been emitted yet.
+	#[func]
+	pub fn is_generating(&self) -> bool {
+		!self.pending.is_empty() || !self.failed.is_empty()
+	}
+
+	/// # Returns
+	/// Whether a schema for class named `class_name` is cached.
+	#[func]
+	pub fn has_schema(&self, class_name: StringName) -> bool {
+		ClassSource::from_class_name(class_name).is_ok_and(|source| self.classes.contains_key(&source))
+	}
+
+	/// Removes the cached schema of class named `class_name`.
+	///
+	/// # Returns
+	/// Whether a schema was removed.
+	#[func]
+	pub fn remove_schema(&mut self, class_name: StringName) -> bool {
+		let Some(schema) = ClassSource::from_class_name(class_name)
+			.ok()
+			.and_then(|source| {
+				self.modified_times.remove(&source);
+				self.classes.remove(&source)
+			})
+		else { return false };
+
+		self.schemas.erase(&schema);
+		true
+	}
+
+	/// Removes every cached schema.
+	#[func]
+	pub fn clear(&mut self) {
+		self.classes.clear();
+		self.schemas.clear();
+		self.modified_times.clear();
+	}
+
+	/// Regenerates the schema of every cached GDScript class whose script file was modified since it was generated,
+	/// along with the cached schemas that include that class in their `$defs`, emitting `schema_reloaded` once per class.
+	///
+	/// Modified scripts are reloaded first (keeping the state of their instances),
+	/// classes that can no longer be generated keep their previous schema and emit `schema_failed` instead.
+	///
+	/// Schemas are reloaded in place, keeping the options and edits made to them (see [`GodotSchema::try_reload()`]).
+	///
+	/// # Returns
+	/// The names of the regenerated classes.
+	#[func]
+	pub fn reload_changed(&mut self) -> PackedStringArray {
+		let changed = self.modified_times
+			.iter()
+			.filter_map(|(source, time)| {
+				let current = script_modified_time(source)?;
+				(current != *time).then(|| (source.clone(), current))
+			})
+			.collect_vec();
+
+		if changed.is_empty() {
+			return PackedStringArray::new();
+		}
+
+		let mut stale = BTreeMap::new();
+
+		for (source, current) in changed {
+			// Also stops failing classes from being retried until their script changes again.
+			self.modified_times.insert(source.clone(), current);
+
+			if let ClassSource::ScriptNamed(script, _) | ClassSource::ScriptUnnamed(script) = &source {
+				let err = script.clone().reload_ex().keep_state(true).done();
+
+				if err != godot::global::Error::OK {
+					godot_warn!("Could not reload script of class \"{}\": {err:?}", source.definition_name());
+				}
+			}
+
+			let name = source.definition_name();
+
+			for (dependent, schema) in &self.classes {
+				if schema.bind().inner.defs.contains_key(&name) {
+					stale.insert(dependent.definition_name(), dependent.clone());
+				}
+			}
+
+			stale.insert(name, source);
+		}
+
+		let mut reloaded = Vec::new();
+
+		for (name, source) in stale {
+			let regenerated = self.filtered(|| RootSchema::from_class(source.clone()));
+
+			// Reloads the cached schema in place, keeping its options, its edits and the references to it.
+			let result = match self.classes.get(&source).cloned() {
+				Some(mut schema) => regenerated.and_then(|regenerated| schema.bind_mut().try_reload(regenerated)),
+				None => self.register(regenerated).map(drop),
+			};
+
+			match result {
+				Ok(_) => {
+					self.base_mut().emit_signal("schema_reloaded", &[StringName::from(name.as_str()).to_variant()]);
+					reloaded.push(GString::from(name));
+				}
+				Err(err) => {
+					let error_message = format!("{err:?}");
+					self.base_mut().emit_signal("schema_failed", &[StringName::from(name.as_str()).to_variant(), error_message.to_variant()]);
+				}
+			}
+		}
+
+		reloaded.into_iter().collect()
+	}
+
+	/// Returns the names of every class with a cached schema, sorted alphabetically.
+	///
+	/// Unnamed GDScript classes are listed by their script's path.
+	#[func]
+	pub fn get_class_names(&self) -> PackedStringArray {
+		self.classes
+			.keys()
+			.map(ClassSource::definition_name)
+			.sorted()
+			.map(GString::from)
+			.collect()
+	}
+
+	/// Returns a `Dictionary` mapping the name of every class with a cached schema to the schema's hash,
+	/// see [`GodotSchema::get_hash()`].
+	///
+	/// Unnamed GDScript classes are listed by their script's path.
+	#[func]
+	pub fn get_manifest(&self) -> Dictionary {
+		let mut manifest = Dictionary::new();
+
+		for (source, schema) in self.classes.iter().sorted_by_key(|(source, _)| source.definition_name()) {
+			manifest.set(source.definition_name(), schema.bind().get_hash());
+		}
+
+		manifest
+	}
+
+	/// Returns a `Dictionary` mapping the name of every class with a cached schema to the definitions (classes, enums, ...)
+	/// it references directly, as a sorted `PackedStringArray`. Follow the names to walk the graph, e.g. to order generation,
+	/// to invalidate the schemas depending on a changed class or to visualize it in editor tools.
+	///
+	/// Definitions are named as in `$defs`. Unnamed GDScript classes are listed by their script's path.
+	#[func]
+	pub fn get_dependency_graph(&self) -> Dictionary {
+		let mut graph = Dictionary::new();
+
+		for (source, schema) in self.classes.iter().sorted_by_key(|(source, _)| source.definition_name()) {
+			let mut references = BTreeSet::new();
+			schema.bind().inner.base.insert_references(&mut references);
+
+			let references = references
+				.into_iter()
+				.map(GString::from)
+				.collect::<PackedStringArray>();
+
+			graph.set(source.definition_name(), references);
+		}
+
+		graph
+	}
+
+	/// Returns the cached schema of class named `class_name`, generating it first if needed.
+	///
+	/// # Returns
+	/// - The `GodotSchema` object containing the class's schema, if successful.
+	/// - Otherwise a `String` containing the error message.
+	#[func]
+	pub fn get_or_generate(&mut self, class_name: StringName) -> Variant {
+		into_variant(self.find_or_generate(class_name))
+	}
+
+	/// Combines the schemas of the classes in `class_names` (generating the ones not cached) into a single schema,
+	/// whose `$defs` contain every listed class and their dependencies. Useful for document stores,
+	/// or for shipping a single schema file to a backend.
+	///
+	/// # Input
+	/// `root`: The listed class the bundle describes, or empty for `oneOf` the listed classes,
+	/// instantiated as whichever class the input matches.
+	///
+	/// # Returns
+	/// - The `GodotSchema` object containing the bundle, if successful. It is not cached, and has the options of the listed schemas.
+	/// - Otherwise a `String` containing the error message, e.g. if two classes define a dependency differently,
+	///   or encode values differently (such as with different `integer_enums`).
+	#[func]
+	pub fn bundle(&mut self, class_names: PackedStringArray, root: String) -> Variant {
+		into_variant(self.try_bundle(class_names, &root))
+	}
+
+	/// Releases the compiled validators of every cached schema, keeping their JSON.
+	///
+	/// Validators are recompiled lazily, the next time each schema needs one.
+	/// Called automatically when the library receives `NOTIFICATION_APPLICATION_PAUSED`.
+	///
+	/// # Returns
+	/// The amount of validators released.
+	#[func]
+	pub fn on_app_pause(&mut self) -> i64 {
+		self.release_validators()
+	}
+
+	/// Same as [`Self::on_app_pause()`], meant for responding to memory pressure.
+	///
+	/// Called automatically when the library receives `NOTIFICATION_OS_MEMORY_WARNING`.
+	///
+	/// # Returns
+	/// The amount of validators released.
+	#[func]
+	pub fn on_low_memory(&mut self) -> i64 {
+		self.release_validators()
+	}
+
+	/// Makes cached schemas reference classes that have their own cached schema with an `$id` (see [`GodotSchema::set_schema_id()`])
+	/// by that `$id`, instead of repeating their definitions in `$defs`.
+	///
+	/// The library resolves these references when compiling validators, instantiation is unaffected.
+	/// Call it again after generating schemas or changing their `$id`s.
+	///
+	/// # Returns
+	/// The amount of definitions replaced by references.
+	#[func]
+	pub fn link_shared_definitions(&mut self) -> i64 {
+		let ids = self.classes
+			.iter()
+			.filter_map(|(source, schema)| Some((source.definition_name(), schema.bind().inner.options.id.clone()?)))
+			.collect::<BTreeMap<_, _>>();
+
+		let mut count = 0;
+
+		for mut schema in self.schemas.iter_shared() {
+			let mut schema = schema.bind_mut();
+			let own_id = schema.inner.options.id.clone();
+
+			let external_refs = schema.inner.defs
+				.keys()
+				.filter_map(|name| Some((name.clone(), ids.get(name)?.clone())))
+				.filter(|(_, id)| Some(id) != own_id.as_ref())
+				.collect::<BTreeMap<_, _>>();
+
+			count += external_refs.len() as i64;
+			schema.inner.options.external_refs = external_refs;
+
+			match schema.inner.to_json_pretty() {
+				Ok(json) => schema.json = json.into(),
+				Err(err) => godot_error!("{err}"),
+			}
+		}
+
+		self.share_external_documents();
+		count
+	}
+
+	/// Returns a single schema document bundling every cached schema: one `$defs` section containing each class
+	/// (named after its definition, e.g. `#/$defs/Person`) and each of their dependencies, stored once.
+	///
+	/// # Returns
+	/// - The bundle, if successful.
+	/// - Otherwise an empty `String`, the error is printed with `godot_error!`,
+	///   e.g. if two schemas define the same name differently (such as with different `integer_enums`).
+	#[func]
+	pub fn export_bundle(&self, pretty: bool) -> String {
+		let result = self.definition_store().and_then(|store| {
+			let bundle = store.to_bundle();
+
+			if pretty {
+				Ok(serde_json::to_string_pretty(&bundle)?)
+			} else {
+				Ok(serde_json::to_string(&bundle)?)
+			}
+		});
+
+		match result {
+			Ok(json) => json,
+			Err(err) => {
+				godot_error!("{err:?}");
+				String::default()
+			}
+		}
+	}
+
+	/// Makes classes generated afterward by this library reference `Resource` properties (`Texture2D`, `AudioStream`, custom resources, ...)
+	/// by path, e.g. `"res://textures/grass.png"`, instead of describing their properties.
+	///
+	/// Paths must be inside one of `allowed_dirs` (e.g. `"res://textures/"`), an empty array allows any `res://` path.
+	/// Instantiation loads each path with `ResourceLoader`, failing if the loaded resource is not of the property's type.
+	#[func]
+	pub fn enable_resource_paths(&mut self, allowed_dirs: PackedStringArray) {
+		self.resource_path_dirs = Some(allowed_dirs.as_slice().iter().map(GString::to_string).collect());
+	}
+
+	#[func]
+	pub fn disable_resource_paths(&mut self) {
+		self.resource_path_dirs = None;
+	}
+
+	/// Registers a custom string format, e.g. item IDs, localization keys or `res://` paths: strings of schemas with
+	/// `"format": name` are passed to `validator` (`func(value: String) -> bool`), `instantiate` rejects the ones it returns `false` for.
+	///
+	/// Formats are checked by the schemas cached by this library that use the `Jsonschema` validator backend, their validators are
+	/// released so they are recompiled with the format. Other schemas need [`Self::apply_custom_validators()`].
+	/// Standard formats (e.g. `date-time`) are still annotations, unless enabled with [`GodotSchema::set_validate_formats()`].
+	///
+	/// `validator` is only called on the thread that registered it, strings validated on other threads are rejected,
+	/// as are the ones it does not return a `bool` for.
+	///
+	/// # Returns
+	/// - `Nil`, if the format was registered.
+	/// - Otherwise a `String` containing the error message.
+	#[func]
+	pub fn register_format(&mut self, name: String, validator: Callable) -> Variant {
+		match self.callables.register(CallableKind::Format, &name, validator) {
+			Ok(()) => {
+				self.share_callables();
+				Variant::nil()
+			}
+			Err(err) => format!("{err:?}").to_variant(),
+		}
+	}
+
+	/// Removes a format registered with [`Self::register_format()`].
+	///
+	/// # Returns
+	/// Whether the format was registered.
+	#[func]
+	pub fn unregister_format(&mut self, name: String) -> bool {
+		let removed = self.callables.unregister(CallableKind::Format, &name);
+
+		if removed {
+			self.share_callables();
+		}
+
+		removed
+	}
+
+	/// Makes `schema`, which is not cached by this library (e.g. imported with [`GodotSchema::from_json_schema()`]),
+	/// check the formats and keywords registered on this library. Its validator is recompiled lazily.
+	#[func]
+	pub fn apply_custom_validators(&self, mut schema: Gd<GodotSchema>) {
+		self.give_callables(&mut schema.bind_mut());
+	}
+
+	/// Registers a custom keyword, e.g. `"x-max-node-count": 10`: values of schemas with keyword `name` are passed to `validator`
+	/// (`func(value: Variant, argument: Variant) -> bool`, both parsed from JSON), `instantiate` rejects the ones it returns `false` for.
+	///
+	/// Keywords are checked by the schemas cached by this library that use the `Jsonschema` validator backend, generated schemas
+	/// can declare them through the "constraints" of their class's `_json_schema_config()`. The validators of cached schemas are
+	/// released so they are recompiled with the keyword. Other schemas need [`Self::apply_custom_validators()`].
+	///
+	/// `validator` is only called on the thread that registered it, values validated on other threads are rejected,
+	/// as are the ones it does not return a `bool` for.
+	///
+	/// # Returns
+	/// - `Nil`, if the keyword was registered.
+	/// - Otherwise a `String` containing the error message.
+	#[func]
+	pub fn register_keyword(&mut self, name: String, validator: Callable) -> Variant {
+		match self.callables.register(CallableKind::Keyword, &name, validator) {
+			Ok(()) => {
+				self.share_callables();
+				Variant::nil()
+			}
+			Err(err) => format!("{err:?}").to_variant(),
+		}
+	}
+
+	/// Removes a keyword registered with [`Self::register_keyword()`].
+	///
+	/// # Returns
+	/// Whether the keyword was registered.
+	#[func]
+	pub fn unregister_keyword(&mut self, name: String) -> bool {
+		let removed = self.callables.unregister(CallableKind::Keyword, &name);
+
+		if removed {
+			self.share_callables();
+		}
+
+		removed
+	}
+
+	/// Generates a parameters schema for each method of `object` named in `methods`, registering them as a tool catalog.
+	///
+	/// Tools are named after their methods, registering a method with the same name as an existing tool replaces it.
+	///
+	/// # Returns
+	/// - `Nil`, if every method was registered.
+	/// - Otherwise a `String` containing the error messages of the methods that failed.
+	#[func]
+	pub fn register_tools(&mut self, object: Gd<Object>, methods: PackedStringArray) -> Variant {
+		let mut errors = String::new();
+
+		for method in methods.as_slice() {
+			match MethodTool::new(object.clone(), StringName::from(method)) {
+				Ok(tool) => { self.tools.insert(method.to_string(), tool); }
+				Err(err) => errors += &format!("Method \"{method}\": {err:?}\n"),
+			}
+		}
+
+		if errors.is_empty() {
+			Variant::nil()
+		} else {
+			errors.to_variant()
+		}
+	}
+
+	/// Returns a JSON array containing the definitions of every tool registered with [`Self::register_tools()`].
+	///
+	/// # Input
+	/// `provider`: The format of the definitions, either "OpenAI" or "Anthropic".
+	///
+	/// # Returns
+	/// - The JSON array, if successful.
+	/// - Otherwise an empty `String`, the error is printed with `godot_error!`.
+	#[func]
+	pub fn get_tool_definitions(&self, provider: String) -> String {
+		let result = ToolProvider::from_name(&provider)
+			.and_then(|provider| tool_definitions(self.tools.values(), provider))
+			.and_then(|value| serde_json::to_string(&value).map_err(anyhow::Error::from));
+
+		match result {
+			Ok(json) => json,
+			Err(err) => {
+				godot_error!("{err}");
+				String::default()
+			}
+		}
+	}
+
+	/// Validates the arguments of an LLM tool call against the registered method's schema, then invokes the method.
+	///
+	/// See [`ToolCall`] for the accepted formats.
+	///
+	/// # Returns
+	/// - The value returned by the method, if successful.
+	/// - Otherwise a `String` containing the error message.
+	#[func]
+	pub fn dispatch(&self, tool_call_json: String) -> Variant {
+		match dispatch_tool_call(&self.tools, &tool_call_json) {
+			Ok(result) => result,
+			Err(err) => format!("{err}").to_variant(),
+		}
+	}
+
+	/// Writes `samples_per_class` generated example instances of every cached schema to `output_path`, in JSONL format.
+	///
+	/// Each line is an object containing a `"prompt"` (describing the schema) and a `"response"` (a JSON string valid according to it),
+	/// meant for preparing fine-tuning or evaluation datasets.
+	///
+	/// Examples are generated deterministically, exporting the same library twice produces the same file.
+	///
+	/// # Returns
+	/// - The amount of lines written, if successful.
+	/// - Otherwise a `String` containing the error message.
+	#[func]
+	pub fn export_finetune_examples(&self, samples_per_class: i64, output_path: String) -> Variant {
+		let try_fn = || -> Result<i64> {
+			let mut jsonl = String::new();
+			let mut count = 0;
+
+			for (idx, schema) in self.schemas.iter_shared().enumerate() {
+				let mut generator = ExampleGenerator::new(idx as u64 + 1);
+
+				for _ in 0..samples_per_class {
+					let example = finetune_example(&schema.bind(), &mut generator)?;
+					jsonl += &serde_json::to_string(&example)?;
+					jsonl.push('\n');
+					count += 1;
+				}
+			}
+
+			let mut file = FileAccess::open(output_path.as_str(), ModeFlags::WRITE)
+				.ok_or_else(|| SchemaError::Io {
+					path: output_path.clone(),
+					message: format!("Could not open for writing: {:?}", FileAccess::get_open_error()),
+				})?;
+
+			file.store_string(jsonl.as_str());
+			file.close();
+
+			Ok(count)
+		};
+
+		match try_fn() {
+			Ok(count) => count.to_variant(),
+			Err(err) => format!("{err:?}").to_variant(),
+		}
+	}
+
+	/// Writes each cached schema to its own `<ClassName>.schema.json` file inside `dir` (created if missing),
+	/// for consumption by external tools, CI validators or server-side code.
+	///
+	/// Unnamed GDScript classes are named after their script's file name,
+	/// schemas with the same name are suffixed with a counter (e.g. `integer_2.schema.json`).
+	///
+	/// # Input
+	/// `pretty`: Whether to indent the JSON.
+	///
+	/// # Returns
+	/// - The amount of files written, if successful.
+	/// - Otherwise a `String` containing the error message.
+	#[func]
+	pub fn export_schemas(&self, dir: String, pretty: bool) -> Variant {
+		let try_fn = || -> Result<i64> {
+			if DirAccess::make_dir_recursive_absolute(dir.as_str()) != godot::global::Error::OK {
+				return Err(SchemaError::Io { path: dir.clone(), message: "Could not create directory.".to_owned() }.into());
+			}
+
+			let mut used_names = HashSet::new();
+
+			for schema in self.schemas.iter_shared() {
+				let schema = schema.bind();
+
+				let name = schema.schema_name();
+				let name = name.rsplit('/').next().unwrap_or_default().trim_end_matches(".gd").to_owned();
+
+				let mut file_name = name.clone();
+				let mut counter = 1;
+
+				while !used_names.insert(file_name.clone()) {
+					counter += 1;
+					file_name = format!("{name}_{counter}");
+				}
+
+				let json = if pretty {
+					schema.json.to_string()
+				} else {
+					schema.inner.to_json_compact()?
+				};
+
+				let path = format!("{}/{file_name}.schema.json", dir.trim_end_matches('/'));
+
+				let mut file = FileAccess::open(path.as_str(), ModeFlags::WRITE)
+					.ok_or_else(|| SchemaError::Io {
+						path: path.clone(),
+						message: format!("Could not open for writing: {:?}", FileAccess::get_open_error()),
+					})?;
+
+				file.store_string(json.as_str());
+				file.close();
+			}
+
+			Ok(used_names.len() as i64)
+		};
+
+		match try_fn() {
+			Ok(count) => count.to_variant(),
+			Err(err) => format!("{err:?}").to_variant(),
+		}
+	}
+
+	/// Generates a schema for every class in [`ProjectSettings::get_global_class_list()`], then writes them to `out_dir`
+	/// (see [`Self::export_schemas()`]), without needing a library in the scene tree.
+	///
+	/// Meant for producing schema artifacts in CI, from a script run with `godot --headless --script export.gd`:
+	/// ```gdscript
+	/// extends SceneTree
+	///
+	/// func _init():
+	///     var report = SchemaLibrary.export_project_schemas_headless("res://schemas")
+	///     quit(1 if report["failed"] or report["error"] else 0)
+	/// ```
+	/// The project must have been imported first (e.g. `godot --headless --import`), for its classes to be registered.
+	///
+	/// Errors are also printed with `godot_error!`, so they show up in the CI's logs.
+	///
+	/// # Returns
+	/// The report of [`Self::generate_all_project_schemas()`], along with the keys "written" (the amount of files written)
+	/// and "error" (why the files could not be written, empty if they were).
+	#[func]
+	pub fn export_project_schemas_headless(out_dir: String) -> Dictionary {
+		let mut library = SchemaLibrary::new_alloc();
+		let mut report = library.bind_mut().generate_all_project_schemas(String::new());
+
+		for (class_name, error) in report.at("failed").to::<Dictionary>().iter_shared() {
+			godot_error!("Could not generate the schema of \"{class_name}\": {error}");
+		}
+
+		let written = library.bind().export_schemas(out_dir, true);
+		library.free();
+
+		match written.try_to::<i64>() {
+			Ok(count) => {
+				report.set("written", count);
+				report.set("error", "");
+			}
+			Err(_) => {
+				godot_error!("{written}");
+				report.set("written", 0);
+				report.set("error", written);
+			}
+		}
+
+		report
+	}
+
+	/// Writes every cached schema to `path` (e.g. "user://schemas.json"), to be restored with [`Self::load_from_file()`]
+	/// instead of regenerating them on every launch.
+	///
+	/// Classes are identified by their engine class name or script path, built-in scripts cannot be saved.
+	///
+	/// # Returns
+	/// - The amount of schemas written, if successful.
+	/// - Otherwise a `String` containing the error message.
+	#[func]
+	pub fn save_to_file(&self, path: String) -> Variant {
+		let try_fn = || -> Result<i64> {
+			let schemas = self.schemas.iter_shared().collect_vec();
+			let binds = schemas.iter().map(|schema| schema.bind()).collect_vec();
+			let document = schemas_to_persisted(binds.iter().map(|schema| &schema.inner))?;
+
+			let mut file = FileAccess::open(path.as_str(), ModeFlags::WRITE)
+				.ok_or_else(|| SchemaError::Io {
+					path: path.clone(),
+					message: format!("Could not open for writing: {:?}", FileAccess::get_open_error()),
+				})?;
+
+			file.store_string(serde_json::to_string(&document)?.as_str());
+			file.close();
+
+			Ok(binds.len() as i64)
+		};
+
+		match try_fn() {
+			Ok(count) => count.to_variant(),
+			Err(err) => format!("{err:?}").to_variant(),
+		}
+	}
+
+	/// Caches the schemas written by [`Self::save_to_file()`], replacing cached schemas of the same classes.
+	///
+	/// Property lists are not fetched, so the classes' scripts are not checked against the saved schemas.
+	/// Validators are compiled lazily, the first time each schema needs one.
+	///
+	/// # Returns
+	/// - The amount of schemas loaded, if successful.
+	/// - Otherwise a `String` containing the error message, no schema is cached in that case.
+	#[func]
+	pub fn load_from_file(&mut self, path: String) -> Variant {
+		let try_fn = || -> Result<Vec<GodotSchema>> {
+			let file = FileAccess::open(path.as_str(), ModeFlags::READ)
+				.ok_or_else(|| SchemaError::Io {
+					path: path.clone(),
+					message: format!("Could not open for reading: {:?}", FileAccess::get_open_error()),
+				})?;
+
+			let document = serde_json::from_str(&file.get_as_text().to_string())?;
+
+			schemas_from_persisted(&document)?
+				.into_iter()
+				.map(|schema| GodotSchema::with_lazy_validator(schema, self.validator_backend))
+				.try_collect()
+		};
+
+		match try_fn() {
+			Ok(schemas) => {
+				let count = schemas.len() as i64;
+
+				for schema in schemas {
+					self.insert(Gd::from_object(schema));
+				}
+
+				self.share_external_documents();
+				count.to_variant()
+			}
+			Err(err) => format!("{err:?}").to_variant(),
+		}
+	}
+
+	/// Regenerates the schema of every cached class and compares it against the cached one,
+	/// e.g. to check at startup that schemas loaded with [`Self::load_from_file()`] still match the shipped scripts.
+	///
+	/// The cache is left untouched.
+	///
+	/// # Returns
+	/// One `Dictionary` per mismatch, an empty array if every schema is up to date.
+	/// See [`schema_mismatches()`] for the keys, classes that can no longer be generated are reported
+	/// with "change" set to "error" and the error message in "message".
+	#[func]
+	pub fn verify_all(&self) -> Array<Dictionary> {
+		let mut report = Array::new();
+
+		for (source, schema) in self.classes.iter().sorted_by_key(|(source, _)| source.definition_name()) {
+			let result = self
+				.filtered(|| RootSchema::from_class(source.clone()))
+				.and_then(|current| schema_mismatches(&schema.bind().inner, &current));
+
+			match result {
+				Ok(mismatches) => {
+					for dict in mismatches {
+						report.push(&dict);
+					}
+				}
+				Err(err) => {
+					let mut dict = Dictionary::new();
+					dict.set("class", source.definition_name());
+					dict.set("change", "error");
+					dict.set("message", format!("{err:?}"));
+					report.push(&dict);
+				}
+			}
 		}
-		
-		variant
+
+		report
 	}
 
 	/// Returns the `GodotSchema` object containing the schema of class named `class_name`.
@@ -95,15 +1001,13 @@ impl SchemaLibrary {
 	/// - Otherwise a `String` containing the error message.
 	#[func]
 	pub fn get_named_class_schema(&self, class_name: StringName) -> Variant {
-		let result = ClassSource::from_class_name(class_name.clone())
-			.and_then(|source| {
-				self.find_class(source).ok_or_else(|| anyhow!("No schema found for class \"{class_name}\"."))
-			});
-		
-		match result {
-			Ok(schema) => schema.to_variant(),
-			Err(err) => format!("{err:?}").to_variant(),
-		}
+		into_variant(self.find_named_class(class_name))
+	}
+
+	/// Same as [`Self::get_named_class_schema()`], but returns a [`SchemaResult`].
+	#[func]
+	pub fn get_named_class_schema_ex(&self, class_name: StringName) -> Gd<SchemaResult> {
+		SchemaResult::from_result(self.find_named_class(class_name))
 	}
 
 	/// Returns the `GodotSchema` object containing the schema of class defined in `script`.
@@ -123,19 +1027,304 @@ impl SchemaLibrary {
 			"No schema found for class from input script.".to_variant()
 		}
 	}
+
+	/// Same as [`Self::get_unnamed_class_schema()`], but returns a [`SchemaResult`].
+	#[func]
+	pub fn get_unnamed_class_schema_ex(&self, script: Gd<Script>) -> Gd<SchemaResult> {
+		let result = self
+			.find_class(ClassSource::from_script(script))
+			.ok_or_else(|| anyhow!("No schema found for class from input script."))
+			.with_code(ErrorCode::ClassNotFound);
+
+		SchemaResult::from_result(result)
+	}
+}
+
+#[godot_api]
+impl INode for SchemaLibrary {
+	fn on_notification(&mut self, what: NodeNotification) {
+		match what {
+			NodeNotification::APPLICATION_PAUSED => { self.on_app_pause(); }
+			NodeNotification::OS_MEMORY_WARNING => { self.on_low_memory(); }
+			_ => {}
+		}
+	}
+
+	fn process(&mut self, delta: f64) {
+		self.poll_pending();
+
+		if self.hot_reload {
+			self.reload_timer += delta;
+
+			if self.reload_timer >= HOT_RELOAD_INTERVAL {
+				self.reload_timer = 0.0;
+				self.reload_changed();
+			}
+		}
+	}
+}
+
+impl Drop for SchemaLibrary {
+	fn drop(&mut self) {
+		self.callables.clear();
+	}
 }
 
 impl SchemaLibrary {
-	pub fn find_class(&self, source: ClassSource) -> Option<Gd<GodotSchema>> {
-		self.schemas.iter_shared().find(|schema| {
-			let base = &schema.bind().inner.base;
-			
-			if let Definition::Class(class) = base {
-				class.source == source
-			} else {
-				false
+	/// Gives the current [`Self::callables`] to every cached schema, releasing their validators.
+	fn share_callables(&mut self) {
+		for mut schema in self.schemas.iter_shared() {
+			self.give_callables(&mut schema.bind_mut());
+		}
+	}
+
+	fn give_callables(&self, schema: &mut GodotSchema) {
+		let callables = Some(self.callables.clone());
+
+		if schema.inner.options.validator.callables != callables {
+			schema.inner.options.validator.callables = callables;
+			schema.release_validator();
+		}
+	}
+
+	fn release_validators(&mut self) -> i64 {
+		let mut count = 0;
+
+		for mut schema in self.schemas.iter_shared() {
+			let mut schema = schema.bind_mut();
+
+			if schema.is_validator_compiled() {
+				schema.release_validator();
+				count += 1;
 			}
-		})
+		}
+
+		count
+	}
+
+	/// Gives every cached schema the JSON of the schemas with an `$id`, for resolving the references made by
+	/// [`Self::link_shared_definitions()`]. Validators are recompiled lazily.
+	fn share_external_documents(&mut self) {
+		let documents = self.schemas
+			.iter_shared()
+			.filter_map(|schema| {
+				let schema = schema.bind();
+				let id = schema.inner.options.id.clone()?;
+				let document = serde_json::from_str(&schema.json.to_string()).ok()?;
+				Some((id, document))
+			})
+			.collect::<BTreeMap<_, _>>();
+
+		let documents = std::sync::Arc::new(documents);
+
+		for mut schema in self.schemas.iter_shared() {
+			let mut schema = schema.bind_mut();
+			schema.inner.options.external_documents = documents.clone();
+			schema.release_validator();
+		}
+	}
+
+	/// Registers the schemas whose validators finished compiling, emitting their signals (after those of [`Self::failed`]).
+	fn poll_pending(&mut self) {
+		use std::sync::mpsc::TryRecvError;
+
+		for (class_name, error_message) in std::mem::take(&mut self.failed) {
+			self.base_mut().emit_signal("schema_failed", &[class_name.to_variant(), error_message.to_variant()]);
+		}
+
+		let mut finished = Vec::new();
+
+		for pending in std::mem::take(&mut self.pending) {
+			match pending.receiver.try_recv() {
+				Ok(result) => finished.push((pending, result)),
+				Err(TryRecvError::Empty) => self.pending.push(pending),
+				Err(TryRecvError::Disconnected) => {
+					let err = anyhow!("Worker thread exited without compiling the schema.");
+					finished.push((pending, Err(err)));
+				}
+			}
+		}
+
+		for (PendingSchema { class_name, schema, json, .. }, result) in finished {
+			let backend = self.validator_backend;
+			let result = result
+				.and_then(|compiled| GodotSchema::from_compiled(schema, json, compiled, backend))
+				.map(|schema| self.insert(Gd::from_object(schema)));
+
+			match result {
+				Ok(schema) => {
+					self.base_mut().emit_signal("schema_ready", &[class_name.to_variant(), schema.to_variant()]);
+				}
+				Err(err) => {
+					let error_message = format!("{err:?}");
+					self.base_mut().emit_signal("schema_failed", &[class_name.to_variant(), error_message.to_variant()]);
+				}
+			}
+		}
+	}
+
+	/// Runs the generation `f` with this library's [`Self::usage_filter`] and generation settings.
+	fn filtered<T>(&self, f: impl FnOnce() -> T) -> T {
+		let options = SchemaOptions {
+			class_inheritance: self.class_inheritance,
+			skip_private_properties: self.skip_private_properties,
+			unsupported_property_policy: self.unsupported_property_policy,
+			resource_path_dirs: self.resource_path_dirs.clone(),
+			scene_instancing: self.scene_instancing,
+			script_defaults: self.script_defaults,
+			..SchemaOptions::default()
+		};
+
+		options.scope(|| scope_usage_filter(self.usage_filter, f))
+	}
+
+	/// Compiles `result` with this library's validator backend and caches it.
+	///
+	/// A class schema replaces the previously cached schema of the same class, if any.
+	fn register(&mut self, result: Result<RootSchema>) -> Result<Gd<GodotSchema>> {
+		let mut schema = result?;
+		schema.options.validator.callables = Some(self.callables.clone());
+
+		let schema = Gd::from_object(GodotSchema::with_backend(schema, self.validator_backend)?);
+		Ok(self.insert(schema))
+	}
+
+	fn insert(&mut self, mut schema: Gd<GodotSchema>) -> Gd<GodotSchema> {
+		self.give_callables(&mut schema.bind_mut());
+
+		if let Definition::Class(class) = &schema.bind().inner.base {
+			if let Some(time) = script_modified_time(&class.source) {
+				self.modified_times.insert(class.source.clone(), time);
+			}
+
+			if let Some(previous) = self.classes.insert(class.source.clone(), schema.clone()) {
+				self.schemas.erase(&previous);
+			}
+		}
+
+		self.schemas.push(&schema);
+		schema
+	}
+
+	fn find_named_class(&self, class_name: StringName) -> Result<Gd<GodotSchema>> {
+		ClassSource::from_class_name(class_name.clone())
+			.and_then(|source| {
+				self.find_class(source)
+					.ok_or_else(|| anyhow!("No schema found for class \"{class_name}\"."))
+					.with_code(ErrorCode::ClassNotFound)
+			})
+	}
+
+	pub fn find_or_generate(&mut self, class_name: StringName) -> Result<Gd<GodotSchema>> {
+		let source = ClassSource::from_class_name(class_name)?;
+
+		match self.classes.get(&source) {
+			Some(schema) => Ok(schema.clone()),
+			None => {
+				let result = self.filtered(|| RootSchema::from_class(source));
+				self.register(result)
+			}
+		}
+	}
+
+	fn try_bundle(&mut self, class_names: PackedStringArray, root: &str) -> Result<Gd<GodotSchema>> {
+		let mut store = DefinitionStore::new();
+		let mut options = None::<SchemaOptions>;
+		let mut names = Vec::new();
+
+		for class_name in class_names.as_slice() {
+			let schema = self.find_or_generate(StringName::from(class_name))?;
+			let schema = schema.bind();
+
+			let Definition::Class(class) = &schema.inner.base
+			else { bail!("Expected \"{class_name}\" to be a class.") };
+
+			match &options {
+				None => options = Some(schema.inner.options.clone()),
+				Some(first) => check_bundled_options(first, &schema.inner.options)
+					.map_err(|err| anyhow!("Class \"{class_name}\": {err}"))?,
+			}
+
+			// Fails if a definition is defined differently by another class.
+			store.intern_schema(&schema.inner)?;
+			names.push(class.source.definition_name());
+		}
+
+		let Some(options) = options
+		else { bail!("Expected at least one class to bundle.") };
+
+		let defs = store.into_definitions();
+
+		let base = if root.is_empty() {
+			JOneOf::new(names.into_iter().map(|name| Type::Ref(JRef::new(name)))).into()
+		} else {
+			defs.get(root)
+				.filter(|_| names.iter().any(|name| name == root))
+				.cloned()
+				.ok_or_else(|| anyhow!("Expected root class \"{root}\" to be one of the bundled classes."))?
+		};
+
+		let schema = RootSchema {
+			defs,
+			base,
+			// The bundle is another document, it cannot share the first class's `$id`.
+			options: SchemaOptions { id: None, ..options },
+			warnings: Vec::new(),
+			name_map: None,
+		};
+
+		Ok(Gd::from_object(GodotSchema::with_backend(schema, self.validator_backend)?))
+	}
+
+	/// The definitions of every cached schema, each stored once, see [`DefinitionStore::intern_schema()`].
+	pub fn definition_store(&self) -> Result<DefinitionStore> {
+		let mut store = DefinitionStore::new();
+
+		for schema in self.schemas.iter_shared() {
+			store.intern_schema(&schema.bind().inner)?;
+		}
+
+		Ok(store)
+	}
+
+	pub fn find_class(&self, source: ClassSource) -> Option<Gd<GodotSchema>> {
+		self.classes.get(&source).cloned()
+	}
+}
+
+/// Bundled definitions are emitted with a single set of options, so the schemas must encode values the same way.
+fn check_bundled_options(first: &SchemaOptions, other: &SchemaOptions) -> Result<()> {
+	let differs = [
+		("variant_encoding", first.variant_encoding != other.variant_encoding),
+		("integer_enums", first.integer_enums != other.integer_enums),
+		("representation", first.representation != other.representation),
+		("property_naming", first.property_naming != other.property_naming),
+	];
+
+	match differs.into_iter().find(|(_, differs)| *differs) {
+		Some((option, _)) => bail!("Expected bundled schemas to have the same `{option}` as the first one."),
+		None => Ok(()),
+	}
+}
+
+/// Modification time of the file `source`'s script was loaded from, `None` for engine classes and built-in scripts.
+fn script_modified_time(source: &ClassSource) -> Option<u64> {
+	let (ClassSource::ScriptNamed(script, _) | ClassSource::ScriptUnnamed(script)) = source
+	else { return None };
+
+	let path = script.get_path();
+
+	if path.is_empty() || path.to_string().contains("::") {
+		return None;
+	}
+
+	Some(FileAccess::get_modified_time(&path))
+}
+
+fn into_variant(result: Result<Gd<GodotSchema>>) -> Variant {
+	match result {
+		Ok(schema) => schema.to_variant(),
+		Err(err) => format!("{err:?}").to_variant(),
 	}
 }
 
@@ -145,9 +1334,13 @@ mod internal_prelude {
 	pub(crate) use crate::schema::*;
 	pub(crate) use anyhow::{anyhow, bail, Result};
 	pub(crate) use declarative_type_state::delegated_enum;
-	pub(crate) use godot::classes::{ClassDb, ProjectSettings, ResourceLoader, Script};
+	pub(crate) use godot::classes::{ClassDb, DirAccess, FileAccess, ProjectSettings, ResourceLoader, ResourceSaver, Script};
+	pub(crate) use godot::classes::file_access::ModeFlags;
+	pub(crate) use godot::classes::notify::NodeNotification;
+	pub(crate) use godot::classes::resource_saver::SaverFlags;
 	pub(crate) use godot::global::{PropertyHint, PropertyUsageFlags};
 	pub(crate) use godot::prelude::*;
+	pub(crate) use indexmap::IndexMap;
 	pub(crate) use itertools::Itertools;
 	pub(crate) use serde::ser::SerializeMap;
 	pub(crate) use serde::{Serialize, Serializer};
@@ -8,6 +8,8 @@ pub struct Builder<T = ()> {
 impl Builder {
 	pub fn object() -> Builder<JObject> { Builder::default() }
 	pub fn string_enum() -> Builder<JEnum> { Builder::default() }
+	pub fn array(items_ty: impl Into<Type>) -> Builder<JArray> { Builder { inner: JArray::new(items_ty) } }
+	pub fn conditional(if_: impl Into<Type>) -> Builder<JConditional> { Builder { inner: JConditional::new(if_) } }
 }
 
 impl<T> Builder<T> {
@@ -34,6 +36,69 @@ impl Builder<JObject> {
 		self.inner.add_property(name, ty);
 		self
 	}
+
+	/// Same as [`Self::property()`], but the property may be absent from the input.
+	pub fn optional_property(mut self, name: impl Into<String>, ty: impl Into<Type>) -> Self {
+		let name = name.into();
+		self.inner.optional.insert(name.clone());
+		self.inner.add_property(name, ty);
+		self
+	}
+
+	/// The only value the object accepts (`const`).
+	pub fn const_value(mut self, value: Value) -> Self {
+		self.inner.const_value = Some(value);
+		self
+	}
+
+	/// The value of the object when it is an absent optional property (`default`).
+	pub fn default(mut self, value: Value) -> Self {
+		self.inner.default = Some(value);
+		self
+	}
+
+	/// Whenever property `name` is present, every property of `dependencies` must be too.
+	pub fn dependent_required(mut self, name: impl Into<String>, dependencies: impl IntoIterator<Item = impl Into<String>>) -> Self {
+		self.inner.dependent_required
+			.entry(name.into())
+			.or_default()
+			.extend(dependencies.into_iter().map(Into::into));
+		self
+	}
+}
+
+impl Builder<JArray> {
+	pub fn description(self, description: impl Into<String>) -> Self {
+		debug_assert!(self.inner.description.is_none());
+
+		Self {
+			inner: JArray {
+				description: Some(description.into()),
+				..self.inner
+			}
+		}
+	}
+
+	pub fn min_items(mut self, min_items: usize) -> Self {
+		self.inner.min_items = Some(min_items);
+		self
+	}
+
+	pub fn max_items(mut self, max_items: usize) -> Self {
+		self.inner.max_items = Some(max_items);
+		self
+	}
+
+	pub fn unique_items(mut self) -> Self {
+		self.inner.unique_items = true;
+		self
+	}
+
+	/// At least one element must match `ty`.
+	pub fn contains(mut self, ty: impl Into<Type>) -> Self {
+		self.inner.contains = Some(Box::new(ty.into()));
+		self
+	}
 }
 
 impl Builder<JEnum> {
@@ -52,4 +117,37 @@ impl Builder<JEnum> {
 		self.inner.add_variant(name, value);
 		self
 	}
+
+	/// Adds a variant described by `description`, see [`JEnum::variant_descriptions`].
+	pub fn described_variant(mut self, name: impl Into<String>, value: impl Into<i64>, description: impl Into<String>) -> Self {
+		let name = name.into();
+		self.inner.variant_descriptions.insert(name.clone(), description.into());
+		self.inner.add_variant(name, value);
+		self
+	}
+}
+
+impl Builder<JConditional> {
+	pub fn description(self, description: impl Into<String>) -> Self {
+		debug_assert!(self.inner.description.is_none());
+
+		Self {
+			inner: JConditional {
+				description: Some(description.into()),
+				..self.inner
+			}
+		}
+	}
+
+	/// The schema of the values that satisfy the condition.
+	pub fn then(mut self, ty: impl Into<Type>) -> Self {
+		self.inner.then = Some(Box::new(ty.into()));
+		self
+	}
+
+	/// The schema of the values that do not satisfy the condition (`else`).
+	pub fn otherwise(mut self, ty: impl Into<Type>) -> Self {
+		self.inner.else_ = Some(Box::new(ty.into()));
+		self
+	}
 }
\ No newline at end of file
@@ -0,0 +1,63 @@
+use super::*;
+use std::borrow::Cow;
+
+pub const DEFAULT_CONFIDENCE_SUFFIX: &str = "_confidence";
+
+impl RootSchema {
+	/// Returns a copy of this schema where every root property `<name>` is paired with a required
+	/// `<name><suffix>` number, which agent frameworks can use to report how confident they are in each value.
+	///
+	/// When instantiating, the confidences are routed into a separate `Dictionary` instead of the instance,
+	/// see [`split_confidence()`].
+	pub fn with_confidence(&self, suffix: impl Into<String>) -> Result<RootSchema> {
+		let suffix = suffix.into();
+
+		if suffix.is_empty() {
+			bail!("The confidence suffix cannot be empty.");
+		}
+
+		let mut schema = self.clone();
+
+		let properties = match &mut schema.base {
+			Definition::Class(class) => &mut class.properties,
+			Definition::Object(object) if !object.properties.is_empty() => &mut object.properties,
+			_ => bail!("Confidence annotations require the schema's root to be a class or an object with properties."),
+		};
+
+		let names = properties
+			.keys()
+			.filter(|name| !name.ends_with(&suffix))
+			.cloned()
+			.collect::<Vec<_>>();
+
+		for name in names {
+			let mut confidence = Number::default();
+			confidence.add_description(format!("Confidence in the value of `{name}`, between 0 (guess) and 1 (certain)."));
+			properties.insert(format!("{name}{suffix}"), confidence.into());
+		}
+
+		schema.options.confidence_suffix = Some(suffix);
+		Ok(schema)
+	}
+}
+
+/// Removes the `<name><suffix>` properties (whose `<name>` is also present) from `value`,
+/// returning the remaining value and a `Dictionary` mapping each `<name>` to its confidence.
+pub fn split_confidence<'a>(value: &'a Value, suffix: &str) -> (Cow<'a, Value>, Dictionary) {
+	let Value::Object(properties) = value
+	else { return (Cow::Borrowed(value), Dictionary::new()) };
+
+	let mut remaining = Map::new();
+	let mut confidences = Dictionary::new();
+
+	for (key, val) in properties {
+		if let Some(name) = key.strip_suffix(suffix)
+			&& properties.contains_key(name) {
+			confidences.set(name, val.as_f64().unwrap_or_default());
+		} else {
+			remaining.insert(key.clone(), val.clone());
+		}
+	}
+
+	(Cow::Owned(Value::Object(remaining)), confidences)
+}
@@ -0,0 +1,95 @@
+use super::*;
+
+/// Definitions collected from several schemas, each stored once, e.g. a `Vector2` or `Stats` class referenced by many classes.
+///
+/// Schemas that define the same name must define it identically (once serialized with their options).
+#[derive(Clone, Debug, Default)]
+pub struct DefinitionStore {
+	defs: BTreeMap<String, StoredDefinition>,
+}
+
+#[derive(Clone, Debug)]
+struct StoredDefinition {
+	/// `None` for built-in types, which every schema emits itself.
+	def: Option<Definition>,
+	/// Serialized with the options of the first schema that stored it.
+	json: Value,
+}
+
+impl DefinitionStore {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Stores the `$defs` of `schema` (including built-in types) and, if it is a class, its root,
+	/// skipping the ones already stored.
+	pub fn intern_schema(&mut self, schema: &RootSchema) -> Result<()> {
+		let mut var_defs = Vec::new();
+		schema.base.insert_variant_definitions(&mut var_defs);
+
+		for def in schema.defs.values() {
+			def.insert_variant_definitions(&mut var_defs);
+		}
+
+		var_defs.retain(|var_def| !schema.defs.contains_key(var_def.name()));
+
+		let root = match &schema.base {
+			Definition::Class(class) => Some((class.source.definition_name(), schema.base.clone())),
+			_ => None,
+		};
+
+		let entries = schema.defs
+			.iter()
+			.map(|(name, def)| (name.clone(), def.clone(), true))
+			.chain(var_defs.into_iter().map(|var_def| (var_def.name().to_owned(), var_def.source_definition(), false)))
+			.chain(root.map(|(name, def)| (name, def, true)));
+
+		for (name, def, own) in entries {
+			// Every definition is stored locally, so references stay in the bundle.
+			let options = SchemaOptions {
+				external_refs: BTreeMap::new(),
+				..schema.options.clone()
+			};
+			let json = options.scope(|| serde_json::to_value(&def))?;
+
+			self.intern(name, own.then_some(def), json)?;
+		}
+
+		Ok(())
+	}
+
+	/// Stores `def` as `name`, unless an identical definition is already stored.
+	fn intern(&mut self, name: String, def: Option<Definition>, json: Value) -> Result<()> {
+		if let Some(stored) = self.defs.get(&name) {
+			if stored.json != json {
+				bail!("Definition \"{name}\" is defined differently by several schemas.");
+			}
+
+			return Ok(());
+		}
+
+		self.defs.insert(name, StoredDefinition { def, json });
+		Ok(())
+	}
+
+	/// The stored definitions, keyed by name, except for built-in types.
+	pub fn into_definitions(self) -> BTreeMap<String, Definition> {
+		self.defs
+			.into_iter()
+			.filter_map(|(name, stored)| Some((name, stored.def?)))
+			.collect()
+	}
+
+	/// A single schema document whose `$defs` contain every stored definition, referencing each other locally.
+	pub fn to_bundle(&self) -> Value {
+		let defs = self.defs
+			.iter()
+			.map(|(name, stored)| (name.clone(), stored.json.clone()))
+			.collect::<Map<_, _>>();
+
+		serde_json::json!({
+			"$schema": "https://json-schema.org/draft/2020-12/schema",
+			"$defs": defs,
+		})
+	}
+}
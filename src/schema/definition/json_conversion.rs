@@ -68,6 +68,27 @@ impl_json_convert! {
 			.try_collect()?
 }
 
+impl_into_json! {
+	[bool, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64]
+	|this| Value::from(*this)
+}
+
+impl_into_json! {
+	[String, GString, StringName, NodePath]
+	|this| Value::String(this.to_string())
+}
+
+impl_into_json! {
+	[Rid]
+	|this| Value::from(this.to_u64())
+}
+
+impl<T: ToJson, const N: usize> ToJson for [T; N] {
+	fn to_json(&self) -> Value {
+		Value::Array(self.iter().map(T::to_json).collect())
+	}
+}
+
 object_definitions!(
 	Vector2  { x: f32, y: f32 }
 	Vector2i { x: i32, y: i32 }
@@ -77,16 +98,81 @@ object_definitions!(
 	Vector4i { x: i32, y: i32, z: i32, w: i32 }
 	Rect2 { position: Vector2, size: Vector2 }
 	Rect2i { position: Vector2i, size: Vector2i }
-	Transform2D { a: Vector2, b: Vector2, origin: Vector2 }
-	Transform3D { basis: Basis, origin: Vector3 }
 	Plane { normal: Vector3, d: real }
-	Quaternion { x: real, y: real, z: real, w: real }
 	Aabb { position: Vector3, size: Vector3 }
-	Basis { rows: [Vector3; 3] }
 	Projection { cols: [Vector4; 4] }
-	Color { r: f32, g: f32, b: f32, a: f32 }
 );
 
+/// The pattern of hex colors, see [`VariantRepresentation::hex_colors`].
+pub const HEX_COLOR_PATTERN: &str = "^#?[0-9a-fA-F]{6,8}$";
+
+// Color is not part of `object_definitions!` since it can also be represented as a hex string.
+impl GetDefinition for Color {
+	fn get_definition() -> Definition {
+		VariantDefinition::Color.into()
+	}
+}
+
+impl VariantSourceDefinition for Color {
+	fn source_definition() -> Definition {
+		let representation = VariantRepresentation::current();
+
+		if representation.hex_colors {
+			JString {
+				description: None,
+				pattern: Some(HEX_COLOR_PATTERN.to_owned()),
+				const_value: None,
+				default: None,
+			}.into()
+		} else if representation.compact_math {
+			definition_of::<[f32; 4]>()
+		} else {
+			Builder::object()
+				.property("r", definition_of::<f32>())
+				.property("g", definition_of::<f32>())
+				.property("b", definition_of::<f32>())
+				.property("a", definition_of::<f32>())
+				.done()
+				.into()
+		}
+	}
+}
+
+impl FromJson for Color {
+	fn try_from_json(json: &Value) -> Result<Self> {
+		// Every representation is accepted, regardless of the one the schema declares.
+		match json {
+			Value::Object(properties) => Ok(Color {
+				r: try_value_at_key("r", properties)?,
+				g: try_value_at_key("g", properties)?,
+				b: try_value_at_key("b", properties)?,
+				a: try_value_at_key("a", properties)?,
+			}),
+			Value::Array(_) => {
+				let [r, g, b, a] = <[f32; 4]>::try_from_json(json)?;
+				Ok(Color { r, g, b, a })
+			}
+			Value::String(html) => Color::from_html(html.as_str())
+				.ok_or_else(|| anyhow!("Expected a hex color (e.g. \"#ff8000\").\nGot: {html}")),
+			_ => bail!("Expected JSON value to be of type \"object\".\nGot: {json:?}"),
+		}
+	}
+}
+
+impl ToJson for Color {
+	fn to_json(&self) -> Value {
+		let representation = VariantRepresentation::current();
+
+		if representation.hex_colors {
+			Value::String(format!("#{}", self.to_html()))
+		} else if representation.compact_math {
+			[self.r, self.g, self.b, self.a].to_json()
+		} else {
+			serde_json::json!({ "r": self.r, "g": self.g, "b": self.b, "a": self.a })
+		}
+	}
+}
+
 // Fixed Rust arrays == Tuples in Json 
 impl<T: GetDefinition, const N: usize> GetDefinition for [T; N] {
 	fn get_definition() -> Definition {
@@ -234,7 +320,7 @@ tuple_definitions!(T1, T2, T3, T4, T5, T6);
 tuple_definitions!(T1, T2, T3, T4, T5, T6, T7);
 tuple_definitions!(T1, T2, T3, T4, T5, T6, T7, T8);
 
-fn try_value_at_key<T: FromJson>(key: &str, properties: &Map<String, Value>) -> Result<T> {
+pub(super) fn try_value_at_key<T: FromJson>(key: &str, properties: &Map<String, Value>) -> Result<T> {
 	let value = properties
 		.get(key)
 		.ok_or_else(|| anyhow!("Expected property `{key}` to be present."))?;
@@ -32,6 +32,10 @@ macro_rules! object_definitions {
 					    }
 				    }
 					
+					if crate::VariantDefinition::$Object.compact_encoding() {
+						return crate::JTuple::new([$( crate::definition_of::<$Type>() ),*]).into();
+					}
+					
 					crate::Builder::object()
 				        $( .property(stringify!($Field), crate::definition_of::<$Type>()) )*
 						.done()
@@ -41,14 +45,35 @@ macro_rules! object_definitions {
 	    
 	        impl crate::FromJson for $Object {
 				fn try_from_json(json: &serde_json::Value) -> Result<Self> {
-					let serde_json::Value::Object(properties) = json 
-					else { bail!("Expected JSON value to be of type \"object\".\nGot: {json:?}") };
-					
-					Ok(Self {
-						$( $Field: try_value_at_key(stringify!($Field), properties)?, )*
-					})
+					match json {
+						serde_json::Value::Object(properties) => Ok(Self {
+							$( $Field: try_value_at_key(stringify!($Field), properties)?, )*
+						}),
+						// Either representation is accepted, regardless of the one the schema declares.
+						serde_json::Value::Array(vec) if crate::VariantDefinition::$Object.has_compact_encoding() => {
+							let [$( $Field ),*] = vec.as_slice()
+							else { bail!("Expected JSON array to have {} elements.\nGot: {vec:?}", [$( stringify!($Field) ),*].len()) };
+							
+							Ok(Self {
+								$( $Field: crate::FromJson::try_from_json($Field)?, )*
+							})
+						}
+						_ => bail!("Expected JSON value to be of type \"object\".\nGot: {json:?}"),
+					}
 				}
 			}
+		    
+		    impl crate::ToJson for $Object {
+			    fn to_json(&self) -> serde_json::Value {
+				    if crate::VariantDefinition::$Object.compact_encoding() {
+					    return serde_json::Value::Array(vec![$( crate::ToJson::to_json(&self.$Field) ),*]);
+				    }
+				    
+				    let mut properties = serde_json::Map::new();
+				    $( properties.insert(stringify!($Field).to_owned(), crate::ToJson::to_json(&self.$Field)); )*
+				    serde_json::Value::Object(properties)
+			    }
+		    }
 	    )*
     };
 }
@@ -123,6 +148,12 @@ macro_rules! packed_array_definitions {
 						.try_collect()
 				}
 			}
+		    
+		    impl crate::ToJson for $Name {
+			    fn to_json(&self) -> Value {
+				    Value::Array(self.as_slice().iter().map(crate::ToJson::to_json).collect())
+			    }
+		    }
 	    )*
     };
 }
@@ -182,6 +213,15 @@ macro_rules! variant_definitions {
 			    }
 		    }
 		    
+		    pub fn var_to_json(&self, variant: &godot::prelude::Variant) -> Result<serde_json::Value> {
+			    match self {
+				    $( $E::$T => variant
+					    .try_to::<$T>()
+					    .map(|v| crate::ToJson::to_json(&v))
+					    .map_err(|err| anyhow!("{err:?}")), )*
+			    }
+		    }
+		    
 		    pub const fn variant_type(&self) -> godot::prelude::VariantType {
 			    match self {
 				    $( $E::$T => $P, )*
@@ -191,8 +231,23 @@ macro_rules! variant_definitions {
     };
 }
 
+macro_rules! impl_into_json {
+    ([$($T: ty),* $(,)?] |$this: ident| $Convert: expr) => {
+	    $(
+	        #[allow(clippy::useless_conversion)]
+	        impl crate::ToJson for $T {
+		        fn to_json(&self) -> serde_json::Value {
+			        let $this = self;
+			        $Convert
+		        }
+	        }
+	    )*
+    };
+}
+
 pub(crate) use {
 	object_definitions, 
+	impl_into_json,
 	primitive_definitions, 
 	tuple_definitions, 
 	packed_array_definitions, 
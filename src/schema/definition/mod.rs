@@ -1,9 +1,12 @@
 pub use variant::*;
+pub use representation::*;
 use super::*;
 use macros::*;
 
 pub mod json_conversion;
 pub mod variant;
+pub mod representation;
+pub mod transforms;
 mod macros;
 
 delegated_enum! {
@@ -21,6 +24,10 @@ delegated_enum! {
 			Enum(JEnum),
 			Class(JClass),
 			Variant(VariantDefinition),
+			Any(JAny),
+			ResourcePath(JResourcePath),
+			Conditional(JConditional),
+			OneOf(JOneOf),
 		}
 	}
 	
@@ -48,6 +55,7 @@ impl Definition {
 	pub fn integer() -> Definition { Integer::default().into() }
 	pub fn number() -> Definition { Number::default().into() }
 	pub fn string() -> Definition { JString::default().into() }
+	pub fn any() -> Definition { JAny::default().into() }
 	pub fn untyped_array() -> Definition { JArray::untyped().into() }
 	pub fn dictionary() -> Definition { JObject::new().into() }
 	
@@ -67,12 +75,108 @@ impl Definition {
 		JEnum::new(variants).into()
 	}
 	
+	/// The `const` (the only value accepted) and `default` (the value of absent optional properties) keywords,
+	/// `None` for the definitions that cannot have them (only primitives and objects can).
+	pub fn value_keywords(&self) -> Option<(&Option<Value>, &Option<Value>)> {
+		match self {
+			| Definition::Null(Null { const_value, default, .. })
+			| Definition::Boolean(Boolean { const_value, default, .. })
+			| Definition::Integer(Integer { const_value, default, .. })
+			| Definition::Number(Number { const_value, default, .. })
+			| Definition::String(JString { const_value, default, .. })
+			| Definition::Object(JObject { const_value, default, .. }) => Some((const_value, default)),
+			_ => None,
+		}
+	}
+
+	pub fn value_keywords_mut(&mut self) -> Option<(&mut Option<Value>, &mut Option<Value>)> {
+		match self {
+			| Definition::Null(Null { const_value, default, .. })
+			| Definition::Boolean(Boolean { const_value, default, .. })
+			| Definition::Integer(Integer { const_value, default, .. })
+			| Definition::Number(Number { const_value, default, .. })
+			| Definition::String(JString { const_value, default, .. })
+			| Definition::Object(JObject { const_value, default, .. }) => Some((const_value, default)),
+			_ => None,
+		}
+	}
+
+	pub fn const_value(&self) -> Option<&Value> {
+		self.value_keywords().and_then(|(const_value, _)| const_value.as_ref())
+	}
+
+	pub fn default_value(&self) -> Option<&Value> {
+		self.value_keywords().and_then(|(_, default)| default.as_ref())
+	}
+
+	/// The name of this definition's kind, e.g. "integer", "class" or "one_of".
+	pub fn kind(&self) -> &'static str {
+		match self {
+			Definition::Null(_) => "null",
+			Definition::Boolean(_) => "boolean",
+			Definition::Integer(_) => "integer",
+			Definition::Number(_) => "number",
+			Definition::String(_) => "string",
+			Definition::Object(_) => "object",
+			Definition::Array(_) => "array",
+			Definition::Tuple(_) => "tuple",
+			Definition::Enum(_) => "enum",
+			Definition::Class(_) => "class",
+			Definition::Variant(_) => "variant",
+			Definition::Any(_) => "any",
+			Definition::ResourcePath(_) => "resource_path",
+			Definition::Conditional(_) => "conditional",
+			Definition::OneOf(_) => "one_of",
+		}
+	}
+
 	pub fn into_reference(self, name: impl Into<String>, defs: &mut BTreeMap<String, Definition>) -> JRef {
 		let name = name.into();
 		defs.insert(name.clone(), self);
 		JRef::new(name)
 	}
 	
+	/// Inserts the names of every definition referenced by this one (not recursively through `$defs`).
+	pub fn insert_references(&self, fill_me: &mut BTreeSet<String>) {
+		match self {
+			Definition::Object(JObject { properties, values_ty, .. }) => {
+				for ty in properties.values().chain(values_ty.as_deref()) {
+					ty.insert_references(fill_me);
+				}
+			}
+			Definition::Class(JClass { properties, base, .. }) => {
+				for ty in properties.values() {
+					ty.insert_references(fill_me);
+				}
+
+				if let Some(base) = base {
+					fill_me.insert(base.reference.name.clone());
+				}
+			}
+			Definition::Array(JArray { items_ty, contains, .. }) => {
+				for ty in items_ty.iter().chain(contains) {
+					ty.insert_references(fill_me);
+				}
+			}
+			Definition::Tuple(JTuple { items, .. }) => {
+				for ty in items {
+					ty.insert_references(fill_me);
+				}
+			}
+			Definition::Conditional(conditional) => {
+				for ty in conditional.types() {
+					ty.insert_references(fill_me);
+				}
+			}
+			Definition::OneOf(JOneOf { options, .. }) => {
+				for ty in options {
+					ty.insert_references(fill_me);
+				}
+			}
+			_ => {}
+		}
+	}
+
 	pub fn insert_variant_definitions(&self, fill_me: &mut Vec<VariantDefinition>) {
 		match self {
 			Definition::Object(obj) => obj.insert_variant_definitions(fill_me),
@@ -80,6 +184,8 @@ impl Definition {
 			Definition::Tuple(tuple) => tuple.insert_variant_definitions(fill_me),
 			Definition::Class(class) => class.insert_variant_definitions(fill_me),
 			Definition::Variant(var) => var.insert_variant_definitions(fill_me),
+			Definition::Conditional(conditional) => conditional.insert_variant_definitions(fill_me),
+			Definition::OneOf(one_of) => one_of.insert_variant_definitions(fill_me),
 			_ => {}
 		}
 	}
@@ -89,6 +195,10 @@ pub trait FromJson: Sized {
 	fn try_from_json(json: &Value) -> Result<Self>;
 }
 
+pub trait ToJson {
+	fn to_json(&self) -> Value;
+}
+
 pub trait GetDefinition {
 	fn get_definition() -> Definition;
 }
@@ -0,0 +1,33 @@
+use super::*;
+
+/// Alternative encodings of Godot's built-in types, see [`SchemaOptions::representation`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct VariantRepresentation {
+	/// If set, vectors, quaternions and colors are serialized as fixed-length number arrays
+	/// (`[x, y]`, `[r, g, b, a]`) instead of objects, see [`VariantDefinition::has_compact_encoding()`].
+	///
+	/// Instantiation accepts either.
+	pub compact_math: bool,
+	/// If set, colors are serialized as hex strings (`"#ff8000"`, or `"#ff800080"` with alpha) matching [`HEX_COLOR_PATTERN`],
+	/// taking precedence over [`Self::compact_math`].
+	///
+	/// Instantiation accepts either, parsing strings with `Color::from_html()`.
+	pub hex_colors: bool,
+	/// If set, `Transform2D`, `Transform3D` and `Basis` are serialized as their components
+	/// (`{"position": .., "rotation_degrees": .., "scale": ..}`) instead of their basis vectors, see [`TRANSFORM_EULER_ORDER`].
+	///
+	/// Instantiation accepts either. Skew and shear are lost when serializing.
+	pub friendly_transforms: bool,
+	/// If set, quaternions are serialized as Euler angles in degrees (`{"yaw": .., "pitch": .., "roll": ..}`),
+	/// taking precedence over [`Self::compact_math`].
+	///
+	/// Instantiation accepts either.
+	pub euler_quaternions: bool,
+}
+
+impl VariantRepresentation {
+	/// The representation built-in types are currently serialized with, see [`SchemaOptions::scope()`].
+	pub fn current() -> Self {
+		SchemaOptions::read_current(|options| options.representation)
+	}
+}
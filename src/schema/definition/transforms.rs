@@ -0,0 +1,278 @@
+use super::*;
+use super::json_conversion::try_value_at_key;
+use godot::builtin::EulerOrder;
+
+/// The order `rotation_degrees` are applied in, when [`VariantRepresentation::friendly_transforms`] is set.
+/// Same as the default of `Node3D.rotation_order`.
+pub const TRANSFORM_EULER_ORDER: EulerOrder = EulerOrder::YXZ;
+
+// Transforms and quaternions are not part of `object_definitions!` since they can also be represented as their components.
+
+impl GetDefinition for Transform2D {
+	fn get_definition() -> Definition {
+		VariantDefinition::Transform2D.into()
+	}
+}
+
+impl VariantSourceDefinition for Transform2D {
+	fn source_definition() -> Definition {
+		let builder = if VariantRepresentation::current().friendly_transforms {
+			Builder::object()
+				.property("position", definition_of::<Vector2>())
+				.property("rotation_degrees", definition_of::<real>())
+				.property("scale", definition_of::<Vector2>())
+		} else {
+			Builder::object()
+				.property("a", definition_of::<Vector2>())
+				.property("b", definition_of::<Vector2>())
+				.property("origin", definition_of::<Vector2>())
+		};
+
+		builder.done().into()
+	}
+}
+
+impl FromJson for Transform2D {
+	fn try_from_json(json: &Value) -> Result<Self> {
+		let Value::Object(properties) = json
+		else { bail!("Expected JSON value to be of type \"object\".\nGot: {json:?}") };
+
+		// Either representation is accepted, regardless of the one the schema declares.
+		if properties.contains_key("rotation_degrees") {
+			let rotation = try_value_at_key::<real>("rotation_degrees", properties)?.to_radians();
+			let scale = try_value_at_key("scale", properties)?;
+			let position = try_value_at_key("position", properties)?;
+
+			Ok(Transform2D::from_angle_scale_skew_origin(rotation, scale, 0.0, position))
+		} else {
+			Ok(Transform2D {
+				a: try_value_at_key("a", properties)?,
+				b: try_value_at_key("b", properties)?,
+				origin: try_value_at_key("origin", properties)?,
+			})
+		}
+	}
+}
+
+impl ToJson for Transform2D {
+	fn to_json(&self) -> Value {
+		if VariantRepresentation::current().friendly_transforms {
+			serde_json::json!({
+				"position": self.origin.to_json(),
+				"rotation_degrees": self.rotation().to_degrees(),
+				"scale": self.scale().to_json(),
+			})
+		} else {
+			serde_json::json!({
+				"a": self.a.to_json(),
+				"b": self.b.to_json(),
+				"origin": self.origin.to_json(),
+			})
+		}
+	}
+}
+
+impl GetDefinition for Basis {
+	fn get_definition() -> Definition {
+		VariantDefinition::Basis.into()
+	}
+}
+
+impl VariantSourceDefinition for Basis {
+	fn source_definition() -> Definition {
+		let builder = if VariantRepresentation::current().friendly_transforms {
+			Builder::object()
+				.property("rotation_degrees", definition_of::<Vector3>())
+				.property("scale", definition_of::<Vector3>())
+		} else {
+			Builder::object()
+				.property("rows", definition_of::<[Vector3; 3]>())
+		};
+
+		builder.done().into()
+	}
+}
+
+impl FromJson for Basis {
+	fn try_from_json(json: &Value) -> Result<Self> {
+		let Value::Object(properties) = json
+		else { bail!("Expected JSON value to be of type \"object\".\nGot: {json:?}") };
+
+		if properties.contains_key("rotation_degrees") {
+			basis_from_components(properties)
+		} else {
+			Ok(Basis { rows: try_value_at_key("rows", properties)? })
+		}
+	}
+}
+
+impl ToJson for Basis {
+	fn to_json(&self) -> Value {
+		if VariantRepresentation::current().friendly_transforms {
+			let (rotation_degrees, scale) = basis_components(self);
+
+			serde_json::json!({
+				"rotation_degrees": rotation_degrees.to_json(),
+				"scale": scale.to_json(),
+			})
+		} else {
+			serde_json::json!({ "rows": self.rows.to_json() })
+		}
+	}
+}
+
+impl GetDefinition for Transform3D {
+	fn get_definition() -> Definition {
+		VariantDefinition::Transform3D.into()
+	}
+}
+
+impl VariantSourceDefinition for Transform3D {
+	fn source_definition() -> Definition {
+		let builder = if VariantRepresentation::current().friendly_transforms {
+			Builder::object()
+				.property("position", definition_of::<Vector3>())
+				.property("rotation_degrees", definition_of::<Vector3>())
+				.property("scale", definition_of::<Vector3>())
+		} else {
+			Builder::object()
+				.property("basis", definition_of::<Basis>())
+				.property("origin", definition_of::<Vector3>())
+		};
+
+		builder.done().into()
+	}
+}
+
+impl FromJson for Transform3D {
+	fn try_from_json(json: &Value) -> Result<Self> {
+		let Value::Object(properties) = json
+		else { bail!("Expected JSON value to be of type \"object\".\nGot: {json:?}") };
+
+		if properties.contains_key("rotation_degrees") {
+			let basis = basis_from_components(properties)?;
+			let position = try_value_at_key("position", properties)?;
+
+			Ok(Transform3D::new(basis, position))
+		} else {
+			Ok(Transform3D {
+				basis: try_value_at_key("basis", properties)?,
+				origin: try_value_at_key("origin", properties)?,
+			})
+		}
+	}
+}
+
+impl ToJson for Transform3D {
+	fn to_json(&self) -> Value {
+		if VariantRepresentation::current().friendly_transforms {
+			let (rotation_degrees, scale) = basis_components(&self.basis);
+
+			serde_json::json!({
+				"position": self.origin.to_json(),
+				"rotation_degrees": rotation_degrees.to_json(),
+				"scale": scale.to_json(),
+			})
+		} else {
+			serde_json::json!({
+				"basis": self.basis.to_json(),
+				"origin": self.origin.to_json(),
+			})
+		}
+	}
+}
+
+impl GetDefinition for Quaternion {
+	fn get_definition() -> Definition {
+		VariantDefinition::Quaternion.into()
+	}
+}
+
+impl VariantSourceDefinition for Quaternion {
+	fn source_definition() -> Definition {
+		let representation = VariantRepresentation::current();
+
+		if representation.euler_quaternions {
+			Builder::object()
+				.property("yaw", definition_of::<real>())
+				.property("pitch", definition_of::<real>())
+				.property("roll", definition_of::<real>())
+				.done()
+				.into()
+		} else if representation.compact_math {
+			definition_of::<[real; 4]>()
+		} else {
+			Builder::object()
+				.property("x", definition_of::<real>())
+				.property("y", definition_of::<real>())
+				.property("z", definition_of::<real>())
+				.property("w", definition_of::<real>())
+				.done()
+				.into()
+		}
+	}
+}
+
+impl FromJson for Quaternion {
+	fn try_from_json(json: &Value) -> Result<Self> {
+		// Every representation is accepted, regardless of the one the schema declares.
+		match json {
+			Value::Object(properties) if properties.contains_key("yaw") => {
+				let yaw = try_value_at_key::<real>("yaw", properties)?.to_radians();
+				let pitch = try_value_at_key::<real>("pitch", properties)?.to_radians();
+				let roll = try_value_at_key::<real>("roll", properties)?.to_radians();
+
+				// Same axes as `Node3D.rotation`: pitch around X, yaw around Y and roll around Z.
+				Ok(Quaternion::from_euler(Vector3::new(pitch, yaw, roll)))
+			}
+			Value::Object(properties) => Ok(Quaternion {
+				x: try_value_at_key("x", properties)?,
+				y: try_value_at_key("y", properties)?,
+				z: try_value_at_key("z", properties)?,
+				w: try_value_at_key("w", properties)?,
+			}),
+			Value::Array(_) => {
+				let [x, y, z, w] = <[real; 4]>::try_from_json(json)?;
+				Ok(Quaternion { x, y, z, w })
+			}
+			_ => bail!("Expected JSON value to be of type \"object\".\nGot: {json:?}"),
+		}
+	}
+}
+
+impl ToJson for Quaternion {
+	fn to_json(&self) -> Value {
+		let representation = VariantRepresentation::current();
+
+		if representation.euler_quaternions {
+			let radians = self.to_euler(TRANSFORM_EULER_ORDER);
+
+			serde_json::json!({
+				"yaw": radians.y.to_degrees(),
+				"pitch": radians.x.to_degrees(),
+				"roll": radians.z.to_degrees(),
+			})
+		} else if representation.compact_math {
+			[self.x, self.y, self.z, self.w].to_json()
+		} else {
+			serde_json::json!({ "x": self.x, "y": self.y, "z": self.z, "w": self.w })
+		}
+	}
+}
+
+/// Rotates by `rotation_degrees` (in [`TRANSFORM_EULER_ORDER`]), after scaling by `scale`.
+fn basis_from_components(properties: &Map<String, Value>) -> Result<Basis> {
+	let rotation = try_value_at_key::<Vector3>("rotation_degrees", properties)?;
+	let scale = try_value_at_key("scale", properties)?;
+
+	let radians = Vector3::new(rotation.x.to_radians(), rotation.y.to_radians(), rotation.z.to_radians());
+	Ok(Basis::from_euler(TRANSFORM_EULER_ORDER, radians) * Basis::from_scale(scale))
+}
+
+/// The reverse of [`basis_from_components()`]: the rotation (in degrees) and scale of `basis`.
+fn basis_components(basis: &Basis) -> (Vector3, Vector3) {
+	let radians = basis.orthonormalized().to_euler(TRANSFORM_EULER_ORDER);
+	let degrees = Vector3::new(radians.x.to_degrees(), radians.y.to_degrees(), radians.z.to_degrees());
+
+	(degrees, basis.scale())
+}
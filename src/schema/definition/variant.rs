@@ -45,6 +45,25 @@ impl VariantDefinition {
 		godot_warn!("`VariantDefinition::add_description` is not allowed.");
 	}
 
+	/// Whether this type can be represented as a fixed-length number array, see [`VariantRepresentation::compact_math`].
+	pub const fn has_compact_encoding(&self) -> bool {
+		matches!(self,
+			VariantDefinition::Vector2
+			| VariantDefinition::Vector2i
+			| VariantDefinition::Vector3
+			| VariantDefinition::Vector3i
+			| VariantDefinition::Vector4
+			| VariantDefinition::Vector4i
+			| VariantDefinition::Quaternion
+			| VariantDefinition::Color
+		)
+	}
+
+	/// Whether this type is currently serialized as a fixed-length number array.
+	pub fn compact_encoding(&self) -> bool {
+		self.has_compact_encoding() && VariantRepresentation::current().compact_math
+	}
+
 	pub fn insert_variant_definitions(&self, fill_me: &mut Vec<VariantDefinition>) {
 		fill_me.push(*self);
 
@@ -63,6 +82,9 @@ impl VariantDefinition {
 
 			VariantDefinition::Rect2i => fill_me.push(VariantDefinition::Vector2i),
 			VariantDefinition::PackedColorArray => fill_me.push(VariantDefinition::Color),
+			VariantDefinition::Transform3D if VariantRepresentation::current().friendly_transforms => {
+				fill_me.push(VariantDefinition::Vector3);
+			}
 			VariantDefinition::Transform3D => fill_me.extend([
 				VariantDefinition::Vector3,
 				VariantDefinition::Basis,
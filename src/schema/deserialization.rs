@@ -0,0 +1,549 @@
+use super::*;
+
+/// Annotates class definitions with the class they were generated from: its name, or the script's path for unnamed classes.
+pub const GODOT_CLASS_KEY: &str = "x-godot-class";
+/// Annotates enum definitions with the integer value of each variant.
+pub const GODOT_ENUM_KEY: &str = "x-godot-enum";
+/// Annotates a root that wraps a non-class type with the name of the wrapping property.
+pub const GODOT_WRAPPER_KEY: &str = "x-godot-wrapper";
+/// Annotates a root generated with [`RootSchema::with_confidence()`] with its suffix.
+pub const GODOT_CONFIDENCE_SUFFIX_KEY: &str = "x-godot-confidence-suffix";
+/// Annotates a root whose untyped values are serialized with [`VariantEncoding::Tagged`].
+pub const GODOT_VARIANT_ENCODING_KEY: &str = "x-godot-variant-encoding";
+/// Annotates a root whose enums are serialized as integers, see [`SchemaOptions::integer_enums`].
+pub const GODOT_ENUM_ENCODING_KEY: &str = "x-godot-enum-encoding";
+/// Annotates a root whose math types are serialized as number arrays, see [`VariantRepresentation::compact_math`].
+pub const GODOT_MATH_ENCODING_KEY: &str = "x-godot-math-encoding";
+/// Annotates a root whose colors are serialized as hex strings, see [`VariantRepresentation::hex_colors`].
+pub const GODOT_COLOR_ENCODING_KEY: &str = "x-godot-color-encoding";
+/// Annotates a root whose transforms are serialized as their components, see [`VariantRepresentation::friendly_transforms`].
+pub const GODOT_TRANSFORM_ENCODING_KEY: &str = "x-godot-transform-encoding";
+/// Annotates a root whose quaternions are serialized as Euler angles, see [`VariantRepresentation::euler_quaternions`].
+pub const GODOT_QUATERNION_ENCODING_KEY: &str = "x-godot-quaternion-encoding";
+/// Annotates a property whose name in JSON differs from its Godot name with the latter, see [`JClass::aliases`].
+pub const GODOT_PROPERTY_KEY: &str = "x-godot-property";
+
+/// Whether the serialization running on this thread emits the annotations above, see [`SchemaOptions::round_trip_annotations`].
+pub fn round_trip_annotations() -> bool {
+	SchemaOptions::read_current(|options| options.round_trip_annotations)
+}
+
+impl RootSchema {
+	/// The reverse of [`Self::to_json_pretty()`]/[`Self::to_json_compact()`]: reconstructs the definitions from this crate's own output,
+	/// serialized with [`SchemaOptions::round_trip_annotations`].
+	///
+	/// Classes are resolved from their `x-godot-class` annotations (see [`GODOT_CLASS_KEY`]), without fetching their property lists.
+	///
+	/// For schemas authored elsewhere, use [`Self::from_json_schema()`] instead.
+	pub fn from_value(value: &Value) -> Result<RootSchema> {
+		let Value::Object(root) = value
+		else { bail!("Expected schema to be an object.\nGot: {value}") };
+
+		let def_schemas = match root.get("$defs") {
+			None => Map::new(),
+			Some(Value::Object(defs)) => defs.clone(),
+			Some(other) => bail!("Expected `$defs` to be an object.\nGot: {other}"),
+		};
+
+		// Definitions of Godot's built-in types are generated during serialization, they are referenced as `VariantDefinition`s instead.
+		let def_names = def_schemas
+			.keys()
+			.filter(|name| VariantDefinition::try_from_name(name).is_none())
+			.cloned()
+			.collect::<BTreeSet<_>>();
+
+		let parser = Parser { def_names: &def_names };
+
+		let mut defs = def_names
+			.iter()
+			.map(|name| {
+				parser.definition(&def_schemas[name])
+					.map(|def| (name.clone(), def))
+					.map_err(|err| anyhow!("Definition \"{name}\": {err}"))
+			})
+			.try_collect()?;
+
+		let wrapper = root.get(GODOT_WRAPPER_KEY).and_then(Value::as_str);
+
+		let mut base = match wrapper {
+			Some(wrapper) => {
+				let wrapped = root.get("properties")
+					.and_then(|properties| properties.get(wrapper))
+					.ok_or_else(|| anyhow!("Expected wrapped property \"{wrapper}\" to be in `properties`."))?;
+
+				match parser.ty(wrapped)? {
+					Type::Definition(def) => def,
+					Type::Ref(JRef { name, .. }) => bail!("Expected wrapped property to be a definition.\nGot a reference to \"{name}\"."),
+				}
+			}
+			None => parser.definition(value)?,
+		};
+
+		let confidence_suffix = root
+			.get(GODOT_CONFIDENCE_SUFFIX_KEY)
+			.and_then(Value::as_str)
+			.map(str::to_owned);
+
+		let variant_encoding = match root.get(GODOT_VARIANT_ENCODING_KEY).and_then(Value::as_str) {
+			Some("tagged") => VariantEncoding::Tagged,
+			_ => VariantEncoding::Plain,
+		};
+
+		flatten_inheritance(&mut defs, &mut base)?;
+
+		let integer_enums = root.get(GODOT_ENUM_ENCODING_KEY).and_then(Value::as_str) == Some("integer");
+		let id = root.get("$id").and_then(Value::as_str).map(str::to_owned);
+		let omit_meta_schema = root.get("$schema").is_none();
+
+		let representation = VariantRepresentation {
+			compact_math: root.get(GODOT_MATH_ENCODING_KEY).and_then(Value::as_str) == Some("compact"),
+			hex_colors: root.get(GODOT_COLOR_ENCODING_KEY).and_then(Value::as_str) == Some("hex"),
+			friendly_transforms: root.get(GODOT_TRANSFORM_ENCODING_KEY).and_then(Value::as_str) == Some("components"),
+			euler_quaternions: root.get(GODOT_QUATERNION_ENCODING_KEY).and_then(Value::as_str) == Some("euler"),
+		};
+
+		// Annotations are emitted for every class property, finding any means they were enabled.
+		let type_annotations = defs
+			.values()
+			.chain([&base])
+			.any(|def| matches!(def, Definition::Class(class) if !class.type_infos.is_empty()));
+
+		// Non-class roots without a wrapper were emitted with `SchemaOptions::unwrapped_root`.
+		let unwrapped_root = wrapper.is_none() && !matches!(base, Definition::Class(_) | Definition::Object(_));
+		let class_inheritance = defs
+			.values()
+			.chain([&base])
+			.any(|def| matches!(def, Definition::Class(JClass { base: Some(_), .. })));
+
+		Ok(RootSchema {
+			defs,
+			base,
+			options: SchemaOptions {
+				confidence_suffix,
+				variant_encoding,
+				type_annotations,
+				round_trip_annotations: true,
+				integer_enums,
+				representation,
+				id,
+				omit_meta_schema,
+				external_refs: BTreeMap::new(),
+				external_documents: Default::default(),
+				property_naming: PropertyNaming::AsIs,
+				accept_godot_names: false,
+				wrapper_key: wrapper.filter(|key| *key != DEFAULT_WRAPPER_KEY).map(str::to_owned),
+				unwrapped_root,
+				class_inheritance,
+				// The other generation settings leave no trace in the JSON.
+				..SchemaOptions::default()
+			},
+			warnings: Vec::new(),
+			name_map: None,
+		})
+	}
+}
+
+impl ClassSource {
+	/// The reverse of [`Self::definition_name()`], paths (e.g. "res://enemy.gd") are loaded as scripts.
+	pub fn from_definition_name(name: &str) -> Result<Self> {
+		if !name.contains("://") {
+			return Self::from_class_name(name);
+		}
+
+		let script = ResourceLoader::singleton()
+			.load(name)
+			.ok_or_else(|| SchemaError::Io { path: name.to_owned(), message: "Could not load script.".to_owned() })?
+			.try_cast::<Script>()
+			.map_err(|err| anyhow!("{err:?}"))?;
+
+		Ok(Self::from_script(script))
+	}
+}
+
+/// The names of the properties in `keywords` that are not `required`.
+fn optional(keywords: &Map<String, Value>) -> BTreeSet<String> {
+	let required = keywords
+		.get("required")
+		.and_then(Value::as_array)
+		.map(|required| required.iter().filter_map(Value::as_str).collect::<HashSet<_>>())
+		.unwrap_or_default();
+
+	keywords.get("properties")
+		.and_then(Value::as_object)
+		.into_iter()
+		.flatten()
+		.map(|(name, _)| name)
+		.filter(|name| !required.contains(name.as_str()))
+		.cloned()
+		.collect()
+}
+
+/// Reads the [`GodotTypeInfo`] annotations of each property in `keywords`, properties without annotations are skipped.
+fn type_infos(keywords: &Map<String, Value>) -> BTreeMap<String, GodotTypeInfo> {
+	keywords.get("properties")
+		.and_then(Value::as_object)
+		.into_iter()
+		.flatten()
+		.filter_map(|(name, schema)| {
+			let info = GodotTypeInfo::from_annotations(schema.as_object()?)?;
+			Some((name.clone(), info))
+		})
+		.collect()
+}
+
+/// Renames the properties annotated with [`GODOT_PROPERTY_KEY`] in `keywords` back to their Godot name,
+/// keeping their name in JSON as an alias, see [`JClass::aliases`].
+fn restore_aliases(class: &mut JClass, keywords: &Map<String, Value>) {
+	let godot_names = keywords.get("properties")
+		.and_then(Value::as_object)
+		.into_iter()
+		.flatten()
+		.filter_map(|(json_name, schema)| Some((json_name.clone(), schema.get(GODOT_PROPERTY_KEY)?.as_str()?.to_owned())))
+		.collect::<BTreeMap<_, _>>();
+
+	if godot_names.is_empty() {
+		return;
+	}
+
+	let rename = |name: &String| godot_names.get(name).cloned().unwrap_or_else(|| name.clone());
+
+	class.properties = std::mem::take(&mut class.properties)
+		.into_iter()
+		.map(|(name, ty)| (rename(&name), ty))
+		.collect();
+
+	class.optional = class.optional.iter().map(rename).collect();
+
+	class.type_infos = std::mem::take(&mut class.type_infos)
+		.into_iter()
+		.map(|(name, info)| (rename(&name), info))
+		.collect();
+
+	class.aliases = godot_names
+		.into_iter()
+		.map(|(json_name, name)| (name, json_name))
+		.collect();
+}
+
+/// Copies the properties of each class's base class into it (see [`JClass::inherit()`]), bases first.
+fn flatten_inheritance(defs: &mut BTreeMap<String, Definition>, base: &mut Definition) -> Result<()> {
+	let mut flattened = defs
+		.iter()
+		.filter(|(_, def)| !matches!(def, Definition::Class(JClass { base: Some(_), .. })))
+		.map(|(name, _)| name.clone())
+		.collect::<BTreeSet<_>>();
+
+	let base_of = |def: &Definition| match def {
+		Definition::Class(JClass { base: Some(class_base), .. }) => Some(class_base.reference.name.clone()),
+		_ => None,
+	};
+
+	while flattened.len() < defs.len() {
+		let ready = defs
+			.iter()
+			.filter(|(name, def)| !flattened.contains(*name) && base_of(def).is_some_and(|base| flattened.contains(&base)))
+			.map(|(name, def)| (name.clone(), base_of(def).unwrap_or_default()))
+			.collect_vec();
+
+		if ready.is_empty() {
+			let pending = defs.keys().filter(|name| !flattened.contains(*name)).join(", ");
+			bail!("Expected the base classes of {pending} to be class definitions, without cycles.");
+		}
+
+		for (name, base_name) in ready {
+			let Some(Definition::Class(base_class)) = defs.get(&base_name).cloned()
+			else { bail!("Expected base class \"{base_name}\" of \"{name}\" to be a class definition.") };
+
+			if let Some(Definition::Class(class)) = defs.get_mut(&name) {
+				class.inherit(&base_class);
+			}
+
+			flattened.insert(name);
+		}
+	}
+
+	if let Some(base_name) = base_of(base) {
+		let Some(Definition::Class(base_class)) = defs.get(&base_name)
+		else { bail!("Expected base class \"{base_name}\" to be a class definition.") };
+
+		if let Definition::Class(class) = base {
+			class.inherit(base_class);
+		}
+	}
+
+	Ok(())
+}
+
+struct Parser<'a> {
+	/// Names of the definitions in `$defs`, references to other names are built-in types.
+	def_names: &'a BTreeSet<String>,
+}
+
+impl Parser<'_> {
+	fn ty(&self, value: &Value) -> Result<Type> {
+		let Some(reference) = value.get("$ref")
+		else { return self.definition(value).map(Type::Definition) };
+
+		let name = reference
+			.as_str()
+			.and_then(|reference| reference.strip_prefix("#/$defs/"))
+			.ok_or_else(|| anyhow!("Expected `$ref` to point into `$defs`.\nGot: {reference}"))?;
+
+		if !self.def_names.contains(name)
+			&& let Some(var_def) = VariantDefinition::try_from_name(name) {
+			return Ok(Type::Definition(var_def.into()));
+		}
+
+		let mut reference = JRef::new(name);
+
+		if let Some(Value::String(description)) = value.get("description") {
+			reference.add_description(description.clone());
+		}
+
+		Ok(Type::Ref(reference))
+	}
+
+	fn definition(&self, value: &Value) -> Result<Definition> {
+		let Value::Object(keywords) = value
+		else { bail!("Expected definition to be an object.\nGot: {value}") };
+
+		// Annotated properties also carry a class name, only definitions without a `x-godot-type` are classes.
+		let class_name = keywords.get(GODOT_CLASS_KEY).filter(|_| !keywords.contains_key(GODOT_TYPE_KEY));
+
+		let mut def = if let Some(class_name) = class_name {
+			let class_name = class_name
+				.as_str()
+				.ok_or_else(|| anyhow!("Expected `{GODOT_CLASS_KEY}` to be a string.\nGot: {class_name}"))?;
+
+			// Inheriting classes hold their own properties in `allOf`, after the reference to their base class.
+			let (base, own) = match keywords.get("allOf") {
+				Some(all_of) => self.class_base(all_of)?,
+				None => (None, keywords),
+			};
+
+			let mut class = JClass {
+				description: None,
+				properties: self.properties(own)?,
+				optional: optional(own),
+				source: ClassSource::from_definition_name(class_name)?,
+				type_infos: type_infos(own),
+				constraints: BTreeMap::new(),
+				base,
+				extensible: !keywords.contains_key("additionalProperties") && !keywords.contains_key("unevaluatedProperties"),
+				aliases: BTreeMap::new(),
+			};
+
+			restore_aliases(&mut class, own);
+			class.into()
+		} else {
+			match keywords.get("type").and_then(Value::as_str) {
+				Some("null") => Definition::null(),
+				Some("boolean") => Definition::boolean(),
+				Some("integer") if keywords.contains_key(GODOT_ENUM_KEY) => self.integer_enumeration(keywords)?,
+				Some("integer") => Definition::integer(),
+				Some("number") => Definition::number(),
+				Some("string") if keywords.get("format").and_then(Value::as_str) == Some(RESOURCE_PATH_FORMAT) => {
+					self.resource_path(keywords)?
+				}
+				Some("string") => match (keywords.get("enum"), keywords.get("oneOf")) {
+					(Some(Value::Array(names)), _) => self.enumeration(keywords, names, BTreeMap::new())?,
+					// Enums with variant descriptions list each variant as `{"const": name, "description": ...}`.
+					(_, Some(Value::Array(variants))) => {
+						let names = variants
+							.iter()
+							.map(|variant| variant.get("const").cloned().unwrap_or_default())
+							.collect_vec();
+
+						let descriptions = variants
+							.iter()
+							.filter_map(|variant| {
+								let name = variant.get("const")?.as_str()?;
+								let description = variant.get("description")?.as_str()?;
+								Some((name.to_owned(), description.to_owned()))
+							})
+							.collect();
+
+						self.enumeration(keywords, &names, descriptions)?
+					}
+					_ => JString {
+						description: None,
+						pattern: keywords.get("pattern").and_then(Value::as_str).map(str::to_owned),
+						const_value: None,
+						default: None,
+					}.into(),
+				},
+				Some("array") => self.array(keywords)?,
+				Some("object") => JObject {
+					description: None,
+					properties: self.properties(keywords)?,
+					optional: optional(keywords),
+					dependent_required: match keywords.get("dependentRequired") {
+						None => BTreeMap::new(),
+						Some(dependent_required) => serde_json::from_value(dependent_required.clone())
+							.map_err(|err| anyhow!("Expected `dependentRequired` to map property names to arrays of names.\nError: {err}"))?,
+					},
+					const_value: None,
+					default: None,
+					values_ty: match keywords.get("additionalProperties") {
+						Some(values @ Value::Object(_)) if !keywords.contains_key("properties") => Some(Box::new(self.ty(values)?)),
+						_ => None,
+					},
+				}.into(),
+				Some(other) => bail!("Unknown type \"{other}\"."),
+				None if keywords.contains_key("if") => JConditional {
+					description: None,
+					if_: Box::new(self.ty(&keywords["if"])?),
+					then: keywords.get("then").map(|then| self.ty(then)).transpose()?.map(Box::new),
+					else_: keywords.get("else").map(|else_| self.ty(else_)).transpose()?.map(Box::new),
+				}.into(),
+				None if keywords.get("oneOf").is_some_and(Value::is_array) => JOneOf::new(
+					keywords["oneOf"]
+						.as_array()
+						.into_iter()
+						.flatten()
+						.map(|option| self.ty(option))
+						.try_collect::<_, Vec<_>, _>()?,
+				).into(),
+				None if keywords.keys().all(|key| key == "description") => Definition::any(),
+				None if is_any_of_types(keywords) => Definition::any(),
+				None => bail!("Expected definition to have a `type`.\nGot: {value}"),
+			}
+		};
+
+		if let Some((const_value, default)) = def.value_keywords_mut() {
+			*const_value = keywords.get("const").cloned();
+			*default = keywords.get("default").cloned();
+		}
+
+		if let Some(Value::String(description)) = keywords.get("description") {
+			def.add_description(description.clone());
+		}
+
+		Ok(def)
+	}
+
+	/// Reads `allOf: [{"$ref": base}, {own properties}]`, the inherited properties are copied by [`flatten_inheritance()`].
+	fn class_base<'v>(&self, all_of: &'v Value) -> Result<(Option<ClassBase>, &'v Map<String, Value>)> {
+		let Some([base, Value::Object(own)]) = all_of.as_array().map(Vec::as_slice)
+		else { bail!("Expected `allOf` of a class to hold its base class's reference and its own properties.\nGot: {all_of}") };
+
+		let Type::Ref(reference) = self.ty(base)?
+		else { bail!("Expected the base class to be a reference.\nGot: {base}") };
+
+		Ok((Some(ClassBase { reference, inherited: BTreeSet::new() }), own))
+	}
+
+	fn properties(&self, keywords: &Map<String, Value>) -> Result<IndexMap<String, Type>> {
+		let Some(properties) = keywords.get("properties")
+		else { return Ok(IndexMap::new()) };
+
+		properties
+			.as_object()
+			.ok_or_else(|| anyhow!("Expected `properties` to be an object.\nGot: {properties}"))?
+			.iter()
+			.map(|(name, ty)| {
+				self.ty(ty)
+					.map(|ty| (name.clone(), ty))
+					.map_err(|err| anyhow!("Property \"{name}\": {err}"))
+			})
+			.try_collect()
+	}
+
+	fn array(&self, keywords: &Map<String, Value>) -> Result<Definition> {
+		// Older versions serialized `JTuple` items under "prefixItems " (trailing space), accept both spellings.
+		if let Some(Value::Array(items)) = keywords.get("prefixItems").or_else(|| keywords.get("prefixItems ")) {
+			let items = items.iter().map(|item| self.ty(item)).try_collect::<_, Vec<_>, _>()?;
+			return Ok(JTuple::new(items).into());
+		}
+
+		let mut array = match keywords.get("items") {
+			Some(items) => JArray::new(self.ty(items)?),
+			None => JArray::untyped(),
+		};
+
+		array.min_items = keywords.get("minItems").and_then(Value::as_u64).map(|len| len as usize);
+		array.max_items = keywords.get("maxItems").and_then(Value::as_u64).map(|len| len as usize);
+		array.unique_items = keywords.get("uniqueItems").and_then(Value::as_bool).unwrap_or_default();
+		array.contains = keywords.get("contains").map(|contains| self.ty(contains)).transpose()?.map(Box::new);
+		Ok(array.into())
+	}
+
+	fn resource_path(&self, keywords: &Map<String, Value>) -> Result<Definition> {
+		let class_name = keywords
+			.get(GODOT_RESOURCE_CLASS_KEY)
+			.and_then(Value::as_str)
+			.ok_or_else(|| anyhow!("Expected resource path to have a `{GODOT_RESOURCE_CLASS_KEY}` string."))?;
+
+		let strings_at = |key: &str| keywords
+			.get(key)
+			.and_then(Value::as_array)
+			.map(|strings| strings.iter().filter_map(Value::as_str).map(str::to_owned).collect_vec())
+			.unwrap_or_default();
+
+		Ok(JResourcePath {
+			extensions: strings_at(GODOT_RESOURCE_EXTENSIONS_KEY),
+			instance_scene: keywords.get(GODOT_SCENE_INSTANCE_KEY).and_then(Value::as_bool).unwrap_or_default(),
+			..JResourcePath::new(ClassSource::from_definition_name(class_name)?, strings_at(GODOT_RESOURCE_DIRS_KEY))
+		}.into())
+	}
+
+	/// Reads an enum serialized with [`SchemaOptions::integer_enums`], whose variants are all in [`GODOT_ENUM_KEY`].
+	fn integer_enumeration(&self, keywords: &Map<String, Value>) -> Result<Definition> {
+		let variants = serde_json::from_value::<BTreeMap<String, i64>>(keywords[GODOT_ENUM_KEY].clone())
+			.map_err(|err| anyhow!("Expected `{GODOT_ENUM_KEY}` to map variant names to integers.\nError: {err}"))?;
+
+		// Described variants are `{"const": value, "title": name, "description": ...}`.
+		let variant_descriptions = keywords
+			.get("oneOf")
+			.and_then(Value::as_array)
+			.into_iter()
+			.flatten()
+			.filter_map(|variant| {
+				let name = variant.get("title")?.as_str()?;
+				let description = variant.get("description")?.as_str()?;
+				Some((name.to_owned(), description.to_owned()))
+			})
+			.collect();
+
+		Ok(JEnum { description: None, variants, variant_descriptions }.into())
+	}
+
+	fn enumeration(&self, keywords: &Map<String, Value>, names: &[Value], variant_descriptions: BTreeMap<String, String>) -> Result<Definition> {
+		let values = keywords.get(GODOT_ENUM_KEY).and_then(Value::as_object);
+
+		let variants = names
+			.iter()
+			.enumerate()
+			.map(|(idx, name)| {
+				let name = name
+					.as_str()
+					.ok_or_else(|| anyhow!("Expected enum variants to be strings.\nGot: {name}"))?;
+
+				// Without annotations, variants are numbered in order, like GDScript does by default.
+				let value = match values {
+					Some(values) => values
+						.get(name)
+						.and_then(Value::as_i64)
+						.ok_or_else(|| anyhow!("Expected `{GODOT_ENUM_KEY}` to contain an integer for variant \"{name}\"."))?,
+					None => idx as i64,
+				};
+
+				Ok((name.to_owned(), value))
+			})
+			.try_collect::<_, BTreeMap<_, _>, anyhow::Error>()?;
+
+		Ok(JEnum { description: None, variants, variant_descriptions }.into())
+	}
+}
+
+/// Whether `keywords` is [`JAny`]'s `anyOf` of every JSON type, see [`ANY_TYPES`].
+fn is_any_of_types(keywords: &Map<String, Value>) -> bool {
+	let Some(Value::Array(options)) = keywords.get("anyOf")
+	else { return false };
+
+	options.len() == ANY_TYPES.len()
+		&& options
+			.iter()
+			.zip(ANY_TYPES)
+			.all(|(option, ty)| option.get("type").and_then(Value::as_str) == Some(ty))
+}
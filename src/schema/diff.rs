@@ -0,0 +1,69 @@
+use super::*;
+
+/// A difference found by [`json_diff()`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsonChange {
+	/// JSON Pointer (RFC 6901) of the changed value, e.g. `/stats/hp` or `/tags/2`.
+	pub path: String,
+	/// `None` if the value was added.
+	pub old: Option<Value>,
+	/// `None` if the value was removed.
+	pub new: Option<Value>,
+}
+
+impl JsonChange {
+	pub const fn kind(&self) -> &'static str {
+		match (&self.old, &self.new) {
+			(None, _) => "added",
+			(_, None) => "removed",
+			_ => "changed",
+		}
+	}
+}
+
+/// Compares `old` against `new`, descending into objects and arrays (compared index by index).
+///
+/// # Returns
+/// The changed leaves, in document order. A value whose type changed is reported as a whole.
+pub fn json_diff(old: &Value, new: &Value) -> Vec<JsonChange> {
+	let mut changes = Vec::new();
+	diff_at(String::new(), old, new, &mut changes);
+	changes
+}
+
+fn diff_at(path: String, old: &Value, new: &Value, fill_me: &mut Vec<JsonChange>) {
+	match (old, new) {
+		(Value::Object(old_map), Value::Object(new_map)) => {
+			for (key, old_value) in old_map {
+				let child = format!("{path}/{}", escape_pointer_token(key));
+
+				match new_map.get(key) {
+					Some(new_value) => diff_at(child, old_value, new_value, fill_me),
+					None => fill_me.push(JsonChange { path: child, old: Some(old_value.clone()), new: None }),
+				}
+			}
+
+			for (key, new_value) in new_map.iter().filter(|(key, _)| !old_map.contains_key(*key)) {
+				let child = format!("{path}/{}", escape_pointer_token(key));
+				fill_me.push(JsonChange { path: child, old: None, new: Some(new_value.clone()) });
+			}
+		}
+		(Value::Array(old_vec), Value::Array(new_vec)) => {
+			for idx in 0..old_vec.len().max(new_vec.len()) {
+				let child = format!("{path}/{idx}");
+
+				match (old_vec.get(idx), new_vec.get(idx)) {
+					(Some(old_value), Some(new_value)) => diff_at(child, old_value, new_value, fill_me),
+					(old_value, new_value) => fill_me.push(JsonChange { path: child, old: old_value.cloned(), new: new_value.cloned() }),
+				}
+			}
+		}
+		_ if old != new => fill_me.push(JsonChange { path, old: Some(old.clone()), new: Some(new.clone()) }),
+		_ => {}
+	}
+}
+
+/// Escapes `~` and `/` in a JSON Pointer (RFC 6901) reference token.
+pub fn escape_pointer_token(token: &str) -> String {
+	token.replace('~', "~0").replace('/', "~1")
+}
@@ -0,0 +1,307 @@
+use super::*;
+
+/// The properties of a root class or object, for editing them regardless of which it is.
+struct RootProperties<'a> {
+	properties: &'a mut IndexMap<String, Type>,
+	optional: &'a mut BTreeSet<String>,
+	type_infos: Option<&'a mut BTreeMap<String, GodotTypeInfo>>,
+	aliases: Option<&'a mut BTreeMap<String, String>>,
+}
+
+impl RootSchema {
+	/// Adds (or replaces) root property `name`, of `schema`'s type. `schema`'s definitions are merged into this schema's.
+	///
+	/// On class schemas, the property must exist on the class for instantiation to assign it.
+	pub fn add_property(&mut self, name: &str, schema: &RootSchema) -> Result<()> {
+		for (def_name, def) in &schema.defs {
+			self.defs.entry(def_name.clone()).or_insert_with(|| def.clone());
+		}
+
+		let root = self.root_properties()?;
+		root.properties.insert(name.to_owned(), schema.base.clone().into());
+		root.optional.remove(name);
+
+		if let Some(type_infos) = root.type_infos {
+			type_infos.remove(name);
+		}
+
+		if let Some(aliases) = root.aliases {
+			aliases.remove(name);
+		}
+
+		Ok(())
+	}
+
+	pub fn remove_property(&mut self, name: &str) -> Result<()> {
+		let root = self.root_properties()?;
+
+		if root.properties.shift_remove(name).is_none() {
+			bail!("Expected property \"{name}\" to be in `properties` map.");
+		}
+
+		root.optional.remove(name);
+
+		if let Some(type_infos) = root.type_infos {
+			type_infos.remove(name);
+		}
+
+		if let Some(aliases) = root.aliases {
+			aliases.remove(name);
+		}
+
+		Ok(())
+	}
+
+	pub fn rename_property(&mut self, old_name: &str, new_name: &str) -> Result<()> {
+		let root = self.root_properties()?;
+
+		if root.properties.contains_key(new_name) {
+			bail!("Cannot rename property \"{old_name}\", property \"{new_name}\" already exists.");
+		}
+
+		let (index, _, ty) = root.properties
+			.shift_remove_full(old_name)
+			.ok_or_else(|| anyhow!("Expected property \"{old_name}\" to be in `properties` map."))?;
+
+		// Keeps the property's position in the declaration order.
+		root.properties.shift_insert(index, new_name.to_owned(), ty);
+
+		if root.optional.remove(old_name) {
+			root.optional.insert(new_name.to_owned());
+		}
+
+		if let Some(type_infos) = root.type_infos
+			&& let Some(info) = type_infos.remove(old_name) {
+			type_infos.insert(new_name.to_owned(), info);
+		}
+
+		if let Some(aliases) = root.aliases
+			&& let Some(alias) = aliases.remove(old_name) {
+			aliases.insert(new_name.to_owned(), alias);
+		}
+
+		Ok(())
+	}
+
+	/// Exposes property `name` as `alias` in JSON, see [`JClass::set_alias()`].
+	///
+	/// `name` is a root property, or qualified by the definition name of another class, e.g. "Fact.salient_word".
+	pub fn set_property_alias(&mut self, name: &str, alias: &str) -> Result<()> {
+		let (def, property) = match name.split_once('.') {
+			Some((def_name, property)) => (self.defs.get_mut(def_name), property),
+			None => (Some(&mut self.base), name),
+		};
+
+		match def {
+			Some(Definition::Class(class)) => class.set_alias(property, alias),
+			_ => bail!("Expected \"{name}\" to name a property of a class."),
+		}
+	}
+
+	/// Optional properties may be absent from the input, see [`JObject::optional`]/[`JClass::optional`].
+	pub fn set_property_optional(&mut self, name: &str, optional: bool) -> Result<()> {
+		let root = self.root_properties()?;
+
+		if !root.properties.contains_key(name) {
+			bail!("Expected property \"{name}\" to be in `properties` map.");
+		}
+
+		if optional {
+			root.optional.insert(name.to_owned());
+		} else {
+			root.optional.remove(name);
+		}
+
+		Ok(())
+	}
+
+	/// Sets (or, if `None`, removes) the `default` of root property `name`, which instantiation assigns when the (optional) property is absent.
+	pub fn set_property_default(&mut self, name: &str, default: Option<Value>) -> Result<()> {
+		self.set_property_value_keyword(name, default, |(_, default)| default)
+	}
+
+	/// Sets (or, if `None`, removes) the `const` of root property `name`, the only value accepted for it.
+	pub fn set_property_const(&mut self, name: &str, const_value: Option<Value>) -> Result<()> {
+		self.set_property_value_keyword(name, const_value, |(const_value, _)| const_value)
+	}
+
+	fn set_property_value_keyword(
+		&mut self,
+		name: &str,
+		value: Option<Value>,
+		keyword: impl FnOnce((&mut Option<Value>, &mut Option<Value>)) -> &mut Option<Value>,
+	) -> Result<()> {
+		let defs = self.defs.clone();
+		let root = self.root_properties()?;
+
+		let def = match root.properties.get_mut(name) {
+			Some(Type::Definition(def)) => def,
+			Some(Type::Ref(JRef { name: def_name, .. })) => bail!("Expected property \"{name}\" to be a primitive or object.\nGot a reference to \"{def_name}\"."),
+			None => bail!("Expected property \"{name}\" to be in `properties` map."),
+		};
+
+		if let Some(value) = &value {
+			// Checked without the current keywords, which the new value may replace.
+			let mut unconstrained = def.clone();
+
+			if let Some((const_value, _)) = unconstrained.value_keywords_mut() {
+				*const_value = None;
+			}
+
+			if !matches_type(&Type::Definition(unconstrained), value, &defs) {
+				bail!("Expected the value of property \"{name}\" to match its type.\nGot: {value}");
+			}
+		}
+
+		let keywords = def
+			.value_keywords_mut()
+			.ok_or_else(|| anyhow!("Expected property \"{name}\" to be a primitive or object."))?;
+
+		*keyword(keywords) = value;
+		Ok(())
+	}
+
+	/// Describes `variant` of enum definition `enum_name` (or of the root, if it is an enum and `enum_name` is empty).
+	pub fn set_enum_variant_description(&mut self, enum_name: &str, variant: &str, description: &str) -> Result<()> {
+		let def = if enum_name.is_empty() {
+			&mut self.base
+		} else {
+			self.defs
+				.get_mut(enum_name)
+				.ok_or_else(|| anyhow!("Expected definition \"{enum_name}\" to be in `$defs` map."))?
+		};
+
+		let Definition::Enum(j_enum) = def
+		else { bail!("Expected \"{enum_name}\" to be an enum definition.") };
+
+		j_enum.set_variant_description(variant, description)
+	}
+
+	/// Constrains the array root property `property` (or the root itself, if it is an array and `property` is empty).
+	///
+	/// `None` lengths are unbounded.
+	pub fn set_array_constraints(&mut self, property: &str, min_items: Option<usize>, max_items: Option<usize>, unique_items: bool) -> Result<()> {
+		if let (Some(min_items), Some(max_items)) = (min_items, max_items)
+			&& min_items > max_items {
+			bail!("Expected `min_items` ({min_items}) to be at most `max_items` ({max_items}).");
+		}
+
+		let def = if property.is_empty() {
+			&mut self.base
+		} else {
+			let properties = self.root_properties()?.properties;
+
+			match properties.get_mut(property) {
+				Some(Type::Definition(def)) => def,
+				Some(Type::Ref(JRef { name, .. })) => bail!("Expected property \"{property}\" to be an array.\nGot a reference to \"{name}\"."),
+				None => bail!("Expected property \"{property}\" to be in `properties` map."),
+			}
+		};
+
+		let Definition::Array(array) = def
+		else { bail!("Expected \"{property}\" to be an array.") };
+
+		array.min_items = min_items;
+		array.max_items = max_items;
+		array.unique_items = unique_items;
+		Ok(())
+	}
+
+	/// A copy of this schema with only the root properties in `names` (or, if `omit`, without them).
+	///
+	/// Definitions no longer referenced are removed from `$defs`.
+	pub fn project_properties(&self, names: &[String], omit: bool) -> Result<RootSchema> {
+		let mut schema = self.clone();
+		let root = schema.root_properties()?;
+
+		if let Some(unknown) = names.iter().find(|name| !root.properties.contains_key(*name)) {
+			bail!("Expected property \"{unknown}\" to be in `properties` map.");
+		}
+
+		let keep = |name: &String| names.contains(name) != omit;
+
+		root.properties.retain(|name, _| keep(name));
+		root.optional.retain(keep);
+
+		if let Some(type_infos) = root.type_infos {
+			type_infos.retain(|name, _| keep(name));
+		}
+
+		schema.prune_defs();
+		Ok(schema)
+	}
+
+	/// Removes the definitions that are not referenced, directly or through other definitions, by [`Self::base`].
+	pub fn prune_defs(&mut self) {
+		let reachable = self.dependencies();
+		self.defs.retain(|name, _| reachable.contains(name));
+	}
+
+	/// Makes every class emit its inherited properties itself, instead of referencing its base class with `allOf`.
+	pub fn flatten_inheritance(&mut self) {
+		for def in self.defs.values_mut().chain([&mut self.base]) {
+			if let Definition::Class(class) = def {
+				class.base = None;
+				class.extensible = false;
+			}
+		}
+	}
+
+	/// A copy of this schema where every property of every class and object (at the root, nested or in `$defs`) is optional,
+	/// e.g. to validate partial updates.
+	pub fn with_all_optional(&self) -> RootSchema {
+		let mut schema = self.clone();
+
+		for def in schema.defs.values_mut().chain([&mut schema.base]) {
+			make_optional(def);
+		}
+
+		schema
+	}
+
+	fn root_properties(&mut self) -> Result<RootProperties<'_>> {
+		match &mut self.base {
+			Definition::Class(JClass { properties, optional, type_infos, base, aliases, .. }) => {
+				// Edited classes no longer match their base class, every property is emitted by the class itself.
+				*base = None;
+
+				Ok(RootProperties {
+					properties,
+					optional,
+					type_infos: Some(type_infos),
+					aliases: Some(aliases),
+				})
+			}
+			Definition::Object(JObject { properties, optional, .. }) => Ok(RootProperties {
+				properties,
+				optional,
+				type_infos: None,
+				aliases: None,
+			}),
+			_ => bail!("Only the properties of class or object schemas can be edited."),
+		}
+	}
+}
+
+fn make_optional(def: &mut Definition) {
+	let nested = |ty: &mut Type| {
+		if let Type::Definition(def) = ty {
+			make_optional(def);
+		}
+	};
+
+	match def {
+		| Definition::Class(JClass { properties, optional, .. })
+		| Definition::Object(JObject { properties, optional, .. }) => {
+			optional.extend(properties.keys().cloned());
+			properties.values_mut().for_each(nested);
+		}
+		Definition::Array(array) => {
+			if let Some(items_ty) = &mut array.items_ty {
+				nested(items_ty);
+			}
+		}
+		Definition::Tuple(tuple) => tuple.items.iter_mut().for_each(nested),
+		_ => {}
+	}
+}
@@ -0,0 +1,153 @@
+use super::*;
+use godot::classes::control::SizeFlags;
+use godot::classes::editor_plugin::DockSlot;
+use godot::classes::{
+	Button, DisplayServer, EditorInterface, EditorPlugin, IEditorPlugin, Label, Resource, Script, TextEdit, VBoxContainer,
+};
+
+/// Adds a "Schema" dock to the editor, previewing the schema generated from the script open in the script editor.
+///
+/// The schema is regenerated whenever that script is saved. Generation errors are shown in place of the schema.
+#[derive(GodotClass)]
+#[class(tool, init, editor_plugin, base = EditorPlugin)]
+pub struct SchemaPreviewPlugin {
+	base: Base<EditorPlugin>,
+	dock: Option<SchemaPreviewDock>,
+	script: Option<Gd<Script>>,
+	schema: Option<Gd<GodotSchema>>,
+}
+
+struct SchemaPreviewDock {
+	root: Gd<VBoxContainer>,
+	title: Gd<Label>,
+	copy_button: Gd<Button>,
+	preview: Gd<TextEdit>,
+}
+
+#[godot_api]
+impl IEditorPlugin for SchemaPreviewPlugin {
+	fn enter_tree(&mut self) {
+		let this = self.to_gd();
+
+		let mut title = Label::new_alloc();
+		title.set_text("No script selected.");
+
+		let mut copy_button = Button::new_alloc();
+		copy_button.set_text("Copy OpenAI format");
+		copy_button.set_disabled(true);
+		copy_button.connect("pressed", &Callable::from_object_method(&this, "copy_open_ai_format"));
+
+		let mut preview = TextEdit::new_alloc();
+		preview.set_editable(false);
+		preview.set_v_size_flags(SizeFlags::EXPAND_FILL);
+
+		let mut root = VBoxContainer::new_alloc();
+		root.set_name("Schema");
+		root.add_child(&title);
+		root.add_child(&copy_button);
+		root.add_child(&preview);
+
+		self.base_mut().add_control_to_dock(DockSlot::RIGHT_UL, &root);
+		self.base_mut().connect("resource_saved", &Callable::from_object_method(&this, "on_resource_saved"));
+
+		if let Some(mut script_editor) = EditorInterface::singleton().get_script_editor() {
+			script_editor.connect("editor_script_changed", &Callable::from_object_method(&this, "on_script_changed"));
+		}
+
+		self.dock = Some(SchemaPreviewDock { root, title, copy_button, preview });
+
+		let current = EditorInterface::singleton()
+			.get_script_editor()
+			.and_then(|script_editor| script_editor.get_current_script());
+
+		self.on_script_changed(current);
+	}
+
+	fn exit_tree(&mut self) {
+		let this = self.to_gd();
+
+		// Re-enabling the plugin connects again.
+		self.base_mut().disconnect("resource_saved", &Callable::from_object_method(&this, "on_resource_saved"));
+
+		if let Some(mut script_editor) = EditorInterface::singleton().get_script_editor() {
+			script_editor.disconnect("editor_script_changed", &Callable::from_object_method(&this, "on_script_changed"));
+		}
+
+		if let Some(SchemaPreviewDock { mut root, .. }) = self.dock.take() {
+			self.base_mut().remove_control_from_docks(&root);
+			root.queue_free();
+		}
+
+		self.script = None;
+		self.schema = None;
+	}
+}
+
+#[godot_api]
+impl SchemaPreviewPlugin {
+	/// Connected to the script editor's `editor_script_changed`.
+	#[func]
+	fn on_script_changed(&mut self, script: Option<Gd<Script>>) {
+		self.script = script;
+		self.regenerate();
+	}
+
+	/// Connected to the plugin's `resource_saved`.
+	#[func]
+	fn on_resource_saved(&mut self, resource: Gd<Resource>) {
+		let is_previewed = self.script
+			.as_ref()
+			.is_some_and(|script| script.get_path() == resource.get_path());
+
+		if is_previewed {
+			self.regenerate();
+		}
+	}
+
+	/// Connected to the dock's "Copy OpenAI format" button.
+	#[func]
+	fn copy_open_ai_format(&self) {
+		let (Some(schema), Some(script)) = (&self.schema, &self.script)
+		else { return };
+
+		let name = script.get_global_name();
+		let name = if name.is_empty() { "response".to_owned() } else { name.to_string() };
+		let format = schema.bind().open_ai_response_format(name).to::<String>();
+
+		if !format.is_empty() {
+			DisplayServer::singleton().clipboard_set(&format);
+		}
+	}
+}
+
+impl SchemaPreviewPlugin {
+	/// Generates the schema of the previewed script, then shows it (or the error) in the dock.
+	fn regenerate(&mut self) {
+		let Some(dock) = &mut self.dock
+		else { return };
+
+		let Some(script) = &self.script
+		else {
+			self.schema = None;
+			dock.title.set_text("No script selected.");
+			dock.preview.set_text("");
+			dock.copy_button.set_disabled(true);
+			return;
+		};
+
+		dock.title.set_text(&script.get_path());
+
+		match GodotSchema::try_from_class_script(script.clone()) {
+			Ok(schema) => {
+				dock.preview.set_text(&schema.json);
+				self.schema = Some(Gd::from_object(schema));
+			}
+			Err(err) => {
+				dock.preview.set_text(&format!("{err:?}"));
+				self.schema = None;
+			}
+		}
+
+		dock.copy_button.set_disabled(self.schema.is_none());
+	}
+}
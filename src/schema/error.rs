@@ -0,0 +1,163 @@
+use super::*;
+
+/// The kind of failure reported by a [`SchemaResult`], allowing callers to branch without matching on error messages.
+///
+/// Exposed to GDScript as constants of `SchemaResult`, e.g. `SchemaResult.CLASS_NOT_FOUND`.
+#[derive(GodotConvert, Var, Export, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[godot(via = i64)]
+pub enum ErrorCode {
+	#[default]
+	Ok = 0,
+	/// A failure that does not fit any of the other kinds.
+	Failed = 1,
+	/// The requested class is not in `ClassDb`/`ProjectSettings`, or no schema was generated for it.
+	ClassNotFound = 2,
+	/// A property's type cannot be represented by a schema.
+	UnsupportedPropertyType = 3,
+	/// The input is valid JSON but does not match the schema.
+	ValidationFailed = 4,
+	/// The input is not valid JSON.
+	JsonParseError = 5,
+	/// The input matches the schema, but the Godot value could not be created from it.
+	InstantiationFailed = 6,
+	/// A file could not be read or written.
+	FileError = 7,
+}
+
+impl ErrorCode {
+	/// Returns the code of the [`SchemaError`] inside `err`,
+	/// falling back to [`ErrorCode::JsonParseError`] for JSON parsing errors and [`ErrorCode::Failed`] for everything else.
+	pub fn of(err: &anyhow::Error) -> Self {
+		if let Some(schema_err) = err.downcast_ref::<SchemaError>() {
+			return schema_err.code();
+		}
+
+		if err.chain().any(|cause| cause.is::<serde_json::Error>()) {
+			return ErrorCode::JsonParseError;
+		}
+
+		ErrorCode::Failed
+	}
+}
+
+/// The error type of this crate's public Rust API, allowing extension authors to match on failure kinds.
+///
+/// Internally, errors are propagated as `anyhow::Error`, which can be converted back with `SchemaError::from`.
+#[derive(Debug)]
+pub enum SchemaError {
+	/// The class is not in `ClassDb`/`ProjectSettings`, or no schema was generated for it.
+	UnknownClass(String),
+	/// A property's type cannot be represented by a schema.
+	UnsupportedProperty { name: String, variant_type: String, hint: String },
+	/// The input does not match the schema, one issue per violation.
+	ValidationFailed(Vec<ValidationIssue>),
+	JsonParse(serde_json::Error),
+	/// A JSON value could not be converted into the Godot type expected at `path`.
+	ConversionFailed { path: String, expected: String, got: String },
+	/// A file could not be read or written.
+	Io { path: String, message: String },
+	/// Any other failure, tagged with the code reported to GDScript.
+	Other { code: ErrorCode, source: anyhow::Error },
+}
+
+impl SchemaError {
+	pub const fn code(&self) -> ErrorCode {
+		match self {
+			SchemaError::UnknownClass(_) => ErrorCode::ClassNotFound,
+			SchemaError::UnsupportedProperty { .. } => ErrorCode::UnsupportedPropertyType,
+			SchemaError::ValidationFailed(_) => ErrorCode::ValidationFailed,
+			SchemaError::JsonParse(_) => ErrorCode::JsonParseError,
+			SchemaError::ConversionFailed { .. } => ErrorCode::InstantiationFailed,
+			SchemaError::Io { .. } => ErrorCode::FileError,
+			SchemaError::Other { code, .. } => *code,
+		}
+	}
+
+	/// Prepends `segment` to the path of a [`SchemaError::ConversionFailed`], other errors are returned unchanged.
+	///
+	/// Called while unwinding out of nested values, so that the final path points at the offending value.
+	pub fn at(err: anyhow::Error, segment: impl std::fmt::Display) -> anyhow::Error {
+		match err.downcast::<SchemaError>() {
+			Ok(SchemaError::ConversionFailed { path, expected, got }) => {
+				SchemaError::ConversionFailed { path: format!("/{segment}{path}"), expected, got }.into()
+			}
+			Ok(other) => other.into(),
+			Err(err) => err,
+		}
+	}
+}
+
+impl std::fmt::Display for SchemaError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			SchemaError::UnknownClass(class_name) => {
+				write!(f, "Expected class \"{class_name}\" to be in either `ClassDb` or `ProjectSettings`.")
+			}
+			SchemaError::UnsupportedProperty { name, variant_type, hint } if hint.is_empty() => {
+				write!(f, "Unsupported property type: {variant_type} (property \"{name}\")")
+			}
+			SchemaError::UnsupportedProperty { name, variant_type, hint } => {
+				write!(f, "Unsupported property type: {variant_type} (property \"{name}\", hint: {hint})")
+			}
+			SchemaError::ValidationFailed(issues) => {
+				for issue in issues {
+					writeln!(f, "{issue}")?;
+				}
+
+				Ok(())
+			}
+			SchemaError::JsonParse(err) => write!(f, "{err}"),
+			SchemaError::ConversionFailed { path, expected, got } if path.is_empty() => write!(f, "Expected {expected}, got: {got}"),
+			SchemaError::ConversionFailed { path, expected, got } => write!(f, "At \"{path}\": expected {expected}, got: {got}"),
+			SchemaError::Io { path, message } => write!(f, "\"{path}\": {message}"),
+			SchemaError::Other { source, .. } => write!(f, "{source}"),
+		}
+	}
+}
+
+impl std::error::Error for SchemaError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			SchemaError::JsonParse(err) => Some(err),
+			SchemaError::Other { source, .. } => Some(source.as_ref()),
+			_ => None,
+		}
+	}
+}
+
+impl From<anyhow::Error> for SchemaError {
+	fn from(err: anyhow::Error) -> Self {
+		match err.downcast::<SchemaError>() {
+			Ok(schema_err) => schema_err,
+			Err(err) => match err.downcast::<serde_json::Error>() {
+				Ok(json_err) => SchemaError::JsonParse(json_err),
+				Err(source) => SchemaError::Other { code: ErrorCode::Failed, source },
+			},
+		}
+	}
+}
+
+impl From<serde_json::Error> for SchemaError {
+	fn from(err: serde_json::Error) -> Self {
+		SchemaError::JsonParse(err)
+	}
+}
+
+pub trait WithErrorCode<T> {
+	/// Tags the error with `code`, unless it already is a [`SchemaError`] (the innermost error is the most specific).
+	fn with_code(self, code: ErrorCode) -> Result<T>;
+}
+
+impl<T, E: Into<anyhow::Error>> WithErrorCode<T> for std::result::Result<T, E> {
+	fn with_code(self, code: ErrorCode) -> Result<T> {
+		self.map_err(|err| {
+			let source = err.into();
+
+			if source.is::<SchemaError>() {
+				source
+			} else {
+				SchemaError::Other { code, source }.into()
+			}
+		})
+	}
+}
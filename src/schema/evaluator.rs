@@ -0,0 +1,205 @@
+use super::*;
+
+/// Aggregates how well a set of model outputs conforms to a schema, for comparing prompts/models of a content generation pipeline.
+///
+/// Outputs are only parsed and validated, never instantiated.
+#[derive(GodotClass)]
+#[class(init, base = RefCounted)]
+pub struct OutputEvaluator {
+	#[var] pub schema: Option<Gd<GodotSchema>>,
+	metrics: EvaluationMetrics,
+}
+
+#[derive(Default)]
+struct EvaluationMetrics {
+	total: i64,
+	valid: i64,
+	parse_errors: i64,
+	/// Instance path (with array indices replaced by "*") => amount of violations.
+	field_errors: BTreeMap<String, i64>,
+	/// Schema keyword => amount of violations.
+	constraint_errors: BTreeMap<String, i64>,
+}
+
+#[godot_api]
+impl OutputEvaluator {
+	#[func]
+	pub fn create(schema: Gd<GodotSchema>) -> Gd<Self> {
+		Gd::from_object(Self {
+			schema: Some(schema),
+			metrics: EvaluationMetrics::default(),
+		})
+	}
+
+	/// Evaluates every file in the directory at `path`, each file must contain a single model output.
+	///
+	/// Metrics accumulate across calls, use [`Self::reset()`] to start over.
+	///
+	/// # Returns
+	/// - The metrics `Dictionary` (see [`Self::get_metrics()`]), if successful.
+	/// - Otherwise a `String` containing the error message.
+	#[func]
+	pub fn evaluate_directory(&mut self, path: String) -> Variant {
+		let mut try_fn = || -> Result<()> {
+			let files = DirAccess::get_files_at(path.as_str());
+
+			if files.is_empty() && !DirAccess::dir_exists_absolute(path.as_str()) {
+				bail!("Directory \"{path}\" does not exist.");
+			}
+
+			for file in files.as_slice() {
+				let file_path = format!("{}/{file}", path.trim_end_matches('/'));
+				let output = FileAccess::get_file_as_string(file_path.as_str());
+				self.evaluate_output(output.to_string())?;
+			}
+
+			Ok(())
+		};
+
+		match try_fn() {
+			Ok(()) => self.get_metrics().to_variant(),
+			Err(err) => format!("{err:?}").to_variant(),
+		}
+	}
+
+	/// Evaluates a single model output.
+	///
+	/// # Returns
+	/// - `Nil`, if `output` is valid according to the schema.
+	/// - Otherwise a `String` containing the parsing or validation errors.
+	#[func]
+	pub fn evaluate(&mut self, output: String) -> Variant {
+		match self.evaluate_output(output) {
+			Ok(()) => Variant::nil(),
+			Err(err) => format!("{err}").to_variant(),
+		}
+	}
+
+	/// # Returns
+	/// A `Dictionary` with the keys:
+	/// - "total", "valid", "parse_errors": amount of outputs.
+	/// - "validity_rate": `valid / total`, between 0 and 1.
+	/// - "field_errors": instance path => amount of violations, array indices are replaced by "*".
+	/// - "constraint_errors": schema keyword => amount of violations.
+	/// - "most_violated_constraints": the keywords of "constraint_errors", most violated first.
+	#[func]
+	pub fn get_metrics(&self) -> Dictionary {
+		let metrics = &self.metrics;
+		let mut dict = Dictionary::new();
+
+		dict.set("total", metrics.total);
+		dict.set("valid", metrics.valid);
+		dict.set("parse_errors", metrics.parse_errors);
+		dict.set("validity_rate", metrics.validity_rate());
+		dict.set("field_errors", counts_to_dict(&metrics.field_errors));
+		dict.set("constraint_errors", counts_to_dict(&metrics.constraint_errors));
+
+		let most_violated = metrics.constraint_errors
+			.iter()
+			.sorted_by(|(_, a), (_, b)| b.cmp(a))
+			.map(|(keyword, _)| GString::from(keyword.as_str()))
+			.collect::<PackedStringArray>();
+
+		dict.set("most_violated_constraints", most_violated);
+		dict
+	}
+
+	/// Returns the metrics as CSV with the columns "metric,key,value", for importing into spreadsheets.
+	#[func]
+	pub fn to_csv(&self) -> String {
+		let metrics = &self.metrics;
+		let mut csv = "metric,key,value\n".to_owned();
+
+		csv += &format!("total,,{}\n", metrics.total);
+		csv += &format!("valid,,{}\n", metrics.valid);
+		csv += &format!("parse_errors,,{}\n", metrics.parse_errors);
+		csv += &format!("validity_rate,,{}\n", metrics.validity_rate());
+
+		for (path, count) in &metrics.field_errors {
+			csv += &format!("field_errors,{},{count}\n", csv_field(path));
+		}
+
+		for (keyword, count) in &metrics.constraint_errors {
+			csv += &format!("constraint_errors,{},{count}\n", csv_field(keyword));
+		}
+
+		csv
+	}
+
+	#[func]
+	pub fn reset(&mut self) {
+		self.metrics = EvaluationMetrics::default();
+	}
+}
+
+impl OutputEvaluator {
+	fn evaluate_output(&mut self, output: String) -> Result<()> {
+		let schema = self.schema
+			.clone()
+			.ok_or_else(|| anyhow!("No schema was assigned to the `OutputEvaluator`."))?;
+
+		let metrics = &mut self.metrics;
+		metrics.total += 1;
+
+		let value = match serde_json::from_str::<Value>(&output) {
+			Ok(value) => value,
+			Err(err) => {
+				metrics.parse_errors += 1;
+				bail!("{err}")
+			}
+		};
+
+		let schema = schema.bind();
+
+		match schema.validator()?.validate(&value) {
+			Ok(()) => {
+				metrics.valid += 1;
+				Ok(())
+			}
+			Err(issues) => {
+				for issue in &issues {
+					*metrics.field_errors.entry(generic_path(&issue.instance_path)).or_default() += 1;
+					*metrics.constraint_errors.entry(issue.keyword.clone()).or_default() += 1;
+				}
+
+				bail!("{}", issues.iter().join("\n"))
+			}
+		}
+	}
+}
+
+impl EvaluationMetrics {
+	fn validity_rate(&self) -> f64 {
+		if self.total == 0 {
+			0.0
+		} else {
+			self.valid as f64 / self.total as f64
+		}
+	}
+}
+
+/// Replaces array indices with "*", so that errors in different elements of the same array are counted together.
+fn generic_path(instance_path: &str) -> String {
+	instance_path
+		.split('/')
+		.map(|segment| if !segment.is_empty() && segment.chars().all(|ch| ch.is_ascii_digit()) { "*" } else { segment })
+		.join("/")
+}
+
+fn counts_to_dict(counts: &BTreeMap<String, i64>) -> Dictionary {
+	let mut dict = Dictionary::new();
+
+	for (key, count) in counts {
+		dict.set(key.as_str(), *count);
+	}
+
+	dict
+}
+
+fn csv_field(field: &str) -> String {
+	if field.contains([',', '"', '\n']) {
+		format!("\"{}\"", field.replace('"', "\"\""))
+	} else {
+		field.to_owned()
+	}
+}
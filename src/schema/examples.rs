@@ -0,0 +1,373 @@
+use super::*;
+
+/// Beyond this depth, arrays are generated empty so that recursive definitions terminate.
+const MAX_DEPTH: usize = 16;
+/// How many times an element of a `uniqueItems` array is regenerated before giving up, see [`ExampleGenerator::generate_distinct()`].
+const MAX_DISTINCT_ATTEMPTS: usize = 64;
+
+/// Generates example JSON documents that are valid according to a [`RootSchema`].
+///
+/// Uses a small xorshift generator, so results are reproducible for a given seed.
+pub struct ExampleGenerator {
+	state: u64,
+	/// See [`Self::placeholders()`].
+	placeholders: bool,
+	/// Which placeholder is generated, `0` for the first (which prefers defaults), increased for the elements of `uniqueItems` arrays.
+	placeholder_idx: u64,
+	/// See [`Self::loadable()`].
+	loadable: bool,
+	/// The [`JResourcePath::loadable_paths()`] of each resource path definition met so far.
+	loadable_paths: BTreeMap<String, Vec<String>>,
+}
+
+impl ExampleGenerator {
+	pub const fn new(seed: u64) -> Self {
+		// Xorshift gets stuck on zero.
+		Self {
+			state: if seed == 0 { 0x9E37_79B9_7F4A_7C15 } else { seed },
+			placeholders: false,
+			placeholder_idx: 0,
+			loadable: false,
+			loadable_paths: BTreeMap::new(),
+		}
+	}
+
+	/// A generator that uses the definitions' `default` (see [`Definition::value_keywords()`]) instead of random values,
+	/// or a placeholder for their type: `0`, `false`, the property's name, the first enum variant, arrays of one element...
+	///
+	/// The elements of `uniqueItems` arrays get the next placeholders instead: `1`, `true`, the name followed by "2"...
+	pub fn placeholders() -> Self {
+		Self { placeholders: true, ..Self::new(0) }
+	}
+
+	/// A generator whose resource paths are existing files of the expected class (see [`JResourcePath::loadable_paths()`]),
+	/// so that its documents can be instantiated. Fails for resource paths that have no such file.
+	pub fn loadable(seed: u64) -> Self {
+		Self { loadable: true, ..Self::new(seed) }
+	}
+
+	fn next(&mut self) -> u64 {
+		self.state ^= self.state << 13;
+		self.state ^= self.state >> 7;
+		self.state ^= self.state << 17;
+		self.state
+	}
+
+	pub(crate) fn range(&mut self, max_exclusive: u64) -> u64 {
+		if self.placeholders {
+			return self.placeholder_idx % max_exclusive.max(1);
+		}
+
+		self.next() % max_exclusive.max(1)
+	}
+
+	/// Generates a document for `schema`, including the wrapper of non-class roots (see [`RootSchema::wrap_value()`]).
+	pub fn generate(&mut self, schema: &RootSchema) -> Result<Value> {
+		let value = schema.options.scope(|| self.generate_definition(&schema.base, &schema.defs, "value", 0))?;
+
+		schema.to_json_names(schema.wrap_value(value))
+	}
+
+	fn generate_type(&mut self, ty: &Type, defs: &BTreeMap<String, Definition>, name: &str, depth: usize) -> Result<Value> {
+		self.generate_definition(ty.resolve(defs)?, defs, name, depth)
+	}
+
+	/// Generates a value for each of `properties`, meeting the extra keywords in `constraints` (see [`JClass::constraints`]).
+	fn generate_properties(
+		&mut self,
+		properties: &IndexMap<String, Type>,
+		constraints: Option<&BTreeMap<String, Map<String, Value>>>,
+		defs: &BTreeMap<String, Definition>,
+		depth: usize,
+	) -> Result<Value> {
+		let mut map = Map::new();
+
+		for (name, ty) in properties {
+			let value = self.generate_type(ty, defs, name, depth + 1)?;
+
+			let value = match constraints.and_then(|constraints| constraints.get(name)) {
+				Some(keywords) => self.constrain(value, keywords),
+				None => value,
+			};
+
+			map.insert(name.clone(), value);
+		}
+
+		Ok(Value::Object(map))
+	}
+
+	/// Adjusts `value` to meet `keywords`: `const`, `enum`, numeric bounds, `multipleOf` and string lengths.
+	///
+	/// Other keywords (e.g. `pattern`, `format`) are not considered, the value may still break them.
+	fn constrain(&mut self, value: Value, keywords: &Map<String, Value>) -> Value {
+		if let Some(const_value) = keywords.get("const") {
+			return const_value.clone();
+		}
+
+		if let Some(Value::Array(variants)) = keywords.get("enum")
+			&& !variants.is_empty() {
+			return variants[self.range(variants.len() as u64) as usize].clone();
+		}
+
+		match value {
+			Value::Number(number) => {
+				let integer = !number.is_f64();
+				let constrained = constrain_number(number.as_f64().unwrap_or_default(), keywords, integer);
+
+				if integer { Value::from(constrained as i64) } else { Value::from(constrained) }
+			}
+			Value::String(string) => {
+				let length = |keyword: &str| keywords.get(keyword).and_then(Value::as_u64).map(|length| length as usize);
+				let mut string: String = string.chars().take(length("maxLength").unwrap_or(usize::MAX)).collect();
+				let missing = length("minLength").unwrap_or_default().saturating_sub(string.chars().count());
+
+				string.push_str(&"x".repeat(missing));
+				Value::String(string)
+			}
+			other => other,
+		}
+	}
+
+	/// Generates an element of an array whose items are `items_ty` (strings, if `None`).
+	fn generate_item(&mut self, items_ty: Option<&Type>, defs: &BTreeMap<String, Definition>, name: &str, depth: usize) -> Result<Value> {
+		match items_ty {
+			Some(ty) => self.generate_type(ty, defs, name, depth + 1),
+			None if self.placeholders => Ok(self.placeholder_string(name)),
+			None => Ok(Value::String(format!("{name} {}", self.range(1000)))),
+		}
+	}
+
+	/// Calls `generate` until it returns a value that is not [`json_equal()`] to any of `existing`, for `uniqueItems` arrays.
+	///
+	/// Placeholder generators skip as many placeholders as there are `existing` elements, so the elements are successive placeholders.
+	fn generate_distinct(&mut self, existing: &[Value], name: &str, mut generate: impl FnMut(&mut Self) -> Result<Value>) -> Result<Value> {
+		let placeholder_idx = self.placeholder_idx;
+		let mut result = Err(anyhow!(
+			"Cannot generate {} distinct elements for \"{name}\", its items have too few possible values.",
+			existing.len() + 1,
+		));
+
+		for attempt in 0..MAX_DISTINCT_ATTEMPTS {
+			self.placeholder_idx = placeholder_idx + (existing.len() + attempt) as u64;
+
+			match generate(self) {
+				Ok(value) if existing.iter().any(|other| json_equal(other, &value)) => {}
+				other => {
+					result = other;
+					break;
+				}
+			}
+		}
+
+		self.placeholder_idx = placeholder_idx;
+		result
+	}
+
+	/// The `placeholder_idx`th placeholder of a string named `name`.
+	fn placeholder_string(&self, name: &str) -> Value {
+		match self.placeholder_idx {
+			0 => Value::String(name.to_owned()),
+			idx => Value::String(format!("{name} {}", idx + 1)),
+		}
+	}
+
+	fn generate_definition(&mut self, def: &Definition, defs: &BTreeMap<String, Definition>, name: &str, depth: usize) -> Result<Value> {
+		if depth > MAX_DEPTH * 2 {
+			bail!("Cannot generate an example for \"{name}\", its definition is infinitely recursive.");
+		}
+
+		if let Some(const_value) = def.const_value() {
+			return Ok(const_value.clone());
+		}
+
+		if self.placeholders
+			&& self.placeholder_idx == 0
+			&& let Some(default) = def.default_value() {
+			return Ok(default.clone());
+		}
+
+		Ok(match def {
+			Definition::Null(_) => Value::Null,
+			Definition::Boolean(_) => Value::Bool(self.range(2) == 1),
+			Definition::Integer(_) => Value::from(self.range(100)),
+			Definition::Number(_) => Value::from(self.range(10_000) as f64 / 100.0),
+			Definition::String(_) if self.placeholders => self.placeholder_string(name),
+			Definition::String(_) => Value::String(format!("{name} {}", self.range(1000))),
+			Definition::Object(JObject { properties, values_ty: Some(values_ty), .. }) if properties.is_empty() => {
+				let mut map = Map::new();
+
+				if depth < MAX_DEPTH {
+					let key = format!("{name}_1");
+					map.insert(key.clone(), self.generate_type(values_ty, defs, &key, depth + 1)?);
+				}
+
+				Value::Object(map)
+			}
+			Definition::Object(object) => self.generate_properties(&object.properties, None, defs, depth)?,
+			Definition::Class(class) => self.generate_properties(&class.properties, Some(&class.constraints), defs, depth)?,
+			Definition::Array(JArray { items_ty, min_items, max_items, unique_items, contains, .. }) => {
+				let min = min_items.unwrap_or_default() as u64;
+				let max = max_items.map_or(min.max(1) + 2, |max| max as u64);
+
+				let len = if depth >= MAX_DEPTH && min == 0 {
+					0
+				} else if self.placeholders {
+					// A single element still shows the shape of the items.
+					min.max(1).min(max)
+				} else {
+					min + self.range(max.saturating_sub(min) + 1)
+				};
+				let mut vec = Vec::new();
+
+				for _ in 0..len {
+					let item = if *unique_items {
+						self.generate_distinct(&vec, name, |generator| generator.generate_item(items_ty.as_ref(), defs, name, depth))?
+					} else {
+						self.generate_item(items_ty.as_ref(), defs, name, depth)?
+					};
+
+					vec.push(item);
+				}
+
+				// The generated element is distinct from the others, since none of them match `contains`.
+				if let Some(contains) = contains
+					&& !vec.iter().any(|item| matches_type(contains, item, defs)) {
+					let item = self.generate_type(contains, defs, name, depth + 1)?;
+
+					if let Some(items_ty) = items_ty
+						&& !matches_type(items_ty, &item, defs) {
+						bail!("Cannot generate an element of \"{name}\" matching both its `items` and its `contains`.");
+					}
+
+					if (vec.len() as u64) < max {
+						vec.push(item);
+					} else if vec.is_empty() {
+						bail!("Cannot generate an element of \"{name}\" matching its `contains`, its `maxItems` is 0.");
+					} else {
+						let idx = self.range(vec.len() as u64) as usize;
+						vec[idx] = item;
+					}
+				}
+
+				Value::Array(vec)
+			}
+			Definition::Tuple(JTuple { items, .. }) => items
+				.iter()
+				.map(|ty| self.generate_type(ty, defs, name, depth + 1))
+				.try_collect()?,
+			Definition::Enum(JEnum { variants, .. }) => {
+				let idx = self.range(variants.len() as u64) as usize;
+
+				variants
+					.iter()
+					.nth(idx)
+					.map(|(variant, value)| if JEnum::integer_encoding() { Value::from(*value) } else { Value::String(variant.clone()) })
+					.ok_or_else(|| anyhow!("Enum \"{name}\" has no variants."))?
+			}
+			// The pattern of hex colors cannot be generated from their definition.
+			Definition::Variant(VariantDefinition::Color) if VariantRepresentation::current().hex_colors => {
+				Value::String(format!("#{:06x}", self.range(0x100_0000)))
+			}
+			Definition::Variant(var_def) => self.generate_definition(&var_def.source_definition(), defs, name, depth + 1)?,
+			Definition::Any(_) => Value::Null,
+			Definition::ResourcePath(path_def) if self.loadable => {
+				let key = format!("{}:{:?}:{:?}:{}", path_def.source.definition_name(), path_def.allowed_dirs, path_def.extensions, path_def.instance_scene);
+				let count = self.loadable_paths.entry(key.clone()).or_insert_with(|| path_def.loadable_paths()).len();
+
+				if count == 0 {
+					bail!(
+						"Cannot generate a path for \"{name}\", no file in \"{}\" loads as a {}.",
+						if path_def.allowed_dirs.is_empty() { "res://".to_owned() } else { path_def.allowed_dirs.join("\", \"") },
+						path_def.source.definition_name(),
+					);
+				}
+
+				let idx = self.range(count as u64) as usize;
+				Value::String(self.loadable_paths[&key][idx].clone())
+			}
+			Definition::ResourcePath(JResourcePath { allowed_dirs, extensions, .. }) => {
+				let dir = allowed_dirs.first().map_or("res://", String::as_str);
+				let extension = extensions.first().map_or("tres", String::as_str);
+				Value::String(format!("{dir}{name}_{}.{extension}", self.range(1000)))
+			}
+			Definition::Conditional(conditional) => {
+				let then = match &conditional.then {
+					Some(then) => self.generate_type(then, defs, name, depth + 1)?,
+					None => self.generate_type(&conditional.if_, defs, name, depth + 1)?,
+				};
+
+				match &conditional.else_ {
+					Some(else_) if !conditional.condition_holds(&then, defs) => self.generate_type(else_, defs, name, depth + 1)?,
+					_ => then,
+				}
+			}
+			Definition::OneOf(JOneOf { options, .. }) => {
+				let ty = options
+					.get(self.range(options.len() as u64) as usize)
+					.ok_or_else(|| anyhow!("Expected `oneOf` to have options."))?;
+
+				self.generate_type(ty, defs, name, depth + 1)?
+			}
+		})
+	}
+}
+
+/// Moves `value` inside the bounds of `keywords` (`minimum`, `exclusiveMaximum`...), then onto a multiple of `multipleOf`.
+fn constrain_number(value: f64, keywords: &Map<String, Value>, integer: bool) -> f64 {
+	let keyword = |name: &str| keywords.get(name).and_then(Value::as_f64);
+
+	// Exclusive bounds are moved inward, so both bounds are inclusive.
+	let above = |min: f64| if integer { min.floor() + 1.0 } else { min + min.abs().max(1.0) * 1e-6 };
+	let below = |max: f64| if integer { max.ceil() - 1.0 } else { max - max.abs().max(1.0) * 1e-6 };
+
+	let low = [keyword("minimum").map(|min| if integer { min.ceil() } else { min }), keyword("exclusiveMinimum").map(above)]
+		.into_iter()
+		.flatten()
+		.reduce(f64::max);
+	let high = [keyword("maximum").map(|max| if integer { max.floor() } else { max }), keyword("exclusiveMaximum").map(below)]
+		.into_iter()
+		.flatten()
+		.reduce(f64::min);
+
+	let mut value = match (low, high) {
+		(Some(low), Some(high)) if high > low && !(low..=high).contains(&value) => {
+			// Wraps instead of clamping, so the values are not all at the bounds.
+			let span = if integer { high - low + 1.0 } else { high - low };
+			low + value.rem_euclid(span)
+		}
+		_ => value.max(low.unwrap_or(f64::MIN)).min(high.unwrap_or(f64::MAX)),
+	};
+
+	if let Some(step) = keyword("multipleOf").filter(|step| *step > 0.0) {
+		value = (value / step).round() * step;
+
+		if low.is_some_and(|low| value < low) {
+			value += step;
+		} else if high.is_some_and(|high| value > high) {
+			value -= step;
+		}
+	}
+
+	value
+}
+
+/// Builds a fine-tuning record for `schema`: a prompt describing the schema, paired with a generated valid response.
+///
+/// The response is validated against the schema before being returned.
+pub fn finetune_example(schema: &GodotSchema, generator: &mut ExampleGenerator) -> Result<Value> {
+	let type_name = match &schema.inner.base {
+		Definition::Class(class) => class.source.definition_name(),
+		_ => "value".to_owned(),
+	};
+
+	let response = generator.generate(&schema.inner)?;
+	schema.validate_value(&response)?;
+
+	Ok(serde_json::json!({
+		"prompt": format!(
+			"Generate a JSON object describing a `{type_name}`, matching this JSON schema:\n{}",
+			schema.inner.to_json_compact()?,
+		),
+		"response": serde_json::to_string(&response)?,
+	}))
+}
@@ -0,0 +1,62 @@
+use super::*;
+use godot::classes::HashingContext;
+use godot::classes::hashing_context::HashType;
+
+impl RootSchema {
+	/// SHA-256 of the schema's canonical JSON (compact, with every object's keys and `required` lists sorted),
+	/// as a lowercase hex string.
+	///
+	/// Stable across runs and platforms, and independent of the order properties were declared in:
+	/// two schemas with the same hash describe the same documents.
+	pub fn fingerprint(&self) -> Result<String> {
+		let canonical = serde_json::to_string(&canonicalize(serde_json::to_value(self)?, true))?;
+		sha256_hex(canonical.as_bytes())
+	}
+}
+
+/// Sorts the keys of every object in `value`, `serde_json` otherwise keeps them in insertion order,
+/// and the property names of `required` lists, which follow the declaration order.
+///
+/// `in_schema` is unset inside values (e.g. `default`), whose arrays keep their order.
+fn canonicalize(value: Value, in_schema: bool) -> Value {
+	match value {
+		Value::Object(map) => {
+			let sorted = map
+				.into_iter()
+				.map(|(key, value)| {
+					let value = match value {
+						Value::Array(mut names) if in_schema && key == "required" => {
+							names.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+							Value::Array(names)
+						}
+						value => canonicalize(value, in_schema && !matches!(key.as_str(), "const" | "default" | "enum" | "examples")),
+					};
+
+					(key, value)
+				})
+				.sorted_by(|(a, _), (b, _)| a.cmp(b))
+				.collect();
+
+			Value::Object(sorted)
+		}
+		Value::Array(values) => Value::Array(values.into_iter().map(|value| canonicalize(value, in_schema)).collect()),
+		other => other,
+	}
+}
+
+fn sha256_hex(bytes: &[u8]) -> Result<String> {
+	let mut context = HashingContext::new_gd();
+
+	if context.start(HashType::SHA256) != godot::global::Error::OK {
+		bail!("Could not start SHA-256 hashing context.");
+	}
+
+	context.update(&PackedByteArray::from(bytes));
+
+	Ok(context
+		.finish()
+		.as_slice()
+		.iter()
+		.map(|byte| format!("{byte:02x}"))
+		.collect())
+}
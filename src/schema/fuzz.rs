@@ -0,0 +1,185 @@
+use super::*;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// An input that broke instantiation, found by [`fuzz_instantiate()`].
+#[derive(Clone, Debug)]
+pub struct FuzzFailure {
+	pub input: Value,
+	pub message: String,
+	/// Whether instantiation panicked, instead of rejecting a valid document.
+	pub panicked: bool,
+}
+
+impl FuzzFailure {
+	pub fn to_dictionary(&self) -> Dictionary {
+		let mut dict = Dictionary::new();
+		dict.set("input", self.input.to_string());
+		dict.set("message", self.message.as_str());
+		dict.set("panicked", self.panicked);
+		dict
+	}
+}
+
+/// Instantiates `runs` random documents matching `schema` (generated by [`ExampleGenerator::loadable()`]), then a mutated copy of each
+/// (wrong types, missing or unknown keys, out of range numbers, resized arrays...).
+///
+/// Valid documents must instantiate, mutated ones are converted without validation first and must not panic.
+/// Generated documents the validator rejects (e.g. breaking a `pattern` the generator cannot follow) are skipped,
+/// it is an error if all of them are. Instances are freed afterward, see [`free_instance()`].
+pub fn fuzz_instantiate(schema: &GodotSchema, runs: usize, seed: u64) -> Result<Vec<FuzzFailure>> {
+	let mut generator = ExampleGenerator::loadable(seed);
+	let mut failures = Vec::new();
+	let mut tested = 0;
+	let mut last_rejection = None;
+
+	for _ in 0..runs {
+		let valid = generator.generate(&schema.inner)?;
+
+		match instantiate_caught(|| schema.validate_unreported(&valid)) {
+			Ok(Ok(())) => tested += 1,
+			Ok(Err(err)) => {
+				last_rejection = Some(err);
+				continue;
+			}
+			Err(message) => {
+				failures.push(FuzzFailure { input: valid, message, panicked: true });
+				continue;
+			}
+		}
+
+		match instantiate_caught(|| schema.instantiate_input(&valid)) {
+			Ok(Ok(instance)) => free_instance(&instance),
+			Ok(Err(err)) => failures.push(FuzzFailure { input: valid.clone(), message: err.to_string(), panicked: false }),
+			Err(message) => failures.push(FuzzFailure { input: valid.clone(), message, panicked: true }),
+		}
+
+		let mut mutated = valid;
+		mutate(&mut mutated, &mut generator);
+
+		match instantiate_caught(|| schema.instantiate_input(&mutated)) {
+			Ok(Ok(instance)) => free_instance(&instance),
+			Ok(Err(_)) => {}
+			Err(message) => failures.push(FuzzFailure { input: mutated, message, panicked: true }),
+		}
+	}
+
+	if tested == 0
+		&& let Some(err) = last_rejection {
+		bail!("Could not generate a valid document in {runs} attempts.\nLast error: {err}");
+	}
+
+	Ok(failures)
+}
+
+/// Runs `f`, returning the message of the panic if it panicked.
+fn instantiate_caught<T>(f: impl FnOnce() -> T) -> std::result::Result<T, String> {
+	catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+		payload
+			.downcast_ref::<&str>()
+			.map(|message| (*message).to_owned())
+			.or_else(|| payload.downcast_ref::<String>().cloned())
+			.unwrap_or_else(|| "Instantiation panicked.".to_owned())
+	})
+}
+
+/// Frees the objects not extending `RefCounted` in `instance`, along with the ones held by their stored properties,
+/// arrays and dictionaries.
+fn free_instance(instance: &Variant) {
+	let mut objects = Vec::new();
+	collect_objects(instance, &mut objects);
+
+	for object in objects {
+		// Freeing a node also frees its children, which may be among the objects.
+		if object.is_instance_valid() && !object.is_class("RefCounted") {
+			object.free();
+		}
+	}
+}
+
+fn collect_objects(variant: &Variant, objects: &mut Vec<Gd<Object>>) {
+	match variant.get_type() {
+		VariantType::OBJECT => {
+			let Ok(object) = variant.try_to::<Gd<Object>>()
+			else { return };
+
+			// Objects may reference each other.
+			if objects.contains(&object) {
+				return;
+			}
+
+			objects.push(object.clone());
+
+			for property in object.get_property_list().iter_shared() {
+				let stored = try_get::<i64>(&property, "usage").is_ok_and(|usage| usage as u64 & PropertyUsageFlags::STORAGE.ord() != 0);
+
+				match try_get::<String>(&property, "name") {
+					Ok(name) if stored && name != "script" => collect_objects(&object.get(name.as_str()), objects),
+					_ => {}
+				}
+			}
+		}
+		VariantType::ARRAY => {
+			for element in variant.to::<VariantArray>().iter_shared() {
+				collect_objects(&element, objects);
+			}
+		}
+		VariantType::DICTIONARY => {
+			for (_, value) in variant.to::<Dictionary>().iter_shared() {
+				collect_objects(&value, objects);
+			}
+		}
+		_ => {}
+	}
+}
+
+/// Values of the wrong type, or at the edges of what the numeric conversions accept.
+fn near_value(generator: &mut ExampleGenerator) -> Value {
+	match generator.range(10) {
+		0 => Value::Null,
+		1 => Value::Bool(true),
+		2 => Value::from(u64::MAX),
+		3 => Value::from(i64::MIN),
+		4 => Value::from(1.5e300),
+		5 => Value::from(-0.5),
+		6 => Value::String(String::new()),
+		7 => Value::String("#zz".to_owned()),
+		8 => Value::Array(Vec::new()),
+		_ => Value::Object(Map::new()),
+	}
+}
+
+/// Applies a single mutation at a random depth of `value`.
+fn mutate(value: &mut Value, generator: &mut ExampleGenerator) {
+	// Descends into a random child half of the time, if there is one.
+	let descend = generator.range(2) == 0;
+
+	match value {
+		Value::Object(properties) if !properties.is_empty() => {
+			let idx = generator.range(properties.len() as u64) as usize;
+			let name = properties.keys().nth(idx).cloned().unwrap_or_default();
+
+			if descend {
+				if let Some(child) = properties.get_mut(&name) {
+					mutate(child, generator);
+				}
+			} else if generator.range(2) == 0 {
+				properties.remove(&name);
+			} else {
+				properties.insert("unknown_property".to_owned(), near_value(generator));
+			}
+		}
+		Value::Array(vec) if !vec.is_empty() => {
+			let idx = generator.range(vec.len() as u64) as usize;
+
+			if descend {
+				mutate(&mut vec[idx], generator);
+			} else if generator.range(2) == 0 {
+				vec.remove(idx);
+			} else {
+				let element = vec[idx].clone();
+				vec.push(element);
+			}
+		}
+		_ => *value = near_value(generator),
+	}
+}
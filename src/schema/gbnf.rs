@@ -0,0 +1,330 @@
+use super::*;
+
+const SPACE_RULE: &str = r#"[ \t\n]*"#;
+const STRING_RULE: &str = r#""\"" ( [^"\\\x7F\x00-\x1F] | "\\" (["\\/bfnrt] | "u" [0-9a-fA-F]{4}) )* "\"" space"#;
+const INTEGER_RULE: &str = r#""-"? ([0-9] | [1-9] [0-9]*) space"#;
+const NUMBER_RULE: &str = r#""-"? ([0-9] | [1-9] [0-9]*) ("." [0-9]+)? ([eE] [-+]? [0-9]+)? space"#;
+const BOOLEAN_RULE: &str = r#"("true" | "false") space"#;
+const NULL_RULE: &str = r#""null" space"#;
+const VALUE_RULE: &str = r#"object | array | string | number | boolean | null"#;
+const OBJECT_RULE: &str = r#""{" space ( string ":" space value ("," space string ":" space value)* )? "}" space"#;
+const ARRAY_RULE: &str = r#""[" space ( value ("," space value)* )? "]" space"#;
+
+impl RootSchema {
+	/// Converts this schema into a [llama.cpp GBNF grammar](https://github.com/ggerganov/llama.cpp/blob/master/grammars/README.md).
+	///
+	/// The conversion works on the serialized schema, so every keyword emitted by this crate is supported:
+	/// objects (optional properties may be omitted, maps repeat their key/value pairs), arrays, tuples, enums, numbers,
+	/// strings, booleans, nulls and `$defs` references.
+	///
+	/// Class inheritance is flattened first, other `allOf` schemas are merged. A grammar cannot test an `if` condition,
+	/// so conditionals accept either branch.
+	pub fn to_gbnf(&self) -> Result<String> {
+		let mut flattened = self.clone();
+		flattened.flatten_inheritance();
+		// Definitions shared with other schemas are written in `$defs`, since they cannot be resolved here.
+		flattened.options.external_refs.clear();
+		let schema = serde_json::to_value(&flattened)?;
+
+		let mut converter = GbnfConverter {
+			defs: schema.get("$defs").and_then(Value::as_object),
+			rules: Vec::new(),
+		};
+
+		converter.add_rule("space", SPACE_RULE);
+		let root = converter.visit(&schema)?;
+		converter.rules.insert(0, ("root".to_owned(), root));
+
+		let mut grammar = String::new();
+
+		for (name, body) in &converter.rules {
+			grammar += &format!("{name} ::= {body}\n");
+		}
+
+		Ok(grammar)
+	}
+}
+
+struct GbnfConverter<'a> {
+	defs: Option<&'a Map<String, Value>>,
+	// Kept in insertion order, llama.cpp does not care about the order but humans reading the grammar do.
+	rules: Vec<(String, String)>,
+}
+
+impl<'a> GbnfConverter<'a> {
+	fn has_rule(&self, name: &str) -> bool {
+		self.rules.iter().any(|(rule, _)| rule == name)
+	}
+
+	fn add_rule(&mut self, name: impl Into<String>, body: impl Into<String>) {
+		let name = name.into();
+
+		if !self.has_rule(&name) {
+			self.rules.push((name, body.into()));
+		}
+	}
+
+	fn primitive(&mut self, name: &'static str) -> String {
+		if self.has_rule(name) {
+			return name.to_owned();
+		}
+
+		let (body, dependencies): (&str, &[&'static str]) = match name {
+			"string" => (STRING_RULE, &[]),
+			"integer" => (INTEGER_RULE, &[]),
+			"number" => (NUMBER_RULE, &[]),
+			"boolean" => (BOOLEAN_RULE, &[]),
+			"null" => (NULL_RULE, &[]),
+			"value" => (VALUE_RULE, &["object", "array", "string", "number", "boolean", "null"]),
+			"object" => (OBJECT_RULE, &["string", "value"]),
+			"array" => (ARRAY_RULE, &["value"]),
+			_ => unreachable!(),
+		};
+
+		self.add_rule(name, body);
+
+		for dependency in dependencies {
+			self.primitive(dependency);
+		}
+
+		name.to_owned()
+	}
+
+	fn visit(&mut self, schema: &Value) -> Result<String> {
+		let Value::Object(keywords) = schema
+		else { return Ok(self.primitive("value")) };
+
+		if let Some(Value::String(reference)) = keywords.get("$ref") {
+			return self.visit_ref(reference);
+		}
+
+		if let Some(constant) = keywords.get("const") {
+			return Ok(format!("{} space", literal(constant)?));
+		}
+
+		if let Some(Value::Array(variants)) = keywords.get("enum") {
+			let alternatives = variants
+				.iter()
+				.map(literal)
+				.try_collect::<_, Vec<_>, _>()?
+				.join(" | ");
+
+			return Ok(format!("({alternatives}) space"));
+		}
+
+		if let Some(Value::Array(parts)) = keywords.get("allOf") {
+			return self.visit_all_of(keywords, parts);
+		}
+
+		if keywords.contains_key("if") {
+			let branches = [keywords.get("then"), keywords.get("else")];
+
+			// A missing branch accepts any value.
+			if branches.contains(&None) {
+				return Ok(self.primitive("value"));
+			}
+
+			let alternatives = branches
+				.into_iter()
+				.flatten()
+				.map(|branch| self.visit(branch))
+				.try_collect::<_, Vec<_>, _>()?
+				.join(" | ");
+
+			return Ok(format!("({alternatives})"));
+		}
+
+		if let Some(Value::Array(options)) = keywords.get("oneOf").or_else(|| keywords.get("anyOf")) {
+			let alternatives = options
+				.iter()
+				.map(|option| self.visit(option))
+				.try_collect::<_, Vec<_>, _>()?
+				.join(" | ");
+
+			return Ok(format!("({alternatives})"));
+		}
+
+		match keywords.get("type") {
+			Some(Value::String(ty)) => self.visit_typed(ty, keywords),
+			Some(Value::Array(types)) => {
+				let alternatives = types
+					.iter()
+					.filter_map(Value::as_str)
+					.map(|ty| self.visit_typed(ty, keywords))
+					.try_collect::<_, Vec<_>, _>()?
+					.join(" | ");
+
+				Ok(format!("({alternatives})"))
+			}
+			_ => Ok(self.primitive("value")),
+		}
+	}
+
+	/// Merges the `properties` and `required` of `parts` (resolving their references) into `keywords`, then visits the result.
+	fn visit_all_of(&mut self, keywords: &Map<String, Value>, parts: &[Value]) -> Result<String> {
+		let mut merged = keywords.clone();
+		merged.remove("allOf");
+
+		for part in parts {
+			let part = match part.get("$ref").and_then(Value::as_str) {
+				Some(reference) => self.resolve(reference)?,
+				None => part,
+			};
+
+			let Value::Object(part) = part
+			else { continue };
+
+			for (keyword, value) in part {
+				match (keyword.as_str(), merged.get_mut(keyword), value) {
+					("properties", Some(Value::Object(properties)), Value::Object(more)) => {
+						properties.extend(more.iter().map(|(name, property)| (name.clone(), property.clone())));
+					}
+					("required", Some(Value::Array(required)), Value::Array(more)) => required.extend(more.iter().cloned()),
+					(_, None, _) => { merged.insert(keyword.clone(), value.clone()); }
+					_ => {}
+				}
+			}
+		}
+
+		self.visit(&Value::Object(merged))
+	}
+
+	fn resolve(&self, reference: &str) -> Result<&'a Value> {
+		let def_name = reference
+			.strip_prefix("#/$defs/")
+			.ok_or_else(|| anyhow!("Only local `$defs` references are supported.\nGot: {reference}"))?;
+
+		self.defs
+			.and_then(|defs| defs.get(def_name))
+			.ok_or_else(|| anyhow!("Expected definition \"{def_name}\" to be in `$defs` map."))
+	}
+
+	fn visit_ref(&mut self, reference: &str) -> Result<String> {
+		let def_name = reference
+			.strip_prefix("#/$defs/")
+			.ok_or_else(|| anyhow!("Only local `$defs` references are supported.\nGot: {reference}"))?;
+
+		let rule_name = format!("def-{}", sanitize_rule_name(def_name));
+
+		// Insert a placeholder first so recursive definitions terminate.
+		if !self.has_rule(&rule_name) {
+			self.rules.push((rule_name.clone(), String::new()));
+
+			let body = self.visit(self.resolve(reference)?)?;
+
+			if let Some((_, placeholder)) = self.rules.iter_mut().find(|(name, _)| *name == rule_name) {
+				*placeholder = body;
+			}
+		}
+
+		Ok(rule_name)
+	}
+
+	fn visit_typed(&mut self, ty: &str, keywords: &Map<String, Value>) -> Result<String> {
+		match ty {
+			"object" => self.visit_object(keywords),
+			"array" => self.visit_array(keywords),
+			"string" => Ok(self.primitive("string")),
+			"integer" => Ok(self.primitive("integer")),
+			"number" => Ok(self.primitive("number")),
+			"boolean" => Ok(self.primitive("boolean")),
+			"null" => Ok(self.primitive("null")),
+			other => bail!("Unsupported JSON type `{other}`."),
+		}
+	}
+
+	/// Required properties come first, in declaration order, followed by the optional ones, which may each be omitted.
+	fn visit_object(&mut self, keywords: &Map<String, Value>) -> Result<String> {
+		let properties = match keywords.get("properties") {
+			Some(Value::Object(properties)) if !properties.is_empty() => properties,
+			_ => return match keywords.get("additionalProperties") {
+				Some(values @ Value::Object(_)) => {
+					let key = self.primitive("string");
+					let value = self.visit(values)?;
+					let member = format!("{key} \":\" space {value}");
+					Ok(format!("\"{{\" space ({member} (\",\" space {member})*)? \"}}\" space"))
+				}
+				_ => Ok(self.primitive("object")),
+			},
+		};
+
+		let required = keywords
+			.get("required")
+			.and_then(Value::as_array)
+			.map(|required| required.iter().filter_map(Value::as_str).collect_vec())
+			.unwrap_or_default();
+
+		let (mut required_members, mut optional_members) = (Vec::new(), Vec::new());
+
+		for (name, property) in properties {
+			let key = literal(&Value::String(name.clone()))?;
+			let member = format!("{key} space \":\" space {}", self.visit(property)?);
+
+			if required.contains(&name.as_str()) {
+				required_members.push(member);
+			} else {
+				optional_members.push(member);
+			}
+		}
+
+		let members = if required_members.is_empty() {
+			// The first optional member present is not preceded by a comma.
+			let alternatives = (0..optional_members.len())
+				.map(|first| {
+					let rest = optional_members[first + 1..].iter().map(|member| format!(" (\",\" space {member})?")).join("");
+					format!("{}{rest}", optional_members[first])
+				})
+				.join(" | ");
+
+			if alternatives.is_empty() { String::new() } else { format!("({alternatives})? ") }
+		} else {
+			let optional = optional_members.iter().map(|member| format!(" (\",\" space {member})?")).join("");
+			format!("{}{optional} ", required_members.join(" \",\" space "))
+		};
+
+		Ok(format!("\"{{\" space {members}\"}}\" space"))
+	}
+
+	fn visit_array(&mut self, keywords: &Map<String, Value>) -> Result<String> {
+		if let Some(Value::Array(prefix_items)) = keywords.get("prefixItems") {
+			let items = prefix_items
+				.iter()
+				.map(|item| self.visit(item))
+				.try_collect::<_, Vec<_>, _>()?;
+
+			return Ok(format!("\"[\" space {} \"]\" space", items.join(" \",\" space ")));
+		}
+
+		let item = match keywords.get("items") {
+			Some(items) => self.visit(items)?,
+			None => self.primitive("value"),
+		};
+
+		let min_items = keywords.get("minItems").and_then(Value::as_u64).unwrap_or(0);
+		let max_items = keywords.get("maxItems").and_then(Value::as_u64);
+
+		let elements = match (min_items, max_items) {
+			(_, Some(0)) => String::new(),
+			(0, None) => format!("({item} (\",\" space {item})*)?"),
+			(0, Some(max)) => format!("({item} (\",\" space {item}){{0,{}}})?", max - 1),
+			(min, None) => format!("{item} (\",\" space {item}){{{},}}", min - 1),
+			(min, Some(max)) => format!("{item} (\",\" space {item}){{{},{}}}", min - 1, max.max(min) - 1),
+		};
+
+		Ok(format!("\"[\" space {elements} \"]\" space"))
+	}
+}
+
+/// Converts a JSON literal into a quoted GBNF terminal.
+fn literal(value: &Value) -> Result<String> {
+	let json = serde_json::to_string(value)?;
+	let escaped = json.replace('\\', "\\\\").replace('"', "\\\"");
+	Ok(format!("\"{escaped}\""))
+}
+
+/// GBNF rule names may only contain alphanumeric characters and dashes.
+fn sanitize_rule_name(name: &str) -> String {
+	name.chars()
+		.map(|ch| if ch.is_ascii_alphanumeric() { ch } else { '-' })
+		.collect()
+}
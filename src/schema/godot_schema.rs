@@ -1,16 +1,45 @@
 use super::*;
-use jsonschema::Validator;
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::sync::OnceLock;
+
+/// An edit made with one of the editing methods (e.g. [`GodotSchema::add_property()`]), replayed by [`GodotSchema::try_reload()`].
+type SchemaEdit = Rc<dyn Fn(&mut RootSchema) -> Result<()>>;
 
 #[derive(GodotClass)]
 #[class(no_init, base = RefCounted)]
 pub struct GodotSchema {
 	pub inner: RootSchema,
-	pub validator: Validator,
+	/// Compiled lazily, may be released with [`Self::release_validator()`] to save memory.
+	pub validator: OnceLock<Box<dyn SchemaValidator>>,
+	/// This schema with every property optional, which validates merge patches, see [`Self::merge_patch_value()`].
+	/// Built on first use, released along with [`Self::validator`].
+	relaxed: OnceLock<Box<GodotSchema>>,
+	/// The successful edits made to this schema, in order.
+	edits: Vec<SchemaEdit>,
+	pub backend: ValidatorBackend,
 	#[var(get)] pub json: GString,
 }
 
 #[godot_api]
 impl GodotSchema {
+	#[constant] pub const VALIDATOR_JSONSCHEMA: i64 = ValidatorBackend::Jsonschema as i64;
+	#[constant] pub const VALIDATOR_STRUCTURAL: i64 = ValidatorBackend::Structural as i64;
+	#[constant] pub const VALIDATOR_TRUST: i64 = ValidatorBackend::Trust as i64;
+	#[constant] pub const ENCODING_PLAIN: i64 = VariantEncoding::Plain as i64;
+	#[constant] pub const ENCODING_TAGGED: i64 = VariantEncoding::Tagged as i64;
+	#[constant] pub const SETTERS_UNCHECKED: i64 = SetterCheck::Off as i64;
+	#[constant] pub const SETTERS_WARN: i64 = SetterCheck::Warn as i64;
+	#[constant] pub const SETTERS_ERROR: i64 = SetterCheck::Error as i64;
+	#[constant] pub const MERGE_ERROR: i64 = ConflictPolicy::Error as i64;
+	#[constant] pub const MERGE_PREFER_LEFT: i64 = ConflictPolicy::PreferLeft as i64;
+	#[constant] pub const MERGE_PREFER_RIGHT: i64 = ConflictPolicy::PreferRight as i64;
+	#[constant] pub const UNSUPPORTED_ERROR: i64 = UnsupportedPropertyPolicy::Error as i64;
+	#[constant] pub const UNSUPPORTED_SKIP: i64 = UnsupportedPropertyPolicy::Skip as i64;
+	#[constant] pub const UNSUPPORTED_STUB_AS_NULL: i64 = UnsupportedPropertyPolicy::StubAsNull as i64;
+	#[constant] pub const NAMING_AS_IS: i64 = NamingPolicy::AsIs as i64;
+	#[constant] pub const NAMING_CAMEL_CASE: i64 = NamingPolicy::CamelCase as i64;
+
 	/// Generates a schema for class named `class_name`.
 	///
 	/// If it is a GDScript class, it must be registered in `ProjectSettings::get_global_class_list()`. 
@@ -22,16 +51,18 @@ impl GodotSchema {
 	/// - Otherwise a `String` containing the error message.
 	#[func]
 	pub fn from_class_name(class_name: StringName) -> Variant {
-		let result = ClassSource::from_class_name(class_name)
-			.and_then(RootSchema::from_class)
-			.and_then(Self::new);
-
-		match result {
+		match Self::try_from_class_name(class_name) {
 			Ok(schema) => Gd::from_object(schema).to_variant(),
 			Err(err) => format!("{err:?}").to_variant(),
 		}
 	}
 
+	/// Same as [`Self::from_class_name()`], but returns a [`SchemaResult`].
+	#[func]
+	pub fn from_class_name_ex(class_name: StringName) -> Gd<SchemaResult> {
+		SchemaResult::from_result(Self::try_from_class_name(class_name).map(Gd::from_object))
+	}
+
 	/// Generates a schema for a GdScript class defined in `script`.
 	///
 	/// Unlike [`from_class_name()`](Self::from_class_name), 
@@ -42,15 +73,18 @@ impl GodotSchema {
 	/// - Otherwise a `String` containing the error message.
 	#[func]
 	pub fn from_class_script(script: Gd<Script>) -> Variant {
-		let source = ClassSource::from_script(script);
-		let result = RootSchema::from_class(source).and_then(Self::new);
-
-		match result {
+		match Self::try_from_class_script(script) {
 			Ok(schema) => Gd::from_object(schema).to_variant(),
 			Err(err) => format!("{err:?}").to_variant(),
 		}
 	}
 
+	/// Same as [`Self::from_class_script()`], but returns a [`SchemaResult`].
+	#[func]
+	pub fn from_class_script_ex(script: Gd<Script>) -> Gd<SchemaResult> {
+		SchemaResult::from_result(Self::try_from_class_script(script).map(Gd::from_object))
+	}
+
 	/// Generates a schema for a Godot type.
 	///
 	/// Godot's type info system is a bit convoluted, read each property's documentation for more info.
@@ -82,19 +116,89 @@ impl GodotSchema {
 		hint_string: String,
 		usage: PropertyUsageFlags,
 	) -> Variant {
-		let info = PropertyTypeInfo {
-			variant_type,
-			class_name,
-			hint,
-			hint_string,
-			usage,
-			property_name: format!("{variant_type:?}"),
-		};
+		let info = PropertyTypeInfo::new(variant_type, class_name, hint, hint_string, usage);
+
+		match RootSchema::from_type_info(info).and_then(Self::new) {
+			Ok(inner) => Gd::from_object(inner).to_variant(),
+			Err(err) => format!("{err:?}").to_variant(),
+		}
+	}
+
+	/// Same as [`Self::from_type_info()`], but returns a [`SchemaResult`].
+	#[func]
+	pub fn from_type_info_ex(
+		variant_type: VariantType,
+		class_name: StringName,
+		hint: PropertyHint,
+		hint_string: String,
+		usage: PropertyUsageFlags,
+	) -> Gd<SchemaResult> {
+		let info = PropertyTypeInfo::new(variant_type, class_name, hint, hint_string, usage);
+		SchemaResult::from_result(RootSchema::from_type_info(info).and_then(Self::new).map(Gd::from_object))
+	}
+
+	/// Parses a JSON Schema (draft 2020-12) document authored outside Godot, e.g. by an API or a config spec.
+	///
+	/// Input is validated against the original document, then instantiated into `Dictionary`, `Array` and primitive values.
+	/// See [`RootSchema::from_json_schema()`] for the supported subset.
+	///
+	/// # Returns
+	/// - The `GodotSchema` object, if successful.
+	/// - Otherwise a `String` containing the error message.
+	#[func]
+	pub fn from_json_schema(json: String) -> Variant {
+		match Self::try_from_json_schema(&json) {
+			Ok(schema) => Gd::from_object(schema).to_variant(),
+			Err(err) => format!("{err:?}").to_variant(),
+		}
+	}
+
+	/// Builds a schema from a declarative shape, without writing a class, e.g.
+	/// `{"name": TYPE_STRING, "hp": TYPE_INT, "tags": [TYPE_STRING], "weapon": "Weapon"}`.
+	///
+	/// Values are `Variant.Type` constants, type or class names, scripts, nested `Dictionary`s (objects)
+	/// and single-element `Array`s (typed arrays). See [`RootSchema::from_shape()`] for the details.
+	///
+	/// # Returns
+	/// - The `GodotSchema` object, if successful.
+	/// - Otherwise a `String` containing the error message.
+	#[func]
+	pub fn from_shape(shape: Variant) -> Variant {
+		match RootSchema::from_shape(&shape).and_then(Self::new) {
+			Ok(schema) => Gd::from_object(schema).to_variant(),
+			Err(err) => format!("{err:?}").to_variant(),
+		}
+	}
 
-		let result = RootSchema::from_type_info(info).and_then(Self::new);
+	/// Synthesizes a best-guess schema from a sample value (`Dictionary`, `Array` or primitive), e.g. existing save data.
+	///
+	/// `Dictionary`s with `String` keys become objects, arrays are typed if their elements share a type.
+	/// See [`RootSchema::infer_from_variant()`] for the details.
+	///
+	/// # Returns
+	/// - The `GodotSchema` object, if successful.
+	/// - Otherwise a `String` containing the error message.
+	#[func]
+	pub fn infer_from_value(value: Variant) -> Variant {
+		match RootSchema::infer_from_variant(&value).and_then(Self::new) {
+			Ok(schema) => Gd::from_object(schema).to_variant(),
+			Err(err) => format!("{err:?}").to_variant(),
+		}
+	}
+
+	/// Generates a schema for the parameters of `object`'s method named `method`.
+	///
+	/// The schema is an object with one property per parameter, see [`ToolDispatcher`] for invoking methods from LLM tool calls.
+	///
+	/// # Returns
+	/// - The `GodotSchema` object containing the method's parameters schema, if successful.
+	/// - Otherwise a `String` containing the error message.
+	#[func]
+	pub fn from_method(object: Gd<Object>, method: StringName) -> Variant {
+		let result = RootSchema::from_method(&object, &method).and_then(Self::new);
 
 		match result {
-			Ok(inner) => Gd::from_object(inner).to_variant(),
+			Ok(schema) => Gd::from_object(schema).to_variant(),
 			Err(err) => format!("{err:?}").to_variant(),
 		}
 	}
@@ -109,120 +213,2076 @@ impl GodotSchema {
 	/// - Otherwise a `String` containing the error message.
 	#[func]
 	pub fn get_array_schema(&self, item_name: String) -> Variant {
-		let mut defs = self.inner.defs.clone();
-		let self_def = self.inner.base.clone().into_reference(item_name, &mut defs);
+		match self.array_schema(item_name) {
+			Ok(inner) => Gd::from_object(inner).to_variant(),
+			Err(err) => format!("{err:?}").to_variant(),
+		}
+	}
 
-		let array = JArray::new(self_def);
-		let schema = RootSchema {
-			defs,
-			base: array.into(),
-		};
+	/// Same as [`Self::get_array_schema()`], but returns a [`SchemaResult`].
+	#[func]
+	pub fn get_array_schema_ex(&self, item_name: String) -> Gd<SchemaResult> {
+		SchemaResult::from_result(self.array_schema(item_name).map(Gd::from_object))
+	}
 
-		match Self::new(schema) {
+	/// Generates a schema for a string-keyed map of this schema's type (`additionalProperties`), e.g. an "id => entity" collection.
+	/// It is instantiated into a `Dictionary` of instances.
+	///
+	/// # Input
+	/// `value_name`: The map's schema will have a definition of this type named `value_name`.
+	///
+	/// # Returns
+	/// - The `GodotSchema` object containing the map's schema, if successful.
+	/// - Otherwise a `String` containing the error message.
+	#[func]
+	pub fn get_dictionary_schema(&self, value_name: String) -> Variant {
+		match self.dictionary_schema(value_name) {
 			Ok(inner) => Gd::from_object(inner).to_variant(),
 			Err(err) => format!("{err:?}").to_variant(),
 		}
 	}
 
-	/// Instantiates the type defined by this schema from JSON input containing the values of the type.
+	/// Same as [`Self::get_dictionary_schema()`], but returns a [`SchemaResult`].
+	#[func]
+	pub fn get_dictionary_schema_ex(&self, value_name: String) -> Gd<SchemaResult> {
+		SchemaResult::from_result(self.dictionary_schema(value_name).map(Gd::from_object))
+	}
+
+	/// Replaces the validator used to check JSON input, see [`ValidatorBackend`] for the available options.
 	///
-	/// Notes:
-	/// - The JSON input must be valid according to the schema.
-	/// - The JSON input must contain all fields defined in the schema (i.e. the schema's "required" array has all of your type's properties).
-	/// - The JSON input must not contain any additional properties (i.e. the schema's "additionalProperties" key is set to false).
+	/// - `GodotSchema.VALIDATOR_JSONSCHEMA`: Full JSON Schema validation.
+	/// - `GodotSchema.VALIDATOR_STRUCTURAL`: Lightweight structural checks only.
+	/// - `GodotSchema.VALIDATOR_TRUST`: No validation.
 	///
 	/// # Returns
-	/// - The instantiated type, if successful.
-	/// - Otherwise, a `String` containing the error message.
+	/// - `Nil`, if successful.
+	/// - Otherwise a `String` containing the error message.
 	#[func]
-	pub fn instantiate(&self, input_json: String) -> Variant {
-		let try_fn = || {
-			let value = serde_json::from_str(&input_json)?;
-			let result = self.validator.validate(&value);
+	pub fn set_validator_backend(&mut self, backend: ValidatorBackend) -> Variant {
+		let result = serde_json::from_str(&self.json.to_string())
+			.map_err(anyhow::Error::from)
+			.and_then(|json_value| backend.build(&self.inner, &json_value));
 
-			match result {
-				Ok(()) => {
-					drop(result);
-
-					// If we are a wrapper for a non-class type, the actual input is in the "value" property.
-					let value =
-						if let Value::Object(properties) = &value
-							&& properties.len() == 1
-							&& let Some(inner) = properties.get("value")
-							&& !matches!(self.inner.base, Definition::Class(_) | Definition::Object(_)) {
-							inner
-						} else {
-							&value
-						};
-					
-					self.inner.instantiate(value)
-				}
-				Err(errors) => {
-					let mut msg = String::new();
+		match result {
+			Ok(validator) => {
+				self.validator = OnceLock::from(validator);
+				self.relaxed.take();
+				self.backend = backend;
+				Variant::nil()
+			}
+			Err(err) => format!("{err:?}").to_variant(),
+		}
+	}
 
-					for err in errors {
-						msg += &format!("{err:?}\n");
-					}
+	#[func]
+	pub fn get_validator_backend(&self) -> ValidatorBackend {
+		self.backend
+	}
 
-					bail!("{msg}")
-				}
+	/// Selects how [`Self::serialize_instance()`] converts values inside `Dictionary` and untyped `Array` properties:
+	///
+	/// - `GodotSchema.ENCODING_PLAIN`: Plain JSON, e.g. a `Vector3` becomes `{"x": 1.0, "y": 2.0, "z": 3.0}`.
+	/// - `GodotSchema.ENCODING_TAGGED`: Types without a JSON equivalent are tagged, e.g. `{"$type": "Vector3", "value": {...}}`,
+	///   so that they are instantiated back into the same type instead of a `Dictionary`.
+	///
+	/// Instantiation accepts both forms regardless of this setting.
+	#[func]
+	pub fn set_variant_encoding(&mut self, encoding: VariantEncoding) {
+		self.inner.options.variant_encoding = encoding;
+	}
+
+	#[func]
+	pub fn get_variant_encoding(&self) -> VariantEncoding {
+		self.inner.options.variant_encoding
+	}
+
+	/// If `enabled`, each class property in [`Self::json`] is annotated with the Godot type it was generated from:
+	/// `x-godot-type` (`Variant.Type`), `x-godot-class`, `x-godot-hint` (`PropertyHint`), `x-godot-hint-string` and `x-godot-usage`.
+	///
+	/// Validators ignore these keys, they are meant for tools that need to reconstruct the original property list.
+	/// Disabled by default.
+	#[func]
+	pub fn set_type_annotations(&mut self, enabled: bool) {
+		if self.inner.options.type_annotations == enabled {
+			return;
+		}
+
+		self.inner.options.type_annotations = enabled;
+
+		match self.inner.to_json_pretty() {
+			Ok(json) => self.json = json.into(),
+			Err(err) => godot_error!("{err}"),
+		}
+	}
+
+	#[func]
+	pub fn get_type_annotations(&self) -> bool {
+		self.inner.options.type_annotations
+	}
+
+	/// If `enabled`, [`Self::json`] annotates classes with `x-godot-class`, enums with `x-godot-enum` (variant values)
+	/// and the root with its wrapper and encodings, so that `RootSchema::from_value()` can parse it back into the exact definitions.
+	///
+	/// Provider payloads (e.g. [`Self::open_ai_response_format()`]) never include them. Disabled by default.
+	#[func]
+	pub fn set_round_trip_annotations(&mut self, enabled: bool) {
+		if self.inner.options.round_trip_annotations == enabled {
+			return;
+		}
+
+		self.inner.options.round_trip_annotations = enabled;
+
+		match self.inner.to_json_pretty() {
+			Ok(json) => self.json = json.into(),
+			Err(err) => godot_error!("{err}"),
+		}
+	}
+
+	#[func]
+	pub fn get_round_trip_annotations(&self) -> bool {
+		self.inner.options.round_trip_annotations
+	}
+
+	/// If `enabled`, enums are described (and serialized back by [`Self::serialize_instance()`]) as their integer values,
+	/// e.g. `{"type": "integer", "enum": [0, 1, 2]}`, for APIs that expect enums as numbers. Disabled by default.
+	///
+	/// Instantiation accepts either the variant's name or its value.
+	#[func]
+	pub fn set_integer_enums(&mut self, enabled: bool) {
+		if self.inner.options.integer_enums == enabled {
+			return;
+		}
+
+		self.inner.options.integer_enums = enabled;
+
+		if let Err(err) = self.try_rebuild() {
+			godot_error!("{err:?}");
+		}
+	}
+
+	#[func]
+	pub fn get_integer_enums(&self) -> bool {
+		self.inner.options.integer_enums
+	}
+
+	/// Emits `id` as the schema's `$id`, e.g. a versioned URL that other schemas reference it by. Empty removes it.
+	#[func]
+	pub fn set_schema_id(&mut self, id: String) {
+		let id = (!id.is_empty()).then_some(id);
+
+		if self.inner.options.id == id {
+			return;
+		}
+
+		self.inner.options.id = id;
+
+		if let Err(err) = self.try_rebuild() {
+			godot_error!("{err:?}");
+		}
+	}
+
+	#[func]
+	pub fn get_schema_id(&self) -> String {
+		self.inner.options.id.clone().unwrap_or_default()
+	}
+
+	/// Whether to emit the `$schema` keyword, some consumers (e.g. OpenAI's strict mode) reject it. Enabled by default.
+	#[func]
+	pub fn set_emit_meta_schema(&mut self, enabled: bool) {
+		if self.inner.options.omit_meta_schema != enabled {
+			return;
+		}
+
+		self.inner.options.omit_meta_schema = !enabled;
+
+		if let Err(err) = self.try_rebuild() {
+			godot_error!("{err:?}");
+		}
+	}
+
+	#[func]
+	pub fn get_emit_meta_schema(&self) -> bool {
+		!self.inner.options.omit_meta_schema
+	}
+
+	/// Whether the validator rejects strings that do not match their `format` (e.g. `date-time`), instead of treating it as an annotation.
+	/// Strict for network input, lenient for parsing LLM output.
+	///
+	/// By default, formats are annotations. Custom formats (see [`SchemaLibrary::register_format()`]) are checked either way.
+	/// Only affects the `VALIDATOR_JSONSCHEMA` backend.
+	#[func]
+	pub fn set_validate_formats(&mut self, enabled: bool) {
+		if self.inner.options.validator.format_assertion == Some(enabled) {
+			return;
+		}
+
+		self.inner.options.validator.format_assertion = Some(enabled);
+
+		if let Err(err) = self.rebuild_validator() {
+			godot_error!("{err:?}");
+		}
+	}
+
+	#[func]
+	pub fn get_validate_formats(&self) -> bool {
+		self.inner.options.validator.format_assertion.unwrap_or(false)
+	}
+
+	/// Makes the validator fail to compile if the schema has a regex (`pattern` or `patternProperties`) longer than `max_length` characters,
+	/// e.g. to bound the cost of compiling schemas received at runtime. `0` (the default) removes the limit.
+	///
+	/// Only affects the `VALIDATOR_JSONSCHEMA` backend.
+	///
+	/// # Returns
+	/// - `null`, if successful.
+	/// - Otherwise an error message, e.g. if the schema already has a longer regex. The limit is not changed.
+	#[func]
+	pub fn set_max_pattern_length(&mut self, max_length: i64) -> Variant {
+		let max_length = (max_length > 0).then_some(max_length as usize);
+		let previous = std::mem::replace(&mut self.inner.options.validator.max_pattern_length, max_length);
+
+		match self.rebuild_validator() {
+			Ok(()) => Variant::nil(),
+			Err(err) => {
+				self.inner.options.validator.max_pattern_length = previous;
+				format!("{err:?}").to_variant()
 			}
+		}
+	}
+
+	#[func]
+	pub fn get_max_pattern_length(&self) -> i64 {
+		self.inner.options.validator.max_pattern_length.unwrap_or_default() as i64
+	}
+
+	/// Sets the property non-class roots (e.g. arrays or enums) are wrapped in, "value" by default.
+	/// An empty `key` emits them as is, e.g. `{"type": "array", ..}`, for consumers that accept non-object roots.
+	///
+	/// Documents are wrapped (or not) accordingly by [`Self::serialize_instance()`], and expected so by [`Self::instantiate()`].
+	#[func]
+	pub fn set_root_wrapper(&mut self, key: String) {
+		let (wrapper_key, unwrapped_root) = match key.as_str() {
+			"" => (self.inner.options.wrapper_key.clone(), true),
+			DEFAULT_WRAPPER_KEY => (None, false),
+			_ => (Some(key), false),
 		};
 
-		match try_fn() {
-			Ok(obj) => obj.to_variant(),
-			Err(err) => format!("{err}").to_variant(),
+		if self.inner.options.wrapper_key == wrapper_key && self.inner.options.unwrapped_root == unwrapped_root {
+			return;
+		}
+
+		self.inner.options.wrapper_key = wrapper_key;
+		self.inner.options.unwrapped_root = unwrapped_root;
+
+		if let Err(err) = self.try_rebuild() {
+			godot_error!("{err:?}");
 		}
 	}
 
-	/// Returns the JSON schema response format for this schema in OpenAI format.
-	/// 	
-	/// This is useful for calling structured outputs with an LLM using a type-specific schema.
-	/// 
+	/// The property non-class roots are wrapped in, empty if they are emitted as is. Class roots are never wrapped.
+	#[func]
+	pub fn get_root_wrapper(&self) -> String {
+		if self.inner.options.unwrapped_root {
+			String::new()
+		} else {
+			self.inner.options.wrapper_key.clone().unwrap_or_else(|| DEFAULT_WRAPPER_KEY.to_owned())
+		}
+	}
+
+	/// Names class and object properties in the schema (and in documents) with `policy`, translating them back when instantiating.
+	/// Godot properties keep their `snake_case` names.
+	///
 	/// # Input
-	/// `name`: The root name of the schema, must be a valid identifier. (Cannot contain spaces)
+	/// `policy`: `GodotSchema.NAMING_AS_IS` (the default) or `NAMING_CAMEL_CASE`, e.g. `max_health` is named `maxHealth`.
+	///
+	/// # Returns
+	/// - `null`, if successful.
+	/// - Otherwise an error message, e.g. if two properties of the same class end up with the same name.
 	#[func]
-	pub fn open_ai_response_format(&self, name: String) -> Variant {
-		let schema = &self.inner;
-
-		let result = std::panic::catch_unwind(||
-			serde_json::json!({
-				"type": "json_schema",
-				"json_schema": {
-					"name": name,
-					"schema": schema,
-				},
-			}))
-			.map_err(|err| anyhow!("{err:?}"))
-			.and_then(|value| {
-				// In integration tests, return a bigger but more readable JSON.
-				#[cfg(feature = "integration_tests")]
-				return serde_json::to_string_pretty(&value).map_err(anyhow::Error::from);
+	pub fn set_property_naming(&mut self, policy: NamingPolicy) -> Variant {
+		self.set_naming(policy.into())
+	}
 
-				#[cfg(not(feature = "integration_tests"))]
-				return serde_json::to_string(&value).map_err(anyhow::Error::from);
+	/// Same as [`Self::set_property_naming()`], naming each property with `mapping`, a `Callable` that takes a property name
+	/// and returns its name in JSON.
+	///
+	/// `mapping` is called once per property declared in the schema, when this is called.
+	#[func]
+	pub fn set_property_naming_callable(&mut self, mapping: Callable) -> Variant {
+		let result = self.inner
+			.to_annotated_names()
+			.and_then(|schema| {
+				declared_property_names(&schema)
+					.into_iter()
+					.map(|name| {
+						let json_name = mapping
+							.call(&[name.to_variant()])
+							.try_to::<String>()
+							.map_err(|err| anyhow!("Expected `mapping` to return a String for property \"{name}\".\nGot error: {err:?}"))?;
+
+						Ok((name, json_name))
+					})
+					.try_collect::<_, BTreeMap<_, _>, anyhow::Error>()
 			});
 
 		match result {
-			Ok(json) => json.to_variant(),
-			Err(err) => {
-				godot_error!("{err}");
-				String::default().to_variant()
+			Ok(names) => self.set_naming(PropertyNaming::Custom(names)),
+			Err(err) => format!("{err:?}").to_variant(),
+		}
+	}
+
+	/// Exposes property `godot_name` of this schema's class as `json_name` in the schema (e.g. "hit_points" as "hp"),
+	/// instantiation still assigns the Godot property. An empty `json_name` removes the alias.
+	///
+	/// Properties of other classes in `$defs` are qualified with their definition name, e.g. "Fact.salient_word".
+	///
+	/// # Returns
+	/// - `null`, if successful.
+	/// - Otherwise an error message, e.g. if the property does not exist or another one is already named `json_name`.
+	#[func]
+	pub fn set_property_alias(&mut self, godot_name: String, json_name: String) -> Variant {
+		let result = self.inner
+			.set_property_alias(&godot_name, &json_name)
+			.and_then(|()| self.try_rebuild());
+
+		match result {
+			Ok(()) => {
+				self.edits.push(Rc::new(move |schema| schema.set_property_alias(&godot_name, &json_name)));
+				Variant::nil()
 			}
+			Err(err) => format!("{err:?}").to_variant(),
 		}
 	}
-}
 
-impl GodotSchema {
-	pub fn new(schema: RootSchema) -> Result<Self> {
-		let json = schema.to_json_pretty()?;
-		let json_value = serde_json::from_str(&json)?;
-		let validator = jsonschema::draft202012::new(&json_value)?;
+	/// If `enabled`, inputs may also name aliased or renamed properties by their Godot name,
+	/// see [`Self::set_property_alias()`] and [`Self::set_property_naming()`]. Disabled by default.
+	#[func]
+	pub fn set_accept_godot_names(&mut self, enabled: bool) {
+		self.inner.options.accept_godot_names = enabled;
+	}
 
-		Ok(Self {
-			inner: schema,
-			json: json.into(),
-			validator,
-		})
+	#[func]
+	pub fn get_accept_godot_names(&self) -> bool {
+		self.inner.options.accept_godot_names
+	}
+
+	/// The name `property` of this schema's class has in JSON, see [`Self::set_property_naming()`] and [`Self::set_property_alias()`].
+	#[func]
+	pub fn get_json_property_name(&self, property: String) -> String {
+		match &self.inner.base {
+			Definition::Class(class) if class.aliases.contains_key(&property) => class.json_name(&property).to_owned(),
+			_ => self.inner.options.property_naming.json_name(&property).into_owned(),
+		}
+	}
+
+	/// If `enabled`, vectors, quaternions and colors are described (and serialized back by [`Self::serialize_instance()`])
+	/// as fixed-length number arrays, e.g. `[x, y]` or `[r, g, b, a]`, roughly halving the tokens of geometry-heavy schemas.
+	/// Disabled by default.
+	#[func]
+	pub fn set_compact_math(&mut self, enabled: bool) {
+		if self.inner.options.representation.compact_math == enabled {
+			return;
+		}
+
+		self.inner.options.representation.compact_math = enabled;
+
+		if let Err(err) = self.try_rebuild() {
+			godot_error!("{err:?}");
+		}
+	}
+
+	#[func]
+	pub fn get_compact_math(&self) -> bool {
+		self.inner.options.representation.compact_math
 	}
-}
\ No newline at end of file
+
+	/// If `enabled`, colors are described (and serialized back by [`Self::serialize_instance()`]) as hex strings,
+	/// e.g. `"#ff8000"`, which LLMs produce far more reliably than RGBA floats. Takes precedence over [`Self::set_compact_math()`].
+	/// Disabled by default.
+	#[func]
+	pub fn set_hex_colors(&mut self, enabled: bool) {
+		if self.inner.options.representation.hex_colors == enabled {
+			return;
+		}
+
+		self.inner.options.representation.hex_colors = enabled;
+
+		if let Err(err) = self.try_rebuild() {
+			godot_error!("{err:?}");
+		}
+	}
+
+	#[func]
+	pub fn get_hex_colors(&self) -> bool {
+		self.inner.options.representation.hex_colors
+	}
+
+	/// If `enabled`, `Transform2D`, `Transform3D` and `Basis` are described (and serialized back by [`Self::serialize_instance()`])
+	/// as `{"position": .., "rotation_degrees": .., "scale": ..}` instead of their basis vectors, which LLMs cannot produce sensibly.
+	/// 3D rotations are applied in `YXZ` order, like `Node3D.rotation_degrees`. Disabled by default.
+	#[func]
+	pub fn set_friendly_transforms(&mut self, enabled: bool) {
+		if self.inner.options.representation.friendly_transforms == enabled {
+			return;
+		}
+
+		self.inner.options.representation.friendly_transforms = enabled;
+
+		if let Err(err) = self.try_rebuild() {
+			godot_error!("{err:?}");
+		}
+	}
+
+	#[func]
+	pub fn get_friendly_transforms(&self) -> bool {
+		self.inner.options.representation.friendly_transforms
+	}
+
+	/// If `enabled`, quaternions are described (and serialized back by [`Self::serialize_instance()`]) as Euler angles
+	/// in degrees, `{"yaw": .., "pitch": .., "roll": ..}`, since raw `xyzw` quaternions from an LLM are unusable.
+	/// Takes precedence over [`Self::set_compact_math()`]. Disabled by default.
+	#[func]
+	pub fn set_euler_quaternions(&mut self, enabled: bool) {
+		if self.inner.options.representation.euler_quaternions == enabled {
+			return;
+		}
+
+		self.inner.options.representation.euler_quaternions = enabled;
+
+		if let Err(err) = self.try_rebuild() {
+			godot_error!("{err:?}");
+		}
+	}
+
+	#[func]
+	pub fn get_euler_quaternions(&self) -> bool {
+		self.inner.options.representation.euler_quaternions
+	}
+
+	/// Combines the root properties and `$defs` of this schema and `other` (both class or object schemas) into an object schema,
+	/// e.g. for composite prompts such as "character stats + dialogue style". The result is instantiated into a `Dictionary`.
+	///
+	/// # Input
+	/// `conflict_policy`: What to do with properties defined differently by both schemas:
+	/// `GodotSchema.MERGE_ERROR` fails, `MERGE_PREFER_LEFT` keeps this schema's, `MERGE_PREFER_RIGHT` keeps `other`'s.
+	///
+	/// # Returns
+	/// - The merged `GodotSchema` object, if successful.
+	/// - Otherwise a `String` containing the error message.
+	#[func]
+	pub fn merge_with(&self, other: Gd<GodotSchema>, conflict_policy: ConflictPolicy) -> Variant {
+		let result = self.inner
+			.merge(&other.bind().inner, conflict_policy)
+			.and_then(|schema| Self::with_backend(schema, self.backend));
+
+		match result {
+			Ok(schema) => Gd::from_object(schema).to_variant(),
+			Err(err) => format!("{err:?}").to_variant(),
+		}
+	}
+
+	/// Checks whether data valid according to `old` (e.g. save files written by a previous version of a class)
+	/// is still valid according to this schema.
+	///
+	/// # Returns
+	/// - `true`, if no breaking change was found.
+	/// - Otherwise an `Array` with one `Dictionary` per breaking change (removed properties, type changes, new required properties...),
+	///   see [`breaking_changes()`] for the keys.
+	/// - A `String` containing the error message, if the schemas could not be compared.
+	#[func]
+	pub fn is_backward_compatible_with(&self, old: Gd<GodotSchema>) -> Variant {
+		match breaking_changes(&old.bind().inner, &self.inner) {
+			Ok(changes) if changes.is_empty() => true.to_variant(),
+			Ok(changes) => {
+				let mut array = Array::<Dictionary>::new();
+
+				for dict in changes {
+					array.push(&dict);
+				}
+
+				array.to_variant()
+			}
+			Err(err) => format!("{err:?}").to_variant(),
+		}
+	}
+
+	/// Creates a schema with only the root properties in `names`, e.g. to have an LLM fill a few fields of a large class.
+	///
+	/// Instantiating a class schema leaves the other properties at their default values.
+	/// Definitions only used by the removed properties are dropped from `$defs`.
+	///
+	/// # Returns
+	/// - The new `GodotSchema` object, if successful.
+	/// - Otherwise a `String` containing the error message.
+	#[func]
+	pub fn pick_properties(&self, names: PackedStringArray) -> Variant {
+		self.projected(names, false)
+	}
+
+	/// Same as [`Self::pick_properties()`], but keeps every root property except the ones in `names`.
+	#[func]
+	pub fn omit_properties(&self, names: PackedStringArray) -> Variant {
+		self.projected(names, true)
+	}
+
+	/// Adds (or replaces) root property `name`, typed as `type_schema`'s root (e.g. from [`Self::from_shape()`]).
+	///
+	/// Like the other editing methods, only available for class and object schemas,
+	/// and only takes effect on [`Self::json`] and validation once [`Self::rebuild()`] is called.
+	///
+	/// # Returns
+	/// - `null`, if successful.
+	/// - Otherwise a `String` containing the error message.
+	#[func]
+	pub fn add_property(&mut self, name: String, type_schema: Gd<GodotSchema>) -> Variant {
+		let type_schema = type_schema.bind().inner.clone();
+		self.edit(move |schema| schema.add_property(&name, &type_schema))
+	}
+
+	#[func]
+	pub fn remove_property(&mut self, name: String) -> Variant {
+		self.edit(move |schema| schema.remove_property(&name))
+	}
+
+	#[func]
+	pub fn rename_property(&mut self, old_name: String, new_name: String) -> Variant {
+		self.edit(move |schema| schema.rename_property(&old_name, &new_name))
+	}
+
+	/// Optional properties may be absent from the input, instantiation then leaves them at their default value.
+	#[func]
+	pub fn set_property_optional(&mut self, name: String, optional: bool) -> Variant {
+		self.edit(move |schema| schema.set_property_optional(&name, optional))
+	}
+
+	/// Sets the `default` of the primitive or `Dictionary` property `name`, assigned by instantiation when the (optional) property is absent.
+	/// A `null` value removes it.
+	#[func]
+	pub fn set_property_default(&mut self, name: String, value: Variant) -> Variant {
+		self.edit(move |schema| schema.set_property_default(&name, keyword_value(&value)?))
+	}
+
+	/// Sets the `const` of the primitive or `Dictionary` property `name`, the only value instantiation accepts for it.
+	/// A `null` value removes it.
+	#[func]
+	pub fn set_property_const(&mut self, name: String, value: Variant) -> Variant {
+		self.edit(move |schema| schema.set_property_const(&name, keyword_value(&value)?))
+	}
+
+	/// Describes `variant` of the enum definition `enum_name` (an empty name targets a root enum),
+	/// the enum's variants are then emitted as `oneOf: [{"const": "Happy", "description": "..."}, ...]`.
+	#[func]
+	pub fn set_enum_variant_description(&mut self, enum_name: String, variant: String, description: String) -> Variant {
+		self.edit(move |schema| schema.set_enum_variant_description(&enum_name, &variant, &description))
+	}
+
+	/// Constrains the array property `property` (an empty name targets a root array), e.g. to cap how many elements an LLM may generate.
+	///
+	/// A negative `min_items`/`max_items` leaves that bound unset.
+	#[func]
+	pub fn set_array_constraints(&mut self, property: String, min_items: i64, max_items: i64, unique_items: bool) -> Variant {
+		let min_items = usize::try_from(min_items).ok();
+		let max_items = usize::try_from(max_items).ok();
+		self.edit(move |schema| schema.set_array_constraints(&property, min_items, max_items, unique_items))
+	}
+
+	/// Re-serializes [`Self::json`] and recompiles the validator, applying the edits made since the last rebuild.
+	///
+	/// # Returns
+	/// - `null`, if successful.
+	/// - Otherwise a `String` containing the error message.
+	#[func]
+	pub fn rebuild(&mut self) -> Variant {
+		match self.try_rebuild() {
+			Ok(()) => Variant::nil(),
+			Err(err) => format!("{err:?}").to_variant(),
+		}
+	}
+
+	/// Drops the compiled validator, keeping the schema's JSON.
+	///
+	/// The validator is recompiled on the next call that needs it.
+	#[func]
+	pub fn release_validator(&mut self) {
+		self.validator.take();
+		self.relaxed.take();
+	}
+
+	#[func]
+	pub fn is_validator_compiled(&self) -> bool {
+		self.validator.get().is_some()
+	}
+
+	/// Sets a callable invoked whenever validation or instantiation fails, from any schema, for piping failure telemetry to analytics.
+	///
+	/// The callable receives `(schema_name: String, error_summary: String, input_hash: int)`,
+	/// the input itself is not forwarded since it may contain player data.
+	///
+	/// Only failures on the main thread are reported.
+	#[func]
+	pub fn set_failure_hook(hook: Callable) {
+		set_failure_hook(Some(hook));
+	}
+
+	#[func]
+	pub fn clear_failure_hook() {
+		set_failure_hook(None);
+	}
+
+	/// Returns the schema (the same document as [`Self::json`]) as a `Dictionary` tree, so it can be inspected or tweaked
+	/// without parsing `json` with Godot's `JSON` class.
+	///
+	/// Arrays whose elements share a type are typed, e.g. `required` is an `Array[String]`.
+	///
+	/// # Returns
+	/// - The schema, if successful.
+	/// - Otherwise an empty `Dictionary`, the error is printed with `godot_error!`.
+	#[func]
+	pub fn get_schema_dict(&self) -> Dictionary {
+		let try_fn = || -> Result<Dictionary> {
+			let schema = serde_json::to_value(&self.inner)?;
+
+			raw_variant_from_json(&schema)?
+				.try_to::<Dictionary>()
+				.map_err(|err| anyhow!("Expected the schema to be a JSON object.\nGot: {err:?}"))
+		};
+
+		match try_fn() {
+			Ok(dict) => dict,
+			Err(err) => {
+				godot_error!("{err:?}");
+				Dictionary::new()
+			}
+		}
+	}
+
+	/// Returns the names of the root's properties (their Godot names), in declaration order.
+	/// Empty if the root is not a class or an object.
+	#[func]
+	pub fn get_property_names(&self) -> PackedStringArray {
+		self.inner
+			.property_names()
+			.into_iter()
+			.map(GString::from)
+			.collect()
+	}
+
+	/// Describes root property `name`, e.g. for editor tools displaying the schema's structure.
+	///
+	/// # Returns
+	/// - A `Dictionary` with the keys "kind" (e.g. "integer", "array", "class", "enum"; references are followed),
+	///   "ref" (the name of the referenced definition, empty if the property is not a reference), "optional",
+	///   "description" (empty if none) and "constraints" (a `Dictionary` of keywords such as `minimum`, `maxLength` or `enum`).
+	/// - Otherwise an empty `Dictionary`, the error is printed with `godot_error!`.
+	#[func]
+	pub fn get_property_type(&self, name: String) -> Dictionary {
+		match self.inner.property_type(&name).and_then(|property| property.to_dictionary()) {
+			Ok(dict) => dict,
+			Err(err) => {
+				godot_error!("{err:?}");
+				Dictionary::new()
+			}
+		}
+	}
+
+	/// Returns the names of the definitions (classes, enums, ...) the schema references, directly or through other definitions.
+	#[func]
+	pub fn get_dependencies(&self) -> PackedStringArray {
+		self.inner
+			.dependencies()
+			.into_iter()
+			.map(GString::from)
+			.collect()
+	}
+
+	/// Returns a stable SHA-256 hash (lowercase hex) of the schema's canonical JSON,
+	/// e.g. to invalidate schemas persisted to disk or to check that a client and a server agree on a schema.
+	///
+	/// Keys and `required` lists are sorted before hashing, so the hash does not depend on property declaration order.
+	///
+	/// # Returns
+	/// - The hash, if successful.
+	/// - Otherwise an empty `String`, the error is printed with `godot_error!`.
+	#[func]
+	pub fn get_hash(&self) -> String {
+		match self.inner.fingerprint() {
+			Ok(hash) => hash,
+			Err(err) => {
+				godot_error!("{err:?}");
+				String::default()
+			}
+		}
+	}
+
+	/// Builds an example document of this schema from the properties' defaults (or placeholders for their type,
+	/// e.g. `0`, the property's name, the first enum variant), including nested classes. Useful as a few-shot example in prompts.
+	///
+	/// The elements of arrays with `uniqueItems` are successive placeholders (e.g. `0`, `1`, `2`), so they stay unique.
+	///
+	/// # Returns
+	/// - The example, if successful.
+	/// - Otherwise an empty `String`, the error is printed with `godot_error!`.
+	#[func]
+	pub fn example_json(&self, pretty: bool) -> String {
+		let result = ExampleGenerator::placeholders()
+			.generate(&self.inner)
+			.and_then(|example| {
+				if pretty {
+					Ok(serde_json::to_string_pretty(&example)?)
+				} else {
+					Ok(serde_json::to_string(&example)?)
+				}
+			});
+
+		match result {
+			Ok(json) => json,
+			Err(err) => {
+				godot_error!("{err:?}");
+				String::default()
+			}
+		}
+	}
+
+	/// Fuzzes instantiation of this schema's type: instantiates `runs` random valid documents, then a mutated copy of each
+	/// (wrong types, missing keys, out of range numbers...), e.g. to check that a class's setters and hooks accept any valid input.
+	///
+	/// Documents follow the class's constraints, and resource paths point to existing files. Instances are freed afterward.
+	///
+	/// # Returns
+	/// - An `Array` of the inputs that broke instantiation, each a `Dictionary` with the keys "input" (JSON), "message"
+	///   and "panicked" (`false` if a valid document was rejected). Empty if every input passed.
+	/// - Otherwise a `String` containing the error message.
+	#[func]
+	pub fn self_test(&self, runs: i64) -> Variant {
+		failures_variant(fuzz_instantiate(self, runs.max(0) as usize, 1))
+	}
+
+	/// The warnings raised while generating this schema, e.g. which properties were skipped and why.
+	#[func]
+	pub fn get_generation_warnings(&self) -> PackedStringArray {
+		self.inner.warnings.iter().map(|issue| GString::from(&issue.message)).collect()
+	}
+
+	/// Same as [`Self::get_generation_warnings()`], but structured, e.g. for tooling that lists them per property.
+	///
+	/// # Returns
+	/// An `Array` with a `Dictionary` per issue, with the keys:
+	/// - "kind": "skipped_property" or "stubbed_property" (the property's type is unsupported, see [`SchemaLibrary::unsupported_property_policy`]),
+	///   "unresolved_class" (the class named by the property's type information does not exist, its hint was used instead)
+	///   or "null_fallback" (the property has no type information, it was described as `null`).
+	/// - "class" and "property": Where the issue was raised, empty if unknown (e.g. for non-class roots).
+	/// - "message": The same message as in [`Self::get_generation_warnings()`].
+	#[func]
+	pub fn get_generation_report(&self) -> Array<Dictionary> {
+		self.inner.warnings.iter().map(GenerationIssue::to_dictionary).collect()
+	}
+
+	/// Same as [`Self::instantiate()`], but takes an already parsed `Dictionary` (e.g. from Godot's `JSON` class) instead of a JSON string.
+	///
+	/// Godot's `JSON` parser reads every number as a float, whole floats are accepted for integer properties.
+	///
+	/// # Returns
+	/// - The instantiated type, if successful.
+	/// - Otherwise, a `String` containing the error message.
+	#[func]
+	pub fn instantiate_dict(&self, input: Dictionary) -> Variant {
+		let result = json_from_parsed(&input.to_variant())
+			.map_err(SchemaError::from)
+			.and_then(|value| self.instantiate_value(&value));
+
+		match result {
+			Ok(obj) => obj.to_variant(),
+			Err(err) => format!("{err}").to_variant(),
+		}
+	}
+
+	/// Same as [`Self::instantiate_dict()`], but returns a [`SchemaResult`].
+	#[func]
+	pub fn instantiate_dict_ex(&self, input: Dictionary) -> Gd<SchemaResult> {
+		let result = json_from_parsed(&input.to_variant())
+			.map_err(SchemaError::from)
+			.and_then(|value| self.instantiate_value(&value));
+
+		SchemaResult::from_result(result)
+	}
+
+	/// Validates an already parsed `Dictionary` against this schema, without instantiating it.
+	///
+	/// # Returns
+	/// - `Nil`, if `input` is valid.
+	/// - Otherwise, a `String` containing the error message.
+	#[func]
+	pub fn validate_dict(&self, input: Dictionary) -> Variant {
+		let result = json_from_parsed(&input.to_variant())
+			.map_err(SchemaError::from)
+			.and_then(|value| self.validate_value(&value));
+
+		match result {
+			Ok(()) => Variant::nil(),
+			Err(err) => format!("{err}").to_variant(),
+		}
+	}
+
+	/// Whether `input_json` is valid according to this schema. Only runs the compiled validator, nothing is instantiated,
+	/// e.g. to cheaply check incoming network messages.
+	///
+	/// Unlike the other methods, failures are not passed to the failure hook.
+	#[func]
+	pub fn is_valid(&self, input_json: String) -> bool {
+		serde_json::from_str(&input_json).is_ok_and(|value| self.validate_unreported(&value).is_ok())
+	}
+
+	/// Same as [`Self::is_valid()`], but lists every violation found.
+	///
+	/// # Returns
+	/// An `Array` of `Dictionary`s with the keys "instance_path" (JSON Pointer to the offending value), "keyword" (the violated
+	/// JSON Schema keyword, empty if the input is not JSON) and "message". Empty if `input_json` is valid.
+	#[func]
+	pub fn validate_verbose(&self, input_json: String) -> Array<Dictionary> {
+		let result = serde_json::from_str(&input_json)
+			.map_err(SchemaError::from)
+			.and_then(|value| self.validate_unreported(&value));
+
+		let issues = match result {
+			Ok(()) => Vec::new(),
+			Err(SchemaError::ValidationFailed(issues)) => issues,
+			Err(err) => vec![ValidationIssue {
+				instance_path: String::new(),
+				keyword: String::new(),
+				message: format!("{err}"),
+			}],
+		};
+
+		let mut array = Array::new();
+
+		for issue in &issues {
+			array.push(&issue.to_dictionary());
+		}
+
+		array
+	}
+
+	/// Formats the errors of `invalid_json` (a malformed or invalid model response), along with this schema,
+	/// into a prompt asking the model to correct its response.
+	///
+	/// # Returns
+	/// - The prompt, or an empty `String` if `invalid_json` is valid.
+	/// - Otherwise an empty `String`, the error is printed with `godot_error!`.
+	#[func]
+	pub fn build_repair_prompt(&self, invalid_json: String) -> String {
+		let result = serde_json::from_str(&invalid_json)
+			.map_err(SchemaError::from)
+			.and_then(|value| self.validate_unreported(&value));
+
+		let Err(error) = result
+		else { return String::new() };
+
+		match self.repair_prompt(&invalid_json, &error) {
+			Ok(prompt) => prompt,
+			Err(err) => {
+				godot_error!("{err:?}");
+				String::default()
+			}
+		}
+	}
+
+	/// Same as [`Self::instantiate()`], but first repairs common mistakes in LLM output:
+	/// markdown code fences, trailing commas, single quotes, unquoted keys, comments and truncated documents.
+	///
+	/// See [`repair_json()`] for details.
+	///
+	/// # Returns
+	/// - A `Dictionary` with the keys "instance" (the instantiated type) and "repairs" (a `PackedStringArray` describing each fix), if successful.
+	/// - Otherwise, a `String` containing the error message.
+	#[func]
+	pub fn instantiate_lenient(&self, input: String) -> Variant {
+		let repair = repair_json(&input);
+
+		let result = serde_json::from_str(&repair.json)
+			.map_err(SchemaError::from)
+			.and_then(|value| self.instantiate_value(&value));
+
+		match result {
+			Ok(instance) => {
+				let mut dict = Dictionary::new();
+				dict.set("instance", instance);
+				dict.set("repairs", repair.fixes.iter().map(GString::from).collect::<PackedStringArray>());
+				dict.to_variant()
+			}
+			Err(err) if repair.fixes.is_empty() => format!("{err}").to_variant(),
+			Err(err) => format!("{err}\nRepairs attempted: {}", repair.fixes.join(" ")).to_variant(),
+		}
+	}
+
+	/// Generates a copy of this schema where every root property `<name>` is paired with a `<name><suffix>` number,
+	/// for agent frameworks that report their confidence in each value.
+	///
+	/// Instantiating the returned schema ignores the confidences, use [`Self::instantiate_with_confidence()`] to retrieve them.
+	///
+	/// # Input
+	/// `suffix`: Appended to each property name, if empty, defaults to "_confidence".
+	///
+	/// # Returns
+	/// - The `GodotSchema` object containing the paired schema, if successful.
+	/// - Otherwise a `String` containing the error message.
+	#[func]
+	pub fn with_confidence(&self, suffix: String) -> Variant {
+		let suffix = if suffix.is_empty() { DEFAULT_CONFIDENCE_SUFFIX.to_owned() } else { suffix };
+
+		let result = self.inner
+			.with_confidence(suffix)
+			.and_then(|schema| Self::with_backend(schema, self.backend));
+
+		match result {
+			Ok(schema) => Gd::from_object(schema).to_variant(),
+			Err(err) => format!("{err:?}").to_variant(),
+		}
+	}
+
+	/// Same as [`Self::instantiate()`], but routes the confidences of a schema generated with [`Self::with_confidence()`]
+	/// into a separate `Dictionary`.
+	///
+	/// # Returns
+	/// - A `Dictionary` with the keys "instance" (the instantiated type) and "confidence" (property name => confidence), if successful.
+	/// - Otherwise, a `String` containing the error message.
+	#[func]
+	pub fn instantiate_with_confidence(&self, input_json: String) -> Variant {
+		let result = serde_json::from_str(&input_json)
+			.map_err(SchemaError::from)
+			.and_then(|value| self.instantiate_value_with_confidence(&value));
+
+		match result {
+			Ok((instance, confidences)) => {
+				let mut dict = Dictionary::new();
+				dict.set("instance", instance);
+				dict.set("confidence", confidences);
+				dict.to_variant()
+			}
+			Err(err) => format!("{err}").to_variant(),
+		}
+	}
+
+	/// Instantiates the type defined by this schema from JSON input containing the values of the type.
+	///
+	/// Notes:
+	/// - The JSON input must be valid according to the schema.
+	/// - The JSON input must contain all fields defined in the schema (i.e. the schema's "required" array has all of your type's properties).
+	/// - The JSON input must not contain any additional properties (i.e. the schema's "additionalProperties" key is set to false).
+	///
+	/// # Returns
+	/// - The instantiated type, if successful.
+	/// - Otherwise, a `String` containing the error message.
+	#[func]
+	pub fn instantiate(&self, input_json: String) -> Variant {
+		let result = serde_json::from_str(&input_json)
+			.map_err(SchemaError::from)
+			.and_then(|value| self.instantiate_value(&value));
+
+		match result {
+			Ok(obj) => obj.to_variant(),
+			Err(err) => format!("{err}").to_variant(),
+		}
+	}
+
+	/// Same as [`Self::instantiate()`], but returns a [`SchemaResult`].
+	#[func]
+	pub fn instantiate_ex(&self, input_json: String) -> Gd<SchemaResult> {
+		let result = serde_json::from_str(&input_json)
+			.map_err(SchemaError::from)
+			.and_then(|value| self.instantiate_value(&value));
+
+		SchemaResult::from_result(result)
+	}
+
+	/// Same as [`Self::instantiate()`], but failures return a prompt asking the model to correct `input_json`,
+	/// see [`Self::build_repair_prompt()`].
+	///
+	/// # Returns
+	/// A `Dictionary` with the keys:
+	/// - "instance": The instantiated type, `null` if the instantiation failed.
+	/// - "repair_prompt": The prompt to send back to the model, empty if the instantiation succeeded.
+	/// - "error": The error message, empty if the instantiation succeeded.
+	#[func]
+	pub fn instantiate_or_repair(&self, input_json: String) -> Dictionary {
+		let result = serde_json::from_str(&input_json)
+			.map_err(SchemaError::from)
+			.and_then(|value| self.instantiate_value(&value));
+
+		let mut dict = Dictionary::new();
+
+		match result {
+			Ok(instance) => {
+				dict.set("instance", instance);
+				dict.set("repair_prompt", GString::new());
+				dict.set("error", GString::new());
+			}
+			Err(err) => {
+				let prompt = self.repair_prompt(&input_json, &err).unwrap_or_else(|prompt_err| {
+					godot_error!("{prompt_err:?}");
+					String::new()
+				});
+
+				dict.set("instance", Variant::nil());
+				dict.set("repair_prompt", prompt);
+				dict.set("error", err.to_string());
+			}
+		}
+
+		dict
+	}
+
+	/// Same as [`Self::instantiate()`], with settings that only apply to this call.
+	///
+	/// # Input
+	/// `options`: A `Dictionary` with any of the following keys:
+	/// - "strict_numbers" (`bool`, default false): Rejects JSON integers (e.g. `1`) for float properties, requiring `1.0`.
+	///   Floats are always rejected for int properties. Errors contain the path of the offending value.
+	/// - "setter_check" (`int`, default `SETTERS_UNCHECKED`): After instantiating a class, reads each property back
+	///   and compares it to the assigned value, catching setters that clamp or reject values.
+	///   `SETTERS_WARN` prints the changed properties as a warning, `SETTERS_ERROR` fails the instantiation.
+	///   Classes using a `_from_json` hook are not checked.
+	///
+	/// # Returns
+	/// - The instantiated type, if successful.
+	/// - Otherwise, a `String` containing the error message.
+	#[func]
+	pub fn instantiate_with_options(&self, input_json: String, options: Dictionary) -> Variant {
+		let result = InstantiateOptions::from_dictionary(&options)
+			.map_err(SchemaError::from)
+			.and_then(|options| {
+				let value = serde_json::from_str(&input_json)?;
+				self.instantiate_value_with(&value, options)
+			});
+
+		match result {
+			Ok(obj) => obj,
+			Err(err) => format!("{err}").to_variant(),
+		}
+	}
+
+	/// Instantiates every element of a JSON array of this schema's type, reporting errors per element instead of failing the whole batch.
+	///
+	/// The input may be a plain JSON array, or the output of a schema generated with [`Self::get_array_schema()`]
+	/// (an object with a single array property, e.g. "value").
+	///
+	/// # Returns
+	/// - A `Dictionary` with the keys "instances" (an `Array` of the elements instantiated successfully, in input order)
+	///   and "errors" (element index => error message), if the input is a JSON array.
+	/// - Otherwise, a `String` containing the error message.
+	#[func]
+	pub fn instantiate_array(&self, input_json: String) -> Variant {
+		let result = serde_json::from_str(&input_json)
+			.map_err(anyhow::Error::from)
+			.and_then(|value| match value {
+				Value::Array(elements) => Ok(elements),
+				Value::Object(properties) if properties.len() == 1 => match properties.into_iter().next() {
+					Some((_, Value::Array(elements))) => Ok(elements),
+					_ => bail!("Expected JSON value to be an array, or an object with a single array property."),
+				},
+				other => bail!("Expected JSON value to be an array.\nGot: {other:?}"),
+			});
+
+		match result {
+			Ok(elements) => {
+				let (instances, errors) = self.instantiate_elements(&elements);
+
+				let mut dict = Dictionary::new();
+				dict.set("instances", instances);
+				dict.set("errors", errors);
+				dict.to_variant()
+			}
+			Err(err) => format!("{err}").to_variant(),
+		}
+	}
+
+	/// Same as [`Self::instantiate()`], but only converts the input into an [`InstantiationPlan`], without constructing any object.
+	///
+	/// The plan can be inspected (e.g. shown to the player for approval), then performed with [`Self::apply()`].
+	///
+	/// # Returns
+	/// - The `InstantiationPlan`, if successful.
+	/// - Otherwise, a `String` containing the error message.
+	#[func]
+	pub fn plan(&self, input_json: String) -> Variant {
+		let result = serde_json::from_str(&input_json)
+			.map_err(SchemaError::from)
+			.and_then(|value| self.plan_value(&value));
+
+		match result {
+			Ok(root) => Gd::from_object(InstantiationPlan { root }).to_variant(),
+			Err(err) => format!("{err}").to_variant(),
+		}
+	}
+
+	/// Constructs the objects described by `plan`, see [`Self::plan()`].
+	///
+	/// # Returns
+	/// - The instantiated type, if successful.
+	/// - Otherwise, a `String` containing the error message.
+	#[func]
+	pub fn apply(&self, plan: Gd<InstantiationPlan>) -> Variant {
+		match plan.bind().root.apply() {
+			Ok(instance) => instance,
+			Err(err) => format!("{err}").to_variant(),
+		}
+	}
+
+	/// Instantiates `input_json`, then saves the resulting resource to `save_path` (e.g. "res://items/sword.tres") with `ResourceSaver`.
+	///
+	/// Only available for schemas of classes extending `Resource`. The saved resource's path is set to `save_path`.
+	///
+	/// # Returns
+	/// - The saved `Resource`, if successful.
+	/// - Otherwise, a `String` containing the error message.
+	#[func]
+	pub fn instantiate_resource(&self, input_json: String, save_path: String) -> Variant {
+		let result = serde_json::from_str(&input_json)
+			.map_err(SchemaError::from)
+			.and_then(|value| self.instantiate_resource_value(&value, &save_path));
+
+		match result {
+			Ok(resource) => resource.to_variant(),
+			Err(err) => format!("{err}").to_variant(),
+		}
+	}
+
+	/// Validates `input_json`, then assigns its properties to the existing object `target` instead of creating a new instance.
+	///
+	/// Only available for class schemas.
+	///
+	/// # Input
+	/// - `record_undo`: Whether to record the previous values of the assigned properties, see [`UndoRecord`].
+	///
+	/// # Returns
+	/// - The `UndoRecord` (or `Nil` if `record_undo` is false), if successful.
+	/// - Otherwise, a `String` containing the error message.
+	#[func]
+	pub fn apply_to(&self, mut target: Gd<Object>, input_json: String, record_undo: bool) -> Variant {
+		let result = serde_json::from_str(&input_json)
+			.map_err(SchemaError::from)
+			.and_then(|value| {
+				if record_undo {
+					self.apply_value_recorded(&mut target, &value).map(|record| Gd::from_object(record).to_variant())
+				} else {
+					self.apply_value(&mut target, &value).map(|()| Variant::nil())
+				}
+			});
+
+		match result {
+			Ok(variant) => variant,
+			Err(err) => format!("{err}").to_variant(),
+		}
+	}
+
+	/// Applies the JSON Patch (RFC 6902) `patch_json` to the JSON of the existing object `target`,
+	/// e.g. `[{"op": "replace", "path": "/hp", "value": 10}, {"op": "add", "path": "/tags/-", "value": "brave"}]`.
+	///
+	/// The patched document is validated against the schema, then only the root properties touched by the patch are assigned to `target`.
+	/// Optional properties it removes are reset to their default value. Nothing is assigned if any operation fails.
+	/// Only available for class schemas.
+	///
+	/// # Returns
+	/// - `Nil`, if successful.
+	/// - Otherwise, a `String` containing the error message.
+	#[func]
+	pub fn apply_patch(&self, mut target: Gd<Object>, patch_json: String) -> Variant {
+		let result = serde_json::from_str(&patch_json)
+			.map_err(SchemaError::from)
+			.and_then(|patch| self.apply_patch_value(&mut target, &patch));
+
+		match result {
+			Ok(()) => Variant::nil(),
+			Err(err) => format!("{err}").to_variant(),
+		}
+	}
+
+	/// Applies the JSON Merge Patch (RFC 7386) `patch_json` to the existing object `target`: a partial object whose `null` values
+	/// reset the matching properties to their default values, e.g. `{"hp": 10, "stats": {"luck": 3}, "title": null}`.
+	/// Defaults are the initial values the script (or engine class) declares, `target`'s class is not instantiated.
+	///
+	/// The patched document is validated against a relaxed version of the schema where every property is optional,
+	/// then only the root properties present in the patch are assigned. Only available for class schemas.
+	///
+	/// # Returns
+	/// - `Nil`, if successful.
+	/// - Otherwise, a `String` containing the error message.
+	#[func]
+	pub fn merge_patch(&self, mut target: Gd<Object>, patch_json: String) -> Variant {
+		let result = serde_json::from_str(&patch_json)
+			.map_err(SchemaError::from)
+			.and_then(|patch| self.merge_patch_value(&mut target, &patch));
+
+		match result {
+			Ok(()) => Variant::nil(),
+			Err(err) => format!("{err}").to_variant(),
+		}
+	}
+
+	/// The reverse of [`Self::instantiate()`]: converts `instance` (a value of this schema's type) into JSON.
+	///
+	/// # Returns
+	/// - The JSON, if successful.
+	/// - Otherwise an empty `String`, the error is printed with `godot_error!`.
+	#[func]
+	pub fn serialize_instance(&self, instance: Variant) -> String {
+		let result = self.inner
+			.json_from_instance(&instance)
+			.and_then(|value| output_json(&value));
+
+		match result {
+			Ok(json) => json,
+			Err(err) => {
+				godot_error!("{err}");
+				String::default()
+			}
+		}
+	}
+
+	/// Compares two values of this schema's type, each either an instance or a JSON payload (`String`), e.g. to log the changes of an LLM edit.
+	///
+	/// # Returns
+	/// - A `Dictionary` mapping the JSON Pointer of each changed value (e.g. `/stats/hp`, `/tags/2`) to a `Dictionary` with the keys
+	///   "change" (either "added", "removed" or "changed"), "old" and "new" (as parsed JSON, `null` if missing).
+	///   Empty if both values are equal.
+	/// - Otherwise an empty `Dictionary`, the error is printed with `godot_error!`.
+	#[func]
+	pub fn diff(&self, a: Variant, b: Variant) -> Dictionary {
+		let try_fn = || -> Result<Dictionary> {
+			let mut diff = Dictionary::new();
+
+			for change in json_diff(&self.json_of(&a)?, &self.json_of(&b)?) {
+				let to_variant = |value: &Option<Value>| value.as_ref().map_or(Ok(Variant::nil()), raw_variant_from_json);
+
+				let mut dict = Dictionary::new();
+				dict.set("change", change.kind());
+				dict.set("old", to_variant(&change.old)?);
+				dict.set("new", to_variant(&change.new)?);
+				diff.set(change.path, dict);
+			}
+
+			Ok(diff)
+		};
+
+		match try_fn() {
+			Ok(diff) => diff,
+			Err(err) => {
+				godot_error!("{err:?}");
+				Dictionary::new()
+			}
+		}
+	}
+
+	/// Resolves the JSON Pointer (RFC 6901) `pointer` (e.g. `/stats/hp` or `/facts/0/text`) in `instance_or_json`,
+	/// either a value of this schema's type or a JSON payload (`String`).
+	///
+	/// Instances are walked through their properties, keys and indices, returning the live value (nested objects are not copied).
+	/// JSON payloads are converted into the type the schema describes at `pointer`, or kept as parsed JSON if it describes none.
+	/// Non-class schemas wrap their root in a `"value"` property, pointers into them start with `/value`.
+	///
+	/// # Returns
+	/// - The value, if found.
+	/// - Otherwise a `String` containing the error message.
+	#[func]
+	pub fn get_at_pointer(&self, instance_or_json: Variant, pointer: String) -> Variant {
+		match self.value_at_pointer(&instance_or_json, &pointer) {
+			Ok(value) => value,
+			Err(err) => format!("{err:?}").to_variant(),
+		}
+	}
+
+	/// Returns the JSON schema response format for this schema in OpenAI format.
+	/// 	
+	/// This is useful for calling structured outputs with an LLM using a type-specific schema.
+	/// Strict mode is enabled if every property is required, see [`Self::open_ai_response_format_value()`].
+	/// 
+	/// # Input
+	/// `name`: The root name of the schema, must be a valid identifier. (Cannot contain spaces)
+	#[func]
+	pub fn open_ai_response_format(&self, name: String) -> Variant {
+		let result = self
+			.open_ai_response_format_value(&name)
+			.and_then(|format| output_json(&format));
+
+		match result {
+			Ok(json) => json.to_variant(),
+			Err(err) => {
+				godot_error!("{err}");
+				String::default().to_variant()
+			}
+		}
+	}
+
+	/// Returns the JSON of this schema, like [`Self::json`], with control over its size.
+	///
+	/// # Input
+	/// - `pretty`: Whether to indent the JSON, compact JSON is smaller.
+	/// - `include_descriptions`: Whether to keep the `description` of the schema, its properties and definitions.
+	/// - `include_vendor_keys`: Whether to keep the `x-godot-*` keys, which models do not need,
+	///   but [`Self::from_json_schema()`] does to load the schema back into the same classes and enums.
+	///
+	/// # Returns
+	/// - The JSON, if successful.
+	/// - Otherwise an empty `String`, the error is printed with `godot_error!`.
+	#[func]
+	pub fn to_json_ex(&self, pretty: bool, include_descriptions: bool, include_vendor_keys: bool) -> String {
+		match self.inner.to_json_ex(pretty, include_descriptions, include_vendor_keys) {
+			Ok(json) => json,
+			Err(err) => {
+				godot_error!("{err}");
+				String::default()
+			}
+		}
+	}
+
+	/// Returns a compact, TypeScript-like rendering of this schema, for embedding in the prompts of providers
+	/// that do not accept schemas. Small definitions are inlined and long enums are elided.
+	///
+	/// # Input
+	/// - `max_chars`: The length the text should fit in, or `0` for no limit. Longer renderings drop descriptions,
+	///   then list fewer enum variants, then are truncated.
+	/// - `include_descriptions`: Whether to write descriptions as `//` comments.
+	///
+	/// # Returns
+	/// - The text, if successful.
+	/// - Otherwise an empty `String`, the error is printed with `godot_error!`.
+	#[func]
+	pub fn to_prompt_text(&self, max_chars: i64, include_descriptions: bool) -> String {
+		let max_chars = (max_chars > 0).then_some(max_chars as usize);
+
+		match self.inner.to_prompt_text(max_chars, include_descriptions) {
+			Ok(text) => text,
+			Err(err) => {
+				godot_error!("{err}");
+				String::default()
+			}
+		}
+	}
+
+	/// Returns this schema converted into a llama.cpp GBNF grammar.
+	///
+	/// This is useful for constraining the output of local models when the inference server does not accept JSON schemas.
+	///
+	/// # Returns
+	/// - The grammar, if successful.
+	/// - Otherwise an empty `String`, the error is printed with `godot_error!`.
+	#[func]
+	pub fn to_gbnf(&self) -> String {
+		match self.inner.to_gbnf() {
+			Ok(grammar) => grammar,
+			Err(err) => {
+				godot_error!("{err}");
+				String::default()
+			}
+		}
+	}
+
+	/// Returns the JSON schema payload accepted by the `format` field of [Ollama](https://ollama.com)'s chat/generate endpoints.
+	///
+	/// Ollama converts the schema into a grammar, keywords it does not understand (such as `$schema`) are stripped.
+	#[func]
+	pub fn ollama_format(&self) -> String {
+		let result = self.inner
+			.to_provider_value()
+			.map_err(anyhow::Error::from)
+			.and_then(|value| output_json(&value));
+
+		match result {
+			Ok(json) => json,
+			Err(err) => {
+				godot_error!("{err}");
+				String::default()
+			}
+		}
+	}
+}
+
+impl GodotSchema {
+	pub fn new(schema: RootSchema) -> Result<Self> {
+		Self::with_backend(schema, ValidatorBackend::default())
+	}
+
+	pub fn try_from_class_name(class_name: StringName) -> std::result::Result<Self, SchemaError> {
+		let schema = ClassSource::from_class_name(class_name)
+			.and_then(RootSchema::from_class)
+			.and_then(Self::new)?;
+
+		Ok(schema)
+	}
+
+	pub fn try_from_class_script(script: Gd<Script>) -> std::result::Result<Self, SchemaError> {
+		let source = ClassSource::from_script(script);
+		Ok(RootSchema::from_class(source).and_then(Self::new)?)
+	}
+
+	/// See [`Self::from_json_schema()`].
+	///
+	/// Unlike generated schemas, the schema's JSON is `json` itself, so constraints without an equivalent definition
+	/// (`enum`, `pattern`, unions, ...) are still validated.
+	pub fn try_from_json_schema(json: &str) -> Result<Self> {
+		let document = serde_json::from_str(json)?;
+		let schema = RootSchema::from_json_schema(&document)?;
+		let backend = ValidatorBackend::default();
+		let validator = backend.build(&schema, &document)?;
+
+		Ok(Self {
+			inner: schema,
+			json: json.into(),
+			validator: OnceLock::from(validator),
+			relaxed: OnceLock::new(),
+			edits: Vec::new(),
+			backend,
+		})
+	}
+
+	/// See [`Self::get_array_schema()`].
+	pub fn array_schema(&self, item_name: String) -> Result<Self> {
+		self.collection_schema(item_name, |self_def| JArray::new(self_def).into())
+	}
+
+	/// See [`Self::get_dictionary_schema()`].
+	pub fn dictionary_schema(&self, value_name: String) -> Result<Self> {
+		self.collection_schema(value_name, |self_def| JObject::map_of(self_def).into())
+	}
+
+	/// A schema of `collection`, a container of this schema's type, which is moved to `$defs` as `name`.
+	fn collection_schema(&self, name: String, collection: impl FnOnce(JRef) -> Definition) -> Result<Self> {
+		let mut defs = self.inner.defs.clone();
+		let self_def = self.inner.base.clone().into_reference(name, &mut defs);
+
+		let schema = RootSchema {
+			defs,
+			base: collection(self_def),
+			// The collection is another document, it cannot share this schema's `$id`.
+			options: SchemaOptions { id: None, ..self.inner.options.clone() },
+			warnings: self.inner.warnings.clone(),
+			name_map: None,
+		};
+
+		Self::with_backend(schema, self.backend)
+	}
+
+	pub fn with_backend(mut schema: RootSchema, backend: ValidatorBackend) -> Result<Self> {
+		schema.rebuild_name_map()?;
+		let json = schema.to_json_pretty()?;
+		let json_value = serde_json::from_str(&json)?;
+		let validator = backend.build(&schema, &json_value)?;
+
+		Ok(Self {
+			inner: schema,
+			json: json.into(),
+			validator: OnceLock::from(validator),
+			relaxed: OnceLock::new(),
+			edits: Vec::new(),
+			backend,
+		})
+	}
+
+	/// Same as [`Self::with_backend()`], but the validator is only compiled once it is needed.
+	pub fn with_lazy_validator(mut schema: RootSchema, backend: ValidatorBackend) -> Result<Self> {
+		schema.rebuild_name_map()?;
+
+		Ok(Self {
+			json: schema.to_json_pretty()?.into(),
+			inner: schema,
+			validator: OnceLock::new(),
+			relaxed: OnceLock::new(),
+			edits: Vec::new(),
+			backend,
+		})
+	}
+
+	/// Same as [`Self::with_backend()`], but reuses a validator compiled by [`compile_schema()`] (usually on another thread).
+	///
+	/// `json` must be the JSON `compiled` was produced from.
+	pub fn from_compiled(mut schema: RootSchema, json: String, compiled: CompiledSchema, backend: ValidatorBackend) -> Result<Self> {
+		schema.rebuild_name_map()?;
+
+		let validator = match compiled.validator {
+			Some(validator) => validator,
+			None => backend.build(&schema, &compiled.json_value)?,
+		};
+
+		Ok(Self {
+			inner: schema,
+			json: json.into(),
+			validator: OnceLock::from(validator),
+			relaxed: OnceLock::new(),
+			edits: Vec::new(),
+			backend,
+		})
+	}
+
+	/// Validates `value` against this schema, then instantiates the schema's type from it.
+	pub fn instantiate_value(&self, value: &Value) -> std::result::Result<Variant, SchemaError> {
+		self.instantiate_value_with_confidence(value).map(|(instance, _)| instance)
+	}
+
+	/// Same as [`Self::instantiate_value()`], with settings that only apply to this call.
+	pub fn instantiate_value_with(&self, value: &Value, options: InstantiateOptions) -> std::result::Result<Variant, SchemaError> {
+		options.scope(|| self.instantiate_value(value))
+	}
+
+	/// Same as [`Self::instantiate_value()`], but also returns the confidences found in `value`,
+	/// if this schema was generated with [`RootSchema::with_confidence()`].
+	pub fn instantiate_value_with_confidence(&self, value: &Value) -> std::result::Result<(Variant, Dictionary), SchemaError> {
+		self.reported(value, || self.instantiate_unreported(value))
+	}
+
+	fn instantiate_unreported(&self, value: &Value) -> std::result::Result<(Variant, Dictionary), SchemaError> {
+		self.validate_unreported(value)?;
+
+		let (value, confidences) = self.split_confidence(value);
+
+		let instance = self
+			.instantiate_input(value.as_ref())
+			.with_code(ErrorCode::InstantiationFailed)?;
+
+		Ok((instance, confidences))
+	}
+
+	/// Validates `value` against this schema, then converts it into a plan for instantiating the schema's type.
+	pub fn plan_value(&self, value: &Value) -> std::result::Result<PlanNode, SchemaError> {
+		self.reported(value, || {
+			self.validate_unreported(value)?;
+
+			let (value, _) = self.split_confidence(value);
+
+			let plan = self.inner
+				.to_godot_names(value.as_ref())
+				.and_then(|value| self.inner.base.plan(self.unwrap_input(value.as_ref()), &self.inner.defs))
+				.with_code(ErrorCode::InstantiationFailed)?;
+
+			Ok(plan)
+		})
+	}
+
+	fn split_confidence<'a>(&self, value: &'a Value) -> (Cow<'a, Value>, Dictionary) {
+		match &self.inner.options.confidence_suffix {
+			Some(suffix) => split_confidence(value, suffix),
+			None => (Cow::Borrowed(value), Dictionary::new()),
+		}
+	}
+
+	/// Instantiates an already validated document, translating its property names back first, see [`RootSchema::to_godot_names()`].
+	pub(crate) fn instantiate_input(&self, value: &Value) -> Result<Variant> {
+		let value = self.inner.to_godot_names(value)?;
+		self.inner.instantiate(self.unwrap_input(value.as_ref()))
+	}
+
+	/// If we are a wrapper for a non-class type, the actual input is in the wrapping property, see [`RootSchema::unwrap_document()`].
+	pub(crate) fn unwrap_input<'a>(&self, value: &'a Value) -> &'a Value {
+		self.inner.unwrap_document(value)
+	}
+
+	/// Instantiates each of `elements` independently.
+	///
+	/// # Returns
+	/// The instances that succeeded, and a `Dictionary` of element index => error message for the ones that failed.
+	pub fn instantiate_elements(&self, elements: &[Value]) -> (VariantArray, Dictionary) {
+		let mut instances = VariantArray::new();
+		let mut errors = Dictionary::new();
+
+		for (idx, element) in elements.iter().enumerate() {
+			let result = self.instantiate_value(&self.inner.wrap_value(element.clone()));
+
+			match result {
+				Ok(instance) => instances.push(&instance),
+				Err(err) => { errors.set(idx as i64, format!("{err}")); }
+			}
+		}
+
+		(instances, errors)
+	}
+
+	/// Validates `value` against this schema, then assigns its properties to `target` instead of creating a new instance.
+	///
+	/// Only available for class schemas.
+	pub fn apply_value(&self, target: &mut Gd<Object>, value: &Value) -> std::result::Result<(), SchemaError> {
+		self.reported(value, || self.apply_unreported(target, value, None))
+	}
+
+	/// See [`Self::get_at_pointer()`].
+	pub fn value_at_pointer(&self, instance_or_json: &Variant, pointer: &str) -> Result<Variant> {
+		let tokens = parse_pointer(pointer)?;
+
+		if instance_or_json.get_type() == VariantType::STRING {
+			let json = serde_json::from_str(&instance_or_json.to_string())?;
+			let value = pointee(&json, &tokens)?;
+
+			return match self.inner.definition_at(&tokens) {
+				Some(def) => def.instantiate(value, &self.inner.defs),
+				None => raw_variant_from_json(value),
+			};
+		}
+
+		let tokens = match self.inner.wrapper_key() {
+			None => tokens.as_slice(),
+			Some(key) => match tokens.split_first() {
+				Some((first, rest)) if first == key => rest,
+				Some(_) => bail!("Pointers into this schema must start with \"/{key}\"."),
+				None => return Ok(instance_or_json.clone()),
+			},
+		};
+
+		let mut current = instance_or_json.clone();
+
+		for (idx, token) in tokens.iter().enumerate() {
+			current = match variant_child(&current, token) {
+				Some(child) => child?,
+				// Built-in types (e.g. `Vector3`) are indexed through their JSON.
+				None => return raw_variant_from_json(pointee(&raw_json_from_variant(&current)?, &tokens[idx..])?),
+			};
+		}
+
+		Ok(current)
+	}
+
+	/// The JSON of `value`: parsed if it is a `String`, otherwise converted as an instance of this schema's type.
+	fn json_of(&self, value: &Variant) -> Result<Value> {
+		if value.get_type() == VariantType::STRING {
+			Ok(serde_json::from_str(&value.to_string())?)
+		} else {
+			self.inner.json_from_instance(value)
+		}
+	}
+
+	/// See [`Self::apply_patch()`].
+	pub fn apply_patch_value(&self, target: &mut Gd<Object>, patch: &Value) -> std::result::Result<(), SchemaError> {
+		let Definition::Class(class) = &self.inner.base
+		else { return Err(anyhow!("Applying patches to existing objects requires a class schema.").into()) };
+
+		let mut document = self.inner.json_from_instance(&target.to_variant())?;
+		let touched = apply_json_patch(&mut document, patch)?;
+
+		self.reported(&document, || {
+			self.validate_unreported(&document)?;
+
+			let touched_properties: Value = document
+				.as_object()
+				.into_iter()
+				.flatten()
+				.filter(|(name, _)| touched.contains(*name))
+				.map(|(name, value)| (name.clone(), value.clone()))
+				.collect::<Map<_, _>>()
+				.into();
+
+			// Patches name properties as in JSON, the class by their Godot names.
+			let touched_properties = self.inner.to_godot_names(&touched_properties)?;
+			let touched_properties = touched_properties.as_object().cloned().unwrap_or_default();
+
+			class.set_properties(target, &self.inner.defs, &touched_properties)?;
+
+			// Optional properties the patch removed are no longer in the document, they get their default back.
+			let removed: Value = touched
+				.iter()
+				.filter(|name| document.get(name.as_str()).is_none())
+				.map(|name| (name.clone(), Value::Null))
+				.collect::<Map<_, _>>()
+				.into();
+
+			let removed = self.inner.to_godot_names(&removed)?;
+			reset_properties(target, class, removed.as_object().into_iter().flat_map(Map::keys));
+			Ok(())
+		})
+	}
+
+	/// See [`Self::merge_patch()`].
+	pub fn merge_patch_value(&self, target: &mut Gd<Object>, patch: &Value) -> std::result::Result<(), SchemaError> {
+		let Definition::Class(class) = &self.inner.base
+		else { return Err(anyhow!("Applying patches to existing objects requires a class schema.").into()) };
+
+		if !patch.is_object() {
+			return Err(SchemaError::ConversionFailed {
+				path: String::new(),
+				expected: "object".to_owned(),
+				got: patch.to_string(),
+			});
+		}
+
+		// Merge documents name properties as in JSON, the class by their Godot names.
+		let godot_patch = self.inner.to_godot_names(patch)?;
+		let Value::Object(patch_properties) = godot_patch.as_ref()
+		else { return Err(anyhow!("Expected the translated patch to be an object.").into()) };
+
+		if let Some(unknown) = patch_properties.keys().find(|name| !class.properties.contains_key(*name)) {
+			return Err(anyhow!("Expected property \"{unknown}\" to be in `properties` map.").into());
+		}
+
+		let relaxed = self.relaxed()?;
+
+		let mut document = self.inner.json_from_instance(&target.to_variant())?;
+		apply_merge_patch(&mut document, patch);
+
+		self.reported(&document, || {
+			relaxed.validate_unreported(&document)?;
+
+			let Definition::Class(relaxed_class) = &relaxed.inner.base
+			else { return Err(anyhow!("Expected relaxed schema to be a class schema.").into()) };
+
+			let document = self.inner.to_godot_names(&document)?;
+
+			let assigned = document
+				.as_object()
+				.into_iter()
+				.flatten()
+				.filter(|(name, _)| patch_properties.contains_key(*name))
+				.map(|(name, value)| (name.clone(), value.clone()))
+				.collect();
+
+			relaxed_class.set_properties(target, &relaxed.inner.defs, &assigned)?;
+
+			let reset = patch_properties.iter().filter(|(_, value)| value.is_null()).map(|(name, _)| name);
+			reset_properties(target, class, reset);
+			Ok(())
+		})
+	}
+
+	/// See [`Self::rebuild()`].
+	pub fn try_rebuild(&mut self) -> Result<()> {
+		self.inner.rebuild_name_map()?;
+
+		let json = self.inner.to_json_pretty()?;
+		let json_value = serde_json::from_str(&json)?;
+		let validator = self.backend.build(&self.inner, &json_value)?;
+
+		self.json = json.into();
+		self.validator = OnceLock::from(validator);
+		self.relaxed.take();
+		Ok(())
+	}
+
+	/// Replaces this schema's definitions with `regenerated` (its class, generated again after the script changed),
+	/// keeping the options set on this schema (but the generation settings of `regenerated`) and replaying its edits, then rebuilds it.
+	///
+	/// This schema is left unchanged if an edit no longer applies or the rebuild fails.
+	pub fn try_reload(&mut self, mut regenerated: RootSchema) -> Result<()> {
+		regenerated.options = self.inner.options.clone().with_generation_settings(&regenerated.options);
+
+		for edit in &self.edits {
+			edit(&mut regenerated)?;
+		}
+
+		let previous = std::mem::replace(&mut self.inner, regenerated);
+
+		if let Err(err) = self.try_rebuild() {
+			self.inner = previous;
+			return Err(err);
+		}
+
+		Ok(())
+	}
+
+	/// Recompiles the validator from [`Self::json`], e.g. after changing [`SchemaOptions::validator`], which does not affect the JSON.
+	fn rebuild_validator(&mut self) -> Result<()> {
+		let json_value = serde_json::from_str(&self.json.to_string())?;
+		self.validator = OnceLock::from(self.backend.build(&self.inner, &json_value)?);
+		self.relaxed.take();
+		Ok(())
+	}
+
+	fn set_naming(&mut self, naming: PropertyNaming) -> Variant {
+		let previous = std::mem::replace(&mut self.inner.options.property_naming, naming);
+
+		match self.try_rebuild() {
+			Ok(()) => Variant::nil(),
+			Err(err) => {
+				self.inner.options.property_naming = previous;
+				format!("{err:?}").to_variant()
+			}
+		}
+	}
+
+	fn projected(&self, names: PackedStringArray, omit: bool) -> Variant {
+		let names = names.as_slice().iter().map(GString::to_string).collect_vec();
+
+		let result = self.inner
+			.project_properties(&names, omit)
+			.and_then(|schema| Self::with_backend(schema, self.backend));
+
+		match result {
+			Ok(schema) => Gd::from_object(schema).to_variant(),
+			Err(err) => format!("{err:?}").to_variant(),
+		}
+	}
+
+	/// Applies `f` to [`Self::inner`], recording it in [`Self::edits`] if it succeeds.
+	fn edit(&mut self, f: impl Fn(&mut RootSchema) -> Result<()> + 'static) -> Variant {
+		match f(&mut self.inner) {
+			Ok(()) => {
+				self.edits.push(Rc::new(f));
+				Variant::nil()
+			}
+			Err(err) => format!("{err:?}").to_variant(),
+		}
+	}
+
+	/// See [`Self::instantiate_resource()`].
+	pub fn instantiate_resource_value(&self, value: &Value, save_path: &str) -> std::result::Result<Gd<Resource>, SchemaError> {
+		let is_resource = matches!(&self.inner.base, Definition::Class(class) if class.source.inherits_engine_class("Resource"));
+
+		if !is_resource {
+			return Err(anyhow!("Saving instances requires the schema of a class extending `Resource`.\nGot: {}", self.schema_name()).into());
+		}
+
+		let resource = self
+			.instantiate_value(value)?
+			.try_to::<Gd<Resource>>()
+			.map_err(|err| anyhow!("{err:?}"))?;
+
+		let error = ResourceSaver::singleton()
+			.save_ex(&resource)
+			.path(save_path)
+			.flags(SaverFlags::CHANGE_PATH)
+			.done();
+
+		if error != godot::global::Error::OK {
+			return Err(SchemaError::Io { path: save_path.to_owned(), message: format!("Could not save resource: {error:?}") });
+		}
+
+		Ok(resource)
+	}
+
+	/// Same as [`Self::apply_value()`], but returns the previous values of the assigned properties.
+	///
+	/// Only the root properties are recorded, nested objects are always newly created, so restoring those reverts them too.
+	pub fn apply_value_recorded(&self, target: &mut Gd<Object>, value: &Value) -> std::result::Result<UndoRecord, SchemaError> {
+		let mut record = UndoRecord::default();
+		self.reported(value, || self.apply_unreported(target, value, Some(&mut record)))?;
+		Ok(record)
+	}
+
+	fn apply_unreported(
+		&self,
+		target: &mut Gd<Object>,
+		value: &Value,
+		record: Option<&mut UndoRecord>,
+	) -> std::result::Result<(), SchemaError> {
+		let Definition::Class(class) = &self.inner.base
+		else { return Err(anyhow!("Applying values to existing objects requires a class schema.").into()) };
+
+		self.validate_unreported(value)?;
+
+		// Same as `instantiate_unreported()`: confidences are not properties, and properties are assigned by their Godot names.
+		let (value, _) = self.split_confidence(value);
+		let value = self.inner
+			.to_godot_names(value.as_ref())
+			.with_code(ErrorCode::InstantiationFailed)?;
+
+		let Value::Object(properties) = value.as_ref()
+		else {
+			return Err(SchemaError::ConversionFailed {
+				path: String::new(),
+				expected: "object".to_owned(),
+				got: value.to_string(),
+			});
+		};
+
+		let Some(record) = record
+		else { return Ok(class.set_properties(target, &self.inner.defs, properties)?) };
+
+		let before = properties
+			.keys()
+			.map(|name| (StringName::from(name.as_str()), target.get(name.as_str())))
+			.collect_vec();
+
+		let result = class.set_properties(target, &self.inner.defs, properties);
+
+		for (property, before) in before {
+			let after = target.get(&property);
+
+			if after != before {
+				record.changes.push(PropertyChange { target: target.clone(), property, before, after });
+			}
+		}
+
+		// Don't leave the target half-applied, the caller has no record to revert it with.
+		if result.is_err() {
+			record.revert();
+		}
+
+		Ok(result?)
+	}
+
+	pub fn validate_value(&self, value: &Value) -> std::result::Result<(), SchemaError> {
+		self.reported(value, || self.validate_unreported(value))
+	}
+
+	/// The `response_format` of OpenAI's chat completions for this schema, built from [`RootSchema::to_provider_value()`].
+	///
+	/// Strict mode is enabled when the schema allows it: every property of every object is `required`.
+	pub fn open_ai_response_format_value(&self, name: &str) -> Result<Value> {
+		let schema = self.inner.to_provider_value()?;
+
+		Ok(serde_json::json!({
+			"type": "json_schema",
+			"json_schema": {
+				"name": name,
+				"strict": supports_strict_mode(&schema),
+				"schema": schema,
+			},
+		}))
+	}
+
+	/// A prompt asking a model to correct `invalid_json`, listing `error` along with this schema (as sent to providers).
+	pub fn repair_prompt(&self, invalid_json: &str, error: &SchemaError) -> Result<String> {
+		let schema = serde_json::to_string(&self.inner.to_provider_value()?)?;
+
+		Ok(format!(
+			"Your previous response does not match the JSON schema.\n\n\
+			Errors:\n{}\n\n\
+			Previous response:\n{invalid_json}\n\n\
+			Schema:\n{schema}\n\n\
+			Respond again with only the corrected JSON.",
+			error.to_string().trim_end(),
+		))
+	}
+
+	pub(crate) fn validate_unreported(&self, value: &Value) -> std::result::Result<(), SchemaError> {
+		// Instantiation translates either name, only the validator needs the names in JSON.
+		let value = if self.inner.options.accept_godot_names {
+			self.inner.json_names_of(value)?
+		} else {
+			Cow::Borrowed(value)
+		};
+
+		self.validator()?
+			.validate(&value)
+			.map_err(SchemaError::ValidationFailed)
+	}
+
+	/// Runs `f`, passing its error (if any) to the failure hook, see [`Self::set_failure_hook()`].
+	fn reported<T>(
+		&self,
+		input: &Value,
+		f: impl FnOnce() -> std::result::Result<T, SchemaError>,
+	) -> std::result::Result<T, SchemaError> {
+		let result = f();
+
+		if let Err(err) = &result {
+			log(LogLevel::Info, || format!("Schema \"{}\" rejected input: {err}", self.schema_name()));
+			report_failure(&self.schema_name(), err, input);
+		}
+
+		result
+	}
+
+	/// The name of the class this schema was generated from, or the JSON type of non-class schemas.
+	pub fn schema_name(&self) -> String {
+		match &self.inner.base {
+			Definition::Class(class) => class.source.definition_name(),
+			Definition::Null(_) => "null".to_owned(),
+			Definition::Boolean(_) => "boolean".to_owned(),
+			Definition::Integer(_) => "integer".to_owned(),
+			Definition::Number(_) => "number".to_owned(),
+			Definition::String(_) => "string".to_owned(),
+			Definition::Object(_) => "object".to_owned(),
+			| Definition::Array(_)
+			| Definition::Tuple(_) => "array".to_owned(),
+			Definition::Enum(_) => "enum".to_owned(),
+			Definition::Variant(var_def) => var_def.name().to_owned(),
+			| Definition::Any(_)
+			| Definition::Conditional(_)
+			| Definition::OneOf(_) => "any".to_owned(),
+			Definition::ResourcePath(_) => "string".to_owned(),
+		}
+	}
+
+	/// See [`Self::relaxed`].
+	fn relaxed(&self) -> Result<&GodotSchema> {
+		if let Some(relaxed) = self.relaxed.get() {
+			return Ok(relaxed);
+		}
+
+		let relaxed = Self::with_backend(self.inner.with_all_optional(), self.backend)?;
+		Ok(self.relaxed.get_or_init(|| Box::new(relaxed)))
+	}
+
+	/// Returns the compiled validator, compiling it first if it was released.
+	pub fn validator(&self) -> Result<&dyn SchemaValidator> {
+		if let Some(validator) = self.validator.get() {
+			return Ok(validator.as_ref());
+		}
+
+		let json_value = serde_json::from_str(&self.json.to_string())?;
+		let validator = self.backend.build(&self.inner, &json_value)?;
+		Ok(self.validator.get_or_init(|| validator).as_ref())
+	}
+}
+
+/// Converts a Godot value tree into JSON, turning whole floats into integers since Godot's `JSON` class parses every number as a float.
+fn json_from_parsed(variant: &Variant) -> Result<Value> {
+	fn integral_floats_to_ints(value: &mut Value) {
+		match value {
+			Value::Number(number) => {
+				if let Some(float) = number.as_f64()
+					&& !number.is_i64()
+					&& !number.is_u64()
+					&& float.fract() == 0.0
+					&& float.abs() < i64::MAX as f64 {
+					*value = Value::from(float as i64);
+				}
+			}
+			Value::Array(vec) => vec.iter_mut().for_each(integral_floats_to_ints),
+			Value::Object(properties) => properties.values_mut().for_each(integral_floats_to_ints),
+			_ => {}
+		}
+	}
+
+	let mut value = raw_json_from_variant(variant)?;
+	integral_floats_to_ints(&mut value);
+	Ok(value)
+}
+
+fn output_json(value: &Value) -> Result<String> {
+	serde_json::to_string(value).map_err(anyhow::Error::from)
+}
+
+/// The JSON of a `const`/`default` passed from GDScript, `None` if `null`.
+fn keyword_value(value: &Variant) -> Result<Option<Value>> {
+	if value.is_nil() {
+		Ok(None)
+	} else {
+		raw_json_from_variant(value).map(Some)
+	}
+}
+
+/// Assigns each of `names` its default value, see [`ClassSource::property_default()`].
+fn reset_properties<'a>(target: &mut Gd<Object>, class: &JClass, names: impl IntoIterator<Item = &'a String>) {
+	for name in names {
+		assign_property(target, name, class.source.property_default(name));
+	}
+}
+
+fn failures_variant(result: Result<Vec<FuzzFailure>>) -> Variant {
+	match result {
+		Ok(failures) => {
+			let mut array = Array::<Dictionary>::new();
+
+			for failure in &failures {
+				array.push(&failure.to_dictionary());
+			}
+
+			array.to_variant()
+		}
+		Err(err) => format!("{err:?}").to_variant(),
+	}
+}
+
+/// Whether OpenAI's strict mode accepts `schema`: every object lists all of its properties in `required`.
+fn supports_strict_mode(schema: &Value) -> bool {
+	match schema {
+		Value::Object(keywords) => {
+			let all_required = match keywords.get("properties") {
+				Some(Value::Object(properties)) => {
+					let required = keywords.get("required").and_then(Value::as_array);
+					properties.keys().all(|name| required.is_some_and(|required| required.iter().any(|item| item == name)))
+				}
+				_ => true,
+			};
+
+			all_required && keywords.values().all(supports_strict_mode)
+		}
+		Value::Array(items) => items.iter().all(supports_strict_mode),
+		_ => true,
+	}
+}
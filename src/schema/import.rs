@@ -0,0 +1,179 @@
+use super::*;
+
+impl RootSchema {
+	/// Parses a JSON Schema (draft 2020-12) document authored outside Godot into definitions,
+	/// allowing data to be instantiated into Godot values (`Dictionary`, `Array` and primitives).
+	///
+	/// Supported: the primitive types, `properties`/`required`/`items`/`prefixItems`, `minItems`/`maxItems`, `description`
+	/// and local references (`#/$defs/Name` or `#/definitions/Name`).
+	///
+	/// Constructs without a direct equivalent (unions, `enum`, objects with additional properties, ...) fall back to
+	/// their closest type, or to [`JAny`] (any JSON value). Their constraints are not lost, as long as the original
+	/// document is used for validation, see [`GodotSchema::try_from_json_schema()`].
+	pub fn from_json_schema(document: &Value) -> Result<RootSchema> {
+		let defs = match document.get("$defs").or_else(|| document.get("definitions")) {
+			None => BTreeMap::new(),
+			Some(Value::Object(defs)) => defs
+				.iter()
+				.map(|(name, schema)| {
+					definition_from_json_schema(schema)
+						.map(|def| (name.clone(), def))
+						.map_err(|err| anyhow!("Definition \"{name}\": {err}"))
+				})
+				.try_collect()?,
+			Some(other) => bail!("Expected `$defs` to be an object.\nGot: {other}"),
+		};
+
+		let base = match type_from_json_schema(document)? {
+			Type::Definition(def) => def,
+			Type::Ref(JRef { name, .. }) => defs
+				.get(&name)
+				.cloned()
+				.ok_or_else(|| anyhow!("Expected definition \"{name}\" to be in `$defs` map."))?,
+		};
+
+		Ok(RootSchema {
+			defs,
+			base,
+			options: SchemaOptions::default(),
+			warnings: Vec::new(),
+			name_map: None,
+		})
+	}
+}
+
+fn type_from_json_schema(schema: &Value) -> Result<Type> {
+	if let Some(reference) = schema.get("$ref") {
+		let reference = reference
+			.as_str()
+			.ok_or_else(|| anyhow!("Expected `$ref` to be a string.\nGot: {reference}"))?;
+
+		let name = reference
+			.strip_prefix("#/$defs/")
+			.or_else(|| reference.strip_prefix("#/definitions/"))
+			.ok_or_else(|| anyhow!("Only local references (\"#/$defs/Name\") are supported.\nGot: {reference}"))?;
+
+		let mut ty = JRef::new(name);
+
+		if let Some(Value::String(description)) = schema.get("description") {
+			ty.add_description(description.clone());
+		}
+
+		return Ok(Type::Ref(ty));
+	}
+
+	definition_from_json_schema(schema).map(Type::Definition)
+}
+
+fn definition_from_json_schema(schema: &Value) -> Result<Definition> {
+	let keywords = match schema {
+		Value::Object(keywords) => keywords,
+		Value::Bool(true) => return Ok(Definition::any()),
+		other => bail!("Expected schema to be an object or `true`.\nGot: {other}"),
+	};
+
+	if keywords.contains_key("$ref") {
+		// A definition aliasing another one cannot be represented, the validator still checks the referenced schema.
+		return Ok(Definition::any());
+	}
+
+	let is_union = ["anyOf", "oneOf", "allOf", "not", "if"].iter().any(|key| keywords.contains_key(*key));
+
+	let ty = match keywords.get("type") {
+		_ if is_union => None,
+		Some(Value::String(ty)) => Some(ty.as_str()),
+		Some(Value::Array(types)) if types.len() == 1 => types[0].as_str(),
+		Some(_) => None,
+		None if keywords.contains_key("properties") => Some("object"),
+		None if keywords.contains_key("items") || keywords.contains_key("prefixItems") => Some("array"),
+		None => None,
+	};
+
+	let mut def = match ty {
+		Some("null") => Definition::null(),
+		Some("boolean") => Definition::boolean(),
+		Some("integer") => Definition::integer(),
+		Some("number") => Definition::number(),
+		Some("string") => JString {
+			description: None,
+			pattern: keywords.get("pattern").and_then(Value::as_str).map(str::to_owned),
+			const_value: None,
+			default: None,
+		}.into(),
+		Some("array") => array_from_json_schema(keywords)?,
+		Some("object") => object_from_json_schema(keywords)?,
+		Some(other) => bail!("Unknown type \"{other}\"."),
+		None => Definition::any(),
+	};
+
+	if let Some((const_value, default)) = def.value_keywords_mut() {
+		*const_value = keywords.get("const").cloned();
+		*default = keywords.get("default").cloned();
+	}
+
+	if let Some(Value::String(description)) = keywords.get("description") {
+		def.add_description(description.clone());
+	}
+
+	Ok(def)
+}
+
+fn array_from_json_schema(keywords: &Map<String, Value>) -> Result<Definition> {
+	let closed = matches!(keywords.get("items"), Some(Value::Bool(false)));
+
+	if let Some(Value::Array(prefix_items)) = keywords.get("prefixItems") {
+		// Tuples are instantiated with exactly one value per item, extra items need a plain array.
+		if !closed {
+			return Ok(Definition::untyped_array());
+		}
+
+		let items = prefix_items.iter().map(type_from_json_schema).try_collect::<_, Vec<_>, _>()?;
+		return Ok(JTuple::new(items).into());
+	}
+
+	let mut array = match keywords.get("items") {
+		Some(items @ Value::Object(_)) => JArray::new(type_from_json_schema(items)?),
+		_ => JArray::untyped(),
+	};
+
+	array.min_items = keywords.get("minItems").and_then(Value::as_u64).map(|len| len as usize);
+	array.max_items = keywords.get("maxItems").and_then(Value::as_u64).map(|len| len as usize);
+	Ok(array.into())
+}
+
+fn object_from_json_schema(keywords: &Map<String, Value>) -> Result<Definition> {
+	let Some(Value::Object(properties)) = keywords.get("properties")
+	else { return Ok(Definition::dictionary()) };
+
+	let required = keywords
+		.get("required")
+		.and_then(Value::as_array)
+		.map(|required| required.iter().filter_map(Value::as_str).collect::<HashSet<_>>())
+		.unwrap_or_default();
+
+	let closed = matches!(keywords.get("additionalProperties"), Some(Value::Bool(false)));
+
+	// Typed objects are instantiated with at most their properties, anything looser is kept as a `Dictionary`.
+	if !closed {
+		return Ok(Definition::dictionary());
+	}
+
+	let properties = properties
+		.iter()
+		.map(|(name, schema)| {
+			type_from_json_schema(schema)
+				.map(|ty| (name.clone(), ty))
+				.map_err(|err| anyhow!("Property \"{name}\": {err}"))
+		})
+		.try_collect::<_, Vec<_>, _>()?;
+
+	let mut object = JObject::with_properties(properties.into_iter());
+
+	object.optional = object.properties
+		.keys()
+		.filter(|name| !required.contains(name.as_str()))
+		.cloned()
+		.collect();
+
+	Ok(object.into())
+}
@@ -0,0 +1,81 @@
+use super::*;
+
+impl RootSchema {
+	/// Synthesizes a best-guess schema from a sample value, e.g. save data without a class:
+	///
+	/// - `Dictionary`s with `String` keys become objects with one property per key, other `Dictionary`s are untyped.
+	/// - `Array`s become typed arrays if all their elements share a type (integers and floats unify into floats),
+	///   untyped arrays otherwise.
+	/// - Primitives and built-in types (`Vector2`, `Color`, ...) become their usual definitions,
+	///   `StringName`/`NodePath` become strings (enums cannot be told apart from strings).
+	///
+	/// Objects are rejected, generate their schema from their class instead.
+	pub fn infer_from_variant(value: &Variant) -> Result<RootSchema> {
+		Ok(RootSchema {
+			defs: BTreeMap::new(),
+			base: infer_definition(value)?,
+			options: SchemaOptions::default(),
+			warnings: Vec::new(),
+			name_map: None,
+		})
+	}
+}
+
+fn infer_definition(value: &Variant) -> Result<Definition> {
+	Ok(match value.get_type() {
+		VariantType::NIL => Definition::null(),
+		| VariantType::STRING_NAME
+		| VariantType::NODE_PATH => Definition::string(),
+		VariantType::DICTIONARY => {
+			let dict = value.to::<Dictionary>();
+
+			if dict.is_empty() || dict.keys_array().iter_shared().any(|key| key.get_type() != VariantType::STRING) {
+				return Ok(Definition::dictionary());
+			}
+
+			let properties = dict
+				.iter_shared()
+				.map(|(key, value)| {
+					let name = key.to::<String>();
+					infer_definition(&value)
+						.map(|def| (name.clone(), def))
+						.map_err(|err| anyhow!("Key \"{name}\": {err}"))
+				})
+				.try_collect::<_, Vec<_>, _>()?;
+
+			Definition::object(properties.into_iter())
+		}
+		VariantType::ARRAY => {
+			let elements = array_elements(value)?
+				.iter()
+				.enumerate()
+				.map(|(idx, element)| infer_definition(element).map_err(|err| anyhow!("Element {idx}: {err}")))
+				.try_collect::<_, Vec<_>, _>()?;
+
+			let unified = elements
+				.into_iter()
+				.map(Some)
+				.reduce(|unified, def| unify(unified?, def?));
+
+			match unified {
+				Some(Some(items)) => Definition::array(items),
+				_ => Definition::untyped_array(),
+			}
+		}
+		VariantType::OBJECT => bail!("Cannot infer the schema of an object, generate it from its class instead."),
+		other => raw_definition_from_type(other)
+			.ok_or_else(|| anyhow!("Cannot infer the schema of a value of type {other:?}."))?,
+	})
+}
+
+/// The definition accepting both `a` and `b`, `None` if they do not share a type.
+fn unify(a: Definition, b: Definition) -> Option<Definition> {
+	match (&a, &b) {
+		| (Definition::Integer(_), Definition::Number(_))
+		| (Definition::Number(_), Definition::Integer(_)) => Some(Definition::number()),
+		_ => {
+			let same = serde_json::to_value(&a).ok()? == serde_json::to_value(&b).ok()?;
+			same.then_some(a)
+		}
+	}
+}
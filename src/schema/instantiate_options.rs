@@ -0,0 +1,70 @@
+use super::*;
+use std::cell::Cell;
+
+/// Per-call settings of [`GodotSchema::instantiate_value_with()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct InstantiateOptions {
+	/// Rejects JSON integers (numbers without a fraction or exponent, e.g. `1`) for float properties, matching GDScript's typing.
+	///
+	/// Floats are always rejected for integer properties.
+	pub strict_numbers: bool,
+	/// Whether each property is read back after instantiation and compared to the value assigned to it, see [`SetterCheck`].
+	pub setter_check: SetterCheck,
+}
+
+/// What happens when a property setter clamps or rejects an assigned value, leaving the object diverging from the JSON.
+#[derive(GodotConvert, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[godot(via = i64)]
+pub enum SetterCheck {
+	/// Properties are not read back.
+	#[default]
+	Off = 0,
+	/// Changed properties are listed in a warning.
+	Warn = 1,
+	/// Changed properties fail the instantiation.
+	Error = 2,
+}
+
+thread_local! {
+	static ACTIVE_OPTIONS: Cell<InstantiateOptions> = const {
+		Cell::new(InstantiateOptions { strict_numbers: false, setter_check: SetterCheck::Off })
+	};
+}
+
+impl InstantiateOptions {
+	/// Reads the options from a GDScript dictionary, missing keys keep their default values.
+	///
+	/// # Keys
+	/// - "strict_numbers": `bool`, see [`Self::strict_numbers`].
+	/// - "setter_check": `int`, see [`SetterCheck`].
+	pub fn from_dictionary(dict: &Dictionary) -> Result<Self> {
+		let mut options = Self::default();
+
+		for (key, value) in dict.iter_shared() {
+			let key = key.to_string();
+
+			match key.as_str() {
+				"strict_numbers" => options.strict_numbers = value.try_to().map_err(|err| anyhow!("Option \"{key}\": {err:?}"))?,
+				"setter_check" => options.setter_check = value.try_to().map_err(|err| anyhow!("Option \"{key}\": {err:?}"))?,
+				_ => bail!("Unknown instantiate option \"{key}\"."),
+			}
+		}
+
+		Ok(options)
+	}
+
+	/// The options of the instantiation running on this thread, the defaults if none is.
+	pub fn current() -> Self {
+		ACTIVE_OPTIONS.get()
+	}
+
+	/// Runs `f` with these options as [`Self::current()`], restoring the previous ones afterward.
+	///
+	/// The options are scoped instead of passed down, since every [`Definition`] would otherwise need to forward them.
+	pub fn scope<T>(self, f: impl FnOnce() -> T) -> T {
+		let previous = ACTIVE_OPTIONS.replace(self);
+		let result = f();
+		ACTIVE_OPTIONS.set(previous);
+		result
+	}
+}
@@ -0,0 +1,105 @@
+use super::*;
+
+/// The keywords of a property's schema listed by [`PropertyType::constraints`].
+const CONSTRAINT_KEYWORDS: &[&str] = &[
+	"minimum", "maximum", "exclusiveMinimum", "exclusiveMaximum", "multipleOf",
+	"minLength", "maxLength", "pattern", "format",
+	"minItems", "maxItems", "uniqueItems", "minProperties", "maxProperties",
+	"enum", "const", "default",
+];
+
+/// The structure of a root property, see [`RootSchema::property_type()`].
+#[derive(Clone, Debug)]
+pub struct PropertyType {
+	/// The kind of the property's definition (after following its reference), see [`Definition::kind()`].
+	pub kind: &'static str,
+	/// The name of the definition in `$defs` that the property references, if any.
+	pub reference: Option<String>,
+	pub optional: bool,
+	pub description: Option<String>,
+	/// The keywords restricting the property's values, e.g. `minimum` or `enum`.
+	pub constraints: Map<String, Value>,
+}
+
+impl PropertyType {
+	pub fn to_dictionary(&self) -> Result<Dictionary> {
+		let mut dict = Dictionary::new();
+		dict.set("kind", self.kind);
+		dict.set("ref", self.reference.clone().unwrap_or_default());
+		dict.set("optional", self.optional);
+		dict.set("description", self.description.clone().unwrap_or_default());
+		dict.set("constraints", raw_variant_from_json(&Value::Object(self.constraints.clone()))?);
+		Ok(dict)
+	}
+}
+
+impl RootSchema {
+	/// The names of the root's properties, in declaration order. Empty if the root is not a class or an object.
+	pub fn property_names(&self) -> Vec<&String> {
+		match &self.base {
+			| Definition::Class(JClass { properties, .. })
+			| Definition::Object(JObject { properties, .. }) => properties.keys().collect(),
+			_ => Vec::new(),
+		}
+	}
+
+	/// Describes root property `name`.
+	pub fn property_type(&self, name: &str) -> Result<PropertyType> {
+		let (ty, optional, class) = match &self.base {
+			Definition::Class(class) => (class.properties.get(name), class.optional.contains(name), Some(class)),
+			Definition::Object(object) => (object.properties.get(name), object.optional.contains(name), None),
+			_ => bail!("Only class or object schemas have properties."),
+		};
+
+		let ty = ty.ok_or_else(|| anyhow!("Expected property \"{name}\" to be in `properties` map."))?;
+
+		let def = ty.resolve(&self.defs)?;
+
+		let (reference, description) = match ty {
+			Type::Definition(_) => (None, def.description()),
+			Type::Ref(JRef { name: def_name, description }) => (Some(def_name.clone()), description.as_ref().or(def.description())),
+		};
+
+		let Value::Object(keywords) = serde_json::to_value(def)?
+		else { bail!("Expected the definition of property \"{name}\" to serialize into a JSON object.") };
+
+		let mut constraints = keywords
+			.into_iter()
+			.filter(|(keyword, _)| CONSTRAINT_KEYWORDS.contains(&keyword.as_str()))
+			.collect::<Map<_, _>>();
+
+		if let Some(extra) = class.and_then(|class| class.constraints.get(name)) {
+			let extra = extra
+				.iter()
+				.filter(|(keyword, _)| keyword.as_str() != "description")
+				.map(|(keyword, value)| (keyword.clone(), value.clone()));
+
+			constraints.extend(extra);
+		}
+
+		Ok(PropertyType {
+			kind: def.kind(),
+			reference,
+			optional,
+			description: description.cloned(),
+			constraints,
+		})
+	}
+
+	/// The names of the definitions in `$defs` referenced by the root, directly or through other definitions.
+	pub fn dependencies(&self) -> BTreeSet<String> {
+		let mut pending = BTreeSet::new();
+		self.base.insert_references(&mut pending);
+
+		let mut reachable = BTreeSet::new();
+
+		while let Some(name) = pending.pop_first() {
+			if let Some(def) = self.defs.get(&name)
+				&& reachable.insert(name) {
+				def.insert_references(&mut pending);
+			}
+		}
+
+		reachable
+	}
+}
@@ -0,0 +1,211 @@
+use super::*;
+
+/// Applies the JSON Patch (RFC 6902) `patch`, an array of operations, to `document`.
+///
+/// Operations are applied in order, if any fails `document` is left untouched.
+///
+/// # Returns
+/// The names of the root properties touched by the patch (`test` operations touch nothing).
+pub fn apply_json_patch(document: &mut Value, patch: &Value) -> Result<BTreeSet<String>> {
+	let Value::Array(operations) = patch
+	else { bail!("Expected JSON Patch to be an array of operations.\nGot: {patch}") };
+
+	let mut patched = document.clone();
+	let mut touched = BTreeSet::new();
+
+	for (idx, operation) in operations.iter().enumerate() {
+		apply_operation(&mut patched, operation, &mut touched)
+			.map_err(|err| anyhow!("Operation {idx}: {err}"))?;
+	}
+
+	*document = patched;
+	Ok(touched)
+}
+
+fn apply_operation(document: &mut Value, operation: &Value, touched: &mut BTreeSet<String>) -> Result<()> {
+	let Value::Object(fields) = operation
+	else { bail!("Expected operation to be an object.\nGot: {operation}") };
+
+	let string_field = |key: &str| -> Result<&str> {
+		fields.get(key)
+			.and_then(Value::as_str)
+			.ok_or_else(|| anyhow!("Expected key \"{key}\" to be a string."))
+	};
+
+	let value_field = || -> Result<Value> {
+		fields.get("value")
+			.cloned()
+			.ok_or_else(|| anyhow!("Expected key \"value\"."))
+	};
+
+	let op = string_field("op")?;
+	let path = parse_pointer(string_field("path")?)?;
+
+	let mut touch = |document: &Value, tokens: &[String]| match tokens.first() {
+		Some(name) => { touched.insert(name.clone()); }
+		// The whole document is replaced.
+		None => touched.extend(document.as_object().into_iter().flat_map(Map::keys).cloned()),
+	};
+
+	match op {
+		"add" => {
+			touch(document, &path);
+			add(document, &path, value_field()?)?;
+			touch(document, &path);
+		}
+		"remove" => {
+			touch(document, &path);
+			remove(document, &path)?;
+		}
+		"replace" => {
+			touch(document, &path);
+			*pointee_mut(document, &path)? = value_field()?;
+			touch(document, &path);
+		}
+		"move" => {
+			let from = parse_pointer(string_field("from")?)?;
+
+			if path.len() > from.len() && path.starts_with(&from) {
+				bail!("Cannot move a value into one of its own children.");
+			}
+
+			touch(document, &from);
+			let value = remove(document, &from)?;
+			add(document, &path, value)?;
+			touch(document, &path);
+		}
+		"copy" => {
+			let from = parse_pointer(string_field("from")?)?;
+			let value = pointee(document, &from)?.clone();
+			add(document, &path, value)?;
+			touch(document, &path);
+		}
+		"test" => {
+			let expected = value_field()?;
+			let actual = pointee(document, &path)?;
+
+			if *actual != expected {
+				bail!("Test failed, expected {expected}.\nGot: {actual}");
+			}
+		}
+		_ => bail!("Unknown operation \"{op}\", expected \"add\", \"remove\", \"replace\", \"move\", \"copy\" or \"test\"."),
+	}
+
+	Ok(())
+}
+
+/// Splits a JSON Pointer (RFC 6901) into its unescaped reference tokens, the empty pointer refers to the whole document.
+pub fn parse_pointer(pointer: &str) -> Result<Vec<String>> {
+	if pointer.is_empty() {
+		return Ok(Vec::new());
+	}
+
+	let Some(tokens) = pointer.strip_prefix('/')
+	else { bail!("Expected JSON Pointer \"{pointer}\" to start with '/'.") };
+
+	Ok(tokens
+		.split('/')
+		.map(|token| token.replace("~1", "/").replace("~0", "~"))
+		.collect())
+}
+
+/// Parses `token` as an index of `array`, `len` is accepted only if `allow_end` (for insertions).
+fn array_index(token: &str, len: usize, allow_end: bool) -> Result<usize> {
+	if token == "-" && allow_end {
+		return Ok(len);
+	}
+
+	let is_canonical = !token.is_empty()
+		&& token.bytes().all(|byte| byte.is_ascii_digit())
+		&& (token == "0" || !token.starts_with('0'));
+
+	let index = is_canonical
+		.then(|| token.parse::<usize>().ok())
+		.flatten()
+		.ok_or_else(|| anyhow!("Expected \"{token}\" to be an array index."))?;
+
+	if index > len || (index == len && !allow_end) {
+		bail!("Index {index} is out of bounds, the array has {len} elements.");
+	}
+
+	Ok(index)
+}
+
+/// The value of `document` that `tokens` (see [`parse_pointer()`]) refers to.
+pub fn pointee<'a>(document: &'a Value, tokens: &[String]) -> Result<&'a Value> {
+	tokens.iter().try_fold(document, |value, token| match value {
+		Value::Object(map) => map.get(token).ok_or_else(|| anyhow!("Key \"{token}\" does not exist.")),
+		Value::Array(vec) => Ok(&vec[array_index(token, vec.len(), false)?]),
+		_ => bail!("Cannot index into {value} with \"{token}\"."),
+	})
+}
+
+fn pointee_mut<'a>(document: &'a mut Value, tokens: &[String]) -> Result<&'a mut Value> {
+	tokens.iter().try_fold(document, |value, token| match value {
+		Value::Object(map) => map.get_mut(token).ok_or_else(|| anyhow!("Key \"{token}\" does not exist.")),
+		Value::Array(vec) => {
+			let index = array_index(token, vec.len(), false)?;
+			Ok(&mut vec[index])
+		}
+		_ => bail!("Cannot index into {value} with \"{token}\"."),
+	})
+}
+
+fn add(document: &mut Value, tokens: &[String], value: Value) -> Result<()> {
+	let Some((last, parent)) = tokens.split_last()
+	else {
+		*document = value;
+		return Ok(());
+	};
+
+	match pointee_mut(document, parent)? {
+		Value::Object(map) => { map.insert(last.clone(), value); }
+		Value::Array(vec) => {
+			let index = array_index(last, vec.len(), true)?;
+			vec.insert(index, value);
+		}
+		other => bail!("Cannot add \"{last}\" to {other}."),
+	}
+
+	Ok(())
+}
+
+fn remove(document: &mut Value, tokens: &[String]) -> Result<Value> {
+	let Some((last, parent)) = tokens.split_last()
+	else { bail!("Cannot remove the whole document.") };
+
+	match pointee_mut(document, parent)? {
+		Value::Object(map) => map.remove(last).ok_or_else(|| anyhow!("Key \"{last}\" does not exist.")),
+		Value::Array(vec) => {
+			let index = array_index(last, vec.len(), false)?;
+			Ok(vec.remove(index))
+		}
+		other => bail!("Cannot remove \"{last}\" from {other}."),
+	}
+}
+
+/// Applies the JSON Merge Patch (RFC 7386) `patch` to `document`.
+///
+/// Objects are merged recursively, `null` values remove the matching keys and any other value replaces the target's.
+pub fn apply_merge_patch(document: &mut Value, patch: &Value) {
+	let Value::Object(patch_properties) = patch
+	else {
+		*document = patch.clone();
+		return;
+	};
+
+	if !document.is_object() {
+		*document = Value::Object(Map::new());
+	}
+
+	let Value::Object(properties) = document
+	else { return };
+
+	for (name, value) in patch_properties {
+		if value.is_null() {
+			properties.remove(name);
+		} else {
+			apply_merge_patch(properties.entry(name.clone()).or_insert(Value::Null), value);
+		}
+	}
+}
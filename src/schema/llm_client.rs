@@ -0,0 +1,330 @@
+use super::*;
+use godot::classes::http_client::Method;
+use godot::classes::HttpRequest;
+
+const OPEN_AI_ENDPOINT: &str = "https://api.openai.com/v1/chat/completions";
+const ANTHROPIC_ENDPOINT: &str = "https://api.anthropic.com/v1/messages";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// The tool Anthropic models are forced to call, its input is the structured output.
+const RESPONSE_TOOL: &str = "respond";
+
+/// Requests structured output from an LLM provider, then validates and instantiates it with a [`GodotSchema`].
+///
+/// Responses that fail validation are sent back to the model along with the errors, up to [`Self::max_retries`] times.
+/// OpenAI requests use `response_format`, Anthropic requests force a call to a tool whose input is the schema.
+///
+/// Must be in the scene tree, since requests are performed by a child `HTTPRequest`.
+#[derive(GodotClass)]
+#[class(init, base = Node)]
+pub struct LlmStructuredClient {
+	base: Base<Node>,
+	/// Either "OpenAI" or "Anthropic".
+	#[var]
+	#[init(val = GString::from("OpenAI"))]
+	pub provider: GString,
+	/// The chat completions (OpenAI) or messages (Anthropic) URL, or empty for the provider's own.
+	#[var]
+	pub endpoint: GString,
+	#[var]
+	pub api_key: GString,
+	#[var]
+	pub model: GString,
+	/// How many times invalid responses are sent back to the model before emitting `failed`.
+	#[var]
+	#[init(val = 2)]
+	pub max_retries: i64,
+	/// Only sent to Anthropic, which requires it.
+	#[var]
+	#[init(val = 1024)]
+	pub max_tokens: i64,
+	http: Option<Gd<HttpRequest>>,
+	pending: Option<PendingRequest>,
+}
+
+struct PendingRequest {
+	schema: Gd<GodotSchema>,
+	provider: ToolProvider,
+	messages: Vec<Value>,
+	retries: i64,
+}
+
+/// The structured output of a response, and what is needed to send it back to the model.
+struct ResponseOutput {
+	json: Result<Value>,
+	/// The output as the model wrote it, quoted when asking it to correct its response.
+	text: String,
+	/// The response's message, in the format `messages` expects.
+	message: Value,
+	tool_use_id: Option<String>,
+}
+
+#[godot_api]
+impl LlmStructuredClient {
+	/// Emitted once a response was validated and instantiated.
+	#[signal]
+	fn result(instance: Variant);
+
+	/// Emitted when the request fails, or when the model's last retry is still invalid.
+	#[signal]
+	fn failed(error: GString);
+
+	/// Emitted each time an invalid response is sent back to the model.
+	#[signal]
+	fn retrying(attempt: i64, error: GString);
+
+	/// Sends `prompt` to the model, asking for a response matching `schema`. The outcome is emitted with `result` or `failed`.
+	///
+	/// # Returns
+	/// - `null`, if the request was sent.
+	/// - Otherwise an error message, e.g. if a request is already pending.
+	#[func]
+	pub fn request(&mut self, prompt: String, schema: Gd<GodotSchema>) -> Variant {
+		let result = (|| {
+			if self.pending.is_some() {
+				bail!("Expected the previous request to be completed, call `cancel()` to abort it.");
+			}
+
+			self.pending = Some(PendingRequest {
+				schema,
+				provider: ToolProvider::from_name(&self.provider.to_string())?,
+				messages: vec![serde_json::json!({ "role": "user", "content": prompt })],
+				retries: 0,
+			});
+
+			self.send().inspect_err(|_| self.pending = None)
+		})();
+
+		match result {
+			Ok(()) => Variant::nil(),
+			Err(err) => format!("{err:?}").to_variant(),
+		}
+	}
+
+	#[func]
+	pub fn is_busy(&self) -> bool {
+		self.pending.is_some()
+	}
+
+	/// Aborts the pending request (if any), without emitting `failed`.
+	#[func]
+	pub fn cancel(&mut self) {
+		self.pending = None;
+
+		if let Some(http) = &mut self.http {
+			http.cancel_request();
+		}
+	}
+
+	/// Connected to the child `HTTPRequest`'s `request_completed`.
+	#[func]
+	fn on_request_completed(&mut self, result: i64, response_code: i64, _headers: PackedStringArray, body: PackedByteArray) {
+		if self.pending.is_none() {
+			return;
+		}
+
+		let body = body.get_string_from_utf8().to_string();
+
+		let outcome = if result != 0 {
+			Err(anyhow!("Expected the HTTP request to succeed.\nGot result: {result}"))
+		} else if !(200..300).contains(&response_code) {
+			Err(anyhow!("Expected a successful response.\nGot code {response_code}: {body}"))
+		} else {
+			self.receive(&body)
+		};
+
+		match outcome {
+			Ok(Some(instance)) => {
+				self.pending = None;
+				self.base_mut().emit_signal("result", &[instance]);
+			}
+			Ok(None) => {}
+			Err(err) => {
+				self.pending = None;
+				self.base_mut().emit_signal("failed", &[format!("{err:?}").to_variant()]);
+			}
+		}
+	}
+}
+
+impl LlmStructuredClient {
+	/// # Returns
+	/// - The instance, if the response is valid.
+	/// - `None`, if it was invalid and sent back to the model.
+	fn receive(&mut self, body: &str) -> Result<Option<Variant>> {
+		let mut pending = self.pending
+			.take()
+			.ok_or_else(|| anyhow!("Received a response without a pending request."))?;
+
+		let response = serde_json::from_str(body)?;
+		let output = extract_output(pending.provider, &response)?;
+
+		let schema = pending.schema.bind();
+
+		let error = match output.json.map_err(SchemaError::from).and_then(|json| schema.instantiate_value(&json)) {
+			Ok(instance) => return Ok(Some(instance)),
+			Err(err) => err,
+		};
+
+		if pending.retries >= self.max_retries {
+			bail!("Expected a valid response after {} retries.\nLast error: {error}", pending.retries);
+		}
+
+		let feedback = schema.repair_prompt(&output.text, &error)?;
+		drop(schema);
+
+		pending.retries += 1;
+		pending.messages.push(output.message);
+
+		pending.messages.push(match output.tool_use_id {
+			Some(id) => serde_json::json!({
+				"role": "user",
+				"content": [{ "type": "tool_result", "tool_use_id": id, "is_error": true, "content": feedback }],
+			}),
+			None => serde_json::json!({ "role": "user", "content": feedback }),
+		});
+
+		let attempt = pending.retries;
+		self.pending = Some(pending);
+		self.send()?;
+
+		self.base_mut().emit_signal("retrying", &[attempt.to_variant(), error.to_string().to_variant()]);
+		Ok(None)
+	}
+
+	fn send(&mut self) -> Result<()> {
+		let pending = self.pending
+			.as_ref()
+			.ok_or_else(|| anyhow!("Expected a pending request."))?;
+
+		let body = self.request_body(pending)?.to_string();
+		let api_key = self.api_key.to_string();
+
+		let (default_endpoint, headers) = match pending.provider {
+			ToolProvider::OpenAi => (OPEN_AI_ENDPOINT, vec![format!("Authorization: Bearer {api_key}")]),
+			ToolProvider::Anthropic => (ANTHROPIC_ENDPOINT, vec![
+				format!("x-api-key: {api_key}"),
+				format!("anthropic-version: {ANTHROPIC_VERSION}"),
+			]),
+		};
+
+		let endpoint = if self.endpoint.is_empty() { default_endpoint.to_owned() } else { self.endpoint.to_string() };
+
+		let headers = ["Content-Type: application/json".to_owned()]
+			.into_iter()
+			.chain(headers)
+			.map(GString::from)
+			.collect::<PackedStringArray>();
+
+		let error = self.http_request()
+			.request_ex(endpoint.as_str())
+			.custom_headers(&headers)
+			.method(Method::POST)
+			.request_data(body.as_str())
+			.done();
+
+		if error != godot::global::Error::OK {
+			bail!("Expected the HTTP request to start.\nGot: {error:?}");
+		}
+
+		Ok(())
+	}
+
+	/// Built from the same payloads as [`GodotSchema::open_ai_response_format()`] and tool definitions.
+	fn request_body(&self, pending: &PendingRequest) -> Result<Value> {
+		let schema = pending.schema.bind();
+
+		Ok(match pending.provider {
+			ToolProvider::OpenAi => serde_json::json!({
+				"model": self.model.to_string(),
+				"messages": pending.messages,
+				"response_format": schema.open_ai_response_format_value(&response_name(&schema.schema_name()))?,
+			}),
+			ToolProvider::Anthropic => serde_json::json!({
+				"model": self.model.to_string(),
+				"max_tokens": self.max_tokens,
+				"messages": pending.messages,
+				"tools": [{
+					"name": RESPONSE_TOOL,
+					"description": "Responds with the requested structured output.",
+					"input_schema": schema.inner.to_provider_value()?,
+				}],
+				"tool_choice": { "type": "tool", "name": RESPONSE_TOOL },
+			}),
+		})
+	}
+
+	/// The child `HTTPRequest`, created on the first request.
+	fn http_request(&mut self) -> Gd<HttpRequest> {
+		if let Some(http) = &self.http {
+			return http.clone();
+		}
+
+		let mut http = HttpRequest::new_alloc();
+		http.connect("request_completed", &Callable::from_object_method(&self.to_gd(), "on_request_completed"));
+		self.base_mut().add_child(&http);
+		self.http = Some(http.clone());
+		http
+	}
+}
+
+fn extract_output(provider: ToolProvider, response: &Value) -> Result<ResponseOutput> {
+	match provider {
+		ToolProvider::OpenAi => {
+			let content = response
+				.pointer("/choices/0/message/content")
+				.and_then(Value::as_str)
+				.ok_or_else(|| anyhow!("Expected the response to have a message content.\nGot: {response}"))?;
+
+			// Models sometimes wrap their output in code fences or leave trailing commas.
+			let json = serde_json::from_str(&repair_json(content).json)
+				.map_err(|err| anyhow!("Expected the response to be JSON.\nGot error: {err}"));
+
+			Ok(ResponseOutput {
+				json,
+				text: content.to_owned(),
+				message: serde_json::json!({ "role": "assistant", "content": content }),
+				tool_use_id: None,
+			})
+		}
+		ToolProvider::Anthropic => {
+			let content = response
+				.get("content")
+				.and_then(Value::as_array)
+				.ok_or_else(|| anyhow!("Expected the response to have a content array.\nGot: {response}"))?;
+
+			let tool_use = content.iter().find(|block| {
+				block.get("type").and_then(Value::as_str) == Some("tool_use")
+					&& block.get("name").and_then(Value::as_str) == Some(RESPONSE_TOOL)
+			});
+
+			let (json, tool_use_id) = match tool_use {
+				Some(block) => (
+					block.get("input").cloned().ok_or_else(|| anyhow!("Expected the tool call to have an input.")),
+					block.get("id").and_then(Value::as_str).map(str::to_owned),
+				),
+				None => (Err(anyhow!("Expected the response to call tool \"{RESPONSE_TOOL}\".")), None),
+			};
+
+			let text = match tool_use.and_then(|block| block.get("input")) {
+				Some(input) => input.to_string(),
+				None => Value::Array(content.clone()).to_string(),
+			};
+
+			Ok(ResponseOutput {
+				json,
+				text,
+				message: serde_json::json!({ "role": "assistant", "content": content }),
+				tool_use_id,
+			})
+		}
+	}
+}
+
+/// OpenAI only accepts letters, digits, underscores and dashes in response format names.
+fn response_name(schema_name: &str) -> String {
+	schema_name
+		.chars()
+		.map(|ch| if ch.is_ascii_alphanumeric() || ch == '-' { ch } else { '_' })
+		.collect()
+}
@@ -0,0 +1,55 @@
+use super::*;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How much of the crate's internal work is printed, see [`SchemaLibrary::set_log_level()`].
+///
+/// Each level includes the messages of the previous ones.
+#[derive(GodotConvert, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[godot(via = i64)]
+pub enum LogLevel {
+	/// Nothing is printed.
+	#[default]
+	Off = 0,
+	/// Problems that do not fail the operation, e.g. properties skipped by the generation (printed with `godot_warn!`).
+	Warn = 1,
+	/// Schemas generated, and inputs rejected by validation or instantiation.
+	Info = 2,
+	/// How each property was resolved (or why it was excluded), e.g. to find out why a property became `null` in the schema.
+	Debug = 3,
+}
+
+static LOG_LEVEL: AtomicU8 = AtomicU8::new(LogLevel::Off as u8);
+
+/// Sets the level of the messages printed afterward, on every thread.
+pub fn set_log_level(level: LogLevel) {
+	LOG_LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn log_level() -> LogLevel {
+	match LOG_LEVEL.load(Ordering::Relaxed) {
+		0 => LogLevel::Off,
+		1 => LogLevel::Warn,
+		2 => LogLevel::Info,
+		_ => LogLevel::Debug,
+	}
+}
+
+/// Prints the message built by `message` if `level` is enabled, it is not built otherwise.
+pub fn log(level: LogLevel, message: impl FnOnce() -> String) {
+	if level == LogLevel::Off || level > log_level() {
+		return;
+	}
+
+	match level {
+		LogLevel::Warn => godot_warn!("[godot_json_schema] {}", message()),
+		_ => godot_print!("[godot_json_schema] {}", message()),
+	}
+}
+
+/// How a property's type was resolved, e.g. "integer" or "$ref Fact".
+pub fn describe_type(ty: &Type) -> String {
+	match ty {
+		Type::Definition(def) => def.kind().to_owned(),
+		Type::Ref(JRef { name, .. }) => format!("$ref {name}"),
+	}
+}
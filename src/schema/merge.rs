@@ -0,0 +1,97 @@
+use super::*;
+
+/// How [`RootSchema::merge()`] resolves properties (or definitions) defined differently by both schemas.
+#[derive(GodotConvert, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[godot(via = i64)]
+pub enum ConflictPolicy {
+	/// Fails the merge.
+	#[default]
+	Error = 0,
+	/// Keeps the left schema's version.
+	PreferLeft = 1,
+	/// Keeps the right schema's version.
+	PreferRight = 2,
+}
+
+impl RootSchema {
+	/// Combines the root properties and `$defs` of two class or object schemas into an object schema,
+	/// e.g. for composite prompts such as "character stats + dialogue style".
+	///
+	/// The result is instantiated into a `Dictionary`. Entries defined identically by both schemas are not conflicts.
+	pub fn merge(&self, other: &RootSchema, policy: ConflictPolicy) -> Result<RootSchema> {
+		let (left_properties, left_optional) = root_properties(&self.base)?;
+		let (right_properties, right_optional) = root_properties(&other.base)?;
+
+		let mut object = JObject {
+			description: None,
+			properties: left_properties.clone(),
+			optional: left_optional.clone(),
+			dependent_required: BTreeMap::new(),
+			const_value: None,
+			default: None,
+			values_ty: None,
+		};
+
+		for (name, ty) in right_properties {
+			if !take_right(object.properties.get(name), ty, policy, "Property", name)? {
+				continue;
+			}
+
+			object.properties.insert(name.clone(), ty.clone());
+
+			if right_optional.contains(name) {
+				object.optional.insert(name.clone());
+			} else {
+				object.optional.remove(name);
+			}
+		}
+
+		for base in [&self.base, &other.base] {
+			if let Definition::Object(JObject { dependent_required, .. }) = base {
+				for (name, dependencies) in dependent_required {
+					object.dependent_required.entry(name.clone()).or_default().extend(dependencies.iter().cloned());
+				}
+			}
+		}
+
+		let mut defs = self.defs.clone();
+
+		for (name, def) in &other.defs {
+			if take_right(defs.get(name), def, policy, "Definition", name)? {
+				defs.insert(name.clone(), def.clone());
+			}
+		}
+
+		Ok(RootSchema {
+			defs,
+			base: object.into(),
+			options: self.options.clone(),
+			warnings: self.warnings.iter().chain(&other.warnings).cloned().collect(),
+			name_map: None,
+		})
+	}
+}
+
+fn root_properties(base: &Definition) -> Result<(&IndexMap<String, Type>, &BTreeSet<String>)> {
+	match base {
+		| Definition::Class(JClass { properties, optional, .. })
+		| Definition::Object(JObject { properties, optional, .. }) => Ok((properties, optional)),
+		_ => bail!("Only class or object schemas can be merged."),
+	}
+}
+
+/// Whether the right schema's `right` should replace the left schema's `left`.
+fn take_right<T: Serialize>(left: Option<&T>, right: &T, policy: ConflictPolicy, kind: &str, name: &str) -> Result<bool> {
+	let Some(left) = left
+	else { return Ok(true) };
+
+	if serde_json::to_value(left)? == serde_json::to_value(right)? {
+		return Ok(false);
+	}
+
+	match policy {
+		ConflictPolicy::Error => bail!("{kind} \"{name}\" is defined differently by both schemas."),
+		ConflictPolicy::PreferLeft => Ok(false),
+		ConflictPolicy::PreferRight => Ok(true),
+	}
+}
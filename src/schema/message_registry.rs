@@ -0,0 +1,155 @@
+use super::*;
+
+/// Decodes JSON messages (e.g. multiplayer or WebSocket traffic) with the schema registered for their type.
+///
+/// Packets hold their type under [`Self::type_key`] and their payload under [`Self::payload_key`]:
+/// ```json
+/// { "type": "chat", "payload": { "author": "Charlie", "text": "Hello!" } }
+/// ```
+/// If [`Self::payload_key`] is empty, the payload is the packet itself, without its type key.
+#[derive(GodotClass)]
+#[class(init, base = Node)]
+pub struct MessageSchemaRegistry {
+	base: Base<Node>,
+	/// The key holding each packet's message type.
+	#[var]
+	#[init(val = GString::from("type"))]
+	pub type_key: GString,
+	/// The key holding each packet's payload, or empty if the payload is the packet itself.
+	#[var]
+	#[init(val = GString::from("payload"))]
+	pub payload_key: GString,
+	schemas: BTreeMap<String, Gd<GodotSchema>>,
+}
+
+#[godot_api]
+impl MessageSchemaRegistry {
+	/// Emitted by [`Self::decode()`] for each valid packet.
+	#[signal]
+	fn message_decoded(message_type: GString, payload: Variant);
+
+	/// Emitted by [`Self::decode()`] for each packet that could not be decoded, `message_type` is empty if it could not be read.
+	#[signal]
+	fn message_rejected(message_type: GString, error: Gd<SchemaResult>);
+
+	/// Registers `schema` as the schema of messages of type `message_type`, replacing the previous one (if any).
+	#[func]
+	pub fn register(&mut self, message_type: String, schema: Gd<GodotSchema>) {
+		self.schemas.insert(message_type, schema);
+	}
+
+	/// # Returns
+	/// Whether `message_type` was registered.
+	#[func]
+	pub fn unregister(&mut self, message_type: String) -> bool {
+		self.schemas.remove(&message_type).is_some()
+	}
+
+	#[func]
+	pub fn get_message_types(&self) -> PackedStringArray {
+		self.schemas.keys().map(GString::from).collect()
+	}
+
+	/// Reads the type of `packet_json`, validates its payload against that type's schema then instantiates it.
+	///
+	/// # Returns
+	/// A [`SchemaResult`] holding the instantiated payload. Packets without a registered type (or payload) fail with
+	/// `VALIDATION_FAILED`, with the offending key as the issue's path.
+	#[func]
+	pub fn decode(&mut self, packet_json: String) -> Gd<SchemaResult> {
+		let (message_type, result) = match serde_json::from_str(&packet_json) {
+			Ok(packet) => self.decode_value(packet),
+			Err(err) => (String::new(), Err(SchemaError::from(err))),
+		};
+
+		match result {
+			Ok(payload) => {
+				self.base_mut().emit_signal("message_decoded", &[message_type.to_variant(), payload.clone()]);
+				SchemaResult::ok(payload)
+			}
+			Err(err) => {
+				let result = SchemaResult::err(err.into());
+				self.base_mut().emit_signal("message_rejected", &[message_type.to_variant(), result.to_variant()]);
+				result
+			}
+		}
+	}
+
+	/// The reverse of [`Self::decode()`]: wraps the JSON of `payload` (a value of `message_type`'s schema) in a packet.
+	///
+	/// # Returns
+	/// - The packet's JSON, if successful.
+	/// - Otherwise an empty `String`, the error is printed with `godot_error!`.
+	#[func]
+	pub fn encode(&self, message_type: String, payload: Variant) -> String {
+		match self.encode_value(&message_type, &payload) {
+			Ok(packet) => packet.to_string(),
+			Err(err) => {
+				godot_error!("{err:?}");
+				String::default()
+			}
+		}
+	}
+}
+
+impl MessageSchemaRegistry {
+	/// # Returns
+	/// The packet's type (empty if it could not be read), and its instantiated payload.
+	pub fn decode_value(&self, packet: Value) -> (String, std::result::Result<Variant, SchemaError>) {
+		let type_key = self.type_key.to_string();
+		let payload_key = self.payload_key.to_string();
+
+		let Value::Object(mut packet) = packet
+		else { return (String::new(), Err(envelope_issue("", "type", "expected the packet to be an object"))) };
+
+		let Some(Value::String(message_type)) = packet.remove(&type_key)
+		else { return (String::new(), Err(envelope_issue(&type_key, "required", &format!("expected a string \"{type_key}\"")))) };
+
+		let Some(schema) = self.schemas.get(&message_type)
+		else { return (message_type.clone(), Err(envelope_issue(&type_key, "enum", &format!("unknown message type \"{message_type}\"")))) };
+
+		let payload = if payload_key.is_empty() {
+			Value::Object(packet)
+		} else {
+			match packet.remove(&payload_key) {
+				Some(payload) => payload,
+				None => return (message_type, Err(envelope_issue(&payload_key, "required", &format!("expected a \"{payload_key}\"")))),
+			}
+		};
+
+		let result = schema.bind().instantiate_value(&payload);
+		(message_type, result)
+	}
+
+	pub fn encode_value(&self, message_type: &str, payload: &Variant) -> Result<Value> {
+		let schema = self.schemas
+			.get(message_type)
+			.ok_or_else(|| anyhow!("Unknown message type \"{message_type}\"."))?;
+
+		let payload = schema.bind().inner.json_from_instance(payload)?;
+		let mut packet = Map::new();
+		packet.insert(self.type_key.to_string(), message_type.into());
+
+		if self.payload_key.is_empty() {
+			let Value::Object(properties) = payload
+			else { bail!("Expected the payload of \"{message_type}\" to be an object, since `payload_key` is empty.") };
+
+			packet.extend(properties);
+		} else {
+			packet.insert(self.payload_key.to_string(), payload);
+		}
+
+		Ok(Value::Object(packet))
+	}
+}
+
+/// A [`SchemaError::ValidationFailed`] about the packet's envelope, at key `key`.
+fn envelope_issue(key: &str, keyword: &str, message: &str) -> SchemaError {
+	let path = if key.is_empty() { String::new() } else { format!("/{}", escape_pointer_token(key)) };
+
+	SchemaError::ValidationFailed(vec![ValidationIssue {
+		message: format!("at \"{path}\": {message}"),
+		instance_path: path,
+		keyword: keyword.to_owned(),
+	}])
+}
@@ -0,0 +1,169 @@
+use super::*;
+
+/// Key of the class name in JSON written by [`SchemaMigrator::serialize_versioned()`].
+pub const MIGRATION_CLASS_KEY: &str = "$class";
+/// Key of the version in JSON written by [`SchemaMigrator::serialize_versioned()`].
+pub const MIGRATION_VERSION_KEY: &str = "$version";
+
+/// Upgrades JSON written by older versions of a class (e.g. save files) before instantiating it with the class's current schema.
+///
+/// Each class is registered with its current schema and version, migrations upgrade the JSON of a class from one version to a later one.
+/// Migrations are chained, e.g. a save of version 1 goes through `1 -> 2` then `2 -> 4` to reach version 4.
+#[derive(GodotClass)]
+#[class(init, base = RefCounted)]
+pub struct SchemaMigrator {
+	/// Class name => (current schema, current version).
+	classes: BTreeMap<String, (Gd<GodotSchema>, i64)>,
+	/// (class name, from version) => (to version, migration).
+	migrations: BTreeMap<(String, i64), (i64, Callable)>,
+}
+
+#[godot_api]
+impl SchemaMigrator {
+	/// Registers `schema` as the current schema of its class, at version `version`.
+	///
+	/// The class is named after the schema's class (or script path, for unnamed GDScript classes).
+	#[func]
+	pub fn register_schema(&mut self, schema: Gd<GodotSchema>, version: i64) {
+		let class_name = schema.bind().schema_name();
+		self.classes.insert(class_name, (schema, version));
+	}
+
+	/// Registers `migration`, which upgrades the JSON of class `class_name` from `from_version` to `to_version`.
+	///
+	/// `migration` receives the JSON as a `Dictionary` (without the `$class`/`$version` keys) and must return the upgraded `Dictionary`.
+	/// Registering a migration from the same version twice replaces the previous one.
+	///
+	/// # Returns
+	/// - `null`, if successful.
+	/// - Otherwise a `String` containing the error message.
+	#[func]
+	pub fn register_migration(&mut self, class_name: String, from_version: i64, to_version: i64, migration: Callable) -> Variant {
+		if to_version <= from_version {
+			return format!("Expected `to_version` ({to_version}) to be greater than `from_version` ({from_version}).").to_variant();
+		}
+
+		self.migrations.insert((class_name, from_version), (to_version, migration));
+		Variant::nil()
+	}
+
+	/// # Returns
+	/// The version `class_name` was registered with, or `-1` if it was not registered.
+	#[func]
+	pub fn get_version(&self, class_name: String) -> i64 {
+		self.classes.get(&class_name).map_or(-1, |(_, version)| *version)
+	}
+
+	/// Converts `instance` into JSON with its class's current schema, adding the `$class` and `$version` keys.
+	///
+	/// # Returns
+	/// - The JSON, if successful.
+	/// - Otherwise an empty `String`, the error is printed with `godot_error!`.
+	#[func]
+	pub fn serialize_versioned(&self, class_name: String, instance: Variant) -> String {
+		let try_fn = || -> Result<String> {
+			Ok(serde_json::to_string(&self.versioned_json(&class_name, &instance)?)?)
+		};
+
+		match try_fn() {
+			Ok(json) => json,
+			Err(err) => {
+				godot_error!("{err:?}");
+				String::default()
+			}
+		}
+	}
+
+	/// Reads the `$version` (and `$class`) keys of `json`, runs the chain of migrations up to the class's current version,
+	/// then validates and instantiates the result against the class's current schema.
+	///
+	/// `$class` may be omitted if only one class is registered.
+	///
+	/// # Returns
+	/// - The instantiated type, if successful.
+	/// - Otherwise a `String` containing the error message.
+	#[func]
+	pub fn instantiate_versioned(&self, json: String) -> Variant {
+		let try_fn = || -> Result<Variant> {
+			let (schema, value) = self.migrate(serde_json::from_str(&json)?)?;
+			Ok(schema.bind().instantiate_value(&value)?)
+		};
+
+		match try_fn() {
+			Ok(instance) => instance,
+			Err(err) => format!("{err:?}").to_variant(),
+		}
+	}
+}
+
+impl SchemaMigrator {
+	/// Converts `instance` into JSON with the current schema of `class_name`, adding the `$class` and `$version` keys.
+	pub fn versioned_json(&self, class_name: &str, instance: &Variant) -> Result<Value> {
+		let (schema, version) = self.find_class(class_name)?;
+
+		let Value::Object(mut properties) = schema.bind().inner.json_from_instance(instance)?
+		else { bail!("Expected class \"{class_name}\" to be serialized as a JSON object.") };
+
+		properties.insert(MIGRATION_CLASS_KEY.to_owned(), class_name.into());
+		properties.insert(MIGRATION_VERSION_KEY.to_owned(), (*version).into());
+
+		Ok(Value::Object(properties))
+	}
+
+	/// Upgrades `value` to the current version of its class.
+	///
+	/// # Returns
+	/// The class's current schema and the upgraded JSON, without the `$class`/`$version` keys.
+	pub fn migrate(&self, value: Value) -> Result<(Gd<GodotSchema>, Value)> {
+		let Value::Object(mut properties) = value
+		else { bail!("Expected versioned JSON to be an object.") };
+
+		let class_name = match properties.remove(MIGRATION_CLASS_KEY) {
+			Some(Value::String(class_name)) => class_name,
+			Some(other) => bail!("Expected \"{MIGRATION_CLASS_KEY}\" to be a string.\nGot: {other}"),
+			None if self.classes.len() == 1 => self.classes.keys().next().cloned().unwrap_or_default(),
+			None => bail!("Expected key \"{MIGRATION_CLASS_KEY}\", more than one class is registered."),
+		};
+
+		let mut version = properties
+			.remove(MIGRATION_VERSION_KEY)
+			.as_ref()
+			.and_then(Value::as_i64)
+			.ok_or_else(|| anyhow!("Expected key \"{MIGRATION_VERSION_KEY}\" to be an integer."))?;
+
+		let (schema, current_version) = self.find_class(&class_name)?;
+
+		if version > *current_version {
+			bail!("Version {version} of class \"{class_name}\" is newer than its current version ({current_version}).");
+		}
+
+		let mut value = Value::Object(properties);
+
+		while version < *current_version {
+			let Some((to_version, migration)) = self.migrations.get(&(class_name.clone(), version))
+			else { bail!("No migration registered from version {version} of class \"{class_name}\".") };
+
+			let migrated = migration.call(&[raw_variant_from_json(&value)?]);
+
+			if migrated.get_type() != VariantType::DICTIONARY {
+				bail!("Expected the migration of class \"{class_name}\" from version {version} to return a Dictionary.\nGot: {migrated}");
+			}
+
+			value = raw_json_from_variant(&migrated)?;
+			version = *to_version;
+		}
+
+		if version != *current_version {
+			bail!("The migrations of class \"{class_name}\" skip over its current version ({current_version}), reaching version {version}.");
+		}
+
+		Ok((schema.clone(), value))
+	}
+
+	/// The current schema and version of `class_name`.
+	pub fn find_class(&self, class_name: &str) -> Result<&(Gd<GodotSchema>, i64)> {
+		self.classes
+			.get(class_name)
+			.ok_or_else(|| anyhow!("Class \"{class_name}\" is not registered."))
+	}
+}
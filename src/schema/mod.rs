@@ -5,18 +5,102 @@ pub use types::*;
 pub use type_resolving::*;
 pub use definition::*;
 pub use godot_schema::*;
+pub use validation::*;
+pub use tools::*;
+pub use confidence::*;
+pub use streaming::*;
+pub use scene_serializer::*;
+pub use repair::*;
+pub use examples::*;
+pub use evaluator::*;
+pub use result::*;
+pub use error::*;
+pub use telemetry::*;
+pub use plan::*;
+pub use worker_pool::*;
+pub use undo::*;
+pub use instantiate_options::*;
+pub use persistence::*;
+pub use verification::*;
+pub use variant_encoding::*;
+pub use import::*;
+pub use deserialization::*;
+pub use type_annotations::*;
+pub use inference::*;
+pub use shape::*;
+pub use editing::*;
+pub use merge::*;
+pub use fingerprint::*;
+pub use migration::*;
+pub use save_manager::*;
+pub use json_patch::*;
+pub use diff::*;
+pub use pointer::*;
+pub use fuzz::*;
+pub use message_registry::*;
+#[cfg(feature = "llm_client")]
+pub use llm_client::*;
+pub use def_store::*;
+pub use property_naming::*;
+pub use introspection::*;
+#[cfg(feature = "editor")]
+pub use editor_plugin::*;
+pub use logging::*;
 
 pub mod builder;
 pub mod types;
 pub mod type_resolving;
 pub mod definition;
 pub mod godot_schema;
+pub mod gbnf;
+pub mod validation;
+pub mod tools;
+pub mod confidence;
+pub mod streaming;
+pub mod scene_serializer;
+pub mod repair;
+pub mod examples;
+pub mod evaluator;
+pub mod result;
+pub mod error;
+pub mod telemetry;
+pub mod plan;
+pub mod worker_pool;
+pub mod undo;
+pub mod instantiate_options;
+pub mod persistence;
+pub mod verification;
+pub mod variant_encoding;
+pub mod import;
+pub mod deserialization;
+pub mod type_annotations;
+pub mod inference;
+pub mod shape;
+pub mod editing;
+pub mod merge;
+pub mod fingerprint;
+pub mod migration;
+pub mod save_manager;
+pub mod json_patch;
+pub mod diff;
+pub mod pointer;
+pub mod fuzz;
+pub mod message_registry;
+#[cfg(feature = "llm_client")]
+pub mod llm_client;
+pub mod prompt_text;
+pub mod def_store;
+pub mod property_naming;
+pub mod introspection;
+#[cfg(feature = "editor")]
+pub mod editor_plugin;
+pub mod logging;
 
 trait SerializeFields {
 	fn serialize_fields<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error>;
 }
 
-fn try_get<T: FromGodot>(dict: &Dictionary, key: &str) -> Result<T> {
+pub(crate) fn try_get<T: FromGodot>(dict: &Dictionary, key: &str) -> Result<T> {
 	dict.get(key)
 		.ok_or_else(|| anyhow!("Expected key `name` in property dictionary"))?
 		.try_to()
@@ -0,0 +1,554 @@
+use super::*;
+
+/// Bumped whenever the persisted format changes, files of other versions are rejected.
+pub const PERSISTENCE_VERSION: i64 = 1;
+
+/// Encodes `schemas` in the format read by [`schemas_from_persisted()`].
+///
+/// Unlike the JSON Schema output, the encoding is lossless: it keeps enum values, class identities
+/// (engine class name or script path) and the schema options.
+pub fn schemas_to_persisted<'a>(schemas: impl Iterator<Item = &'a RootSchema>) -> Result<Value> {
+	let schemas = schemas.map(RootSchema::to_persisted).try_collect::<_, Vec<_>, _>()?;
+
+	Ok(serde_json::json!({
+		"version": PERSISTENCE_VERSION,
+		"schemas": schemas,
+	}))
+}
+
+/// The reverse of [`schemas_to_persisted()`].
+///
+/// Scripts are loaded from their paths, their property lists are not fetched.
+pub fn schemas_from_persisted(value: &Value) -> Result<Vec<RootSchema>> {
+	let version = value.get("version").and_then(Value::as_i64);
+
+	if version != Some(PERSISTENCE_VERSION) {
+		bail!("Expected persisted schemas of version {PERSISTENCE_VERSION}.\nGot: {version:?}");
+	}
+
+	value.get("schemas")
+		.and_then(Value::as_array)
+		.ok_or_else(|| anyhow!("Expected key \"schemas\" to be an array."))?
+		.iter()
+		.enumerate()
+		.map(|(idx, schema)| RootSchema::from_persisted(schema).map_err(|err| anyhow!("Schema {idx}: {err}")))
+		.try_collect()
+}
+
+impl RootSchema {
+	pub fn to_persisted(&self) -> Result<Value> {
+		let defs = self.defs
+			.iter()
+			.map(|(name, def)| Ok((name.clone(), def.to_persisted()?)))
+			.try_collect::<_, Map<_, _>, anyhow::Error>()?;
+
+		Ok(serde_json::json!({
+			"defs": defs,
+			"base": self.base.to_persisted()?,
+			"confidence_suffix": self.options.confidence_suffix,
+			"tagged_variants": self.options.variant_encoding == VariantEncoding::Tagged,
+			"type_annotations": self.options.type_annotations,
+			"round_trip_annotations": self.options.round_trip_annotations,
+			"integer_enums": self.options.integer_enums,
+			"compact_math": self.options.representation.compact_math,
+			"hex_colors": self.options.representation.hex_colors,
+			"friendly_transforms": self.options.representation.friendly_transforms,
+			"euler_quaternions": self.options.representation.euler_quaternions,
+			"id": self.options.id,
+			"omit_meta_schema": self.options.omit_meta_schema,
+			"external_refs": self.options.external_refs,
+			"property_naming": match &self.options.property_naming {
+				PropertyNaming::AsIs => Value::Null,
+				PropertyNaming::CamelCase => "camelCase".into(),
+				PropertyNaming::Custom(names) => serde_json::to_value(names)?,
+			},
+			"accept_godot_names": self.options.accept_godot_names,
+			"wrapper_key": self.options.wrapper_key,
+			"unwrapped_root": self.options.unwrapped_root,
+			"class_inheritance": self.options.class_inheritance,
+			"skip_private_properties": self.options.skip_private_properties,
+			"unsupported_property_policy": self.options.unsupported_property_policy.to_godot(),
+			"resource_path_dirs": self.options.resource_path_dirs,
+			"scene_instancing": self.options.scene_instancing,
+			"script_defaults": self.options.script_defaults,
+			"format_assertion": self.options.validator.format_assertion,
+			"max_pattern_length": self.options.validator.max_pattern_length,
+		}))
+	}
+
+	pub fn from_persisted(value: &Value) -> Result<Self> {
+		let defs = value.get("defs")
+			.and_then(Value::as_object)
+			.ok_or_else(|| anyhow!("Expected key \"defs\" to be an object."))?
+			.iter()
+			.map(|(name, def)| {
+				Definition::from_persisted(def)
+					.map(|def| (name.clone(), def))
+					.map_err(|err| anyhow!("Definition \"{name}\": {err}"))
+			})
+			.try_collect()?;
+
+		let base = Definition::from_persisted(field(value, "base")?)?;
+
+		let confidence_suffix = match value.get("confidence_suffix") {
+			None | Some(Value::Null) => None,
+			Some(Value::String(suffix)) => Some(suffix.clone()),
+			Some(other) => bail!("Expected key \"confidence_suffix\" to be a string.\nGot: {other}"),
+		};
+
+		let variant_encoding = match value.get("tagged_variants").and_then(Value::as_bool) {
+			Some(true) => VariantEncoding::Tagged,
+			_ => VariantEncoding::Plain,
+		};
+
+		let type_annotations = value.get("type_annotations").and_then(Value::as_bool).unwrap_or_default();
+		let integer_enums = value.get("integer_enums").and_then(Value::as_bool).unwrap_or_default();
+
+		let representation = VariantRepresentation {
+			compact_math: value.get("compact_math").and_then(Value::as_bool).unwrap_or_default(),
+			hex_colors: value.get("hex_colors").and_then(Value::as_bool).unwrap_or_default(),
+			friendly_transforms: value.get("friendly_transforms").and_then(Value::as_bool).unwrap_or_default(),
+			euler_quaternions: value.get("euler_quaternions").and_then(Value::as_bool).unwrap_or_default(),
+		};
+
+		let id = match value.get("id") {
+			None | Some(Value::Null) => None,
+			Some(Value::String(id)) => Some(id.clone()),
+			Some(other) => bail!("Expected key \"id\" to be a string.\nGot: {other}"),
+		};
+
+		let omit_meta_schema = value.get("omit_meta_schema").and_then(Value::as_bool).unwrap_or_default();
+
+		// The documents they resolve to are provided by the library, see `SchemaLibrary::link_shared_definitions()`.
+		let external_refs = match value.get("external_refs") {
+			None | Some(Value::Null) => BTreeMap::new(),
+			Some(refs) => serde_json::from_value(refs.clone())
+				.map_err(|err| anyhow!("Expected key \"external_refs\" to map definition names to URIs.\nGot error: {err}"))?,
+		};
+
+		let wrapper_key = match value.get("wrapper_key") {
+			None | Some(Value::Null) => None,
+			Some(Value::String(key)) => Some(key.clone()),
+			Some(other) => bail!("Expected key \"wrapper_key\" to be a string.\nGot: {other}"),
+		};
+
+		let property_naming = match value.get("property_naming") {
+			None | Some(Value::Null) => PropertyNaming::AsIs,
+			Some(Value::String(policy)) if policy == "camelCase" => PropertyNaming::CamelCase,
+			Some(names @ Value::Object(_)) => PropertyNaming::Custom(serde_json::from_value(names.clone())
+				.map_err(|err| anyhow!("Expected key \"property_naming\" to map property names to strings.\nGot error: {err}"))?),
+			Some(other) => bail!("Expected key \"property_naming\" to be \"camelCase\" or an object.\nGot: {other}"),
+		};
+
+		Ok(RootSchema {
+			defs,
+			base,
+			options: SchemaOptions {
+				confidence_suffix,
+				variant_encoding,
+				type_annotations,
+				round_trip_annotations: value.get("round_trip_annotations").and_then(Value::as_bool).unwrap_or_default(),
+				integer_enums,
+				representation,
+				id,
+				omit_meta_schema,
+				external_refs,
+				external_documents: Default::default(),
+				property_naming,
+				accept_godot_names: value.get("accept_godot_names").and_then(Value::as_bool).unwrap_or_default(),
+				wrapper_key,
+				unwrapped_root: value.get("unwrapped_root").and_then(Value::as_bool).unwrap_or_default(),
+				class_inheritance: value.get("class_inheritance").and_then(Value::as_bool).unwrap_or_default(),
+				skip_private_properties: value.get("skip_private_properties").and_then(Value::as_bool).unwrap_or_default(),
+				unsupported_property_policy: value.get("unsupported_property_policy")
+					.and_then(Value::as_i64)
+					.and_then(|policy| UnsupportedPropertyPolicy::try_from_godot(policy).ok())
+					.unwrap_or_default(),
+				resource_path_dirs: match value.get("resource_path_dirs") {
+					None | Some(Value::Null) => None,
+					Some(dirs) => Some(serde_json::from_value(dirs.clone())?),
+				},
+				scene_instancing: value.get("scene_instancing").and_then(Value::as_bool).unwrap_or_default(),
+				script_defaults: value.get("script_defaults").and_then(Value::as_bool).unwrap_or_default(),
+				validator: ValidatorOptions {
+					format_assertion: value.get("format_assertion").and_then(Value::as_bool),
+					max_pattern_length: value.get("max_pattern_length").and_then(Value::as_u64).map(|max| max as usize),
+					callables: None,
+				},
+			},
+			warnings: Vec::new(),
+			name_map: None,
+		})
+	}
+}
+
+impl Definition {
+	pub fn to_persisted(&self) -> Result<Value> {
+		let mut map = Map::new();
+
+		match self {
+			Definition::Null(_) => { map.insert("kind".into(), "null".into()); }
+			Definition::Boolean(_) => { map.insert("kind".into(), "boolean".into()); }
+			Definition::Integer(_) => { map.insert("kind".into(), "integer".into()); }
+			Definition::Number(_) => { map.insert("kind".into(), "number".into()); }
+			Definition::String(JString { pattern, .. }) => {
+				map.insert("kind".into(), "string".into());
+				map.insert("pattern".into(), serde_json::to_value(pattern)?);
+			}
+			Definition::Any(_) => { map.insert("kind".into(), "any".into()); }
+			Definition::Object(JObject { properties, optional, dependent_required, values_ty, .. }) => {
+				map.insert("kind".into(), "object".into());
+				map.insert("properties".into(), properties_to_persisted(properties)?);
+				map.insert("optional".into(), serde_json::to_value(optional)?);
+				map.insert("dependent_required".into(), serde_json::to_value(dependent_required)?);
+
+				if let Some(ty) = values_ty {
+					map.insert("values".into(), ty.to_persisted()?);
+				}
+			}
+			Definition::Array(JArray { items_ty, min_items, max_items, unique_items, contains, .. }) => {
+				map.insert("kind".into(), "array".into());
+
+				if let Some(ty) = items_ty {
+					map.insert("items".into(), ty.to_persisted()?);
+				}
+
+				if let Some(min_items) = min_items {
+					map.insert("min_items".into(), (*min_items).into());
+				}
+
+				if let Some(max_items) = max_items {
+					map.insert("max_items".into(), (*max_items).into());
+				}
+
+				map.insert("unique_items".into(), (*unique_items).into());
+
+				if let Some(contains) = contains {
+					map.insert("contains".into(), contains.to_persisted()?);
+				}
+			}
+			Definition::Tuple(JTuple { items, .. }) => {
+				map.insert("kind".into(), "tuple".into());
+				map.insert("items".into(), Value::Array(items.iter().map(Type::to_persisted).try_collect()?));
+			}
+			Definition::Enum(JEnum { variants, variant_descriptions, .. }) => {
+				map.insert("kind".into(), "enum".into());
+				map.insert("variants".into(), serde_json::to_value(variants)?);
+				map.insert("variant_descriptions".into(), serde_json::to_value(variant_descriptions)?);
+			}
+			Definition::Class(JClass { properties, optional, source, type_infos, constraints, base, extensible, aliases, .. }) => {
+				map.insert("kind".into(), "class".into());
+				map.insert("source".into(), source.to_persisted()?);
+				map.insert("properties".into(), properties_to_persisted(properties)?);
+				map.insert("optional".into(), serde_json::to_value(optional)?);
+
+				let type_infos = type_infos
+					.iter()
+					.map(|(name, info)| {
+						let mut annotations = Map::new();
+						info.insert_annotations(&mut annotations);
+						(name.clone(), Value::Object(annotations))
+					})
+					.collect::<Map<_, _>>();
+
+				map.insert("type_infos".into(), Value::Object(type_infos));
+				map.insert("constraints".into(), serde_json::to_value(constraints)?);
+				map.insert("extensible".into(), (*extensible).into());
+				map.insert("aliases".into(), serde_json::to_value(aliases)?);
+
+				if let Some(ClassBase { reference, inherited }) = base {
+					map.insert("base".into(), reference.name.clone().into());
+					map.insert("inherited".into(), serde_json::to_value(inherited)?);
+				}
+			}
+			Definition::ResourcePath(JResourcePath { source, allowed_dirs, extensions, instance_scene, .. }) => {
+				map.insert("kind".into(), "resource_path".into());
+				map.insert("source".into(), source.to_persisted()?);
+				map.insert("allowed_dirs".into(), serde_json::to_value(allowed_dirs)?);
+				map.insert("extensions".into(), serde_json::to_value(extensions)?);
+				map.insert("instance_scene".into(), (*instance_scene).into());
+			}
+			Definition::Variant(var_def) => {
+				map.insert("kind".into(), "variant".into());
+				map.insert("name".into(), var_def.name().into());
+			}
+			Definition::Conditional(JConditional { if_, then, else_, .. }) => {
+				map.insert("kind".into(), "conditional".into());
+				map.insert("if".into(), if_.to_persisted()?);
+
+				if let Some(then) = then {
+					map.insert("then".into(), then.to_persisted()?);
+				}
+
+				if let Some(else_) = else_ {
+					map.insert("else".into(), else_.to_persisted()?);
+				}
+			}
+			Definition::OneOf(JOneOf { options, .. }) => {
+				map.insert("kind".into(), "one_of".into());
+				map.insert("options".into(), options.iter().map(Type::to_persisted).try_collect::<_, Vec<_>, _>()?.into());
+			}
+		}
+
+		if let Some((const_value, default)) = self.value_keywords() {
+			if let Some(const_value) = const_value {
+				map.insert("const".into(), const_value.clone());
+			}
+
+			if let Some(default) = default {
+				map.insert("default".into(), default.clone());
+			}
+		}
+
+		if let Some(description) = self.description() {
+			map.insert("description".into(), description.clone().into());
+		}
+
+		Ok(Value::Object(map))
+	}
+
+	pub fn from_persisted(value: &Value) -> Result<Self> {
+		let kind = field(value, "kind")?
+			.as_str()
+			.ok_or_else(|| anyhow!("Expected key \"kind\" to be a string."))?;
+
+		let mut def = match kind {
+			"null" => Definition::null(),
+			"boolean" => Definition::boolean(),
+			"integer" => Definition::integer(),
+			"number" => Definition::number(),
+			"string" => JString {
+				description: None,
+				pattern: value.get("pattern").and_then(Value::as_str).map(str::to_owned),
+				const_value: None,
+				default: None,
+			}.into(),
+			"any" => Definition::any(),
+			"object" => JObject {
+				description: None,
+				properties: properties_from_persisted(field(value, "properties")?)?,
+				optional: optional_from_persisted(value)?,
+				dependent_required: match value.get("dependent_required") {
+					None => BTreeMap::new(),
+					Some(dependent_required) => serde_json::from_value(dependent_required.clone())?,
+				},
+				const_value: None,
+				default: None,
+				values_ty: value.get("values").map(Type::from_persisted).transpose()?.map(Box::new),
+			}.into(),
+			"array" => JArray {
+				description: None,
+				items_ty: value.get("items").map(Type::from_persisted).transpose()?.map(Box::new),
+				min_items: value.get("min_items").and_then(Value::as_u64).map(|len| len as usize),
+				max_items: value.get("max_items").and_then(Value::as_u64).map(|len| len as usize),
+				unique_items: value.get("unique_items").and_then(Value::as_bool).unwrap_or_default(),
+				contains: value.get("contains").map(Type::from_persisted).transpose()?.map(Box::new),
+			}.into(),
+			"tuple" => {
+				let items = field(value, "items")?
+					.as_array()
+					.ok_or_else(|| anyhow!("Expected key \"items\" to be an array."))?
+					.iter()
+					.map(Type::from_persisted)
+					.try_collect::<_, Vec<_>, _>()?;
+
+				JTuple::new(items).into()
+			}
+			"enum" => JEnum {
+				description: None,
+				variants: serde_json::from_value(field(value, "variants")?.clone())?,
+				variant_descriptions: match value.get("variant_descriptions") {
+					None => BTreeMap::new(),
+					Some(descriptions) => serde_json::from_value(descriptions.clone())?,
+				},
+			}.into(),
+			"class" => JClass {
+				description: None,
+				properties: properties_from_persisted(field(value, "properties")?)?,
+				optional: optional_from_persisted(value)?,
+				source: ClassSource::from_persisted(field(value, "source")?)?,
+				type_infos: type_infos_from_persisted(value.get("type_infos"))?,
+				constraints: match value.get("constraints") {
+					None => BTreeMap::new(),
+					Some(constraints) => serde_json::from_value(constraints.clone())?,
+				},
+				base: match value.get("base").and_then(Value::as_str) {
+					None => None,
+					Some(name) => Some(ClassBase {
+						reference: JRef::new(name),
+						inherited: serde_json::from_value(field(value, "inherited")?.clone())?,
+					}),
+				},
+				extensible: value.get("extensible").and_then(Value::as_bool).unwrap_or_default(),
+				aliases: match value.get("aliases") {
+					None => BTreeMap::new(),
+					Some(aliases) => serde_json::from_value(aliases.clone())?,
+				},
+			}.into(),
+			"resource_path" => JResourcePath {
+				extensions: serde_json::from_value(field(value, "extensions")?.clone())?,
+				instance_scene: value.get("instance_scene").and_then(Value::as_bool).unwrap_or_default(),
+				..JResourcePath::new(
+					ClassSource::from_persisted(field(value, "source")?)?,
+					serde_json::from_value::<Vec<String>>(field(value, "allowed_dirs")?.clone())?,
+				)
+			}.into(),
+			"variant" => {
+				let name = field(value, "name")?.as_str().unwrap_or_default();
+
+				VariantDefinition::try_from_name(name)
+					.ok_or_else(|| anyhow!("Unknown variant type \"{name}\"."))?
+					.into()
+			}
+			"conditional" => JConditional {
+				description: None,
+				if_: Box::new(Type::from_persisted(field(value, "if")?)?),
+				then: value.get("then").map(Type::from_persisted).transpose()?.map(Box::new),
+				else_: value.get("else").map(Type::from_persisted).transpose()?.map(Box::new),
+			}.into(),
+			"one_of" => JOneOf::new(
+				field(value, "options")?
+					.as_array()
+					.ok_or_else(|| anyhow!("Expected key \"options\" to be an array."))?
+					.iter()
+					.map(Type::from_persisted)
+					.try_collect::<_, Vec<_>, _>()?,
+			).into(),
+			other => bail!("Unknown definition kind \"{other}\"."),
+		};
+
+		if let Some((const_value, default)) = def.value_keywords_mut() {
+			*const_value = value.get("const").cloned();
+			*default = value.get("default").cloned();
+		}
+
+		if let Some(Value::String(description)) = value.get("description")
+			&& !matches!(def, Definition::Variant(_)) {
+			def.add_description(description.clone());
+		}
+
+		Ok(def)
+	}
+}
+
+impl Type {
+	pub fn to_persisted(&self) -> Result<Value> {
+		match self {
+			Type::Definition(def) => def.to_persisted(),
+			Type::Ref(JRef { name, description }) => {
+				let mut map = Map::new();
+				map.insert("kind".into(), "ref".into());
+				map.insert("name".into(), name.clone().into());
+
+				if let Some(description) = description {
+					map.insert("description".into(), description.clone().into());
+				}
+
+				Ok(Value::Object(map))
+			}
+		}
+	}
+
+	pub fn from_persisted(value: &Value) -> Result<Self> {
+		if value.get("kind").and_then(Value::as_str) != Some("ref") {
+			return Definition::from_persisted(value).map(Type::Definition);
+		}
+
+		let name = field(value, "name")?
+			.as_str()
+			.ok_or_else(|| anyhow!("Expected key \"name\" to be a string."))?;
+
+		let mut reference = JRef::new(name);
+
+		if let Some(Value::String(description)) = value.get("description") {
+			reference.add_description(description.clone());
+		}
+
+		Ok(Type::Ref(reference))
+	}
+}
+
+impl ClassSource {
+	/// Engine classes are persisted by name, scripts by path.
+	pub fn to_persisted(&self) -> Result<Value> {
+		match self {
+			ClassSource::Engine(class_name) => Ok(serde_json::json!({ "engine": class_name.to_string() })),
+			| ClassSource::ScriptNamed(script, _)
+			| ClassSource::ScriptUnnamed(script) => {
+				let path = script.get_path().to_string();
+
+				if path.is_empty() || path.contains("::") {
+					bail!("Built-in scripts cannot be persisted, save the script \"{}\" to its own file.", self.definition_name());
+				}
+
+				Ok(serde_json::json!({ "script": path }))
+			}
+		}
+	}
+
+	pub fn from_persisted(value: &Value) -> Result<Self> {
+		if let Some(class_name) = value.get("engine").and_then(Value::as_str) {
+			return Ok(ClassSource::Engine(class_name.into()));
+		}
+
+		let path = value.get("script")
+			.and_then(Value::as_str)
+			.ok_or_else(|| anyhow!("Expected class source to have either an \"engine\" or a \"script\" key."))?;
+
+		let script = ResourceLoader::singleton()
+			.load(path)
+			.ok_or_else(|| SchemaError::Io { path: path.to_owned(), message: "Could not load script.".to_owned() })?
+			.try_cast::<Script>()
+			.map_err(|err| anyhow!("{err:?}"))?;
+
+		Ok(ClassSource::from_script(script))
+	}
+}
+
+fn properties_to_persisted(properties: &IndexMap<String, Type>) -> Result<Value> {
+	properties
+		.iter()
+		.map(|(name, ty)| Ok((name.clone(), ty.to_persisted()?)))
+		.try_collect::<_, Map<_, _>, anyhow::Error>()
+		.map(Value::Object)
+}
+
+fn properties_from_persisted(value: &Value) -> Result<IndexMap<String, Type>> {
+	value.as_object()
+		.ok_or_else(|| anyhow!("Expected properties to be an object."))?
+		.iter()
+		.map(|(name, ty)| {
+			Type::from_persisted(ty)
+				.map(|ty| (name.clone(), ty))
+				.map_err(|err| anyhow!("Property \"{name}\": {err}"))
+		})
+		.try_collect()
+}
+
+fn optional_from_persisted(value: &Value) -> Result<BTreeSet<String>> {
+	match value.get("optional") {
+		None => Ok(BTreeSet::new()),
+		Some(optional) => Ok(serde_json::from_value(optional.clone())?),
+	}
+}
+
+fn type_infos_from_persisted(value: Option<&Value>) -> Result<BTreeMap<String, GodotTypeInfo>> {
+	let Some(value) = value
+	else { return Ok(BTreeMap::new()) };
+
+	value.as_object()
+		.ok_or_else(|| anyhow!("Expected key \"type_infos\" to be an object."))?
+		.iter()
+		.map(|(name, annotations)| {
+			annotations
+				.as_object()
+				.and_then(GodotTypeInfo::from_annotations)
+				.map(|info| (name.clone(), info))
+				.ok_or_else(|| anyhow!("Property \"{name}\": invalid type info.\nGot: {annotations}"))
+		})
+		.try_collect()
+}
+
+fn field<'a>(value: &'a Value, key: &str) -> Result<&'a Value> {
+	value.get(key).ok_or_else(|| anyhow!("Expected key \"{key}\" to be present.\nGot: {value}"))
+}
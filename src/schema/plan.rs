@@ -0,0 +1,243 @@
+use super::*;
+
+/// The pure-data result of converting JSON input: which classes to construct and the values of their properties.
+///
+/// Creating a plan does not construct any object, allowing the changes to be inspected (or approved by the player)
+/// before [`GodotSchema::apply()`] performs them, for example at a safe point of the frame.
+#[derive(GodotClass)]
+#[class(no_init, base = RefCounted)]
+pub struct InstantiationPlan {
+	pub root: PlanNode,
+}
+
+#[derive(Clone, Debug)]
+pub enum PlanNode {
+	/// A value that does not contain objects, already converted.
+	Value(Variant),
+	Object {
+		source: ClassSource,
+		properties: Vec<(String, PlanNode)>,
+	},
+	Dictionary(Vec<(String, PlanNode)>),
+	Array {
+		/// An empty array of the element type, elements are appended to a copy of it.
+		empty: Variant,
+		items: Vec<PlanNode>,
+	},
+	Tuple(Vec<PlanNode>),
+}
+
+#[godot_api]
+impl InstantiationPlan {
+	/// Returns the plan as nested Godot values, for displaying or inspecting it.
+	///
+	/// Objects are represented as a `Dictionary` with the keys "class" (the class name) and "properties" (property name => value).
+	#[func]
+	pub fn to_dictionary(&self) -> Variant {
+		self.root.describe()
+	}
+
+	/// Returns the name of the class of every object the plan constructs, once per object.
+	#[func]
+	pub fn get_classes(&self) -> PackedStringArray {
+		let mut classes = Vec::new();
+		self.root.insert_classes(&mut classes);
+		classes.into_iter().collect()
+	}
+}
+
+impl Definition {
+	/// Same as [`Self::instantiate()`], but returns a plan for creating the value instead of creating it.
+	pub fn plan(&self, value: &Value, defs: &BTreeMap<String, Definition>) -> Result<PlanNode> {
+		match (self, value) {
+			(Definition::Class(class), Value::Object(property_values)) => {
+				let mut properties = Vec::with_capacity(property_values.len());
+
+				for (name, value) in property_values {
+					let node = class.properties
+						.get(name)
+						.ok_or_else(|| anyhow!("Expected property \"{name}\" to be in `properties` map."))?
+						.resolve(defs)?
+						.plan(value, defs)
+						.map_err(|err| SchemaError::at(err, name))?;
+
+					properties.push((name.clone(), node));
+				}
+
+				Ok(PlanNode::Object { source: class.source.clone(), properties })
+			}
+			(Definition::Object(object), Value::Object(property_values)) if !object.properties.is_empty() => {
+				let mut entries = Vec::with_capacity(object.properties.len());
+
+				for (name, ty) in &object.properties {
+					let Some(value) = property_values.get(name)
+					else {
+						if object.optional.contains(name) {
+							continue;
+						}
+
+						bail!("Expected property \"{name}\" to be in `properties` map.");
+					};
+
+					let node = ty
+						.resolve(defs)?
+						.plan(value, defs)
+						.map_err(|err| SchemaError::at(err, name))?;
+
+					entries.push((name.clone(), node));
+				}
+
+				Ok(PlanNode::Dictionary(entries))
+			}
+			(Definition::Object(JObject { values_ty: Some(values_ty), .. }), Value::Object(entries)) => {
+				let values_def = values_ty.resolve(defs)?;
+
+				let entries = entries
+					.iter()
+					.map(|(key, json)| Ok((key.clone(), values_def.plan(json, defs).map_err(|err| SchemaError::at(err, key))?)))
+					.try_collect::<_, Vec<_>, anyhow::Error>()?;
+
+				Ok(PlanNode::Dictionary(entries))
+			}
+			(Definition::Array(array @ JArray { items_ty: Some(ty), .. }), Value::Array(vec)) => {
+				array.check_elements(vec, defs)?;
+
+				let item_def = ty.resolve(defs)?;
+
+				let items = vec
+					.iter()
+					.enumerate()
+					.map(|(idx, json)| item_def.plan(json, defs).map_err(|err| SchemaError::at(err, idx)))
+					.try_collect()?;
+
+				Ok(PlanNode::Array { empty: new_array_from_def(item_def)?, items })
+			}
+			(Definition::Tuple(JTuple { items, .. }), Value::Array(vec)) => {
+				if items.len() != vec.len() {
+					bail!("Expected JSON array to have {} elements.\nGot: {}", items.len(), vec.len());
+				}
+
+				let nodes = items
+					.iter()
+					.zip(vec)
+					.enumerate()
+					.map(|(idx, (ty, json))| ty.resolve(defs)?.plan(json, defs).map_err(|err| SchemaError::at(err, idx)))
+					.try_collect()?;
+
+				Ok(PlanNode::Tuple(nodes))
+			}
+			// Everything else cannot contain objects.
+			_ => self.instantiate(value, defs).map(PlanNode::Value),
+		}
+	}
+}
+
+impl PlanNode {
+	/// Constructs the objects and assembles the values described by this plan.
+	pub fn apply(&self) -> Result<Variant> {
+		Ok(match self {
+			PlanNode::Value(variant) => variant.clone(),
+			PlanNode::Object { source, properties } => {
+				let mut values = properties
+					.iter()
+					.map(|(name, node)| Ok((name.clone(), node.apply()?)))
+					.try_collect::<_, BTreeMap<_, _>, anyhow::Error>()?;
+
+				let mut gd = source.construct_from(&mut values)?;
+
+				// Same as `JClass::instantiate()`, the class takes over assigning its values.
+				if gd.has_method(FROM_JSON_HOOK) {
+					let data = values.into_iter().collect::<Dictionary>();
+					gd.call(FROM_JSON_HOOK, &[data.to_variant()]);
+					return Ok(gd.to_variant());
+				}
+
+				for (name, variant) in values {
+					assign_property(&mut gd, &name, variant);
+				}
+
+				gd.to_variant()
+			}
+			PlanNode::Dictionary(entries) => {
+				let mut dict = Dictionary::new();
+
+				for (name, node) in entries {
+					dict.set(name.as_str(), node.apply()?);
+				}
+
+				dict.to_variant()
+			}
+			PlanNode::Array { empty, items } => {
+				let array = empty.call("duplicate", &[]);
+
+				for node in items {
+					array.call("push_back", &[node.apply()?]);
+				}
+
+				array
+			}
+			PlanNode::Tuple(items) => {
+				let mut array = VariantArray::new();
+
+				for node in items {
+					array.push(&node.apply()?);
+				}
+
+				array.to_variant()
+			}
+		})
+	}
+
+	fn describe(&self) -> Variant {
+		match self {
+			PlanNode::Value(variant) => variant.clone(),
+			PlanNode::Object { source, properties } => {
+				let mut property_dict = Dictionary::new();
+
+				for (name, node) in properties {
+					property_dict.set(name.as_str(), node.describe());
+				}
+
+				let mut dict = Dictionary::new();
+				dict.set("class", source.definition_name());
+				dict.set("properties", property_dict);
+				dict.to_variant()
+			}
+			PlanNode::Dictionary(entries) => {
+				let mut dict = Dictionary::new();
+
+				for (name, node) in entries {
+					dict.set(name.as_str(), node.describe());
+				}
+
+				dict.to_variant()
+			}
+			| PlanNode::Array { items, .. }
+			| PlanNode::Tuple(items) => items.iter().map(PlanNode::describe).collect::<VariantArray>().to_variant(),
+		}
+	}
+
+	fn insert_classes(&self, fill_me: &mut Vec<GString>) {
+		match self {
+			PlanNode::Value(_) => {}
+			PlanNode::Object { source, properties } => {
+				fill_me.push(source.definition_name().into());
+
+				for (_, node) in properties {
+					node.insert_classes(fill_me);
+				}
+			}
+			PlanNode::Dictionary(entries) => {
+				for (_, node) in entries {
+					node.insert_classes(fill_me);
+				}
+			}
+			| PlanNode::Array { items, .. }
+			| PlanNode::Tuple(items) => {
+				for node in items {
+					node.insert_classes(fill_me);
+				}
+			}
+		}
+	}
+}
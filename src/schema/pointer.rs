@@ -0,0 +1,75 @@
+use super::*;
+
+impl RootSchema {
+	/// The definition of the value that the JSON Pointer `tokens` (see [`parse_pointer()`]) refers to, in JSON valid according to this schema.
+	///
+	/// Non-class schemas wrap their root in a property (see [`Self::wrapper_key()`]), pointers into them start with e.g. `/value`.
+	///
+	/// # Returns
+	/// `None` if the pointer does not match the schema, or goes through a value whose structure the schema does not describe
+	/// (e.g. a `Dictionary` or the fields of a `Vector3`).
+	pub fn definition_at(&self, tokens: &[String]) -> Option<&Definition> {
+		let tokens = match self.wrapper_key() {
+			None => tokens,
+			Some(key) => match tokens.split_first() {
+				Some((first, rest)) if first == key => rest,
+				_ => return None,
+			},
+		};
+
+		tokens.iter().try_fold(&self.base, |def, token| {
+			let ty = match def {
+				| Definition::Class(JClass { properties, .. })
+				| Definition::Object(JObject { properties, .. }) if !properties.is_empty() => properties.get(token)?,
+				Definition::Object(JObject { values_ty: Some(values_ty), .. }) => values_ty,
+				Definition::Array(JArray { items_ty: Some(items_ty), .. }) if token.parse::<usize>().is_ok() => items_ty,
+				Definition::Tuple(JTuple { items, .. }) => items.get(token.parse::<usize>().ok()?)?,
+				_ => return None,
+			};
+
+			match ty {
+				Type::Definition(def) => Some(def),
+				Type::Ref(JRef { name, .. }) => self.defs.get(name),
+			}
+		})
+	}
+}
+
+/// The child of the Godot value `parent` named `token`: a property of objects, a key of dictionaries or an index of arrays.
+///
+/// # Returns
+/// `None` if `parent` is not a container (e.g. a `Vector3`), its JSON should be indexed instead.
+pub fn variant_child(parent: &Variant, token: &str) -> Option<Result<Variant>> {
+	let child = match parent.get_type() {
+		VariantType::OBJECT => {
+			let Ok(object) = parent.try_to::<Gd<Object>>()
+			else { return Some(Err(anyhow!("Cannot index into a null object with \"{token}\"."))) };
+
+			let has_property = object
+				.get_property_list()
+				.iter_shared()
+				.any(|property| property.get("name").is_some_and(|name| name.to_string() == token));
+
+			if has_property {
+				Ok(object.get(token))
+			} else {
+				Err(anyhow!("Object of class \"{}\" has no property \"{token}\".", object.get_class()))
+			}
+		}
+		VariantType::DICTIONARY => parent
+			.to::<Dictionary>()
+			.get(token)
+			.ok_or_else(|| anyhow!("Key \"{token}\" does not exist.")),
+		VariantType::ARRAY => array_elements(parent).and_then(|elements| {
+			let len = elements.len();
+
+			token.parse::<usize>()
+				.ok()
+				.and_then(|idx| elements.into_iter().nth(idx))
+				.ok_or_else(|| anyhow!("Expected \"{token}\" to be an index of an array with {len} elements."))
+		}),
+		_ => return None,
+	};
+
+	Some(child)
+}
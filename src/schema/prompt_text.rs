@@ -0,0 +1,304 @@
+use super::*;
+
+/// Definitions whose rendering fits on one line of at most this many characters are written in place of their references.
+const INLINE_MAX_CHARS: usize = 40;
+/// How many variants of an enum are listed, before shortening it to fit a budget.
+const ENUM_VARIANTS: usize = 12;
+const SHORT_ENUM_VARIANTS: usize = 4;
+const TRUNCATION_MARKER: &str = "\n...";
+
+impl RootSchema {
+	/// Renders this schema as compact, TypeScript-like text, for embedding in prompts of providers that do not accept schemas.
+	///
+	/// Optional properties end with `?`, descriptions are `//` comments. Definitions are declared after the root,
+	/// except for small ones, which are written in place of their references. Enums with many variants are elided.
+	///
+	/// If the text is longer than `max_chars`, it is rendered again without descriptions, then with shorter enums,
+	/// and finally truncated.
+	pub fn to_prompt_text(&self, max_chars: Option<usize>, include_descriptions: bool) -> Result<String> {
+		let mut flattened = self.clone();
+		flattened.flatten_inheritance();
+		// Definitions shared with other schemas are written in `$defs`, since they cannot be resolved here.
+		flattened.options.external_refs.clear();
+		let schema = serde_json::to_value(&flattened)?;
+
+		let attempts = [
+			(include_descriptions, ENUM_VARIANTS),
+			(false, ENUM_VARIANTS),
+			(false, SHORT_ENUM_VARIANTS),
+		];
+
+		let mut text = String::new();
+
+		for (descriptions, enum_variants) in attempts {
+			let renderer = PromptTextRenderer {
+				defs: schema.get("$defs").and_then(Value::as_object),
+				descriptions,
+				enum_variants,
+				declared: Vec::new(),
+				inlining: Vec::new(),
+			};
+
+			text = renderer.render(&schema)?;
+
+			if max_chars.is_none_or(|max_chars| text.chars().count() <= max_chars) {
+				return Ok(text);
+			}
+		}
+
+		let max_chars = max_chars.unwrap_or_default();
+		let kept = max_chars.saturating_sub(TRUNCATION_MARKER.len());
+		let mut truncated = text.chars().take(kept).collect::<String>();
+
+		if max_chars >= TRUNCATION_MARKER.len() {
+			truncated += TRUNCATION_MARKER;
+		}
+
+		Ok(truncated)
+	}
+}
+
+struct PromptTextRenderer<'a> {
+	defs: Option<&'a Map<String, Value>>,
+	descriptions: bool,
+	enum_variants: usize,
+	/// The definitions declared after the root, in the order they were first referenced.
+	declared: Vec<String>,
+	/// The definitions being rendered in place of a reference, to stop recursive ones from being inlined forever.
+	inlining: Vec<String>,
+}
+
+impl<'a> PromptTextRenderer<'a> {
+	fn render(mut self, schema: &Value) -> Result<String> {
+		let mut text = self.description_comment(schema);
+		text += &self.visit(schema, 0)?;
+
+		let mut idx = 0;
+
+		// Rendering a definition may declare more of them.
+		while let Some(name) = self.declared.get(idx).cloned() {
+			let def = self.def(&name)?;
+			text += "\n\n";
+			text += &self.description_comment(def);
+			text += &format!("{name} = {}", self.visit(def, 0)?);
+			idx += 1;
+		}
+
+		Ok(text)
+	}
+
+	fn def(&self, name: &str) -> Result<&'a Value> {
+		self.defs
+			.and_then(|defs| defs.get(name))
+			.ok_or_else(|| anyhow!("Expected definition \"{name}\" to be in `$defs` map."))
+	}
+
+	fn description(&self, schema: &Value) -> Option<String> {
+		if !self.descriptions {
+			return None;
+		}
+
+		let description = schema.get("description")?.as_str()?;
+		Some(description.split_whitespace().join(" "))
+	}
+
+	fn description_comment(&self, schema: &Value) -> String {
+		self.description(schema)
+			.map(|description| format!("// {description}\n"))
+			.unwrap_or_default()
+	}
+
+	fn visit(&mut self, schema: &Value, depth: usize) -> Result<String> {
+		let Value::Object(keywords) = schema
+		else { return Ok("any".to_owned()) };
+
+		if let Some(Value::String(reference)) = keywords.get("$ref") {
+			return self.visit_ref(reference, depth);
+		}
+
+		if let Some(constant) = keywords.get("const") {
+			return Ok(constant.to_string());
+		}
+
+		if let Some(Value::Array(variants)) = keywords.get("enum") {
+			return Ok(self.enum_text(variants));
+		}
+
+		if let Some(Value::Array(options)) = keywords.get("oneOf").or_else(|| keywords.get("anyOf")) {
+			// Enums with described variants are a `oneOf` of constants.
+			if let Some(constants) = options.iter().map(|option| option.get("const").cloned()).collect::<Option<Vec<_>>>() {
+				return Ok(self.enum_text(&constants));
+			}
+
+			return self.join(options, " | ", depth);
+		}
+
+		if let Some(Value::Array(schemas)) = keywords.get("allOf") {
+			return self.join(schemas, " & ", depth);
+		}
+
+		if let Some(condition) = keywords.get("if") {
+			let mut text = format!("(if {}", self.visit(condition, depth)?);
+
+			if let Some(then) = keywords.get("then") {
+				text += &format!(" then {}", self.visit(then, depth)?);
+			}
+
+			if let Some(otherwise) = keywords.get("else") {
+				text += &format!(" else {}", self.visit(otherwise, depth)?);
+			}
+
+			return Ok(text + ")");
+		}
+
+		match keywords.get("type") {
+			Some(Value::String(ty)) => self.visit_typed(ty, keywords, depth),
+			Some(Value::Array(types)) => Ok(types
+				.iter()
+				.filter_map(Value::as_str)
+				.map(|ty| self.visit_typed(ty, keywords, depth))
+				.try_collect::<_, Vec<_>, _>()?
+				.join(" | ")),
+			_ => Ok("any".to_owned()),
+		}
+	}
+
+	fn visit_ref(&mut self, reference: &str, depth: usize) -> Result<String> {
+		let name = reference
+			.strip_prefix("#/$defs/")
+			.ok_or_else(|| anyhow!("Only local `$defs` references are supported.\nGot: {reference}"))?
+			.to_owned();
+
+		if self.declared.contains(&name) || self.inlining.contains(&name) {
+			return Ok(self.declare(name));
+		}
+
+		let def = self.def(&name)?;
+		let declared = self.declared.len();
+
+		self.inlining.push(name.clone());
+		let inline = self.visit(def, depth);
+		self.inlining.pop();
+		let inline = inline?;
+
+		if !inline.contains('\n') && inline.chars().count() <= INLINE_MAX_CHARS {
+			return Ok(inline);
+		}
+
+		// The definitions declared while rendering it are declared again, after it.
+		self.declared.truncate(declared);
+		Ok(self.declare(name))
+	}
+
+	fn declare(&mut self, name: String) -> String {
+		if !self.declared.contains(&name) {
+			self.declared.push(name.clone());
+		}
+
+		name
+	}
+
+	fn visit_typed(&mut self, ty: &str, keywords: &Map<String, Value>, depth: usize) -> Result<String> {
+		match ty {
+			"object" => {
+				if let Some(Value::Object(properties)) = keywords.get("properties") {
+					return self.object_text(properties, keywords.get("required"), depth);
+				}
+
+				match keywords.get("additionalProperties") {
+					Some(values @ Value::Object(_)) => Ok(format!("{{ [key: string]: {} }}", self.visit(values, depth)?)),
+					_ => Ok("object".to_owned()),
+				}
+			}
+			"array" => {
+				if let Some(Value::Array(items)) = keywords.get("prefixItems") {
+					return Ok(format!("[{}]", self.join(items, ", ", depth)?));
+				}
+
+				let Some(items) = keywords.get("items")
+				else { return Ok("array".to_owned()) };
+
+				let items = self.visit(items, depth)?;
+
+				if items.contains(' ') && !items.starts_with('{') {
+					Ok(format!("({items})[]"))
+				} else {
+					Ok(format!("{items}[]"))
+				}
+			}
+			"integer" | "number" => {
+				let bound = |keyword: &str| keywords.get(keyword).map(Value::to_string);
+
+				match (bound("minimum"), bound("maximum")) {
+					(Some(min), Some(max)) => Ok(format!("{ty} ({min}..{max})")),
+					(Some(min), None) => Ok(format!("{ty} (>= {min})")),
+					(None, Some(max)) => Ok(format!("{ty} (<= {max})")),
+					(None, None) => Ok(ty.to_owned()),
+				}
+			}
+			"string" => match (keywords.get("format"), keywords.get("pattern")) {
+				(Some(Value::String(format)), _) => Ok(format!("string ({format})")),
+				(_, Some(Value::String(pattern))) => Ok(format!("string (pattern: {pattern})")),
+				_ => Ok("string".to_owned()),
+			},
+			_ => Ok(ty.to_owned()),
+		}
+	}
+
+	fn object_text(&mut self, properties: &Map<String, Value>, required: Option<&Value>, depth: usize) -> Result<String> {
+		if properties.is_empty() {
+			return Ok("{}".to_owned());
+		}
+
+		let is_required = |name: &str| {
+			required
+				.and_then(Value::as_array)
+				.is_some_and(|required| required.iter().any(|required| required.as_str() == Some(name)))
+		};
+
+		let indent = "  ".repeat(depth + 1);
+		let mut text = "{\n".to_owned();
+
+		for (name, property) in properties {
+			let is_identifier = name.chars().all(|ch| ch.is_alphanumeric() || ch == '_')
+				&& !name.starts_with(|ch: char| ch.is_ascii_digit());
+
+			let key = if is_identifier { name.clone() } else { Value::from(name.as_str()).to_string() };
+			let optional = if is_required(name) { "" } else { "?" };
+
+			text += &format!("{indent}{key}{optional}: {}", self.visit(property, depth + 1)?);
+
+			if let Some(description) = self.description(property) {
+				text += &format!(" // {description}");
+			}
+
+			text += "\n";
+		}
+
+		text += &"  ".repeat(depth);
+		text += "}";
+		Ok(text)
+	}
+
+	fn join(&mut self, schemas: &[Value], separator: &str, depth: usize) -> Result<String> {
+		Ok(schemas
+			.iter()
+			.map(|schema| self.visit(schema, depth))
+			.try_collect::<_, Vec<_>, _>()?
+			.join(separator))
+	}
+
+	fn enum_text(&self, variants: &[Value]) -> String {
+		let mut text = variants
+			.iter()
+			.take(self.enum_variants)
+			.map(Value::to_string)
+			.join(" | ");
+
+		if variants.len() > self.enum_variants {
+			text += &format!(" | ... ({} more)", variants.len() - self.enum_variants);
+		}
+
+		text
+	}
+}
@@ -0,0 +1,343 @@
+use super::*;
+use std::borrow::Cow;
+
+/// How class and object properties are named in serialized schemas and documents, see [`SchemaOptions::property_naming`].
+///
+/// Godot properties keep their names, documents are translated when instantiating and serializing instances,
+/// see [`to_godot_names()`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum PropertyNaming {
+	#[default]
+	AsIs,
+	/// `max_health` is named `maxHealth`.
+	CamelCase,
+	/// Property name -> name in JSON, e.g. computed once by a user `Callable`. Unlisted properties keep their name.
+	Custom(BTreeMap<String, String>),
+}
+
+/// The [`PropertyNaming`] policies that can be selected from Godot, see [`GodotSchema::set_property_naming()`].
+///
+/// Custom mappings are set with a `Callable` instead, see [`GodotSchema::set_property_naming_callable()`].
+#[derive(GodotConvert, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[godot(via = i64)]
+pub enum NamingPolicy {
+	/// Properties keep their Godot names.
+	#[default]
+	AsIs = 0,
+	/// `max_health` is named `maxHealth`.
+	CamelCase = 1,
+}
+
+impl From<NamingPolicy> for PropertyNaming {
+	fn from(policy: NamingPolicy) -> Self {
+		match policy {
+			NamingPolicy::AsIs => Self::AsIs,
+			NamingPolicy::CamelCase => Self::CamelCase,
+		}
+	}
+}
+
+impl PropertyNaming {
+	pub fn is_as_is(&self) -> bool {
+		matches!(self, Self::AsIs)
+	}
+
+	/// The name `property` has in JSON.
+	pub fn json_name<'a>(&self, property: &'a str) -> Cow<'a, str> {
+		match self {
+			Self::AsIs => Cow::Borrowed(property),
+			Self::CamelCase => Cow::Owned(to_camel_case(property)),
+			Self::Custom(names) => match names.get(property) {
+				Some(name) => Cow::Owned(name.clone()),
+				None => Cow::Borrowed(property),
+			},
+		}
+	}
+
+	/// Renames the properties declared by `schema` and its subschemas, along with their `required` and `dependentRequired` entries.
+	///
+	/// Renamed properties are annotated with [`GODOT_PROPERTY_KEY`]. Aliased ones (already annotated) keep their alias.
+	///
+	/// # Errors
+	/// If two properties of the same schema end up with the same name.
+	pub fn rename_schema(&self, schema: &mut Value) -> Result<()> {
+		let Value::Object(keywords) = schema
+		else { return Ok(()) };
+
+		let mut renamed = HashMap::new();
+
+		if let Some(Value::Object(properties)) = keywords.get_mut("properties") {
+			let mut json_properties = Map::new();
+
+			for (name, mut property) in std::mem::take(properties) {
+				self.rename_schema(&mut property)?;
+
+				let is_aliased = property.get(GODOT_PROPERTY_KEY).is_some();
+				let json_name = if is_aliased { name.clone() } else { self.json_name(&name).into_owned() };
+
+				if json_name != name {
+					if let Value::Object(property_keywords) = &mut property {
+						property_keywords.insert(GODOT_PROPERTY_KEY.to_owned(), name.as_str().into());
+					}
+
+					renamed.insert(name.clone(), json_name.clone());
+				}
+
+				if json_properties.insert(json_name.clone(), property).is_some() {
+					bail!("Expected property names to be unique after renaming.\nGot \"{json_name}\" twice, the second from \"{name}\".");
+				}
+			}
+
+			*properties = json_properties;
+		}
+
+		let rename = |name: &str| renamed.get(name).cloned().unwrap_or_else(|| name.to_owned());
+
+		for (key, value) in keywords.iter_mut() {
+			match key.as_str() {
+				"properties" => {}
+				"required" => {
+					if let Value::Array(names) = value {
+						rename_names(names, rename);
+					}
+				}
+				"dependentRequired" => {
+					if let Value::Object(dependencies) = value {
+						*dependencies = std::mem::take(dependencies)
+							.into_iter()
+							.map(|(name, mut required)| {
+								if let Value::Array(names) = &mut required {
+									rename_names(names, rename);
+								}
+
+								(rename(&name), required)
+							})
+							.collect();
+					}
+				}
+				// Maps of names to schemas, the names are not properties.
+				"patternProperties" | "dependentSchemas" | "$defs" => {
+					if let Value::Object(schemas) = value {
+						for schema in schemas.values_mut() {
+							self.rename_schema(schema)?;
+						}
+					}
+				}
+				// Instance values, not schemas.
+				"const" | "default" | "enum" | "examples" => {}
+				_ => match value {
+					Value::Array(schemas) => {
+						for schema in schemas {
+							self.rename_schema(schema)?;
+						}
+					}
+					schema => self.rename_schema(schema)?,
+				},
+			}
+		}
+
+		Ok(())
+	}
+}
+
+fn rename_names(names: &mut [Value], rename: impl Fn(&str) -> String) {
+	for name in names {
+		if let Value::String(property) = name {
+			*property = rename(property);
+		}
+	}
+}
+
+/// Removes the [`GODOT_PROPERTY_KEY`] annotations of the properties declared by `schema` and its subschemas.
+pub fn strip_property_annotations(schema: &mut Value) {
+	match schema {
+		Value::Object(keywords) => {
+			for (key, value) in keywords.iter_mut() {
+				match key.as_str() {
+					"properties" => {
+						if let Value::Object(properties) = value {
+							for property in properties.values_mut() {
+								if let Value::Object(property_keywords) = property {
+									property_keywords.shift_remove(GODOT_PROPERTY_KEY);
+								}
+
+								strip_property_annotations(property);
+							}
+						}
+					}
+					"const" | "default" | "enum" | "examples" | "required" | "dependentRequired" => {}
+					_ => strip_property_annotations(value),
+				}
+			}
+		}
+		Value::Array(schemas) => {
+			for schema in schemas {
+				strip_property_annotations(schema);
+			}
+		}
+		_ => {}
+	}
+}
+
+/// Renames the properties of `document` to their names in JSON, see [`GODOT_PROPERTY_KEY`].
+/// Properties may be named either way in `document`.
+///
+/// `schema` is the serialized schema of `document`. It tells properties apart from dictionary keys, which are never renamed.
+pub fn to_json_names(document: &Value, schema: &Value) -> Value {
+	translate(document, schema, schema.get("$defs").and_then(Value::as_object), true)
+}
+
+/// The reverse of [`to_json_names()`].
+pub fn to_godot_names(document: &Value, schema: &Value) -> Value {
+	translate(document, schema, schema.get("$defs").and_then(Value::as_object), false)
+}
+
+fn translate(document: &Value, schema: &Value, defs: Option<&Map<String, Value>>, to_json: bool) -> Value {
+	let mut schemas = Vec::new();
+	flatten_schema(schema, defs, &mut schemas);
+
+	match document {
+		Value::Object(entries) => {
+			// Name in JSON, Godot name, schema.
+			let properties = schemas
+				.iter()
+				.filter_map(|schema| schema.get("properties").and_then(Value::as_object))
+				.flatten()
+				.map(|(json_name, property)| {
+					let godot_name = property.get(GODOT_PROPERTY_KEY).and_then(Value::as_str).unwrap_or(json_name);
+					(json_name.as_str(), godot_name, property)
+				})
+				.collect::<Vec<_>>();
+
+			let additional = schemas
+				.iter()
+				.find_map(|schema| schema.get("additionalProperties"))
+				.unwrap_or(&Value::Null);
+
+			entries
+				.iter()
+				.map(|(key, value)| {
+					let property = properties
+						.iter()
+						.find(|(json_name, _, _)| json_name == key)
+						.or_else(|| properties.iter().find(|(_, godot_name, _)| godot_name == key));
+
+					match property {
+						Some((json_name, godot_name, property)) => {
+							let name = if to_json { json_name } else { godot_name };
+							((*name).to_owned(), translate(value, property, defs, to_json))
+						}
+						None => (key.clone(), translate(value, additional, defs, to_json)),
+					}
+				})
+				.collect::<Map<_, _>>()
+				.into()
+		}
+		Value::Array(elements) => {
+			let prefix_items = schemas
+				.iter()
+				.find_map(|schema| schema.get("prefixItems").and_then(Value::as_array));
+
+			let items = schemas
+				.iter()
+				.find_map(|schema| schema.get("items"))
+				.unwrap_or(&Value::Null);
+
+			elements
+				.iter()
+				.enumerate()
+				.map(|(idx, element)| {
+					let item = prefix_items.and_then(|prefix| prefix.get(idx)).unwrap_or(items);
+					translate(element, item, defs, to_json)
+				})
+				.collect::<Vec<_>>()
+				.into()
+		}
+		other => other.clone(),
+	}
+}
+
+/// Collects `schema` and the schemas it is combined with, following local references.
+///
+/// The options of `oneOf` and `anyOf` are all included, since properties are named the same in each.
+fn flatten_schema<'a>(schema: &'a Value, defs: Option<&'a Map<String, Value>>, schemas: &mut Vec<&'a Value>) {
+	if schemas.iter().any(|visited| std::ptr::eq(*visited, schema)) {
+		return;
+	}
+
+	schemas.push(schema);
+
+	if let Some(Value::String(reference)) = schema.get("$ref")
+		&& let Some(name) = reference.strip_prefix("#/$defs/")
+		&& let Some(def) = defs.and_then(|defs| defs.get(name)) {
+		flatten_schema(def, defs, schemas);
+	}
+
+	for keyword in ["allOf", "oneOf", "anyOf"] {
+		if let Some(Value::Array(options)) = schema.get(keyword) {
+			for option in options {
+				flatten_schema(option, defs, schemas);
+			}
+		}
+	}
+
+	for keyword in ["then", "else"] {
+		if let Some(branch) = schema.get(keyword) {
+			flatten_schema(branch, defs, schemas);
+		}
+	}
+}
+
+/// `max_health` -> `maxHealth`. Leading underscores are kept.
+pub fn to_camel_case(name: &str) -> String {
+	let mut camel = String::with_capacity(name.len());
+	let mut upper_next = false;
+
+	for ch in name.chars() {
+		if ch == '_' && camel.chars().any(|ch| ch != '_') {
+			upper_next = true;
+		} else if upper_next {
+			camel.extend(ch.to_uppercase());
+			upper_next = false;
+		} else {
+			camel.push(ch);
+		}
+	}
+
+	camel
+}
+
+/// The Godot names of the properties declared anywhere in `schema`, e.g. to ask a user `Callable` how to name each of them.
+pub fn declared_property_names(schema: &Value) -> BTreeSet<String> {
+	let mut names = BTreeSet::new();
+	insert_property_names(schema, &mut names);
+	names
+}
+
+fn insert_property_names(schema: &Value, names: &mut BTreeSet<String>) {
+	match schema {
+		Value::Object(keywords) => {
+			for (key, value) in keywords {
+				match key.as_str() {
+					"properties" => {
+						if let Value::Object(properties) = value {
+							for (name, property) in properties {
+								let name = property.get(GODOT_PROPERTY_KEY).and_then(Value::as_str).unwrap_or(name);
+								names.insert(name.to_owned());
+								insert_property_names(property, names);
+							}
+						}
+					}
+					"const" | "default" | "enum" | "examples" | "required" | "dependentRequired" => {}
+					_ => insert_property_names(value, names),
+				}
+			}
+		}
+		Value::Array(schemas) => {
+			for schema in schemas {
+				insert_property_names(schema, names);
+			}
+		}
+		_ => {}
+	}
+}
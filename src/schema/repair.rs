@@ -0,0 +1,309 @@
+use super::*;
+
+/// The result of [`repair_json()`].
+pub struct JsonRepair {
+	pub json: String,
+	/// A description of each kind of fix applied, empty if the input was already valid JSON.
+	pub fixes: Vec<String>,
+}
+
+/// Best-effort repair of the mistakes LLMs commonly make when emitting JSON:
+/// - Markdown code fences and prose around the JSON document.
+/// - Single-quoted strings and unquoted keys (JSON5 style).
+/// - Trailing commas, comments, and Python-style `True`/`False`/`None` literals.
+/// - Truncated output: unterminated strings, objects and arrays are closed.
+///
+/// Input that is already valid JSON is returned untouched.
+pub fn repair_json(input: &str) -> JsonRepair {
+	if serde_json::from_str::<Value>(input).is_ok() {
+		return JsonRepair {
+			json: input.to_owned(),
+			fixes: Vec::new(),
+		};
+	}
+
+	let mut fixes = Vec::new();
+	let text = strip_code_fence(input, &mut fixes);
+	let text = trim_to_document(text, &mut fixes);
+	let json = rewrite(text, &mut fixes);
+
+	JsonRepair { json, fixes }
+}
+
+fn add_fix(fixes: &mut Vec<String>, fix: &str) {
+	if !fixes.iter().any(|existing| existing == fix) {
+		fixes.push(fix.to_owned());
+	}
+}
+
+fn strip_code_fence<'a>(input: &'a str, fixes: &mut Vec<String>) -> &'a str {
+	let Some(start) = input.find("```")
+	else { return input };
+
+	let after_fence = &input[start + 3..];
+	// Skip the language tag, e.g. "```json".
+	let content_start = after_fence.find('\n').map_or(0, |idx| idx + 1);
+	let content = &after_fence[content_start..];
+	let content = content.find("```").map_or(content, |end| &content[..end]);
+
+	add_fix(fixes, "Stripped markdown code fence.");
+	content
+}
+
+fn trim_to_document<'a>(input: &'a str, fixes: &mut Vec<String>) -> &'a str {
+	let Some(start) = input.find(['{', '['])
+	else { return input.trim() };
+
+	// Truncated documents are kept whole, `rewrite()` closes them.
+	let end = document_len(&input[start..]).map_or(input.len(), |len| start + len);
+
+	if !input[..start].trim().is_empty() || !input[end..].trim().is_empty() {
+		add_fix(fixes, "Removed text around the JSON document.");
+	}
+
+	&input[start..end]
+}
+
+/// The length (in bytes) of the object or array `text` starts with, up to its closing bracket.
+/// Brackets inside strings are ignored.
+///
+/// `None` if the document is truncated.
+fn document_len(text: &str) -> Option<usize> {
+	let mut depth = 0_usize;
+	let mut quote = None;
+	let mut escaped = false;
+
+	for (idx, ch) in text.char_indices() {
+		match quote {
+			Some(_) if escaped => escaped = false,
+			Some(_) if ch == '\\' => escaped = true,
+			Some(open) if ch == open => quote = None,
+			Some(_) => {}
+			None => match ch {
+				'"' | '\'' => quote = Some(ch),
+				'{' | '[' => depth += 1,
+				'}' | ']' => {
+					depth = depth.saturating_sub(1);
+
+					if depth == 0 {
+						return Some(idx + ch.len_utf8());
+					}
+				}
+				_ => {}
+			},
+		}
+	}
+
+	None
+}
+
+fn rewrite(text: &str, fixes: &mut Vec<String>) -> String {
+	let chars = text.chars().collect::<Vec<_>>();
+	let mut out = String::with_capacity(text.len());
+	let mut closers = Vec::new();
+	let mut idx = 0;
+
+	while idx < chars.len() {
+		let ch = chars[idx];
+
+		match ch {
+			'"' | '\'' => {
+				let (string, next, terminated) = read_string(&chars, idx);
+
+				if ch == '\'' {
+					add_fix(fixes, "Replaced single quotes with double quotes.");
+				}
+
+				if !terminated {
+					add_fix(fixes, "Closed unterminated string.");
+				}
+
+				out += &Value::String(string).to_string();
+				idx = next;
+				continue;
+			}
+			'/' if chars.get(idx + 1) == Some(&'/') => {
+				while idx < chars.len() && chars[idx] != '\n' {
+					idx += 1;
+				}
+
+				add_fix(fixes, "Removed comments.");
+				continue;
+			}
+			'/' if chars.get(idx + 1) == Some(&'*') => {
+				idx += 2;
+
+				while idx < chars.len() && !(chars[idx] == '*' && chars.get(idx + 1) == Some(&'/')) {
+					idx += 1;
+				}
+
+				idx += 2;
+				add_fix(fixes, "Removed comments.");
+				continue;
+			}
+			'{' => {
+				closers.push('}');
+				out.push(ch);
+			}
+			'[' => {
+				closers.push(']');
+				out.push(ch);
+			}
+			'}' | ']' => {
+				remove_trailing_comma(&mut out, fixes);
+				closers.pop();
+				out.push(ch);
+			}
+			// Numbers are copied as is, so that exponents (`1e5`) are not mistaken for bare words.
+			ch if ch.is_ascii_digit() || ch == '-' => {
+				let start = idx;
+				idx += 1;
+
+				while idx < chars.len() && (chars[idx].is_ascii_digit() || matches!(chars[idx], '.' | 'e' | 'E' | '+' | '-')) {
+					idx += 1;
+				}
+
+				out.extend(&chars[start..idx]);
+				continue;
+			}
+			ch if ch.is_alphabetic() || ch == '_' || ch == '$' => {
+				let start = idx;
+
+				while idx < chars.len() && (chars[idx].is_alphanumeric() || chars[idx] == '_' || chars[idx] == '$') {
+					idx += 1;
+				}
+
+				let word = chars[start..idx].iter().collect::<String>();
+				let next_non_space = chars[idx..].iter().find(|ch| !ch.is_whitespace());
+
+				if next_non_space == Some(&':') {
+					add_fix(fixes, "Quoted unquoted keys.");
+					out += &Value::String(word).to_string();
+				} else {
+					match word.as_str() {
+						"true" | "false" | "null" => out += &word,
+						"True" | "False" | "None" => {
+							add_fix(fixes, "Replaced Python literals (True/False/None).");
+							out += match word.as_str() {
+								"True" => "true",
+								"False" => "false",
+								_ => "null",
+							};
+						}
+						_ => {
+							add_fix(fixes, "Quoted bare words.");
+							out += &Value::String(word).to_string();
+						}
+					}
+				}
+
+				continue;
+			}
+			ch => out.push(ch),
+		}
+
+		idx += 1;
+	}
+
+	if !closers.is_empty() {
+		add_fix(fixes, "Closed truncated objects/arrays.");
+		remove_trailing_comma(&mut out, fixes);
+
+		// A truncated object may end right after a key, give it a value so the document parses.
+		if out.trim_end().ends_with(':') {
+			out += "null";
+		}
+
+		while let Some(closer) = closers.pop() {
+			out.push(closer);
+		}
+	}
+
+	out
+}
+
+fn remove_trailing_comma(out: &mut String, fixes: &mut Vec<String>) {
+	let trimmed_len = out.trim_end().len();
+
+	if out[..trimmed_len].ends_with(',') {
+		out.truncate(trimmed_len - 1);
+		add_fix(fixes, "Removed trailing commas.");
+	}
+}
+
+/// Reads a string starting at the quote at `start`, decoding escape sequences.
+///
+/// # Returns
+/// The decoded string, the index after the closing quote, and whether the closing quote was found.
+fn read_string(chars: &[char], start: usize) -> (String, usize, bool) {
+	let quote = chars[start];
+	let mut string = String::new();
+	let mut idx = start + 1;
+
+	while idx < chars.len() {
+		let ch = chars[idx];
+
+		if ch == quote {
+			return (string, idx + 1, true);
+		}
+
+		if ch == '\\' && let Some(&escaped) = chars.get(idx + 1) {
+			idx += 2;
+
+			match escaped {
+				'n' => string.push('\n'),
+				't' => string.push('\t'),
+				'r' => string.push('\r'),
+				'b' => string.push('\u{8}'),
+				'f' => string.push('\u{c}'),
+				'u' => match decode_unicode_escape(&chars[idx..]) {
+					Some((decoded, len)) => {
+						string.push(decoded);
+						idx += len;
+					}
+					None => string.push('u'),
+				},
+				other => string.push(other),
+			}
+
+			continue;
+		}
+
+		string.push(ch);
+		idx += 1;
+	}
+
+	(string, idx, false)
+}
+
+/// Decodes the `XXXX` of a `\uXXXX` escape at the start of `chars`, combining UTF-16 surrogate pairs (`\uD83D\uDE00`).
+///
+/// # Returns
+/// The decoded character (U+FFFD for lone surrogates) and the number of chars read,
+/// `None` if `chars` does not start with 4 hex digits.
+fn decode_unicode_escape(chars: &[char]) -> Option<(char, usize)> {
+	let code_unit = |at: usize| {
+		chars.get(at..at + 4)
+			.filter(|hex| hex.iter().all(char::is_ascii_hexdigit))
+			.and_then(|hex| u32::from_str_radix(&hex.iter().collect::<String>(), 16).ok())
+	};
+
+	let unit = code_unit(0)?;
+
+	if !(0xD800..0xDC00).contains(&unit) {
+		return Some((char::from_u32(unit).unwrap_or(char::REPLACEMENT_CHARACTER), 4));
+	}
+
+	let low = chars.get(4..6)
+		.filter(|escape| **escape == ['\\', 'u'])
+		.and_then(|_| code_unit(6))
+		.filter(|low| (0xDC00..0xE000).contains(low));
+
+	match low {
+		Some(low) => {
+			let code = 0x10000 + ((unit - 0xD800) << 10) + (low - 0xDC00);
+			Some((char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER), 10))
+		}
+		None => Some((char::REPLACEMENT_CHARACTER, 4)),
+	}
+}
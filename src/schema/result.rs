@@ -0,0 +1,63 @@
+use super::*;
+
+/// The return type of the `*_ex` APIs, an alternative to the `Variant` that is either the value or an error `String`.
+///
+/// ```gdscript
+/// var result := GodotSchema.from_class_name_ex(&"Person")
+/// if result.is_ok():
+/// 	var schema: GodotSchema = result.value
+/// else:
+/// 	printerr(result.error_message)
+/// ```
+#[derive(GodotClass)]
+#[class(no_init, base = RefCounted)]
+pub struct SchemaResult {
+	/// `Nil` if the operation failed.
+	#[var(get)] pub value: Variant,
+	/// Empty if the operation succeeded.
+	#[var(get)] pub error_message: GString,
+	/// One of the `SchemaResult` constants, see [`ErrorCode`].
+	#[var(get)] pub error_code: ErrorCode,
+}
+
+#[godot_api]
+impl SchemaResult {
+	#[constant] pub const OK: i64 = ErrorCode::Ok as i64;
+	#[constant] pub const FAILED: i64 = ErrorCode::Failed as i64;
+	#[constant] pub const CLASS_NOT_FOUND: i64 = ErrorCode::ClassNotFound as i64;
+	#[constant] pub const UNSUPPORTED_PROPERTY_TYPE: i64 = ErrorCode::UnsupportedPropertyType as i64;
+	#[constant] pub const VALIDATION_FAILED: i64 = ErrorCode::ValidationFailed as i64;
+	#[constant] pub const JSON_PARSE_ERROR: i64 = ErrorCode::JsonParseError as i64;
+	#[constant] pub const INSTANTIATION_FAILED: i64 = ErrorCode::InstantiationFailed as i64;
+	#[constant] pub const FILE_ERROR: i64 = ErrorCode::FileError as i64;
+
+	#[func]
+	pub fn is_ok(&self) -> bool {
+		self.error_code == ErrorCode::Ok
+	}
+}
+
+impl SchemaResult {
+	pub fn ok(value: Variant) -> Gd<Self> {
+		Gd::from_object(Self {
+			value,
+			error_message: GString::new(),
+			error_code: ErrorCode::Ok,
+		})
+	}
+
+	pub fn err(err: anyhow::Error) -> Gd<Self> {
+		Gd::from_object(Self {
+			value: Variant::nil(),
+			error_code: ErrorCode::of(&err),
+			error_message: format!("{err:?}").into(),
+		})
+	}
+
+	pub fn from_result<T: ToGodot, E: Into<anyhow::Error>>(result: std::result::Result<T, E>) -> Gd<Self> {
+		match result {
+			Ok(value) => Self::ok(value.to_variant()),
+			Err(err) => Self::err(err.into()),
+		}
+	}
+}
@@ -0,0 +1,219 @@
+use super::*;
+
+/// Key of the schema hash embedded in each object saved by [`SchemaSaveManager::save()`], see [`GodotSchema::get_hash()`].
+pub const SAVE_HASH_KEY: &str = "$hash";
+
+/// Saves and loads objects through their class schemas, migrating data written by older versions of their classes.
+///
+/// Classes must be registered (with their current schema and version, plus any migrations) in the manager's [`SchemaMigrator`],
+/// see [`Self::get_migrator()`]. Each saved object embeds its class name, version and schema hash.
+#[derive(GodotClass)]
+#[class(init, base = Node)]
+pub struct SchemaSaveManager {
+	base: Base<Node>,
+	#[init(val = SchemaMigrator::new_gd())]
+	migrator: Gd<SchemaMigrator>,
+	/// The issues found by the last call to [`Self::save()`] or [`Self::load()`].
+	last_report: Array<Dictionary>,
+}
+
+#[godot_api]
+impl SchemaSaveManager {
+	/// Emitted by [`Self::save()`] if any object could not be serialized, nothing is written in that case.
+	#[signal]
+	fn save_failed(path: GString, report: Array<Dictionary>);
+
+	/// Emitted by [`Self::load()`] if any object could not be loaded.
+	#[signal]
+	fn load_failed(path: GString, report: Array<Dictionary>);
+
+	/// The migrator holding the schema, version and migrations of each saved class.
+	#[func]
+	pub fn get_migrator(&self) -> Gd<SchemaMigrator> {
+		self.migrator.clone()
+	}
+
+	/// Serializes each object in `objects` (key => object) with its class's registered schema, writing them to `path`.
+	///
+	/// Objects are keyed the same way in the file, [`Self::load()`] returns them under the same keys.
+	///
+	/// The file is replaced atomically: the document is written to a temporary file first, then renamed to `path`.
+	///
+	/// # Returns
+	/// Whether the file was written, see [`Self::get_last_report()`] otherwise.
+	#[func]
+	pub fn save(&mut self, path: String, objects: Dictionary) -> bool {
+		let mut report = Array::new();
+		let mut document = Map::new();
+
+		for (key, value) in objects.iter_shared() {
+			let key = key.to_string();
+
+			let result = value
+				.try_to::<Gd<Object>>()
+				.map_err(|_| anyhow!("Expected a non-null object.\nGot: {value}"))
+				.and_then(|object| self.save_object(&object));
+
+			match result {
+				Ok(json) => { document.insert(key, json); }
+				Err(err) => report.push(&issue(&key, "error", &format!("{err:?}"))),
+			}
+		}
+
+		if report.is_empty()
+			&& let Err(err) = write_file(&path, &Value::Object(document)) {
+			report.push(&issue("", "error", &format!("{err:?}")));
+		}
+
+		let saved = report.is_empty();
+		self.finish(&path, report, "save_failed");
+		saved
+	}
+
+	/// Reads the objects written by [`Self::save()`] to `path`, migrating each to its class's current version
+	/// then validating and instantiating it.
+	///
+	/// Objects saved with a different schema than the current one of the same version are still loaded,
+	/// but reported as warnings: the class probably changed without its version being bumped.
+	///
+	/// # Returns
+	/// The loaded objects, keyed as they were saved. Objects that failed to load are left out, see [`Self::get_last_report()`].
+	#[func]
+	pub fn load(&mut self, path: String) -> Dictionary {
+		let mut report = Array::new();
+		let mut loaded = Dictionary::new();
+
+		match read_file(&path) {
+			Ok(document) => {
+				for (key, value) in document {
+					match self.load_object(value) {
+						Ok((instance, warning)) => {
+							if let Some(warning) = warning {
+								report.push(&issue(&key, "warning", &warning));
+							}
+
+							loaded.set(key, instance);
+						}
+						Err(err) => report.push(&issue(&key, "error", &format!("{err:?}"))),
+					}
+				}
+			}
+			Err(err) => report.push(&issue("", "error", &format!("{err:?}"))),
+		}
+
+		self.finish(&path, report, "load_failed");
+		loaded
+	}
+
+	/// # Returns
+	/// One `Dictionary` per issue found by the last call to [`Self::save()`] or [`Self::load()`], with the keys:
+	/// - "key": The object's key, empty for issues with the file itself.
+	/// - "severity": Either "error" (the object was not saved/loaded) or "warning".
+	/// - "message": The error message.
+	#[func]
+	pub fn get_last_report(&self) -> Array<Dictionary> {
+		self.last_report.clone()
+	}
+}
+
+impl SchemaSaveManager {
+	fn save_object(&self, object: &Gd<Object>) -> Result<Value> {
+		let class_name = ClassSource::from_object(object).definition_name();
+		let migrator = self.migrator.bind();
+
+		let mut json = migrator.versioned_json(&class_name, &object.to_variant())?;
+		let hash = migrator.find_class(&class_name)?.0.bind().inner.fingerprint()?;
+
+		if let Value::Object(properties) = &mut json {
+			properties.insert(SAVE_HASH_KEY.to_owned(), hash.into());
+		}
+
+		Ok(json)
+	}
+
+	/// # Returns
+	/// The instantiated object, and a warning if it was saved with a different schema than the current one of the same version.
+	fn load_object(&self, mut value: Value) -> Result<(Variant, Option<String>)> {
+		let saved_hash = value.as_object_mut().and_then(|properties| properties.remove(SAVE_HASH_KEY));
+		let saved_version = value.get(MIGRATION_VERSION_KEY).and_then(Value::as_i64);
+
+		let migrator = self.migrator.bind();
+		let (schema, value) = migrator.migrate(value)?;
+		let schema = schema.bind();
+
+		let warning = match (saved_hash, saved_version) {
+			(Some(Value::String(saved_hash)), Some(saved_version))
+				if migrator.get_version(schema.schema_name()) == saved_version
+					&& saved_hash != schema.inner.fingerprint()? => {
+				Some(format!("Saved with a different schema than the current one of class \"{}\", although the version ({saved_version}) is the same.", schema.schema_name()))
+			}
+			_ => None,
+		};
+
+		Ok((schema.instantiate_value(&value)?, warning))
+	}
+
+	fn finish(&mut self, path: &str, report: Array<Dictionary>, failed_signal: &str) {
+		let failed = report
+			.iter_shared()
+			.any(|entry| entry.get("severity").is_some_and(|severity| severity.to_string() == "error"));
+
+		self.last_report = report.clone();
+
+		if failed {
+			self.base_mut().emit_signal(failed_signal, &[path.to_variant(), report.to_variant()]);
+		}
+	}
+}
+
+fn issue(key: &str, severity: &str, message: &str) -> Dictionary {
+	let mut dict = Dictionary::new();
+	dict.set("key", key);
+	dict.set("severity", severity);
+	dict.set("message", message);
+	dict
+}
+
+/// Writes `document` to a temporary file next to `path`, then renames it over `path`,
+/// so a crash or full disk mid-write leaves the previous save intact.
+fn write_file(path: &str, document: &Value) -> Result<()> {
+	let temp_path = format!("{path}.tmp");
+
+	let mut file = FileAccess::open(temp_path.as_str(), ModeFlags::WRITE)
+		.ok_or_else(|| SchemaError::Io {
+			path: temp_path.clone(),
+			message: format!("Could not open for writing: {:?}", FileAccess::get_open_error()),
+		})?;
+
+	file.store_string(serde_json::to_string(document)?.as_str());
+	file.flush();
+	let error = file.get_error();
+	file.close();
+
+	if error != godot::global::Error::OK {
+		DirAccess::remove_absolute(temp_path.as_str());
+		return Err(SchemaError::Io { path: temp_path, message: format!("Could not write: {error:?}") }.into());
+	}
+
+	let error = DirAccess::rename_absolute(temp_path.as_str(), path);
+
+	if error != godot::global::Error::OK {
+		DirAccess::remove_absolute(temp_path.as_str());
+		return Err(SchemaError::Io { path: path.to_owned(), message: format!("Could not replace with \"{temp_path}\": {error:?}") }.into());
+	}
+
+	Ok(())
+}
+
+fn read_file(path: &str) -> Result<Map<String, Value>> {
+	let file = FileAccess::open(path, ModeFlags::READ)
+		.ok_or_else(|| SchemaError::Io {
+			path: path.to_owned(),
+			message: format!("Could not open for reading: {:?}", FileAccess::get_open_error()),
+		})?;
+
+	let Value::Object(document) = serde_json::from_str(&file.get_as_text().to_string())?
+	else { bail!("Expected save file \"{path}\" to contain a JSON object.") };
+
+	Ok(document)
+}
@@ -0,0 +1,126 @@
+use super::*;
+
+/// Checkpoints the state of node groups, using each node's class schema.
+///
+/// Schemas are generated on demand and cached, keyed by each node's script (or engine class).
+#[derive(GodotClass)]
+#[class(init, base = Node)]
+pub struct SceneSerializer {
+	base: Base<Node>,
+	schemas: HashMap<ClassSource, Gd<GodotSchema>>,
+}
+
+#[godot_api]
+impl SceneSerializer {
+	/// Serializes every node in the group `group_name` against its class schema.
+	///
+	/// # Returns
+	/// - A JSON object mapping each node's path to its properties, if successful.
+	///   Nodes that fail to serialize are skipped, the error is printed with `godot_error!`.
+	/// - Otherwise an empty `String`, the error is printed with `godot_error!`.
+	#[func]
+	pub fn serialize_group(&mut self, group_name: StringName) -> String {
+		let result = self.try_serialize_group(&group_name);
+
+		match result {
+			Ok(json) => json,
+			Err(err) => {
+				godot_error!("{err}");
+				String::default()
+			}
+		}
+	}
+
+	/// Applies a document produced by [`Self::serialize_group()`] back to the nodes in the group `group_name`.
+	///
+	/// Nodes are matched by path, nodes missing from the document are left untouched.
+	///
+	/// # Returns
+	/// - `Nil`, if every node was restored.
+	/// - Otherwise a `String` containing the error messages of the nodes that failed.
+	#[func]
+	pub fn restore_group(&mut self, group_name: StringName, json: String) -> Variant {
+		match self.try_restore_group(&group_name, &json) {
+			Ok(()) => Variant::nil(),
+			Err(err) => format!("{err}").to_variant(),
+		}
+	}
+
+	/// Drops every cached schema, forcing them to be regenerated on the next call.
+	#[func]
+	pub fn clear_cache(&mut self) {
+		self.schemas.clear();
+	}
+}
+
+impl SceneSerializer {
+	fn group_nodes(&self, group_name: &StringName) -> Result<Vec<Gd<Node>>> {
+		let tree = self.base()
+			.get_tree()
+			.ok_or_else(|| anyhow!("`SceneSerializer` must be inside the scene tree."))?;
+
+		Ok(tree.get_nodes_in_group(group_name).iter_shared().collect())
+	}
+
+	fn schema_for(&mut self, object: &Gd<Object>) -> Result<Gd<GodotSchema>> {
+		let source = ClassSource::from_object(object);
+
+		if let Some(schema) = self.schemas.get(&source) {
+			return Ok(schema.clone());
+		}
+
+		let schema = Gd::from_object(RootSchema::from_class(source.clone()).and_then(GodotSchema::new)?);
+		self.schemas.insert(source, schema.clone());
+		Ok(schema)
+	}
+
+	fn try_serialize_group(&mut self, group_name: &StringName) -> Result<String> {
+		let mut document = Map::new();
+
+		for node in self.group_nodes(group_name)? {
+			let path = node.get_path().to_string();
+			let object = node.upcast::<Object>();
+
+			let result = self
+				.schema_for(&object)
+				.and_then(|schema| schema.bind().inner.json_from_instance(&object.to_variant()));
+
+			match result {
+				Ok(value) => { document.insert(path, value); }
+				Err(err) => godot_error!("Failed to serialize node \"{path}\": {err}"),
+			}
+		}
+
+		Ok(serde_json::to_string(&Value::Object(document))?)
+	}
+
+	fn try_restore_group(&mut self, group_name: &StringName, json: &str) -> Result<()> {
+		let Value::Object(document) = serde_json::from_str(json)?
+		else { bail!("Expected JSON document to be an object.") };
+
+		let mut errors = String::new();
+
+		for node in self.group_nodes(group_name)? {
+			let path = node.get_path().to_string();
+
+			let Some(value) = document.get(&path)
+			else { continue };
+
+			let mut object = node.upcast::<Object>();
+
+			let result = self
+				.schema_for(&object)
+				.and_then(|schema| Ok(schema.bind().apply_value(&mut object, value)?));
+
+			if let Err(err) = result {
+				errors += &format!("Node \"{path}\": {err}\n");
+			}
+		}
+
+		if errors.is_empty() {
+			Ok(())
+		} else {
+			bail!("{errors}")
+		}
+	}
+}
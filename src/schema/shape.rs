@@ -0,0 +1,101 @@
+use super::*;
+use godot::obj::EngineEnum;
+
+impl RootSchema {
+	/// Builds a schema from a declarative "shape", without writing a class:
+	///
+	/// - `int`: A `Variant.Type` constant, e.g. `TYPE_STRING` or `TYPE_VECTOR2`. `TYPE_ARRAY` is an untyped array.
+	/// - `String`/`StringName`: The name of a built-in type (e.g. "Vector2", "int") or of a class, whose schema is generated.
+	/// - `Script`: A GDScript class, whose schema is generated.
+	/// - `Dictionary`: An object, with one property per key, each value being the property's shape.
+	/// - `Array`: An array of the only element's shape, e.g. `[TYPE_STRING]`. An empty array is untyped.
+	///
+	/// # Example
+	/// `{"name": TYPE_STRING, "hp": TYPE_INT, "tags": [TYPE_STRING], "weapon": "Weapon"}`
+	pub fn from_shape(shape: &Variant) -> Result<RootSchema> {
+		let mut defs = BTreeMap::new();
+		let (base_ty, warnings) = collect_generation_issues(|| type_from_shape(shape, &mut defs));
+
+		let base = match base_ty? {
+			Type::Definition(def) => def,
+			Type::Ref(JRef { name, .. }) => defs
+				.remove(&name)
+				.ok_or_else(|| anyhow!("Expected definition \"{name}\" to be in `$defs` map."))?,
+		};
+
+		Ok(RootSchema {
+			defs,
+			base,
+			options: SchemaOptions::default(),
+			warnings,
+			name_map: None,
+		})
+	}
+}
+
+fn type_from_shape(shape: &Variant, defs: &mut BTreeMap<String, Definition>) -> Result<Type> {
+	match shape.get_type() {
+		VariantType::INT => {
+			let ord = shape.to::<i64>();
+
+			let variant_type = i32::try_from(ord)
+				.ok()
+				.and_then(VariantType::try_from_ord)
+				.ok_or_else(|| anyhow!("Expected a `Variant.Type` constant.\nGot: {ord}"))?;
+
+			match variant_type {
+				VariantType::NIL => Ok(Definition::null().into()),
+				VariantType::ARRAY => Ok(Type::untyped_array()),
+				VariantType::OBJECT => bail!("`TYPE_OBJECT` does not name a class, use the class name instead."),
+				other => raw_definition_from_type(other)
+					.map(Type::Definition)
+					.ok_or_else(|| anyhow!("Type {other:?} cannot be represented by a schema.")),
+			}
+		}
+		| VariantType::STRING
+		| VariantType::STRING_NAME => {
+			let name = shape.to::<String>();
+
+			if let Some(var_def) = VariantDefinition::try_from_name(&name) {
+				return Ok(var_def.into());
+			}
+
+			if let Some(def) = raw_definition_from_name(&name) {
+				return Ok(def.into());
+			}
+
+			class_reference(ClassSource::from_class_name(name)?, defs)
+		}
+		VariantType::OBJECT => {
+			let script = shape
+				.try_to::<Gd<Script>>()
+				.map_err(|_| anyhow!("Expected object shapes to be scripts.\nGot: {shape}"))?;
+
+			class_reference(ClassSource::from_script(script), defs)
+		}
+		VariantType::DICTIONARY => {
+			let properties = shape
+				.to::<Dictionary>()
+				.iter_shared()
+				.map(|(key, shape)| {
+					let name = key.to_string();
+					type_from_shape(&shape, defs)
+						.map(|ty| (name.clone(), ty))
+						.map_err(|err| anyhow!("Property \"{name}\": {err}"))
+				})
+				.try_collect::<_, Vec<_>, _>()?;
+
+			Ok(Type::object(properties.into_iter()))
+		}
+		VariantType::ARRAY => {
+			let elements = array_elements(shape)?;
+
+			match elements.as_slice() {
+				[] => Ok(Type::untyped_array()),
+				[item] => Ok(Type::array(type_from_shape(item, defs).map_err(|err| anyhow!("Array items: {err}"))?)),
+				_ => bail!("Expected array shapes to have at most one element, the shape of the items.\nGot: {shape}"),
+			}
+		}
+		other => bail!("Expected a shape (`Variant.Type`, type name, `Dictionary` or `Array`).\nGot a value of type {other:?}: {shape}"),
+	}
+}
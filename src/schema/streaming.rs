@@ -0,0 +1,231 @@
+use super::*;
+
+/// Instantiates a schema's type from JSON that arrives in chunks, such as an LLM response being streamed.
+///
+/// Each root property is converted as soon as its value is complete, emitting `property_completed`,
+/// which allows driving UI before the whole response arrives. Non-class roots complete their wrapper property
+/// (see [`RootSchema::wrapper_key()`]), unwrapped ones only emit `completed`.
+/// Once the root is complete, it is validated and instantiated as a whole, emitting `completed` (or `failed`).
+///
+/// Any text before the root's first `{` (or `[`, for unwrapped roots) is ignored, such as a markdown code fence.
+#[derive(GodotClass)]
+#[class(init, base = RefCounted)]
+pub struct StreamingInstantiator {
+	base: Base<RefCounted>,
+	#[var] pub schema: Option<Gd<GodotSchema>>,
+	buffer: String,
+	scan: ScanState,
+}
+
+#[derive(Default)]
+struct ScanState {
+	/// Byte index of the next character to scan.
+	pos: usize,
+	depth: usize,
+	in_string: bool,
+	escaped: bool,
+	root_start: Option<usize>,
+	member_start: Option<usize>,
+	finished: bool,
+}
+
+enum StreamEvent {
+	Property(String, Variant),
+	Completed(Variant),
+	Failed(String),
+}
+
+#[godot_api]
+impl StreamingInstantiator {
+	/// `name` is the property's name in Godot, even if it is renamed in JSON (see [`GodotSchema::set_property_naming()`]).
+	/// Confidences (see [`GodotSchema::with_confidence()`]) are not emitted.
+	#[signal]
+	fn property_completed(name: GString, value: Variant);
+
+	#[signal]
+	fn completed(instance: Variant);
+
+	#[signal]
+	fn failed(error_message: GString);
+
+	#[func]
+	pub fn create(schema: Gd<GodotSchema>) -> Gd<Self> {
+		Gd::from_init_fn(|base| Self {
+			base,
+			schema: Some(schema),
+			buffer: String::new(),
+			scan: ScanState::default(),
+		})
+	}
+
+	/// Appends `text` to the received JSON, emitting signals for every property (or the root object) completed by it.
+	#[func]
+	pub fn push_chunk(&mut self, text: String) {
+		let events = match &self.schema {
+			Some(schema) => {
+				self.buffer += &text;
+				scan(&self.buffer, &mut self.scan, &schema.bind())
+			}
+			None => vec![StreamEvent::Failed("No schema was assigned to the `StreamingInstantiator`.".to_owned())],
+		};
+
+		for event in events {
+			match event {
+				StreamEvent::Property(name, value) => {
+					self.base_mut().emit_signal("property_completed", &[name.to_variant(), value]);
+				}
+				StreamEvent::Completed(instance) => {
+					self.base_mut().emit_signal("completed", &[instance]);
+				}
+				StreamEvent::Failed(error_message) => {
+					self.base_mut().emit_signal("failed", &[error_message.to_variant()]);
+				}
+			}
+		}
+	}
+
+	/// Clears the received JSON, allowing the instantiator to be reused for another response.
+	#[func]
+	pub fn reset(&mut self) {
+		self.buffer.clear();
+		self.scan = ScanState::default();
+	}
+
+	#[func]
+	pub fn get_buffer(&self) -> String {
+		self.buffer.clone()
+	}
+
+	#[func]
+	pub fn is_completed(&self) -> bool {
+		self.scan.finished
+	}
+}
+
+fn scan(buffer: &str, state: &mut ScanState, schema: &GodotSchema) -> Vec<StreamEvent> {
+	let mut events = Vec::new();
+	// JSON's structural characters are ASCII, so they never appear inside multi-byte UTF-8 sequences.
+	let bytes = buffer.as_bytes();
+	// Unwrapped non-class roots have no properties to emit, the root may also be an array.
+	let streams_members = schema.inner.wrapper_key().is_some() || matches!(schema.inner.base, Definition::Class(_) | Definition::Object(_));
+
+	while state.pos < bytes.len() && !state.finished {
+		let idx = state.pos;
+		state.pos += 1;
+
+		let byte = bytes[idx];
+
+		if state.root_start.is_none() {
+			if byte == b'{' || (byte == b'[' && !streams_members) {
+				state.root_start = Some(idx);
+				state.member_start = streams_members.then_some(idx + 1);
+				state.depth = 1;
+			}
+
+			continue;
+		}
+
+		if state.in_string {
+			if state.escaped {
+				state.escaped = false;
+			} else if byte == b'\\' {
+				state.escaped = true;
+			} else if byte == b'"' {
+				state.in_string = false;
+			}
+
+			continue;
+		}
+
+		match byte {
+			b'"' => state.in_string = true,
+			b'{' | b'[' => state.depth += 1,
+			b',' if state.depth == 1 && streams_members => {
+				if let Some(start) = state.member_start {
+					events.extend(complete_member(&buffer[start..idx], schema));
+				}
+
+				state.member_start = Some(idx + 1);
+			}
+			b'}' | b']' => {
+				if state.depth == 1 {
+					if let Some(start) = state.member_start.take() {
+						events.extend(complete_member(&buffer[start..idx], schema));
+					}
+
+					let root_start = state.root_start.unwrap_or_default();
+					events.push(complete_root(&buffer[root_start..=idx], schema));
+					state.finished = true;
+				}
+
+				state.depth = state.depth.saturating_sub(1);
+			}
+			_ => {}
+		}
+	}
+
+	events
+}
+
+/// Converts `member`, a `"name": value` pair of the root object, into its property's value.
+///
+/// # Returns
+/// `None` if there is nothing to emit: `member` is empty or a confidence.
+fn complete_member(member: &str, schema: &GodotSchema) -> Option<StreamEvent> {
+	if member.trim().is_empty() {
+		return None;
+	}
+
+	let try_fn = || -> Result<Option<(String, Variant)>> {
+		let Value::Object(document) = serde_json::from_str(&format!("{{{member}}}"))?
+		else { bail!("Expected a property, got: {member}") };
+
+		let root = &schema.inner;
+		let document = root.to_godot_names(&Value::Object(document))?;
+
+		let Some((name, value)) = document.as_object().and_then(|properties| properties.iter().next())
+		else { bail!("Expected a property in: {member}") };
+
+		let def = match (root.wrapper_key(), &root.base) {
+			(Some(key), base) if name == key => base,
+			(Some(key), _) => bail!("Expected the root to be wrapped in property \"{key}\".\nGot: \"{name}\""),
+			(None, Definition::Class(JClass { properties, .. }) | Definition::Object(JObject { properties, .. })) if !properties.is_empty() => {
+				// Paired with the property they rate, see `split_confidence()`.
+				let is_confidence = root.options.confidence_suffix
+					.as_deref()
+					.and_then(|suffix| name.strip_suffix(suffix))
+					.is_some_and(|rated| properties.contains_key(rated));
+
+				if is_confidence {
+					return Ok(None);
+				}
+
+				properties
+					.get(name)
+					.ok_or_else(|| anyhow!("Unexpected property \"{name}\"."))?
+					.resolve(&root.defs)?
+			}
+			(None, Definition::Object(_)) => return Ok(Some((name.clone(), raw_variant_from_json(value)?))),
+			(None, _) => bail!("Expected the root to be an object, the schema's root is not wrapped."),
+		};
+
+		let variant = def.instantiate(value, &root.defs)?;
+		Ok(Some((name.clone(), variant)))
+	};
+
+	match try_fn() {
+		Ok(completed) => completed.map(|(name, value)| StreamEvent::Property(name, value)),
+		Err(err) => Some(StreamEvent::Failed(format!("{err}"))),
+	}
+}
+
+fn complete_root(json: &str, schema: &GodotSchema) -> StreamEvent {
+	let result = serde_json::from_str(json)
+		.map_err(SchemaError::from)
+		.and_then(|value| schema.instantiate_value(&value));
+
+	match result {
+		Ok(instance) => StreamEvent::Completed(instance),
+		Err(err) => StreamEvent::Failed(format!("{err}")),
+	}
+}
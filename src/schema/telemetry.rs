@@ -0,0 +1,45 @@
+use super::*;
+use std::cell::RefCell;
+use std::hash::{DefaultHasher, Hasher};
+
+/// Summaries longer than this are truncated, telemetry backends usually limit event sizes.
+const MAX_SUMMARY_LEN: usize = 256;
+
+thread_local! {
+	// `Callable` is not thread-safe, failures on other threads are not reported.
+	static FAILURE_HOOK: RefCell<Option<Callable>> = const { RefCell::new(None) };
+}
+
+/// Replaces the callable invoked by [`report_failure()`], `None` disables reporting.
+pub fn set_failure_hook(hook: Option<Callable>) {
+	FAILURE_HOOK.with_borrow_mut(|slot| *slot = hook);
+}
+
+pub fn has_failure_hook() -> bool {
+	FAILURE_HOOK.with_borrow(Option::is_some)
+}
+
+/// Invokes the failure hook (if any) with `(schema_name: String, error_summary: String, input_hash: int)`.
+///
+/// The input itself is not forwarded, it may contain player data, the hash allows grouping identical failures.
+pub fn report_failure(schema_name: &str, err: &SchemaError, input: &Value) {
+	let Some(hook) = FAILURE_HOOK.with_borrow(Clone::clone)
+	else { return };
+
+	if !hook.is_valid() {
+		return;
+	}
+
+	let mut summary = err.to_string().lines().next().unwrap_or_default().to_owned();
+
+	if let Some((idx, _)) = summary.char_indices().nth(MAX_SUMMARY_LEN) {
+		summary.truncate(idx);
+		summary += "...";
+	}
+
+	let mut hasher = DefaultHasher::new();
+	hasher.write(input.to_string().as_bytes());
+	let input_hash = hasher.finish() as i64;
+
+	hook.callv(&varray![GString::from(schema_name), summary, input_hash]);
+}
@@ -0,0 +1,229 @@
+use super::*;
+
+/// A command registered in a [`SchemaConsole`].
+pub struct ConsoleCommand {
+	pub schema: Gd<GodotSchema>,
+	pub callable: Callable,
+}
+
+/// Backbone of an in-game debug/cheat console with typed arguments.
+///
+/// Each command's arguments are defined by a schema, input is validated against it and instantiated
+/// before being passed to the command's callable.
+///
+/// # Input formats
+/// - Named arguments: `spawn_enemy kind=Goblin count=3 position={"x":1,"y":2}`
+/// - Positional arguments, in the order of the schema's properties: `spawn_enemy 3 Goblin {"x":1,"y":2}`
+/// - A JSON object with the arguments: `spawn_enemy {"kind": "Goblin", "count": 3, "position": {"x":1,"y":2}}`
+/// - A JSON tool call: `{"name": "spawn_enemy", "arguments": {...}}`
+///
+/// Argument values are parsed as JSON when possible, otherwise they are treated as strings.
+/// Wrap strings containing spaces in quotes: `say text="Hello there"`.
+#[derive(GodotClass)]
+#[class(init, base = RefCounted)]
+pub struct SchemaConsole {
+	pub commands: BTreeMap<String, ConsoleCommand>,
+}
+
+#[godot_api]
+impl SchemaConsole {
+	/// Registers a command named `name`, whose arguments are defined by `schema`.
+	///
+	/// When executed, `callable` is invoked with a single argument: the instance of `schema`'s type.
+	/// (For class schemas, an object of that class; for object schemas, a Dictionary)
+	#[func]
+	pub fn register_command(&mut self, name: String, schema: Gd<GodotSchema>, callable: Callable) {
+		self.commands.insert(name, ConsoleCommand { schema, callable });
+	}
+
+	#[func]
+	pub fn unregister_command(&mut self, name: String) -> bool {
+		self.commands.remove(&name).is_some()
+	}
+
+	#[func]
+	pub fn get_command_names(&self) -> PackedStringArray {
+		self.commands.keys().map(GString::from).collect()
+	}
+
+	/// Returns a usage line for the command named `name`, e.g. "spawn_enemy <count> <kind>", or an empty string if not found.
+	#[func]
+	pub fn get_command_usage(&self, name: String) -> String {
+		let Some(command) = self.commands.get(&name)
+		else { return String::default() };
+
+		let schema = command.schema.bind();
+		let mut usage = name;
+
+		for arg in argument_names(&schema.inner) {
+			usage += &format!(" <{arg}>");
+		}
+
+		usage
+	}
+
+	/// Parses `input`, validates the arguments against the command's schema and invokes the command's callable.
+	///
+	/// # Returns
+	/// - The value returned by the callable, if successful.
+	/// - Otherwise a `String` containing the error message.
+	#[func]
+	pub fn execute(&self, input: String) -> Variant {
+		match self.try_execute(&input) {
+			Ok(result) => result,
+			Err(err) => format!("{err}").to_variant(),
+		}
+	}
+}
+
+impl SchemaConsole {
+	pub fn try_execute(&self, input: &str) -> Result<Variant> {
+		let input = input.trim();
+
+		let (name, arguments) =
+			if input.starts_with('{') {
+				let call = ToolCall::from_json(input)?;
+				(call.name, call.arguments)
+			} else {
+				let mut tokens = tokenize(input)?.into_iter();
+
+				let name = tokens
+					.next()
+					.ok_or_else(|| anyhow!("Expected a command name."))?;
+
+				let command = self.command(&name)?;
+				let arguments = parse_arguments(&command.schema.bind().inner, tokens.collect())?;
+				(name, arguments)
+			};
+
+		let command = self.command(&name)?;
+		let instance = command.schema.bind().instantiate_value(&arguments)?;
+		Ok(command.callable.callv(&varray![instance]))
+	}
+
+	fn command(&self, name: &str) -> Result<&ConsoleCommand> {
+		self.commands
+			.get(name)
+			.ok_or_else(|| anyhow!("Unknown command \"{name}\"."))
+	}
+}
+
+/// Returns the names of the arguments accepted by `schema`, in the order used for positional arguments.
+fn argument_names(schema: &RootSchema) -> Vec<String> {
+	match &schema.base {
+		Definition::Class(class) => class.properties.keys().cloned().collect(),
+		Definition::Object(object) => object.properties.keys().cloned().collect(),
+		_ => vec![schema.options.wrapper_key.clone().unwrap_or_else(|| DEFAULT_WRAPPER_KEY.to_owned())],
+	}
+}
+
+fn parse_arguments(schema: &RootSchema, tokens: Vec<String>) -> Result<Value> {
+	if let [single] = tokens.as_slice()
+		&& single.starts_with('{')
+		&& matches!(schema.base, Definition::Class(_) | Definition::Object(_)) {
+		return Ok(serde_json::from_str(single)?);
+	}
+
+	let names = argument_names(schema);
+	let mut arguments = Map::new();
+	let mut positional = names.iter();
+
+	for token in tokens {
+		let (name, value) = match split_named(&token) {
+			Some((name, value)) => (name.to_owned(), value),
+			None => {
+				let name = positional
+					.next()
+					.ok_or_else(|| anyhow!("Too many arguments, expected at most {}.", names.len()))?;
+
+				(name.clone(), token.as_str())
+			}
+		};
+
+		if arguments.insert(name.clone(), parse_value(value)).is_some() {
+			bail!("Argument \"{name}\" was provided more than once.");
+		}
+	}
+
+	// Unwrapped non-class roots take their single argument as the whole document.
+	if schema.options.unwrapped_root && !matches!(schema.base, Definition::Class(_) | Definition::Object(_)) {
+		return Ok(arguments.remove(&names[0]).unwrap_or_default());
+	}
+
+	Ok(Value::Object(arguments))
+}
+
+/// Splits `name=value` tokens, only if `name` is an identifier.
+fn split_named(token: &str) -> Option<(&str, &str)> {
+	let (name, value) = token.split_once('=')?;
+
+	if !name.is_empty() && name.chars().all(|ch| ch.is_alphanumeric() || ch == '_') {
+		Some((name, value))
+	} else {
+		None
+	}
+}
+
+fn parse_value(token: &str) -> Value {
+	if token.len() >= 2 && token.starts_with('\'') && token.ends_with('\'') {
+		return Value::String(token[1..token.len() - 1].to_owned());
+	}
+
+	serde_json::from_str(token).unwrap_or_else(|_| Value::String(token.to_owned()))
+}
+
+/// Splits console input on whitespace, except inside quotes, objects and arrays.
+fn tokenize(input: &str) -> Result<Vec<String>> {
+	let mut tokens = Vec::new();
+	let mut current = String::new();
+	let mut depth = 0_usize;
+	let mut quote = None;
+	let mut escaped = false;
+
+	for ch in input.chars() {
+		if let Some(quote_ch) = quote {
+			current.push(ch);
+
+			if escaped {
+				escaped = false;
+			} else if ch == '\\' {
+				escaped = true;
+			} else if ch == quote_ch {
+				quote = None;
+			}
+
+			continue;
+		}
+
+		match ch {
+			'"' | '\'' => {
+				quote = Some(ch);
+				current.push(ch);
+			}
+			'{' | '[' => {
+				depth += 1;
+				current.push(ch);
+			}
+			'}' | ']' => {
+				depth = depth.saturating_sub(1);
+				current.push(ch);
+			}
+			ch if ch.is_whitespace() && depth == 0 => {
+				if !current.is_empty() {
+					tokens.push(std::mem::take(&mut current));
+				}
+			}
+			ch => current.push(ch),
+		}
+	}
+
+	if quote.is_some() || depth != 0 {
+		bail!("Unterminated quote or bracket in console input: {input}");
+	}
+
+	if !current.is_empty() {
+		tokens.push(current);
+	}
+
+	Ok(tokens)
+}
@@ -0,0 +1,63 @@
+use super::*;
+
+/// Turns methods of Godot objects into tools that an LLM can call.
+///
+/// Register methods with [`Self::register_method()`], then feed the LLM's tool calls to [`Self::dispatch()`].
+#[derive(GodotClass)]
+#[class(init, base = Node)]
+pub struct ToolDispatcher {
+	pub tools: BTreeMap<String, MethodTool>,
+}
+
+#[godot_api]
+impl ToolDispatcher {
+	/// Generates a schema for the parameters of `object`'s method named `method`, and registers it as a tool named `method`.
+	///
+	/// # Returns
+	/// - The `GodotSchema` object containing the method's parameters schema, if successful.
+	/// - Otherwise a `String` containing the error message.
+	#[func]
+	pub fn register_method(&mut self, object: Gd<Object>, method: StringName) -> Variant {
+		match MethodTool::new(object, method.clone()) {
+			Ok(tool) => {
+				let schema = tool.schema.clone();
+				self.tools.insert(method.to_string(), tool);
+				schema.to_variant()
+			}
+			Err(err) => format!("{err:?}").to_variant(),
+		}
+	}
+
+	/// Returns the parameters schema of the tool named `name`.
+	///
+	/// # Returns
+	/// - The `GodotSchema` object containing the method's parameters schema, if found.
+	/// - Otherwise a `String` containing the error message.
+	#[func]
+	pub fn get_method_schema(&self, name: String) -> Variant {
+		match self.tools.get(&name) {
+			Some(tool) => tool.schema.to_variant(),
+			None => format!("No tool named \"{name}\" is registered.").to_variant(),
+		}
+	}
+
+	/// Validates the arguments of an LLM tool call (`{"name": ..., "arguments": {...}}`) against the method's schema,
+	/// then invokes the method.
+	///
+	/// # Returns
+	/// - The value returned by the method, if successful.
+	/// - Otherwise a `String` containing the error message.
+	#[func]
+	pub fn dispatch(&self, tool_call_json: String) -> Variant {
+		match self.try_dispatch(&tool_call_json) {
+			Ok(result) => result,
+			Err(err) => format!("{err}").to_variant(),
+		}
+	}
+}
+
+impl ToolDispatcher {
+	pub fn try_dispatch(&self, tool_call_json: &str) -> Result<Variant> {
+		dispatch_tool_call(&self.tools, tool_call_json)
+	}
+}
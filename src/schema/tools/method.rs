@@ -0,0 +1,112 @@
+use super::*;
+
+/// A method of a Godot object, along with the schema of its parameters.
+#[derive(Clone)]
+pub struct MethodTool {
+	pub object: Gd<Object>,
+	pub method: StringName,
+	/// In declaration order, the order in which arguments are passed to the method.
+	pub arg_names: Vec<String>,
+	pub schema: Gd<GodotSchema>,
+}
+
+impl MethodTool {
+	pub fn new(object: Gd<Object>, method: StringName) -> Result<Self> {
+		let arguments = fetch_method_arguments(&object, &method)?;
+		let arg_names = arguments.iter().map(|info| info.property_name.clone()).collect();
+		let schema = RootSchema::from_method_arguments(arguments).and_then(GodotSchema::new)?;
+
+		Ok(Self {
+			object,
+			method,
+			arg_names,
+			schema: Gd::from_object(schema),
+		})
+	}
+
+	/// Validates `arguments` against the method's schema, then calls the method with them.
+	pub fn call(&self, arguments: &Value) -> Result<Variant> {
+		if !self.object.is_instance_valid() {
+			bail!("The object that owns method \"{}\" was freed.", self.method);
+		}
+
+		let values = self.schema
+			.bind()
+			.instantiate_value(arguments)?
+			.try_to::<Dictionary>()
+			.map_err(|err| anyhow!("{err:?}"))?;
+
+		let args = self.arg_names
+			.iter()
+			.map(|name| values.get(name.as_str()).unwrap_or_default())
+			.collect::<Vec<_>>();
+
+		Ok(self.object.clone().call(&self.method, &args))
+	}
+
+	/// Returns the definition of this tool, in the format expected by `provider`.
+	pub fn definition(&self, provider: ToolProvider) -> Result<Value> {
+		let parameters = self.schema.bind().inner.to_provider_value()?;
+
+		let name = self.method.to_string();
+
+		Ok(match provider {
+			ToolProvider::OpenAi => serde_json::json!({
+				"type": "function",
+				"function": {
+					"name": name,
+					"parameters": parameters,
+				},
+			}),
+			ToolProvider::Anthropic => serde_json::json!({
+				"name": name,
+				"input_schema": parameters,
+			}),
+		})
+	}
+}
+
+impl RootSchema {
+	/// Generates a schema for the parameters of `object`'s method named `method`.
+	///
+	/// The schema is an object with one property per parameter.
+	pub fn from_method(object: &Gd<Object>, method: &StringName) -> Result<Self> {
+		fetch_method_arguments(object, method).and_then(Self::from_method_arguments)
+	}
+
+	pub fn from_method_arguments(arguments: Vec<PropertyTypeInfo>) -> Result<Self> {
+		let mut defs = BTreeMap::new();
+		let mut base = JObject::new();
+
+		for info in arguments {
+			let ty = info.eval_type(&mut defs)?;
+			base.add_property(info.property_name, ty);
+		}
+
+		Ok(RootSchema {
+			defs,
+			base: base.into(),
+			options: SchemaOptions::default(),
+			warnings: Vec::new(),
+			name_map: None,
+		})
+	}
+}
+
+/// Returns the type info of each parameter of `object`'s method named `method`, in declaration order.
+pub fn fetch_method_arguments(object: &Gd<Object>, method: &StringName) -> Result<Vec<PropertyTypeInfo>> {
+	let method_dict = object
+		.get_method_list()
+		.iter_shared()
+		.find(|dict| try_get::<StringName>(dict, "name").is_ok_and(|name| name == *method))
+		.ok_or_else(|| anyhow!("Expected object to have a method named \"{method}\"."))?;
+
+	try_get::<VariantArray>(&method_dict, "args")?
+		.iter_shared()
+		.map(|arg| {
+			arg.try_to::<Dictionary>()
+				.map_err(|err| anyhow!("{err:?}"))
+				.and_then(PropertyTypeInfo::try_from)
+		})
+		.try_collect()
+}
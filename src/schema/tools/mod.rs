@@ -0,0 +1,78 @@
+use super::*;
+
+pub use console::*;
+pub use dispatcher::*;
+pub use method::*;
+
+pub mod console;
+pub mod dispatcher;
+pub mod method;
+
+/// A tool call emitted by an LLM.
+///
+/// Accepts the common provider shapes:
+/// - `{"name": ..., "arguments": {...}}`
+/// - OpenAI: `{"function": {"name": ..., "arguments": "<json string>"}}`
+/// - Anthropic: `{"name": ..., "input": {...}}`
+pub struct ToolCall {
+	pub name: String,
+	pub arguments: Value,
+}
+
+impl ToolCall {
+	pub fn from_json(json: &str) -> Result<Self> {
+		let Value::Object(mut call) = serde_json::from_str(json)?
+		else { bail!("Expected tool call to be a JSON object.\nGot: {json}") };
+
+		if let Some(Value::Object(function)) = call.remove("function") {
+			call = function;
+		}
+
+		let Some(Value::String(name)) = call.remove("name")
+		else { bail!("Expected tool call to have a string property \"name\".") };
+
+		let arguments = match call.remove("arguments").or_else(|| call.remove("input")) {
+			// OpenAI encodes the arguments as a JSON string.
+			Some(Value::String(arguments)) => serde_json::from_str(&arguments)?,
+			Some(arguments) => arguments,
+			None => Value::Object(Map::new()),
+		};
+
+		Ok(Self { name, arguments })
+	}
+}
+
+/// The LLM provider whose tool definition format is produced by [`tool_definitions()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ToolProvider {
+	OpenAi,
+	Anthropic,
+}
+
+impl ToolProvider {
+	pub fn from_name(name: &str) -> Result<Self> {
+		match name.to_lowercase().replace(['_', '-', ' '], "").as_str() {
+			"openai" => Ok(ToolProvider::OpenAi),
+			"anthropic" => Ok(ToolProvider::Anthropic),
+			_ => bail!("Unknown tool provider \"{name}\", expected one of: \"OpenAI\", \"Anthropic\"."),
+		}
+	}
+}
+
+/// Returns the JSON array of tool definitions for `tools`, in the format expected by `provider`.
+pub fn tool_definitions<'a>(tools: impl IntoIterator<Item = &'a MethodTool>, provider: ToolProvider) -> Result<Value> {
+	tools.into_iter()
+		.map(|tool| tool.definition(provider))
+		.try_collect()
+}
+
+/// Parses `tool_call_json` with [`ToolCall::from_json()`], then calls the matching tool in `tools`.
+pub fn dispatch_tool_call(tools: &BTreeMap<String, MethodTool>, tool_call_json: &str) -> Result<Variant> {
+	let call = ToolCall::from_json(tool_call_json)?;
+
+	let tool = tools
+		.get(&call.name)
+		.ok_or_else(|| anyhow!("No tool named \"{}\" is registered.", call.name))?;
+
+	tool.call(&call.arguments)
+}
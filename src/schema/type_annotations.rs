@@ -0,0 +1,153 @@
+use super::*;
+use godot::obj::{EngineBitfield, EngineEnum};
+
+/// Annotates a class property with its `Variant.Type`, as an integer.
+pub const GODOT_TYPE_KEY: &str = "x-godot-type";
+/// Annotates a class property with its `PropertyHint`, as an integer. Omitted for `PROPERTY_HINT_NONE`.
+pub const GODOT_HINT_KEY: &str = "x-godot-hint";
+/// Annotates a class property with its hint string. Omitted if empty.
+pub const GODOT_HINT_STRING_KEY: &str = "x-godot-hint-string";
+/// Annotates a class property with its `PropertyUsageFlags`, as an integer. Omitted for `PROPERTY_USAGE_DEFAULT`.
+pub const GODOT_USAGE_KEY: &str = "x-godot-usage";
+
+/// The Godot type of a class property, as reported by its property list.
+///
+/// Emitted next to the property's schema when type annotations are enabled (see [`SchemaOptions::type_annotations`]),
+/// allowing tools to reconstruct the original [`PropertyTypeInfo`] instead of guessing it from the JSON types.
+/// The property's class name is emitted under [`GODOT_CLASS_KEY`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GodotTypeInfo {
+	pub variant_type: VariantType,
+	pub class_name: String,
+	pub hint: PropertyHint,
+	pub hint_string: String,
+	pub usage: PropertyUsageFlags,
+}
+
+impl GodotTypeInfo {
+	pub fn to_property_type_info(&self, property_name: impl Into<String>) -> PropertyTypeInfo {
+		PropertyTypeInfo {
+			variant_type: self.variant_type,
+			class_name: self.class_name.as_str().into(),
+			property_name: property_name.into(),
+			hint: self.hint,
+			hint_string: self.hint_string.clone(),
+			usage: self.usage,
+		}
+	}
+
+	pub fn insert_annotations(&self, map: &mut Map<String, Value>) {
+		map.insert(GODOT_TYPE_KEY.into(), self.variant_type.ord().into());
+
+		if !self.class_name.is_empty() {
+			map.insert(GODOT_CLASS_KEY.into(), self.class_name.clone().into());
+		}
+
+		if self.hint != PropertyHint::NONE {
+			map.insert(GODOT_HINT_KEY.into(), self.hint.ord().into());
+		}
+
+		if !self.hint_string.is_empty() {
+			map.insert(GODOT_HINT_STRING_KEY.into(), self.hint_string.clone().into());
+		}
+
+		if self.usage != PropertyUsageFlags::DEFAULT {
+			map.insert(GODOT_USAGE_KEY.into(), self.usage.ord().into());
+		}
+	}
+
+	/// The reverse of [`Self::insert_annotations()`].
+	///
+	/// # Returns
+	/// `None` if `map` has no (valid) [`GODOT_TYPE_KEY`].
+	pub fn from_annotations(map: &Map<String, Value>) -> Option<Self> {
+		let variant_type = map
+			.get(GODOT_TYPE_KEY)
+			.and_then(Value::as_i64)
+			.and_then(|ord| VariantType::try_from_ord(ord as i32))?;
+
+		let hint = match map.get(GODOT_HINT_KEY).and_then(Value::as_i64) {
+			Some(ord) => PropertyHint::try_from_ord(ord as i32)?,
+			None => PropertyHint::NONE,
+		};
+
+		let usage = match map.get(GODOT_USAGE_KEY).and_then(Value::as_u64) {
+			Some(ord) => PropertyUsageFlags::try_from_ord(ord)?,
+			None => PropertyUsageFlags::DEFAULT,
+		};
+
+		let string_at = |key: &str| map.get(key).and_then(Value::as_str).unwrap_or_default().to_owned();
+
+		Some(Self {
+			variant_type,
+			class_name: string_at(GODOT_CLASS_KEY),
+			hint,
+			hint_string: string_at(GODOT_HINT_STRING_KEY),
+			usage,
+		})
+	}
+
+	/// Whether the serialization running on this thread emits type annotations.
+	pub fn annotations_enabled() -> bool {
+		SchemaOptions::read_current(|options| options.type_annotations)
+	}
+}
+
+impl From<&PropertyTypeInfo> for GodotTypeInfo {
+	fn from(info: &PropertyTypeInfo) -> Self {
+		Self {
+			variant_type: info.variant_type,
+			class_name: info.class_name.to_string(),
+			hint: info.hint,
+			hint_string: info.hint_string.clone(),
+			usage: info.usage,
+		}
+	}
+}
+
+/// Serializes the properties of a class (but the inherited ones, see [`JClass::base`]),
+/// annotating each with its [`GodotTypeInfo`] if annotations are enabled, and adding its [`JClass::constraints`].
+pub(crate) struct AnnotatedProperties<'a>(pub &'a JClass);
+
+impl Serialize for AnnotatedProperties<'_> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+		use serde::ser::Error;
+
+		let class = self.0;
+		let enabled = GodotTypeInfo::annotations_enabled();
+		let renames = SchemaOptions::read_current(|options| !options.property_naming.is_as_is());
+		let mut map = serializer.serialize_map(None)?;
+
+		for (name, ty) in class.properties.iter().filter(|(name, _)| !class.is_inherited(name)) {
+			let info = class.type_infos.get(name).filter(|_| enabled);
+			let constraints = class.constraints.get(name);
+			let alias = class.aliases.get(name);
+
+			if info.is_none() && constraints.is_none() && alias.is_none() {
+				map.serialize_entry(name, ty)?;
+				continue;
+			}
+
+			let mut value = serde_json::to_value(ty).map_err(S::Error::custom)?;
+
+			if let Value::Object(keywords) = &mut value {
+				if let Some(constraints) = constraints {
+					keywords.extend(constraints.clone());
+				}
+
+				if let Some(info) = info {
+					info.insert_annotations(keywords);
+				}
+
+				// Renaming keeps aliases, see `PropertyNaming::rename_schema()`.
+				if alias.is_some() && (round_trip_annotations() || renames) {
+					keywords.insert(GODOT_PROPERTY_KEY.to_owned(), name.as_str().into());
+				}
+			}
+
+			map.serialize_entry(class.json_name(name), &value)?;
+		}
+
+		map.end()
+	}
+}
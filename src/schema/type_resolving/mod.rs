@@ -1,7 +1,9 @@
 use super::*;
 
 pub use property_wrapper::*;
+pub use serialization::*;
 pub use utils::*;
 
 pub mod property_wrapper;
+pub mod serialization;
 pub mod utils;
\ No newline at end of file
@@ -1,4 +1,5 @@
 use super::*;
+use godot::obj::EngineEnum;
 
 pub struct PropertyTypeInfo {
 	pub variant_type: VariantType,
@@ -25,18 +26,84 @@ impl TryFrom<Dictionary> for PropertyTypeInfo {
 }
 
 impl PropertyTypeInfo {
+	/// Type info of an unnamed value, the property name is derived from `variant_type`.
+	pub fn new(
+		variant_type: VariantType,
+		class_name: StringName,
+		hint: PropertyHint,
+		hint_string: String,
+		usage: PropertyUsageFlags,
+	) -> Self {
+		Self {
+			variant_type,
+			class_name,
+			hint,
+			hint_string,
+			usage,
+			property_name: format!("{variant_type:?}"),
+		}
+	}
+
+	/// The element type encoded by the subtype syntax of container hint strings, `"<type>/<hint>:<hint string>"`
+	/// (or `"<type>:<hint string>"`), e.g. `"28/31:int"` for the elements of an `Array[Array[int]]`.
+	///
+	/// `None` if the hint string is a plain type name.
+	fn nested_element(&self) -> Option<PropertyTypeInfo> {
+		let (prefix, hint_string) = self.hint_string.split_once(':')?;
+
+		let (variant_type, hint) = match prefix.split_once('/') {
+			Some((variant_type, hint)) => (variant_type, PropertyHint::try_from_ord(hint.parse().ok()?)?),
+			None => (prefix, PropertyHint::NONE),
+		};
+
+		let variant_type = VariantType::try_from_ord(variant_type.parse().ok()?)?;
+
+		// Object elements are named by their hint string, e.g. `"24/17:Texture2D"`.
+		let class_name = if variant_type == VariantType::OBJECT {
+			StringName::from(hint_string)
+		} else {
+			StringName::default()
+		};
+
+		Some(PropertyTypeInfo {
+			variant_type,
+			class_name,
+			property_name: self.property_name.clone(),
+			hint,
+			hint_string: hint_string.to_owned(),
+			usage: PropertyUsageFlags::NONE,
+		})
+	}
+
+	/// Why this property's type cannot be represented by a schema, if it cannot, see [`UnsupportedPropertyPolicy`].
+	pub fn unsupported_reason(&self) -> Option<&'static str> {
+		match self.variant_type {
+			VariantType::CALLABLE => Some("Callables cannot be represented in JSON"),
+			VariantType::SIGNAL => Some("Signals cannot be represented in JSON"),
+			VariantType::RID => Some("RIDs are handles only meaningful to the running engine"),
+			VariantType::OBJECT if self.class_name.is_empty() && self.hint_string.is_empty() => {
+				Some("the property has no class to generate a schema from")
+			}
+			_ => None,
+		}
+	}
+
 	pub fn eval_type(&self, defs: &mut BTreeMap<String, Definition>) -> Result<Type> {
 		let schema = match self.variant_type {
 			VariantType::INT if self.usage.is_set(PropertyUsageFlags::CLASS_IS_ENUM) => {
-				Some(eval_no_type_hint(&self.class_name, &self.hint_string, self.usage, defs)?)
+				Some(self.eval_no_type_hint(defs)?)
 			}
 			VariantType::OBJECT => {
-				Some(eval_no_type_hint(&self.class_name, &self.hint_string, self.usage, defs)?)
+				Some(self.eval_no_type_hint(defs)?)
 			}
+			// Untyped properties (`var data`) accept any value, instantiated as in untyped contexts.
+			VariantType::NIL if self.usage.is_set(PropertyUsageFlags::NIL_IS_VARIANT) => Some(Definition::any().into()),
 			VariantType::ARRAY => {
 				let array =
-					if self.hint == PropertyHint::ARRAY_TYPE {
-						JArray::new(eval_no_type_hint(&self.class_name, &self.hint_string, self.usage, defs)?)
+					if self.hint == PropertyHint::ARRAY_TYPE && let Some(element) = self.nested_element() {
+						JArray::new(element.eval_type(defs)?)
+					} else if self.hint == PropertyHint::ARRAY_TYPE {
+						JArray::new(self.eval_no_type_hint(defs)?)
 					} else {
 						JArray::untyped()
 					}.into();
@@ -47,64 +114,129 @@ impl PropertyTypeInfo {
 		};
 
 		schema.or_else(|| raw_definition_from_type(self.variant_type).map(Type::Definition))
-			.ok_or_else(|| anyhow!("Unsupported property type: {:?}", self.variant_type))
+			.ok_or_else(|| {
+				SchemaError::UnsupportedProperty {
+					name: self.property_name.clone(),
+					variant_type: format!("{:?}", self.variant_type),
+					hint: self.hint_string.clone(),
+				}.into()
+			})
 	}
-}
 
-fn eval_no_type_hint(
-	class_name: &StringName,
-	hint_string: &str,
-	usage: PropertyUsageFlags,
-	defs: &mut BTreeMap<String, Definition>,
-) -> Result<Type> {
-	if usage.is_set(PropertyUsageFlags::CLASS_IS_ENUM) {
-		let (enum_def, enum_name) = JEnum::from_enum_path(class_name)?;
-		let jref = JRef::new(enum_name);
-		defs.insert(jref.name.clone(), enum_def.into());
-		return Ok(jref.into());
-	}
+	fn eval_no_type_hint(&self, defs: &mut BTreeMap<String, Definition>) -> Result<Type> {
+		let Self { class_name, hint_string, usage, .. } = self;
+		let hint_string = hint_string.as_str();
+
+		if usage.is_set(PropertyUsageFlags::CLASS_IS_ENUM) {
+			let (enum_def, enum_name) = JEnum::from_enum_path(class_name)?;
+			let jref = JRef::new(enum_name);
+			defs.insert(jref.name.clone(), enum_def.into());
+			return Ok(jref.into());
+		}
+
+		if !class_name.is_empty() {
+			if let Some(resource_path) = resource_path_type(class_name) {
+				return Ok(resource_path);
+			}
+
+			let class_from_name = ClassSource::from_class_name(class_name.clone())
+				.and_then(|source| class_reference(source, defs));
 
-	if !class_name.is_empty() {
-		let class_from_name = ClassSource::from_class_name(class_name.clone())
-			.and_then(|source| JClass::generate(source, defs))
-			.map(|class| {
-				let jref = class.source.to_reference();
-				defs.insert(jref.name.clone(), class.into());
-				jref.into()
-			});
+			match class_from_name {
+				Ok(class) => return Ok(class),
+				Err(err) => self.push_issue(
+					GenerationIssueKind::UnresolvedClass,
+					format!("Could not resolve class \"{class_name}\" of property \"{}\", using its hint \"{hint_string}\" instead: {err}", self.property_name),
+				),
+			}
+		}
+
+		if hint_string.is_empty() {
+			self.push_issue(
+				GenerationIssueKind::NullFallback,
+				format!("Property \"{}\" (type {:?}) has no class nor hint to describe it with, it was described as `null`.", self.property_name, self.variant_type),
+			);
+
+			return Ok(json_type_of::<Null>());
+		}
+
+		if let Some(ty) = VariantDefinition::try_from_name(hint_string) {
+			return Ok(ty.into());
+		}
 
-		if let Ok(class) = class_from_name {
+		if let Some(ty) = raw_definition_from_name(hint_string) {
+			return Ok(ty.into());
+		}
+
+		if let Some(resource_path) = resource_path_type(&StringName::from(hint_string)) {
+			return Ok(resource_path);
+		}
+
+		let class_from_hint = ClassSource::from_class_name(hint_string)
+			.and_then(|source| class_reference(source, defs));
+
+		if let Ok(class) = class_from_hint {
 			return Ok(class);
 		}
+
+		let (enum_def, enum_name) = JEnum::from_enum_path(hint_string)?;
+
+		let jref = JRef::new(enum_name);
+		defs.insert(jref.name.clone(), enum_def.into());
+		Ok(jref.into())
 	}
 
-	if hint_string.is_empty() {
-		return Ok(json_type_of::<Null>());
+	fn push_issue(&self, kind: GenerationIssueKind, message: String) {
+		push_generation_issue(GenerationIssue {
+			kind,
+			class_name: None,
+			property: Some(self.property_name.clone()),
+			message,
+		});
 	}
+}
+
+/// Values of some classes are referenced by path instead of generated as a class:
+/// - `PackedScene`s, always.
+/// - `Node`s, if scene instancing is enabled (see [`SchemaOptions::scene_instancing`]).
+/// - Other `Resource`s, if resource paths are enabled (see [`SchemaOptions::resource_path_dirs`]).
+fn resource_path_type(class_name: &StringName) -> Option<Type> {
+	let source = ClassSource::from_class_name(class_name.clone()).ok()?;
+	let (allowed_dirs, scene_instancing) = SchemaOptions::read_current(|options| (options.resource_path_dirs.clone(), options.scene_instancing));
 
-	if let Some(ty) = VariantDefinition::try_from_name(hint_string) {
-		return Ok(ty.into());
+	if source.inherits_engine_class("PackedScene") {
+		return Some(JResourcePath::scene(allowed_dirs.unwrap_or_default()).into());
 	}
 
-	if let Some(ty) = raw_definition_from_name(hint_string) {
-		return Ok(ty.into());
+	if scene_instancing && source.inherits_engine_class("Node") {
+		return Some(JResourcePath::scene_instance(source, allowed_dirs.unwrap_or_default()).into());
 	}
 
-	let class_from_hint = ClassSource::from_class_name(hint_string)
-		.and_then(|source| JClass::generate(source, defs))
-		.map(|class| {
-			let jref = class.source.to_reference();
-			defs.insert(jref.name.clone(), class.into());
-			jref.into()
-		});
+	let allowed_dirs = allowed_dirs?;
 
-	if let Ok(class) = class_from_hint {
-		return Ok(class);
-	}
+	source
+		.inherits_engine_class("Resource")
+		.then(|| JResourcePath::new(source, allowed_dirs).into())
+}
 
-	let (enum_def, enum_name) = JEnum::from_enum_path(hint_string)?;
+/// Generates the class defined by `source` into `defs`, returning a reference to it.
+///
+/// Classes already in `defs` are not generated again, which also allows recursive classes.
+pub(crate) fn class_reference(source: ClassSource, defs: &mut BTreeMap<String, Definition>) -> Result<Type> {
+	let jref = source.to_reference();
+
+	if !defs.contains_key(&jref.name) {
+		// Placeholder, replaced once the class is generated.
+		defs.insert(jref.name.clone(), Definition::null());
+
+		match JClass::generate(source, defs) {
+			Ok(class) => { defs.insert(jref.name.clone(), class.into()); }
+			Err(err) => {
+				defs.remove(&jref.name);
+				return Err(err);
+			}
+		}
+	}
 
-	let jref = JRef::new(enum_name);
-	defs.insert(jref.name.clone(), enum_def.into());
 	Ok(jref.into())
-}
\ No newline at end of file
+}
@@ -0,0 +1,168 @@
+use super::*;
+
+impl Definition {
+	/// The reverse of [`Definition::instantiate()`]: converts a Godot value of this definition's type into JSON.
+	pub fn json_from_variant(&self, variant: &Variant, defs: &BTreeMap<String, Definition>) -> Result<Value> {
+		let variant_type = variant.get_type();
+
+		Ok(match self {
+			Definition::Null(_) => Value::Null,
+			Definition::Boolean(_) => Value::Bool(variant.try_to::<bool>().map_err(|err| anyhow!("{err:?}"))?),
+			Definition::Integer(_) => Value::from(variant.try_to::<i64>().map_err(|err| anyhow!("{err:?}"))?),
+			Definition::Number(_) => match variant_type {
+				VariantType::INT => Value::from(variant.to::<i64>()),
+				_ => Value::from(variant.try_to::<f64>().map_err(|err| anyhow!("{err:?}"))?),
+			},
+			Definition::String(_) => match variant_type {
+				| VariantType::STRING
+				| VariantType::STRING_NAME
+				| VariantType::NODE_PATH => Value::String(variant.stringify().to_string()),
+				_ => bail!("Expected string, got: {variant_type:?}"),
+			},
+			Definition::Object(object) => {
+				if object.properties.is_empty() {
+					let Some(values_ty) = &object.values_ty
+					else { return raw_json_from_variant(variant) };
+
+					let values_def = values_ty.resolve(defs)?;
+
+					let dict = variant
+						.try_to::<Dictionary>()
+						.map_err(|err| anyhow!("{err:?}"))?;
+
+					return dict
+						.iter_shared()
+						.map(|(key, value)| {
+							let key = key.stringify().to_string();
+							let value = values_def.json_from_variant(&value, defs).map_err(|err| anyhow!("Key \"{key}\": {err}"))?;
+							Ok((key, value))
+						})
+						.try_collect::<_, Map<_, _>, anyhow::Error>()
+						.map(Value::Object);
+				}
+
+				let dict = variant
+					.try_to::<Dictionary>()
+					.map_err(|err| anyhow!("{err:?}"))?;
+
+				let mut properties = Map::new();
+
+				for (name, ty) in &object.properties {
+					let value = dict
+						.get(name.as_str())
+						.ok_or_else(|| anyhow!("Expected key \"{name}\" to be in dictionary."))?;
+
+					properties.insert(name.clone(), ty.resolve(defs)?.json_from_variant(&value, defs)?);
+				}
+
+				Value::Object(properties)
+			}
+			Definition::Array(JArray { items_ty, .. }) => {
+				let elements = array_elements(variant)?;
+
+				match items_ty {
+					Some(ty) => {
+						let def = ty.resolve(defs)?;
+
+						elements
+							.iter()
+							.map(|element| def.json_from_variant(element, defs))
+							.try_collect()?
+					}
+					None => elements
+						.iter()
+						.map(raw_json_from_variant)
+						.try_collect()?,
+				}
+			}
+			Definition::Tuple(JTuple { items, .. }) => {
+				let elements = array_elements(variant)?;
+
+				if items.len() != elements.len() {
+					bail!("Expected array to have {} elements.\nGot: {}", items.len(), elements.len());
+				}
+
+				items.iter()
+					.zip(&elements)
+					.map(|(ty, element)| ty.resolve(defs)?.json_from_variant(element, defs))
+					.try_collect()?
+			}
+			Definition::Enum(JEnum { variants, .. }) => {
+				let int_value = variant.try_to::<i64>().map_err(|err| anyhow!("{err:?}"))?;
+
+				let (name, _) = variants
+					.iter()
+					.find(|(_, value)| **value == int_value)
+					.ok_or_else(|| anyhow!("Expected one of \"{}\".\nGot: {int_value}.", variants.values().join(", ")))?;
+
+				if JEnum::integer_encoding() {
+					Value::from(int_value)
+				} else {
+					Value::String(name.clone())
+				}
+			}
+			Definition::Class(class) => {
+				if variant.is_nil() {
+					return Ok(Value::Null);
+				}
+
+				let object = variant
+					.try_to::<Gd<Object>>()
+					.map_err(|err| anyhow!("{err:?}"))?;
+
+				class.json_from_object(&object, defs)?
+			}
+			Definition::Variant(var_def) => var_def.var_to_json(variant)?,
+			Definition::Any(_) => raw_json_from_variant(variant)?,
+			Definition::ResourcePath(resource_path) => resource_path.json_from_variant(variant)?,
+			Definition::Conditional(conditional) => conditional.json_from_variant(variant, defs)?,
+			Definition::OneOf(one_of) => one_of.json_from_variant(variant, defs)?,
+		})
+	}
+}
+
+/// The reverse of [`raw_variant_from_json()`], converts any JSON-representable Godot value into JSON.
+pub fn raw_json_from_variant(variant: &Variant) -> Result<Value> {
+	let variant_type = variant.get_type();
+
+	Ok(match variant_type {
+		VariantType::NIL => Value::Null,
+		VariantType::BOOL => Value::Bool(variant.to()),
+		VariantType::INT => Value::from(variant.to::<i64>()),
+		VariantType::FLOAT => Value::from(variant.to::<f64>()),
+		VariantType::STRING => Value::String(variant.stringify().to_string()),
+		| VariantType::STRING_NAME
+		| VariantType::NODE_PATH => tag_value(variant_type, Value::String(variant.stringify().to_string())),
+		VariantType::ARRAY => array_elements(variant)?
+			.iter()
+			.map(raw_json_from_variant)
+			.try_collect()?,
+		VariantType::DICTIONARY => variant
+			.to::<Dictionary>()
+			.iter_shared()
+			.map(|(key, value)| Result::<(String, Value)>::Ok((key.stringify().to_string(), raw_json_from_variant(&value)?)))
+			.try_collect::<_, Map<_, _>, _>()?
+			.into(),
+		other => match VariantDefinition::try_from(other) {
+			Ok(var_def) => tag_value(other, var_def.var_to_json(variant)?),
+			Err(()) => bail!("Cannot convert value of type {other:?} into JSON."),
+		},
+	})
+}
+
+/// Returns the elements of an array `variant`, regardless of whether it is typed or not.
+pub fn array_elements(variant: &Variant) -> Result<Vec<Variant>> {
+	if variant.get_type() != VariantType::ARRAY {
+		bail!("Expected array, got: {:?}", variant.get_type());
+	}
+
+	// Typed arrays cannot be converted into `VariantArray`, so we go through Godot's dynamic calls instead.
+	let size = variant
+		.call("size", &[])
+		.try_to::<i64>()
+		.map_err(|err| anyhow!("{err:?}"))?;
+
+	Ok((0..size)
+		.map(|idx| variant.call("get", &[idx.to_variant()]))
+		.collect())
+}
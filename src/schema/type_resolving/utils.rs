@@ -1,10 +1,18 @@
 use super::*;
+#[cfg(not(feature = "wasm_compat"))]
 use godot::sys;
+#[cfg(not(feature = "wasm_compat"))]
 use godot::sys::{interface_fn, GodotFfi};
+#[cfg(not(feature = "wasm_compat"))]
 use std::ptr;
 
 impl Definition {
 	pub fn instantiate(&self, value: &Value, defs: &BTreeMap<String, Definition>) -> Result<Variant> {
+		if let Some(const_value) = self.const_value()
+			&& !json_equal(value, const_value) {
+			return Err(conversion_failed(&format!("constant {const_value}"), value));
+		}
+
 		match (self, value) {
 			(Definition::Null(_), Value::Null) => Ok(Variant::nil()),
 			(Definition::Boolean(_), Value::Bool(val)) => Ok(val.to_variant()),
@@ -14,9 +22,12 @@ impl Definition {
 				} else if let Some(int) = number.as_u64() {
 					int.to_variant()
 				} else {
-					bail!("Expected integer, got float.");
+					return Err(conversion_failed("integer", value));
 				}
 			),
+			(Definition::Number(_), Value::Number(number)) if !number.is_f64() && InstantiateOptions::current().strict_numbers => {
+				Err(conversion_failed("float (strict numbers mode requires a decimal point, e.g. 1.0)", value))
+			}
 			(Definition::Number(_), Value::Number(number)) => Ok(
 				if let Some(int) = number.as_i64() {
 					int.to_variant()
@@ -31,23 +42,40 @@ impl Definition {
 			(Definition::String(_), Value::String(str)) => Ok(str.to_variant()),
 			(Definition::Object(object), Value::Object(properties)) => {
 				if object.properties.is_empty() {
-					return Dictionary::try_from_json(value).map(|dict| dict.to_variant());
+					let Some(values_ty) = &object.values_ty
+					else { return Dictionary::try_from_json(value).map(|dict| dict.to_variant()) };
+
+					let values_def = values_ty.resolve(defs)?;
+					let mut dict = Dictionary::new();
+
+					for (key, val) in properties {
+						dict.set(key.clone(), values_def.instantiate(val, defs).map_err(|err| SchemaError::at(err, key))?);
+					}
+
+					return Ok(dict.to_variant());
 				}
 
-				if object.properties.len() != properties.len() {
-					bail!("Expected JSON object to have {} properties.\nGot: {}", object.properties.len(), properties.len());
+				if let Some(name) = properties.keys().find(|name| !object.properties.contains_key(*name)) {
+					bail!("Unexpected property \"{name}\".");
 				}
 
 				let mut dict = Dictionary::new();
 
 				for (name, ty) in &object.properties {
 					let var = {
-						let val = properties
-							.get(name)
-							.ok_or_else(|| anyhow!("Expected property \"{name}\" to be in `properties` map."))?;
-
 						let schema = ty.resolve(defs)?;
-						schema.instantiate(val, defs)?
+
+						// Absent optional properties take their `default`, if any.
+						let Some(val) = properties.get(name).or_else(|| schema.default_value().filter(|_| object.optional.contains(name)))
+						else {
+							if object.optional.contains(name) {
+								continue;
+							}
+
+							bail!("Expected property \"{name}\" to be in `properties` map.");
+						};
+
+						schema.instantiate(val, defs).map_err(|err| SchemaError::at(err, name))?
 					};
 
 					dict.set(name.clone(), var);
@@ -55,14 +83,16 @@ impl Definition {
 
 				Ok(dict.to_variant())
 			}
-			(Definition::Array(JArray { items_ty, .. }), Value::Array(vec)) => {
+			(Definition::Array(array_def @ JArray { items_ty, .. }), Value::Array(vec)) => {
+				array_def.check_elements(vec, defs)?;
+
 				if let Some(ty) = items_ty {
 					let array = new_array_from_def(ty.resolve(defs)?)?;
 
-					for json in vec {
+					for (idx, json) in vec.iter().enumerate() {
 						let var = {
 							let schema = ty.resolve(defs)?;
-							schema.instantiate(json, defs)?
+							schema.instantiate(json, defs).map_err(|err| SchemaError::at(err, idx))?
 						};
 
 						array.call("push_back", &[var]);
@@ -86,10 +116,10 @@ impl Definition {
 
 				let mut array = VariantArray::new();
 
-				for (ty, json) in items.iter().zip(vec) {
+				for (idx, (ty, json)) in items.iter().zip(vec).enumerate() {
 					let var = {
 						let schema = ty.resolve(defs)?;
-						schema.instantiate(json, defs)?
+						schema.instantiate(json, defs).map_err(|err| SchemaError::at(err, idx))?
 					};
 
 					array.push(&var);
@@ -97,11 +127,11 @@ impl Definition {
 
 				Ok(array.to_variant())
 			}
-			(Definition::Enum(JEnum { variants, .. }), Value::String(string)) => {
-				if let Some(int_value) = variants.get(string) {
+			(Definition::Enum(j_enum), Value::String(_) | Value::Number(_)) => {
+				if let Some(int_value) = j_enum.variant_value(value) {
 					Ok(int_value.to_variant())
 				} else {
-					bail!("Expected one of \"{}\".\nGot: {string}.", variants.keys().join(", "));
+					bail!("Expected one of \"{}\" (or their values).\nGot: {value}.", j_enum.variants.keys().join(", "));
 				}
 			}
 			(Definition::Class(class), Value::Object(properties)) => {
@@ -110,20 +140,39 @@ impl Definition {
 			(Definition::Variant(variant_def), value) => {
 				variant_def.var_from_json(value)
 			}
-			(Definition::Null(_), _) => bail!("Expected null, got: {value:?}"),
-			(Definition::Boolean(_), _) => bail!("Expected boolean, got: {value:?}"),
-			(Definition::Integer(_), _) => bail!("Expected integer, got: {value:?}"),
-			(Definition::Number(_), _) => bail!("Expected number, got: {value:?}"),
-			(Definition::String(_), _) => bail!("Expected string, got: {value:?}"),
-			(Definition::Array(_), _) => bail!("Expected array, got: {value:?}"),
-			(Definition::Object(_), _) => bail!("Expected object, got: {value:?}"),
-			(Definition::Tuple(_), _) => bail!("Expected tuple, got: {value:?}"),
-			(Definition::Enum(_), _) => bail!("Expected enum, got: {value:?}"),
-			(Definition::Class(_), _) => bail!("Expected class, got: {value:?}"),
+			(Definition::Any(_), value) => raw_variant_from_json(value),
+			(Definition::ResourcePath(resource_path), Value::String(path)) => resource_path.load(path),
+			(Definition::Conditional(conditional), value) => match conditional.branch(value, defs) {
+				Some(ty) => ty.resolve(defs)?.instantiate(value, defs),
+				None => raw_variant_from_json(value),
+			},
+			(Definition::OneOf(one_of), value) => match one_of.matching_option(value, defs) {
+				Some(ty) => ty.resolve(defs)?.instantiate(value, defs),
+				None => bail!("Expected the value to match one of the {} options.\nGot: {value}", one_of.options.len()),
+			},
+			(Definition::Null(_), _) => Err(conversion_failed("null", value)),
+			(Definition::Boolean(_), _) => Err(conversion_failed("boolean", value)),
+			(Definition::Integer(_), _) => Err(conversion_failed("integer", value)),
+			(Definition::Number(_), _) => Err(conversion_failed("number", value)),
+			(Definition::String(_), _) => Err(conversion_failed("string", value)),
+			(Definition::Array(_), _) => Err(conversion_failed("array", value)),
+			(Definition::Object(_), _) => Err(conversion_failed("object", value)),
+			(Definition::Tuple(_), _) => Err(conversion_failed("tuple", value)),
+			(Definition::Enum(_), _) => Err(conversion_failed("enum", value)),
+			(Definition::Class(_), _) => Err(conversion_failed("class", value)),
+			(Definition::ResourcePath(_), _) => Err(conversion_failed("resource path", value)),
 		}
 	}
 }
 
+fn conversion_failed(expected: &str, got: &Value) -> anyhow::Error {
+	SchemaError::ConversionFailed {
+		path: String::new(),
+		expected: expected.to_owned(),
+		got: got.to_string(),
+	}.into()
+}
+
 pub fn raw_definition_from_type(ty: VariantType) -> Option<Definition> {
 	Some(match ty {
 		VariantType::BOOL => definition_of::<bool>(),
@@ -191,19 +240,24 @@ pub fn raw_variant_from_json(value: &Value) -> Result<Variant> {
 
 			typed_array
 		}
-		Value::Object(properties) => properties
-			.iter()
-			.map(|(key, val)| Result::<(String, Variant)>::Ok((key.clone(), raw_variant_from_json(val)?)))
-			.try_collect::<_, Dictionary, _>()?
-			.to_variant(),
+		Value::Object(properties) => if let Some(result) = untag_value(properties) {
+			result?
+		} else {
+			properties
+				.iter()
+				.map(|(key, val)| Result::<(String, Variant)>::Ok((key.clone(), raw_variant_from_json(val)?)))
+				.try_collect::<_, Dictionary, _>()?
+				.to_variant()
+		},
 	})
 }
 
-fn new_array_from_def(ty: &Definition) -> Result<Variant> {
+pub(crate) fn new_array_from_def(ty: &Definition) -> Result<Variant> {
 	let (variant_type, class_name, script) =
 		match ty {
-			Definition::Class(class) => {
-				match &class.source {
+			| Definition::Class(JClass { source, .. })
+			| Definition::ResourcePath(JResourcePath { source, .. }) => {
+				match source {
 					| ClassSource::ScriptNamed(script, _)
 					| ClassSource::ScriptUnnamed(script) => (VariantType::OBJECT, None, Some(script)),
 					
@@ -220,11 +274,15 @@ fn new_array_from_def(ty: &Definition) -> Result<Variant> {
 			Definition::Enum(_) => (VariantType::INT, None, None),
 			Definition::Tuple(_) => (VariantType::ARRAY, None, None),
 			Definition::Variant(var_def) => (var_def.variant_type(), None, None),
+			| Definition::Any(_)
+			| Definition::Conditional(_)
+			| Definition::OneOf(_) => (VariantType::NIL, None, None),
 		};
 
 	Ok(new_array_of_type(variant_type, class_name, script))
 }
 
+#[cfg(not(feature = "wasm_compat"))]
 fn new_array_of_type(
 	variant_type: VariantType,
 	class_name: Option<&StringName>,
@@ -267,4 +325,43 @@ fn new_array_of_type(
 	}
 
 	array.to_variant()
-}
\ No newline at end of file
+}
+
+/// Pure-safe fallback for web exports, where the raw FFI calls above are not guaranteed to be available.
+///
+/// Builtin element types are covered by Rust's statically typed arrays, every other type produces an untyped array.
+/// [`JClass::instantiate`] assigns arrays into the property's existing (typed) array, so class properties keep their element type.
+#[cfg(feature = "wasm_compat")]
+fn new_array_of_type(
+	variant_type: VariantType,
+	_class_name: Option<&StringName>,
+	_script: Option<&Gd<Script>>,
+) -> Variant {
+	match variant_type {
+		VariantType::BOOL => Array::<bool>::new().to_variant(),
+		VariantType::INT => Array::<i64>::new().to_variant(),
+		VariantType::FLOAT => Array::<f64>::new().to_variant(),
+		VariantType::STRING => Array::<GString>::new().to_variant(),
+		VariantType::STRING_NAME => Array::<StringName>::new().to_variant(),
+		VariantType::NODE_PATH => Array::<NodePath>::new().to_variant(),
+		VariantType::DICTIONARY => Array::<Dictionary>::new().to_variant(),
+		VariantType::VECTOR2 => Array::<Vector2>::new().to_variant(),
+		VariantType::VECTOR2I => Array::<Vector2i>::new().to_variant(),
+		VariantType::RECT2 => Array::<Rect2>::new().to_variant(),
+		VariantType::RECT2I => Array::<Rect2i>::new().to_variant(),
+		VariantType::VECTOR3 => Array::<Vector3>::new().to_variant(),
+		VariantType::VECTOR3I => Array::<Vector3i>::new().to_variant(),
+		VariantType::TRANSFORM2D => Array::<Transform2D>::new().to_variant(),
+		VariantType::VECTOR4 => Array::<Vector4>::new().to_variant(),
+		VariantType::VECTOR4I => Array::<Vector4i>::new().to_variant(),
+		VariantType::PLANE => Array::<Plane>::new().to_variant(),
+		VariantType::QUATERNION => Array::<Quaternion>::new().to_variant(),
+		VariantType::AABB => Array::<Aabb>::new().to_variant(),
+		VariantType::BASIS => Array::<Basis>::new().to_variant(),
+		VariantType::TRANSFORM3D => Array::<Transform3D>::new().to_variant(),
+		VariantType::PROJECTION => Array::<Projection>::new().to_variant(),
+		VariantType::COLOR => Array::<Color>::new().to_variant(),
+		VariantType::RID => Array::<Rid>::new().to_variant(),
+		_ => VariantArray::new().to_variant(),
+	}
+}
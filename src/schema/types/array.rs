@@ -5,6 +5,12 @@ pub struct JArray {
 	pub description: Option<String>,
 	// If None, then each element can be of any type
 	pub items_ty: Option<Box<Type>>,
+	pub min_items: Option<usize>,
+	pub max_items: Option<usize>,
+	/// If set, elements must all be different (compared as JSON).
+	pub unique_items: bool,
+	/// If set, at least one element must match this type.
+	pub contains: Option<Box<Type>>,
 }
 
 impl SerializeFields for JArray {
@@ -15,6 +21,22 @@ impl SerializeFields for JArray {
 			map.serialize_entry("items", ty)?;
 		}
 
+		if let Some(min_items) = self.min_items {
+			map.serialize_entry("minItems", &min_items)?;
+		}
+
+		if let Some(max_items) = self.max_items {
+			map.serialize_entry("maxItems", &max_items)?;
+		}
+
+		if self.unique_items {
+			map.serialize_entry("uniqueItems", &true)?;
+		}
+
+		if let Some(contains) = &self.contains {
+			map.serialize_entry("contains", contains)?;
+		}
+
 		Ok(())
 	}
 }
@@ -24,6 +46,10 @@ impl JArray {
 		Self {
 			description: None,
 			items_ty: Some(Box::new(items_ty.into())),
+			min_items: None,
+			max_items: None,
+			unique_items: false,
+			contains: None,
 		}
 	}
 
@@ -31,13 +57,51 @@ impl JArray {
 		Self {
 			description: None,
 			items_ty: None,
+			min_items: None,
+			max_items: None,
+			unique_items: false,
+			contains: None,
+		}
+	}
+
+	/// Checks that `len` elements are within this array's `min_items`/`max_items`.
+	pub fn check_length(&self, len: usize) -> Result<()> {
+		if let Some(min_items) = self.min_items && len < min_items {
+			bail!("Expected JSON array to have at least {min_items} elements.\nGot: {len}");
+		}
+
+		if let Some(max_items) = self.max_items && len > max_items {
+			bail!("Expected JSON array to have at most {max_items} elements.\nGot: {len}");
 		}
+
+		Ok(())
+	}
+
+	/// Checks the length of `elements` (see [`Self::check_length()`]), their uniqueness (see [`json_equal()`]) and that one matches [`Self::contains`].
+	pub fn check_elements(&self, elements: &[Value], defs: &BTreeMap<String, Definition>) -> Result<()> {
+		self.check_length(elements.len())?;
+
+		if self.unique_items
+			&& let Some(idx) = (1..elements.len()).find(|idx| elements[..*idx].iter().any(|other| json_equal(other, &elements[*idx]))) {
+			bail!("Expected JSON array elements to be unique.\nElement {idx} is a duplicate: {}", elements[idx]);
+		}
+
+		if let Some(contains) = &self.contains
+			&& !elements.iter().any(|element| matches_type(contains, element, defs)) {
+			bail!("Expected JSON array to contain at least one element matching `contains`.");
+		}
+
+		Ok(())
 	}
 
 	pub fn insert_variant_definitions(&self, fill_me: &mut Vec<VariantDefinition>) {
 		if let Some(ty) = &self.items_ty {
 			ty.insert_variant_definitions(fill_me);
 		}
+
+		if let Some(ty) = &self.contains {
+			ty.insert_variant_definitions(fill_me);
+		}
 	}
 }
 
@@ -0,0 +1,116 @@
+use super::*;
+use std::cell::Cell;
+
+/// `if`/`then`/`else`: values matching `if_` must match `then`, the others must match `else_`.
+/// A missing branch accepts any value.
+///
+/// Objects and classes inside `if_` are serialized without `additionalProperties: false`, so conditions can test a few properties,
+/// e.g. `if` `{"kind": "Ranged"}` (a single-variant enum) `then` a weapon with a `range`.
+///
+/// Instantiation uses the branch the value satisfies.
+#[derive(Clone, Debug)]
+pub struct JConditional {
+	pub description: Option<String>,
+	pub if_: Box<Type>,
+	pub then: Option<Box<Type>>,
+	pub else_: Option<Box<Type>>,
+}
+
+thread_local! {
+	static IN_CONDITION: Cell<bool> = const { Cell::new(false) };
+}
+
+impl JConditional {
+	pub fn new(if_: impl Into<Type>) -> Self {
+		Self {
+			description: None,
+			if_: Box::new(if_.into()),
+			then: None,
+			else_: None,
+		}
+	}
+
+	/// Whether an `if_` schema is currently being serialized or checked, objects and classes are open in that case.
+	pub fn in_condition() -> bool {
+		IN_CONDITION.get()
+	}
+
+	/// Runs `f` as part of an `if_` schema, see [`Self::in_condition()`].
+	pub fn scope_condition<T>(f: impl FnOnce() -> T) -> T {
+		let previous = IN_CONDITION.replace(true);
+		let result = f();
+		IN_CONDITION.set(previous);
+		result
+	}
+
+	/// Runs `f` outside of any `if_` schema, e.g. to check a definition referenced from one, which `$defs` holds closed.
+	pub fn scope_outside_condition<T>(f: impl FnOnce() -> T) -> T {
+		let previous = IN_CONDITION.replace(false);
+		let result = f();
+		IN_CONDITION.set(previous);
+		result
+	}
+
+	/// Whether `value` satisfies [`Self::if_`].
+	pub fn condition_holds(&self, value: &Value, defs: &BTreeMap<String, Definition>) -> bool {
+		Self::scope_condition(|| matches_type(&self.if_, value, defs))
+	}
+
+	/// The branch `value` must match, `None` if that branch is not set (any value is accepted).
+	pub fn branch(&self, value: &Value, defs: &BTreeMap<String, Definition>) -> Option<&Type> {
+		if self.condition_holds(value, defs) {
+			self.then.as_deref()
+		} else {
+			self.else_.as_deref()
+		}
+	}
+
+	/// Converts `variant` with the first branch whose JSON satisfies the condition as expected,
+	/// or as raw JSON if neither does (a missing branch accepts any value).
+	pub fn json_from_variant(&self, variant: &Variant, defs: &BTreeMap<String, Definition>) -> Result<Value> {
+		let branches = [(&self.then, true), (&self.else_, false)];
+
+		for (branch, expected) in branches {
+			let Some(ty) = branch
+			else { continue };
+
+			if let Ok(value) = ty.resolve(defs).and_then(|def| def.json_from_variant(variant, defs))
+				&& self.condition_holds(&value, defs) == expected {
+				return Ok(value);
+			}
+		}
+
+		raw_json_from_variant(variant)
+	}
+
+	pub fn insert_variant_definitions(&self, fill_me: &mut Vec<VariantDefinition>) {
+		for ty in self.types() {
+			ty.insert_variant_definitions(fill_me);
+		}
+	}
+
+	/// [`Self::if_`], then the branches that are set.
+	pub fn types(&self) -> impl Iterator<Item = &Type> {
+		std::iter::once(self.if_.as_ref())
+			.chain(self.then.as_deref())
+			.chain(self.else_.as_deref())
+	}
+}
+
+impl SerializeFields for JConditional {
+	fn serialize_fields<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+		Self::scope_condition(|| map.serialize_entry("if", &self.if_))?;
+
+		if let Some(then) = &self.then {
+			map.serialize_entry("then", then)?;
+		}
+
+		if let Some(else_) = &self.else_ {
+			map.serialize_entry("else", else_)?;
+		}
+
+		Ok(())
+	}
+}
+
+all_shared_impls!(JConditional);
@@ -1,10 +1,43 @@
 use super::*;
+use std::cell::RefCell;
 
 #[derive(Clone, Debug)]
 pub struct JClass {
 	pub description: Option<String>,
-	pub properties: BTreeMap<String, Type>,
+	pub properties: IndexMap<String, Type>,
+	/// Properties that may be absent from the input (keeping their current value), the others are `required`.
+	pub optional: BTreeSet<String>,
 	pub source: ClassSource,
+	/// The Godot type of each property, emitted when type annotations are enabled, see [`GodotTypeInfo`].
+	pub type_infos: BTreeMap<String, GodotTypeInfo>,
+	/// Extra JSON Schema keywords of each property (e.g. `minimum`), declared by [`SCHEMA_CONFIG_HOOK`].
+	///
+	/// Only enforced by the JSON Schema validator.
+	pub constraints: BTreeMap<String, Map<String, Value>>,
+	/// The base class whose definition describes the inherited properties, see [`SchemaOptions::class_inheritance`].
+	///
+	/// [`Self::properties`] still holds every property, inherited or not.
+	pub base: Option<ClassBase>,
+	/// Whether other classes inherit this one through [`Self::base`]: the definition does not forbid additional properties,
+	/// since JSON Schema cannot extend a closed schema. References to it close it instead, see [`JClass::is_open_class()`].
+	pub extensible: bool,
+	/// Property name -> its name in JSON, for properties exposed under a friendlier name (e.g. "hit_points" -> "hp").
+	///
+	/// Documents are translated back before instantiation, see [`RootSchema::to_annotated_names()`].
+	pub aliases: BTreeMap<String, String>,
+}
+
+/// The base class of a [`JClass`] emitted with `allOf`.
+#[derive(Clone, Debug)]
+pub struct ClassBase {
+	pub reference: JRef,
+	/// The properties described by the base class's definition, the others are emitted by the inheriting class.
+	pub inherited: BTreeSet<String>,
+}
+
+thread_local! {
+	// The extensible classes of the schema being serialized on this thread, see `JClass::scope_open_classes()`.
+	static OPEN_CLASSES: RefCell<BTreeSet<String>> = const { RefCell::new(BTreeSet::new()) };
 }
 
 impl JClass {
@@ -13,43 +46,324 @@ impl JClass {
 	}
 
 	pub fn generate(source: ClassSource, insert_dependencies: &mut BTreeMap<String, Definition>) -> Result<Self> {
-		let properties = source.fetch_property_list(insert_dependencies)?;
+		let config = ClassConfig::fetch(&source)?;
 
-		Ok(Self {
+		let base = if SchemaOptions::read_current(|options| options.class_inheritance) {
+			ClassBase::generate(&source, insert_dependencies)?
+		} else {
+			None
+		};
+
+		let mut type_infos = BTreeMap::new();
+		let properties = source.fetch_property_list(insert_dependencies, &mut type_infos, &config.exclude)?;
+
+		let mut class = Self {
 			description: None,
 			properties,
+			optional: BTreeSet::new(),
 			source,
-		})
+			type_infos,
+			constraints: BTreeMap::new(),
+			base,
+			extensible: false,
+			aliases: BTreeMap::new(),
+		};
+
+		if let Some(base) = &mut class.base {
+			base.inherited.retain(|name| class.properties.contains_key(name));
+		}
+
+		if SchemaOptions::read_current(|options| options.script_defaults) {
+			class.insert_script_defaults(insert_dependencies);
+		}
+
+		config
+			.apply(&mut class)
+			.map_err(|err| anyhow!("`{SCHEMA_CONFIG_HOOK}()` of class \"{}\": {err}", class.source.definition_name()))?;
+
+		log(LogLevel::Info, || format!(
+			"Generated class \"{}\" with {} properties ({} optional).",
+			class.source.definition_name(),
+			class.properties.len(),
+			class.optional.len(),
+		));
+
+		Ok(class)
+	}
+
+	/// The names of the properties that must be present in the input.
+	pub fn required(&self) -> Vec<&String> {
+		self.properties.keys().filter(|name| !self.optional.contains(*name)).collect()
+	}
+
+	/// Sets the `default` of each primitive (or `Dictionary`) property to its initial value in the script,
+	/// which gives models context about the expected values. Instantiation assigns it to absent optional properties.
+	///
+	/// Engine classes do not report their defaults, properties without one (`null`) are left as is.
+	/// Only called if [`SchemaOptions::script_defaults`] is set.
+	fn insert_script_defaults(&mut self, defs: &BTreeMap<String, Definition>) {
+		let (ClassSource::ScriptNamed(script, _) | ClassSource::ScriptUnnamed(script)) = &self.source
+		else { return };
+
+		for (name, ty) in &mut self.properties {
+			let Type::Definition(def) = ty
+			else { continue };
+
+			// Properties stubbed as `null` (see `UnsupportedPropertyPolicy::StubAsNull`) have no default to describe.
+			if def.value_keywords().is_none() || matches!(def, Definition::Null(_)) {
+				continue;
+			}
+
+			let variant = script.clone().get_property_default_value(name.as_str());
+
+			if variant.is_nil() {
+				continue;
+			}
+
+			let Ok(value) = def.json_from_variant(&variant, defs)
+			else { continue };
+
+			if let Some((_, default)) = def.value_keywords_mut() {
+				*default = Some(value);
+			}
+		}
+	}
+
+	/// The name property `name` has in JSON, see [`Self::aliases`].
+	pub fn json_name<'a>(&'a self, name: &'a str) -> &'a str {
+		self.aliases.get(name).map_or(name, String::as_str)
+	}
+
+	/// Exposes property `name` as `alias` in JSON, or under its own name again if `alias` is empty.
+	pub fn set_alias(&mut self, name: &str, alias: &str) -> Result<()> {
+		if !self.properties.contains_key(name) {
+			bail!("Expected property \"{name}\" to be in class \"{}\".", self.source.definition_name());
+		}
+
+		if alias.is_empty() || alias == name {
+			self.aliases.remove(name);
+			return Ok(());
+		}
+
+		if let Some(other) = self.properties.keys().find(|other| *other != name && self.json_name(other) == alias) {
+			bail!("Expected alias \"{alias}\" to be unique, property \"{other}\" is already named so.");
+		}
+
+		self.aliases.insert(name.to_owned(), alias.to_owned());
+		Ok(())
+	}
+
+	/// Runs `f`, the serialization of a schema whose definitions are `defs`, closing the references to its
+	/// [extensible](Self::extensible) classes, see [`Self::is_open_class()`].
+	pub fn scope_open_classes<T>(defs: &BTreeMap<String, Definition>, f: impl FnOnce() -> T) -> T {
+		let open = defs
+			.iter()
+			.filter(|(_, def)| matches!(def, Definition::Class(JClass { extensible: true, .. })))
+			.map(|(name, _)| name.clone())
+			.collect();
+
+		let previous = OPEN_CLASSES.replace(open);
+		let result = f();
+		OPEN_CLASSES.set(previous);
+		result
+	}
+
+	/// Whether definition `name` is a class left open for the classes inheriting it, in the schema being serialized:
+	/// references to it are emitted with `unevaluatedProperties: false`, so it still rejects unknown properties where it is used.
+	pub fn is_open_class(name: &str) -> bool {
+		OPEN_CLASSES.with_borrow(|open| open.contains(name))
 	}
 
+	/// Whether property `name` is described by the base class's definition instead of this one's.
+	pub fn is_inherited(&self, name: &str) -> bool {
+		self.base.as_ref().is_some_and(|base| base.inherited.contains(name))
+	}
+
+	/// Copies the properties (and their settings) of `base` into this class, before its own.
+	///
+	/// Classes parsed from an `allOf` schema only hold their own properties until then.
+	pub fn inherit(&mut self, base: &JClass) {
+		let own = std::mem::take(&mut self.properties);
+		self.properties = base.properties.clone();
+		self.properties.extend(own);
+
+		self.optional.extend(base.optional.iter().cloned());
+
+		for (name, info) in &base.type_infos {
+			self.type_infos.entry(name.clone()).or_insert_with(|| info.clone());
+		}
+
+		for (name, keywords) in &base.constraints {
+			self.constraints.entry(name.clone()).or_insert_with(|| keywords.clone());
+		}
+
+		for (name, alias) in &base.aliases {
+			self.aliases.entry(name.clone()).or_insert_with(|| alias.clone());
+		}
+
+		if let Some(class_base) = &mut self.base {
+			class_base.inherited = base.properties.keys().cloned().collect();
+		}
+	}
+
+	/// Constructs the class and assigns `property_values` to it.
+	///
+	/// Values matching the parameters of the script's `_init` are passed to it, see [`ClassSource::construct_from()`].
+	///
+	/// If the class defines [`FROM_JSON_HOOK`], it receives the remaining converted values as a `Dictionary` instead,
+	/// letting it check invariants, derive caches or handle renamed fields.
 	pub fn instantiate(&self, defs: &BTreeMap<String, Definition>, property_values: &Map<String, Value>) -> Result<Gd<Object>> {
-		let instance_var = match &self.source {
-			// TODO: Check if script has a custom _init with parameters
-			| ClassSource::ScriptNamed(script, _)
-			| ClassSource::ScriptUnnamed(script) => script.clone().call("new", &[]),
-			
-			ClassSource::Engine(class_name) => ClassDb::singleton().instantiate(class_name),
-		};
+		let mut values = self
+			.convert_properties(defs, property_values)?
+			.into_iter()
+			.chain(self.convert_properties(defs, &self.absent_defaults(defs, property_values))?)
+			.collect::<BTreeMap<_, _>>();
+
+		let setter_check = InstantiateOptions::current().setter_check;
+		let requested = (setter_check != SetterCheck::Off).then(|| values.clone());
+
+		let mut gd = self.source.construct_from(&mut values)?;
 
-		let mut gd = instance_var
-			.try_to::<Gd<Object>>()
-			.map_err(|err| anyhow!("{err:?}"))?;
+		if gd.has_method(FROM_JSON_HOOK) {
+			let data = values.into_iter().collect::<Dictionary>();
+			gd.call(FROM_JSON_HOOK, &[data.to_variant()]);
+			return Ok(gd);
+		}
+
+		for (name, variant) in values {
+			assign_property(&mut gd, &name, variant);
+		}
+
+		if let Some(requested) = requested {
+			self.check_setters(&gd, &requested, setter_check)?;
+		}
+
+		Ok(gd)
+	}
 
-		for (name, value) in property_values {
-			let variant = {
+	/// Reads each property in `requested` back from `gd`, reporting the ones whose setter changed the assigned value.
+	fn check_setters(&self, gd: &Gd<Object>, requested: &BTreeMap<String, Variant>, setter_check: SetterCheck) -> Result<()> {
+		let changed = requested
+			.iter()
+			.filter_map(|(name, value)| {
+				let actual = gd.get(name);
+				(actual != *value).then(|| format!("- \"{name}\": assigned {value}, got {actual}"))
+			})
+			.collect_vec();
+
+		if changed.is_empty() {
+			return Ok(());
+		}
+
+		let message = format!(
+			"Setters of class \"{}\" changed {} properties:\n{}",
+			self.source.definition_name(),
+			changed.len(),
+			changed.join("\n"),
+		);
+
+		match setter_check {
+			SetterCheck::Error => bail!(message),
+			_ => {
+				godot_warn!("{message}");
+				Ok(())
+			}
+		}
+	}
+
+	/// Converts each value in `property_values` and assigns it to the matching property of `gd`.
+	pub fn set_properties(&self, gd: &mut Gd<Object>, defs: &BTreeMap<String, Definition>, property_values: &Map<String, Value>) -> Result<()> {
+		for (name, variant) in self.convert_properties(defs, property_values)? {
+			assign_property(gd, &name, variant);
+		}
+
+		Ok(())
+	}
+
+	/// The `default` of each optional property absent from `property_values`, see [`Definition::value_keywords()`].
+	fn absent_defaults(&self, defs: &BTreeMap<String, Definition>, property_values: &Map<String, Value>) -> Map<String, Value> {
+		self.optional
+			.iter()
+			.filter(|name| !property_values.contains_key(*name))
+			.filter_map(|name| {
+				let default = self.properties.get(name)?.resolve(defs).ok()?.default_value()?;
+				Some((name.clone(), default.clone()))
+			})
+			.collect()
+	}
+
+	/// Converts each value in `property_values` into the type of the matching property.
+	fn convert_properties(&self, defs: &BTreeMap<String, Definition>, property_values: &Map<String, Value>) -> Result<Vec<(String, Variant)>> {
+		property_values
+			.iter()
+			.map(|(name, value)| {
 				let ty = self
 					.properties
 					.get(name)
 					.ok_or_else(|| anyhow!("Expected property \"{name}\" to be in `properties` map."))?;
 
 				let schema = ty.resolve(defs)?;
-				schema.instantiate(value, defs)?
-			};
-			
-			gd.set(name, &variant);
+				let variant = schema.instantiate(value, defs).map_err(|err| SchemaError::at(err, name))?;
+
+				// Properties stubbed as `null` (see `UnsupportedPropertyPolicy::StubAsNull`) have no value to assign.
+				if matches!(schema, Definition::Null(_)) {
+					return Ok(None);
+				}
+
+				Ok(Some((name.clone(), variant)))
+			})
+			.filter_map(Result::transpose)
+			.try_collect()
+	}
+
+	/// The reverse of [`Self::instantiate()`]: reads each property of `gd` and converts it into JSON.
+	///
+	/// If the class defines [`TO_JSON_HOOK`], the properties are read from the `Dictionary` it returns instead.
+	pub fn json_from_object(&self, gd: &Gd<Object>, defs: &BTreeMap<String, Definition>) -> Result<Value> {
+		if gd.has_method(TO_JSON_HOOK) {
+			return self.json_from_hook(gd, defs);
 		}
 
-		Ok(gd)
+		let mut properties = Map::new();
+
+		for (name, ty) in &self.properties {
+			let value = ty
+				.resolve(defs)?
+				.json_from_variant(&gd.get(name), defs)
+				.map_err(|err| anyhow!("Property \"{name}\": {err}"))?;
+
+			properties.insert(name.clone(), value);
+		}
+
+		Ok(Value::Object(properties))
+	}
+
+	fn json_from_hook(&self, gd: &Gd<Object>, defs: &BTreeMap<String, Definition>) -> Result<Value> {
+		let data = gd
+			.clone()
+			.call(TO_JSON_HOOK, &[])
+			.try_to::<Dictionary>()
+			.map_err(|err| anyhow!("Expected `{TO_JSON_HOOK}()` to return a Dictionary.\nError: {err:?}"))?;
+
+		let mut properties = Map::new();
+
+		for (name, variant) in data.iter_shared() {
+			let name = name.to_string();
+
+			let ty = self
+				.properties
+				.get(&name)
+				.ok_or_else(|| anyhow!("`{TO_JSON_HOOK}()` returned property \"{name}\", which is not in `properties` map."))?;
+
+			let value = ty
+				.resolve(defs)?
+				.json_from_variant(&variant, defs)
+				.map_err(|err| anyhow!("Property \"{name}\": {err}"))?;
+
+			properties.insert(name, value);
+		}
+
+		Ok(Value::Object(properties))
 	}
 
 	pub fn insert_variant_definitions(&self, fill_me: &mut Vec<VariantDefinition>) {
@@ -59,12 +373,121 @@ impl JClass {
 	}
 }
 
+/// Method called by [`JClass::instantiate()`] with the converted property values (`Dictionary`), instead of assigning them.
+pub const FROM_JSON_HOOK: &str = "_from_json";
+/// Method called by [`JClass::json_from_object()`] to get the property values (`Dictionary`) to serialize.
+pub const TO_JSON_HOOK: &str = "_to_json";
+
+/// Sets property `name` of `gd` to `variant`.
+pub fn assign_property(gd: &mut Gd<Object>, name: &str, variant: Variant) {
+	// Without FFI, arrays may be untyped, they are assigned to a copy of the property's array to convert them to its element type.
+	// The copy is then set like any other value, so the property's setter still runs.
+	#[cfg(feature = "wasm_compat")]
+	if variant.get_type() == VariantType::ARRAY {
+		let current = gd.get(name);
+
+		if current.get_type() == VariantType::ARRAY {
+			let typed = current.call("duplicate", &[]);
+			typed.call("assign", &[variant]);
+			gd.set(name, &typed);
+			return;
+		}
+	}
+
+	gd.set(name, &variant);
+}
+
+impl ClassBase {
+	/// References the script class `source` extends (generating it if needed), `None` for engine classes and scripts without a base script.
+	fn generate(source: &ClassSource, defs: &mut BTreeMap<String, Definition>) -> Result<Option<Self>> {
+		let (ClassSource::ScriptNamed(script, _) | ClassSource::ScriptUnnamed(script)) = source
+		else { return Ok(None) };
+
+		let Some(base_script) = script.get_base_script()
+		else { return Ok(None) };
+
+		let Type::Ref(reference) = class_reference(ClassSource::from_script(base_script), defs)?
+		else { return Ok(None) };
+
+		// The base class is still being generated if the hierarchy is cyclic, its properties are flattened instead.
+		let Some(Definition::Class(base_class)) = defs.get_mut(&reference.name)
+		else { return Ok(None) };
+
+		base_class.extensible = true;
+
+		Ok(Some(Self {
+			inherited: base_class.properties.keys().cloned().collect(),
+			reference,
+		}))
+	}
+}
+
 impl SerializeFields for JClass {
 	fn serialize_fields<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
 		map.serialize_entry("type", "object")?;
-		map.serialize_entry("properties", &self.properties)?;
-		map.serialize_entry("required", &self.properties.keys().collect::<Vec<_>>())?;
-		map.serialize_entry("additionalProperties", &false)
+
+		if round_trip_annotations() {
+			map.serialize_entry(GODOT_CLASS_KEY, &self.source.definition_name())?;
+		}
+
+		let required = self
+			.required()
+			.into_iter()
+			.filter(|name| !self.is_inherited(name))
+			.map(|name| self.json_name(name))
+			.collect_vec();
+
+		// Conditions only test some of the properties, as for objects (see `JConditional::in_condition()`).
+		let closed = !self.extensible && !JConditional::in_condition();
+
+		match &self.base {
+			Some(base) => {
+				map.serialize_entry("allOf", &(BaseReference(&base.reference), OwnProperties { class: self, required }))?;
+
+				// Unlike `additionalProperties`, also accepts the properties evaluated by the base class's schema.
+				if closed {
+					map.serialize_entry("unevaluatedProperties", &false)?;
+				}
+
+				Ok(())
+			}
+			None => {
+				map.serialize_entry("properties", &AnnotatedProperties(self))?;
+				map.serialize_entry("required", &required)?;
+
+				if closed {
+					map.serialize_entry("additionalProperties", &false)?;
+				}
+
+				Ok(())
+			}
+		}
+	}
+}
+
+/// The reference to a [`ClassBase`], left open so the inheriting class can add its own properties.
+struct BaseReference<'a>(&'a JRef);
+
+impl Serialize for BaseReference<'_> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+		let mut map = serializer.serialize_map(Some(1))?;
+		self.0.serialize_ref(&mut map)?;
+		map.end()
+	}
+}
+
+/// The properties a class with a [`ClassBase`] does not inherit, emitted after the base class's reference in `allOf`.
+struct OwnProperties<'a> {
+	class: &'a JClass,
+	required: Vec<&'a str>,
+}
+
+impl Serialize for OwnProperties<'_> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+		let mut map = serializer.serialize_map(Some(2))?;
+		map.serialize_entry("properties", &AnnotatedProperties(self.class))?;
+		map.serialize_entry("required", &self.required)?;
+		map.end()
 	}
 }
 
@@ -0,0 +1,155 @@
+use super::*;
+
+/// Static method a script may define to configure its generated schema, returning a `Dictionary` with the (optional) keys:
+///
+/// - `"exclude"`: Names of the properties left out of the schema.
+/// - `"optional"`: Names of the properties that may be absent from the input, see [`JClass::optional`].
+/// - `"descriptions"`: Property name -> description.
+/// - `"constraints"`: Property name -> extra JSON Schema keywords, e.g. `{ "hp": { "minimum": 0, "maximum": 100 } }`.
+///   `minItems`/`maxItems` of Array properties are also enforced during instantiation.
+pub const SCHEMA_CONFIG_HOOK: &str = "_json_schema_config";
+
+/// The configuration returned by a script's [`SCHEMA_CONFIG_HOOK`].
+#[derive(Clone, Debug, Default)]
+pub struct ClassConfig {
+	pub exclude: BTreeSet<String>,
+	pub optional: BTreeSet<String>,
+	pub descriptions: BTreeMap<String, String>,
+	pub constraints: BTreeMap<String, Map<String, Value>>,
+}
+
+impl ClassConfig {
+	/// Calls the [`SCHEMA_CONFIG_HOOK`] of `source`, classes that do not define it get the default (empty) configuration.
+	pub fn fetch(source: &ClassSource) -> Result<Self> {
+		let (ClassSource::ScriptNamed(script, _) | ClassSource::ScriptUnnamed(script)) = source
+		else { return Ok(Self::default()) };
+
+		if source.script_method(SCHEMA_CONFIG_HOOK).is_none() {
+			return Ok(Self::default());
+		}
+
+		let config = script
+			.clone()
+			.call(SCHEMA_CONFIG_HOOK, &[])
+			.try_to::<Dictionary>()
+			.map_err(|err| anyhow!("Expected `{SCHEMA_CONFIG_HOOK}()` to return a Dictionary.\nError: {err:?}"))?;
+
+		Self::from_dictionary(&config)
+			.map_err(|err| anyhow!("`{SCHEMA_CONFIG_HOOK}()` of class \"{}\": {err}", source.definition_name()))
+	}
+
+	pub fn from_dictionary(dict: &Dictionary) -> Result<Self> {
+		let mut config = Self::default();
+
+		for (key, value) in dict.iter_shared() {
+			let key = key.to_string();
+
+			match key.as_str() {
+				"exclude" => config.exclude = names(&key, &value)?,
+				"optional" => config.optional = names(&key, &value)?,
+				"descriptions" => {
+					for (name, description) in entries(&key, &value)? {
+						let description = description
+							.try_to::<String>()
+							.map_err(|_| anyhow!("\"{key}\": expected the description of \"{name}\" to be a String."))?;
+
+						config.descriptions.insert(name, description);
+					}
+				}
+				"constraints" => {
+					for (name, keywords) in entries(&key, &value)? {
+						let Value::Object(keywords) = raw_json_from_variant(&keywords)?
+						else { bail!("\"{key}\": expected the keywords of \"{name}\" to be a Dictionary.") };
+
+						config.constraints.insert(name, keywords);
+					}
+				}
+				_ => bail!("Unknown key \"{key}\", expected \"exclude\", \"optional\", \"descriptions\" or \"constraints\"."),
+			}
+		}
+
+		Ok(config)
+	}
+
+	/// Applies the configuration to the properties of `class`, the excluded ones are skipped by [`ClassSource::fetch_property_list()`].
+	pub fn apply(self, class: &mut JClass) -> Result<()> {
+		let check_exists = |class: &JClass, key: &str, name: &str| {
+			if class.properties.contains_key(name) {
+				Ok(())
+			} else {
+				Err(anyhow!("\"{key}\": expected property \"{name}\" to exist."))
+			}
+		};
+
+		for name in self.optional {
+			check_exists(class, "optional", &name)?;
+			class.optional.insert(name);
+		}
+
+		for (name, mut keywords) in self.constraints {
+			check_exists(class, "constraints", &name)?;
+
+			if let Some(Type::Definition(Definition::Array(array))) = class.properties.get_mut(&name) {
+				if let Some(min_items) = keywords.remove("minItems") {
+					array.min_items = Some(item_count(&name, "minItems", &min_items)?);
+				}
+
+				if let Some(max_items) = keywords.remove("maxItems") {
+					array.max_items = Some(item_count(&name, "maxItems", &max_items)?);
+				}
+			}
+
+			if !keywords.is_empty() {
+				class.constraints.insert(name, keywords);
+			}
+		}
+
+		for (name, description) in self.descriptions {
+			check_exists(class, "descriptions", &name)?;
+
+			match class.properties.get_mut(&name) {
+				// Variant definitions have a fixed schema, the description is emitted alongside it instead.
+				Some(Type::Definition(Definition::Variant(_))) => {
+					class.constraints
+						.entry(name)
+						.or_default()
+						.insert("description".into(), Value::String(description));
+				}
+				Some(ty) => ty.add_description(description),
+				None => {}
+			}
+		}
+
+		Ok(())
+	}
+}
+
+fn names(key: &str, value: &Variant) -> Result<BTreeSet<String>> {
+	array_elements(value)
+		.map_err(|err| anyhow!("\"{key}\": expected an Array of property names.\nError: {err}"))?
+		.iter()
+		.map(|name| match name.get_type() {
+			| VariantType::STRING
+			| VariantType::STRING_NAME => Ok(name.to_string()),
+			other => bail!("\"{key}\": expected property names to be strings.\nGot a value of type {other:?}: {name}"),
+		})
+		.try_collect()
+}
+
+fn entries(key: &str, value: &Variant) -> Result<Vec<(String, Variant)>> {
+	let dict = value
+		.try_to::<Dictionary>()
+		.map_err(|_| anyhow!("\"{key}\": expected a Dictionary of property name -> value."))?;
+
+	Ok(dict
+		.iter_shared()
+		.map(|(name, value)| (name.to_string(), value))
+		.collect())
+}
+
+fn item_count(name: &str, keyword: &str, value: &Value) -> Result<usize> {
+	value
+		.as_u64()
+		.and_then(|count| usize::try_from(count).ok())
+		.ok_or_else(|| anyhow!("\"constraints\": expected `{keyword}` of \"{name}\" to be a positive integer.\nGot: {value}"))
+}
@@ -1,7 +1,9 @@
 use super::*;
 
 pub use base::*;
+pub use config::*;
 pub use source::*;
 
 mod base;
+mod config;
 mod source;
@@ -1,4 +1,5 @@
 use super::*;
+use std::cell::{Cell, RefCell};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ClassSource {
@@ -16,7 +17,7 @@ impl ClassSource {
 		} else if let Ok(script) = find_script(class_name.clone()) {
 			Ok(Self::from_script(script))
 		} else {
-			bail!("Expected class \"{class_name}\" to be in either `ClassDb` or `ProjectSettings`.");
+			Err(SchemaError::UnknownClass(class_name.to_string()).into())
 		}
 	}
 
@@ -30,6 +31,143 @@ impl ClassSource {
 		}
 	}
 
+	/// Returns the source of `object`'s class: its script, if it has one, otherwise its engine class.
+	pub fn from_object(object: &Gd<Object>) -> Self {
+		match object.get_script().try_to::<Gd<Script>>() {
+			Ok(script) => Self::from_script(script),
+			Err(_) => Self::Engine(StringName::from(&object.get_class())),
+		}
+	}
+
+	/// Creates a new instance of this class, with default property values.
+	///
+	/// Fails if the script's `_init` has parameters without defaults, see [`Self::construct_from()`].
+	pub fn construct(&self) -> Result<Gd<Object>> {
+		self.construct_from(&mut BTreeMap::new())
+	}
+
+	/// Creates a new instance of this class, passing the values of `property_values` named after
+	/// the parameters of the script's `_init` as its arguments, in order.
+	///
+	/// Values passed to `_init` are removed from `property_values`, the remaining ones should be assigned afterward.
+	pub fn construct_from(&self, property_values: &mut BTreeMap<String, Variant>) -> Result<Gd<Object>> {
+		let instance_var = match self {
+			| ClassSource::ScriptNamed(script, _)
+			| ClassSource::ScriptUnnamed(script) => {
+				let mut args = Vec::new();
+
+				for param in self.constructor_parameters()? {
+					if let Some(value) = property_values.remove(&param.name) {
+						args.push(value);
+					} else if param.has_default {
+						// Arguments are positional, the following parameters keep their defaults and their values are assigned instead.
+						break;
+					} else {
+						bail!("Expected `_init` parameter \"{}\" of class \"{}\" to match a property.", param.name, self.definition_name());
+					}
+				}
+
+				script.clone().call("new", &args)
+			}
+
+			ClassSource::Engine(class_name) => ClassDb::singleton().instantiate(class_name),
+		};
+
+		instance_var
+			.try_to::<Gd<Object>>()
+			.map_err(|err| anyhow!("{err:?}"))
+	}
+
+	/// The parameters of the script's `_init`, in order. Engine classes are always constructed without arguments.
+	pub fn constructor_parameters(&self) -> Result<Vec<ConstructorParameter>> {
+		let Some(init) = self.script_method("_init")
+		else { return Ok(Vec::new()) };
+
+		let args = try_get::<VariantArray>(&init, "args")?;
+		let default_count = try_get::<VariantArray>(&init, "default_args")?.len();
+		let first_default = args.len().saturating_sub(default_count);
+
+		args.iter_shared()
+			.enumerate()
+			.map(|(idx, arg)| {
+				let arg = arg
+					.try_to::<Dictionary>()
+					.map_err(|err| anyhow!("{err:?}"))?;
+
+				Ok(ConstructorParameter {
+					name: try_get::<String>(&arg, "name")?,
+					has_default: idx >= first_default,
+				})
+			})
+			.try_collect()
+	}
+
+	/// The description (`Dictionary`) of the script's method `name`, `None` if it is not defined or this is an engine class.
+	pub fn script_method(&self, name: &str) -> Option<Dictionary> {
+		let (ClassSource::ScriptNamed(script, _) | ClassSource::ScriptUnnamed(script)) = self
+		else { return None };
+
+		script
+			.clone()
+			.get_script_method_list()
+			.iter_shared()
+			.find(|method| try_get::<String>(method, "name").is_ok_and(|method_name| method_name == name))
+	}
+
+	/// Whether `object` is an instance of this class, or of a class inheriting it.
+	pub fn is_instance_of(&self, object: &Gd<Object>) -> bool {
+		match self {
+			ClassSource::Engine(class_name) => object.is_class(&GString::from(class_name)),
+			| ClassSource::ScriptNamed(script, _)
+			| ClassSource::ScriptUnnamed(script) => {
+				let mut current = object.get_script().try_to::<Gd<Script>>().ok();
+
+				while let Some(object_script) = current {
+					if object_script == *script {
+						return true;
+					}
+
+					current = object_script.get_base_script();
+				}
+
+				false
+			}
+		}
+	}
+
+	/// Whether this class is the engine class `base`, or inherits it (directly or through its script's base type).
+	pub fn inherits_engine_class(&self, base: &str) -> bool {
+		let engine_class = match self {
+			ClassSource::Engine(class_name) => class_name.clone(),
+			| ClassSource::ScriptNamed(script, _)
+			| ClassSource::ScriptUnnamed(script) => script.get_instance_base_type(),
+		};
+
+		ClassDb::singleton().is_parent_class(&engine_class, base)
+	}
+
+	/// The initial value of property `name`, as declared by the script, otherwise by the engine class.
+	/// `null` if neither declares one (e.g. `Object` properties).
+	///
+	/// Unlike [`Self::construct()`], nothing is instantiated.
+	pub fn property_default(&self, name: &str) -> Variant {
+		let engine_class = match self {
+			ClassSource::Engine(class_name) => class_name.clone(),
+			| ClassSource::ScriptNamed(script, _)
+			| ClassSource::ScriptUnnamed(script) => {
+				let default = script.clone().get_property_default_value(name);
+
+				if !default.is_nil() {
+					return default;
+				}
+
+				script.get_instance_base_type()
+			}
+		};
+
+		ClassDb::singleton().class_get_property_default_value(&engine_class, name)
+	}
+
 	pub fn to_reference(&self) -> JRef {
 		JRef::new(self.definition_name())
 	}
@@ -43,19 +181,102 @@ impl ClassSource {
 		}
 	}
 
-	pub fn fetch_property_list(&self, defs: &mut BTreeMap<String, Definition>) -> Result<BTreeMap<String, Type>> {
-		fn eval_property_type(dict: Dictionary, defs: &mut BTreeMap<String, Definition>) -> Result<(String, Type)> {
-			let wrapper = PropertyTypeInfo::try_from(dict)?;
-			let ty = wrapper.eval_type(defs)?;
-			Ok((wrapper.property_name, ty))
+	/// Evaluates the type of each property, inserting their [`GodotTypeInfo`] in `type_infos`.
+	///
+	/// Skips the properties in `exclude` or in the script's [`EXCLUDE_CONSTANT`],
+	/// the ones prefixed with `_` if [`SchemaOptions::skip_private_properties`] is set,
+	/// and the ones missing any flag of the current [`usage_filter()`].
+	///
+	/// Properties of unsupported types are handled according to [`SchemaOptions::unsupported_property_policy`].
+	pub fn fetch_property_list(
+		&self,
+		defs: &mut BTreeMap<String, Definition>,
+		type_infos: &mut BTreeMap<String, GodotTypeInfo>,
+		exclude: &BTreeSet<String>,
+	) -> Result<IndexMap<String, Type>> {
+		let mut exclude = exclude.clone();
+
+		if let ClassSource::ScriptNamed(script, _) | ClassSource::ScriptUnnamed(script) = self {
+			exclude.extend(excluded_by_constant(script)?);
 		}
+
+		let skip_private = SchemaOptions::read_current(|options| options.skip_private_properties);
+		let required_usage = usage_filter().ord();
+
+		let class_name = self.definition_name();
+
+		let is_included = |dict: &Dictionary| {
+			let has_usage = try_get::<i64>(dict, "usage").map_or(true, |usage| usage as u64 & required_usage == required_usage);
+			let name = try_get::<String>(dict, "name").ok();
+
+			let excluded_by = match &name {
+				_ if !has_usage => Some("its usage does not match the library's usage filter"),
+				Some(name) if exclude.contains(name) => Some("it is excluded by the class"),
+				Some(name) if skip_private && name.starts_with('_') => Some("it is private"),
+				_ => None,
+			};
+
+			if let Some(reason) = excluded_by {
+				log(LogLevel::Debug, || format!("Excluded property \"{}\" of class \"{class_name}\": {reason}.", name.unwrap_or_default()));
+			}
+
+			excluded_by.is_none()
+		};
+
+		let policy = SchemaOptions::read_current(|options| options.unsupported_property_policy);
+
+		let mut eval_property_type = |dict: Dictionary| -> Result<Option<(String, Type)>> {
+			let wrapper = PropertyTypeInfo::try_from(dict)?;
+
+			let ty = match (wrapper.unsupported_reason(), policy) {
+				(None, _) => {
+					let issues = generation_issue_count();
+					let ty = wrapper.eval_type(defs)?;
+					attribute_generation_issues(issues, &class_name);
+					ty
+				}
+				(Some(_), UnsupportedPropertyPolicy::Error) => {
+					return Err(SchemaError::UnsupportedProperty {
+						name: wrapper.property_name.clone(),
+						variant_type: format!("{:?}", wrapper.variant_type),
+						hint: wrapper.hint_string.clone(),
+					}.into());
+				}
+				(Some(reason), UnsupportedPropertyPolicy::Skip) => {
+					push_generation_issue(GenerationIssue {
+						kind: GenerationIssueKind::SkippedProperty,
+						class_name: Some(class_name.clone()),
+						property: Some(wrapper.property_name.clone()),
+						message: format!("Skipped property \"{}\" of class \"{class_name}\": {reason}.", wrapper.property_name),
+					});
+					return Ok(None);
+				}
+				(Some(reason), UnsupportedPropertyPolicy::StubAsNull) => {
+					push_generation_issue(GenerationIssue {
+						kind: GenerationIssueKind::StubbedProperty,
+						class_name: Some(class_name.clone()),
+						property: Some(wrapper.property_name.clone()),
+						message: format!("Stubbed property \"{}\" of class \"{class_name}\" as `null`: {reason}.", wrapper.property_name),
+					});
+					Definition::null().into()
+				}
+			};
+
+			log(LogLevel::Debug, || format!(
+				"Resolved property \"{}\" of class \"{class_name}\" (type {:?}, class \"{}\", hint {:?} \"{}\") as {}.",
+				wrapper.property_name, wrapper.variant_type, wrapper.class_name, wrapper.hint, wrapper.hint_string, describe_type(&ty),
+			));
+
+			type_infos.insert(wrapper.property_name.clone(), GodotTypeInfo::from(&wrapper));
+			Ok(Some((wrapper.property_name, ty)))
+		};
 		
 		match self {
 			| ClassSource::ScriptNamed(script, _) 
 			| ClassSource::ScriptUnnamed(script) => {
 				let properties_dict = script.clone().get_script_property_list();
 
-				properties_dict
+				let mut properties = properties_dict
 					.iter_shared()
 					.filter_map(|dict| {
 						// Skip the `file_name` property
@@ -66,18 +287,205 @@ impl ClassSource {
 							Some(dict)
 						}
 					})
-					.map(|dict| eval_property_type(dict, defs))
-					.try_collect()
+					.filter(is_included)
+					.map(&mut eval_property_type)
+					.filter_map(Result::transpose)
+					.try_collect()?;
+
+				apply_array_lengths(script, &mut properties)?;
+				Ok(properties)
 			}
 			ClassSource::Engine(class_name) => ClassDb::singleton()
 				.class_get_property_list(class_name)
 				.iter_shared()
-				.map(|dict| eval_property_type(dict, defs))
+				.filter(is_included)
+				.map(&mut eval_property_type)
+				.filter_map(Result::transpose)
 				.try_collect(),
 		}
 	}
 }
 
+/// A parameter of a script's `_init`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConstructorParameter {
+	pub name: String,
+	pub has_default: bool,
+}
+
+/// Name of the script constant listing properties left out of the schema, e.g. `const JSON_SCHEMA_EXCLUDE := ["cache"]`.
+pub const EXCLUDE_CONSTANT: &str = "JSON_SCHEMA_EXCLUDE";
+
+thread_local! {
+	static USAGE_FILTER: Cell<PropertyUsageFlags> = const { Cell::new(PropertyUsageFlags::NONE) };
+}
+
+/// The `PropertyUsageFlags` each property must have to be included in generated schemas, `NONE` includes every property.
+pub fn usage_filter() -> PropertyUsageFlags {
+	USAGE_FILTER.get()
+}
+
+/// Runs `f` with `filter` as the [`usage_filter()`], restoring the previous filter afterward.
+pub fn scope_usage_filter<T>(filter: PropertyUsageFlags, f: impl FnOnce() -> T) -> T {
+	let previous = USAGE_FILTER.replace(filter);
+	let result = f();
+	USAGE_FILTER.set(previous);
+	result
+}
+
+/// What [`ClassSource::fetch_property_list()`] does with properties whose type cannot be represented by a schema,
+/// see [`PropertyTypeInfo::unsupported_reason()`].
+#[derive(GodotConvert, Var, Export, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[godot(via = i64)]
+pub enum UnsupportedPropertyPolicy {
+	/// Fails the generation.
+	Error = 0,
+	/// Leaves the property out of the schema.
+	#[default]
+	Skip = 1,
+	/// Describes the property as `null`, instantiation never assigns it.
+	StubAsNull = 2,
+}
+
+/// A decision of the schema generation worth reviewing, e.g. a property skipped or described as `null` for lack of type information.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GenerationIssue {
+	pub kind: GenerationIssueKind,
+	/// The class whose property raised the issue, `None` for non-class roots.
+	pub class_name: Option<String>,
+	pub property: Option<String>,
+	pub message: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GenerationIssueKind {
+	/// Left out of the schema, see [`UnsupportedPropertyPolicy::Skip`].
+	SkippedProperty,
+	/// Described as `null`, see [`UnsupportedPropertyPolicy::StubAsNull`].
+	StubbedProperty,
+	/// The class named by the property's type information does not exist, its hint was used instead.
+	UnresolvedClass,
+	/// The property has no type information to describe it with, it was described as `null`.
+	NullFallback,
+}
+
+impl GenerationIssueKind {
+	pub const fn name(&self) -> &'static str {
+		match self {
+			Self::SkippedProperty => "skipped_property",
+			Self::StubbedProperty => "stubbed_property",
+			Self::UnresolvedClass => "unresolved_class",
+			Self::NullFallback => "null_fallback",
+		}
+	}
+}
+
+impl GenerationIssue {
+	/// A `Dictionary` with the keys "kind" (see [`GenerationIssueKind::name()`]), "class", "property" (both empty if unknown)
+	/// and "message".
+	pub fn to_dictionary(&self) -> Dictionary {
+		let mut dict = Dictionary::new();
+		dict.set("kind", self.kind.name());
+		dict.set("class", self.class_name.clone().unwrap_or_default());
+		dict.set("property", self.property.clone().unwrap_or_default());
+		dict.set("message", self.message.as_str());
+		dict
+	}
+}
+
+impl std::fmt::Display for GenerationIssue {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(&self.message)
+	}
+}
+
+thread_local! {
+	static GENERATION_ISSUES: RefCell<Option<Vec<GenerationIssue>>> = const { RefCell::new(None) };
+}
+
+/// Runs `f`, returning the issues raised by the schema generation it ran (e.g. skipped properties) alongside its result.
+pub fn collect_generation_issues<T>(f: impl FnOnce() -> T) -> (T, Vec<GenerationIssue>) {
+	let previous = GENERATION_ISSUES.replace(Some(Vec::new()));
+	let result = f();
+	let issues = GENERATION_ISSUES.replace(previous).unwrap_or_default();
+	(result, issues)
+}
+
+/// Outside of [`collect_generation_issues()`], issues are printed instead.
+pub fn push_generation_issue(issue: GenerationIssue) {
+	GENERATION_ISSUES.with_borrow_mut(|issues| match issues {
+		Some(issues) => {
+			log(LogLevel::Warn, || issue.message.clone());
+			issues.push(issue);
+		}
+		None => godot_warn!("{issue}"),
+	});
+}
+
+fn generation_issue_count() -> usize {
+	GENERATION_ISSUES.with_borrow(|issues| issues.as_ref().map_or(0, Vec::len))
+}
+
+/// Attributes the issues raised since there were `since` of them, that do not name their class yet, to class `class_name`.
+fn attribute_generation_issues(since: usize, class_name: &str) {
+	GENERATION_ISSUES.with_borrow_mut(|issues| {
+		let Some(issues) = issues
+		else { return };
+
+		for issue in issues.iter_mut().skip(since) {
+			issue.class_name.get_or_insert_with(|| class_name.to_owned());
+		}
+	});
+}
+
+fn excluded_by_constant(script: &Gd<Script>) -> Result<Vec<String>> {
+	let Some(names) = script.clone().get_script_constant_map().get(EXCLUDE_CONSTANT)
+	else { return Ok(Vec::new()) };
+
+	array_elements(&names)
+		.map_err(|err| anyhow!("Expected constant `{EXCLUDE_CONSTANT}` to be an Array of property names.\nError: {err}"))?
+		.iter()
+		.map(|name| match name.get_type() {
+			| VariantType::STRING
+			| VariantType::STRING_NAME => Ok(name.to_string()),
+			other => bail!("`{EXCLUDE_CONSTANT}`: expected property names to be strings.\nGot a value of type {other:?}: {name}"),
+		})
+		.try_collect()
+}
+
+/// Name of the script constant declaring fixed lengths of Array properties, e.g. `const JSON_SCHEMA_ARRAY_LENGTHS := { "ability_slots": 4 }`.
+pub const ARRAY_LENGTHS_CONSTANT: &str = "JSON_SCHEMA_ARRAY_LENGTHS";
+
+fn apply_array_lengths(script: &Gd<Script>, properties: &mut IndexMap<String, Type>) -> Result<()> {
+	let Some(lengths) = script.clone().get_script_constant_map().get(ARRAY_LENGTHS_CONSTANT)
+	else { return Ok(()) };
+
+	let lengths = lengths
+		.try_to::<Dictionary>()
+		.map_err(|err| anyhow!("Expected constant `{ARRAY_LENGTHS_CONSTANT}` to be a Dictionary.\nError: {err:?}"))?;
+
+	for (name, len) in lengths.iter_shared() {
+		let name = name.to_string();
+
+		let len = len
+			.try_to::<i64>()
+			.ok()
+			.and_then(|len| usize::try_from(len).ok())
+			.ok_or_else(|| anyhow!("`{ARRAY_LENGTHS_CONSTANT}`: expected the length of \"{name}\" to be a positive integer."))?;
+
+		match properties.get_mut(&name) {
+			Some(Type::Definition(Definition::Array(array))) => {
+				array.min_items = Some(len);
+				array.max_items = Some(len);
+			}
+			Some(_) => bail!("`{ARRAY_LENGTHS_CONSTANT}`: expected property \"{name}\" to be an Array."),
+			None => bail!("`{ARRAY_LENGTHS_CONSTANT}`: expected property \"{name}\" to exist."),
+		}
+	}
+
+	Ok(())
+}
+
 fn find_script(class_name: StringName) -> Result<Gd<Script>> {
 	let class_list = ProjectSettings::singleton().get_global_class_list();
 
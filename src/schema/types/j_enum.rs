@@ -4,6 +4,8 @@ use super::*;
 pub struct JEnum {
 	pub description: Option<String>,
 	pub variants: BTreeMap<String, i64>,
+	/// Optional description of each variant, if any the variants are emitted as `oneOf` constants carrying them.
+	pub variant_descriptions: BTreeMap<String, String>,
 }
 
 impl JEnum {
@@ -13,28 +15,61 @@ impl JEnum {
 			variants: variants
 				.map(|(k, v)| (k.into(), v.into()))
 				.collect(),
+			variant_descriptions: BTreeMap::new(),
 		}
 	}
 	
+	/// Fetches the variants of `ClassName.EnumName`, or of a single-segment `@GlobalScope` enum (e.g. `Side`, `Key`, `Error`).
 	pub fn from_enum_path(enum_path: impl Into<String>) -> Result<(Self, String)> {
 		let enum_path = enum_path.into();
 		
-		let split = enum_path.split(".").collect::<Vec<_>>();
-		
-		if split.is_empty() {
-			bail!("Cannot fetch variant list from enum {enum_path}.\nGlobal enums are not supported.");
-		}
-		
-		if split.len() != 2 {
-			bail!("Expected split by '.' to have exactly 2 parts: `ClassName.EnumName` .\nGot: {split:?}");
+		match enum_path.split('.').collect::<Vec<_>>().as_slice() {
+			[enum_name] => Ok((Self::from_global_enum(enum_name)?, (*enum_name).to_owned())),
+			[class_name, enum_name] => {
+				let class_source = ClassSource::from_class_name(*class_name)?;
+				let def = Self::from_class_source(&class_source, *enum_name)?;
+				Ok((def, (*enum_name).to_owned()))
+			}
+			split => bail!("Expected enum path to be `EnumName` (global enum) or `ClassName.EnumName`.\nGot: {split:?}"),
 		}
-		
-		let class_source = ClassSource::from_class_name(split[0])?;
-		let enum_name = split[1];
-		let def = Self::from_class_source(&class_source, enum_name)?;
-		Ok((def, enum_name.to_owned()))
 	}
-	
+
+	/// Godot does not list the `@GlobalScope` enums at runtime, their variants are read from gdext's bindings instead.
+	pub fn from_global_enum(enum_name: &str) -> Result<Self> {
+		use godot::global::{
+			ClockDirection, Corner, HorizontalAlignment, InlineAlignment, JoyAxis, JoyButton,
+			Key, MidiMessage, MouseButton, Orientation, Side, VerticalAlignment,
+		};
+
+		// Ordinals of most global enums are small, `Key` also has the "special" keys (`KEY_SPECIAL | n`) and `KEY_UNKNOWN`.
+		let small = || -1..=255;
+		let keys = || small().chain(0x400000..=0x4001FF).chain([0x7FFFFF]);
+
+		let variants = match enum_name {
+			"Side" => global_enum_variants::<Side>(small()),
+			"Corner" => global_enum_variants::<Corner>(small()),
+			"Orientation" => global_enum_variants::<Orientation>(small()),
+			"ClockDirection" => global_enum_variants::<ClockDirection>(small()),
+			"HorizontalAlignment" => global_enum_variants::<HorizontalAlignment>(small()),
+			"VerticalAlignment" => global_enum_variants::<VerticalAlignment>(small()),
+			"InlineAlignment" => global_enum_variants::<InlineAlignment>(small()),
+			"Key" => global_enum_variants::<Key>(keys()),
+			"MouseButton" => global_enum_variants::<MouseButton>(small()),
+			"JoyButton" => global_enum_variants::<JoyButton>(small()),
+			"JoyAxis" => global_enum_variants::<JoyAxis>(small()),
+			"MIDIMessage" => global_enum_variants::<MidiMessage>(small()),
+			"Error" => global_enum_variants::<godot::global::Error>(small()),
+			"PropertyHint" => global_enum_variants::<PropertyHint>(small()),
+			_ => bail!("Unknown global enum \"{enum_name}\"."),
+		};
+
+		Ok(Self {
+			description: None,
+			variants,
+			variant_descriptions: BTreeMap::new(),
+		})
+	}
+
 	pub fn from_class_source(source: &ClassSource, enum_name: impl Into<StringName>) -> Result<Self> {
 		match source {
 			| ClassSource::ScriptNamed(script, _)
@@ -60,6 +95,7 @@ impl JEnum {
 		Ok(Self {
 			description: None,
 			variants,
+			variant_descriptions: BTreeMap::new(),
 		})
 	}
 
@@ -86,6 +122,7 @@ impl JEnum {
 			Ok(Self {
 				description: None,
 				variants,
+				variant_descriptions: BTreeMap::new(),
 			})
 		} else {
 			bail!("Expected enum \"{enum_name}\" to have at least 2 variants.\nGot: {}", variants.len())
@@ -95,12 +132,80 @@ impl JEnum {
 	pub fn add_variant(&mut self, variant: impl Into<String>, value: impl Into<i64>) {
 		self.variants.insert(variant.into(), value.into());
 	}
+
+	pub fn set_variant_description(&mut self, variant: &str, description: impl Into<String>) -> Result<()> {
+		if !self.variants.contains_key(variant) {
+			bail!("Expected variant \"{variant}\" to be in `variants` map.");
+		}
+
+		self.variant_descriptions.insert(variant.to_owned(), description.into());
+		Ok(())
+	}
+}
+
+impl JEnum {
+	/// Whether enums are currently encoded as their integer values instead of their names, see [`SchemaOptions::integer_enums`].
+	pub fn integer_encoding() -> bool {
+		SchemaOptions::read_current(|options| options.integer_enums)
+	}
+
+	/// The value of the variant named `name` or, since either encoding is accepted, whose value is `number`.
+	pub fn variant_value(&self, value: &Value) -> Option<i64> {
+		match value {
+			Value::String(name) => self.variants.get(name).copied(),
+			Value::Number(number) => number.as_i64().filter(|number| self.variants.values().any(|value| value == number)),
+			_ => None,
+		}
+	}
+}
+
+fn global_enum_variants<T: godot::obj::EngineEnum>(ords: impl Iterator<Item = i32>) -> BTreeMap<String, i64> {
+	ords.filter_map(T::try_from_ord)
+		.map(|variant| (variant.godot_name().to_owned(), i64::from(variant.ord())))
+		.collect()
 }
 
 impl SerializeFields for JEnum {
 	fn serialize_fields<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
-		map.serialize_entry("type", "string")?;
-		map.serialize_entry("enum", &self.variants.keys().collect::<Vec<_>>())
+		let integers = Self::integer_encoding();
+		map.serialize_entry("type", if integers { "integer" } else { "string" })?;
+
+		if self.variant_descriptions.is_empty() {
+			if integers {
+				map.serialize_entry("enum", &self.variants.values().collect::<Vec<_>>())?;
+			} else {
+				map.serialize_entry("enum", &self.variants.keys().collect::<Vec<_>>())?;
+			}
+		} else {
+			let variants = self
+				.variants
+				.iter()
+				.map(|(name, value)| {
+					let mut variant = Map::new();
+
+					if integers {
+						variant.insert("const".into(), (*value).into());
+						variant.insert("title".into(), name.clone().into());
+					} else {
+						variant.insert("const".into(), name.clone().into());
+					}
+
+					if let Some(description) = self.variant_descriptions.get(name) {
+						variant.insert("description".into(), description.clone().into());
+					}
+
+					Value::Object(variant)
+				})
+				.collect_vec();
+
+			map.serialize_entry("oneOf", &variants)?;
+		}
+
+		if round_trip_annotations() {
+			map.serialize_entry(GODOT_ENUM_KEY, &self.variants)?;
+		}
+
+		Ok(())
 	}
 }
 
@@ -8,6 +8,9 @@ pub use primitives::*;
 pub use reference::*;
 pub use root_schema::*;
 pub use tuple::*;
+pub use resource_path::*;
+pub use conditional::*;
+pub use one_of::*;
 pub(crate) use shared_impls::*;
 
 pub mod primitives;
@@ -19,6 +22,9 @@ pub mod reference;
 pub mod godot_class;
 pub mod root_schema;
 pub mod shared_impls;
+pub mod resource_path;
+pub mod conditional;
+pub mod one_of;
 
 delegated_enum! {
 	ENUM_OUT: {
@@ -68,6 +74,13 @@ impl Type {
 		JEnum::new(variants).into()
 	}
 
+	pub fn insert_references(&self, fill_me: &mut BTreeSet<String>) {
+		match self {
+			Type::Definition(def) => def.insert_references(fill_me),
+			Type::Ref(JRef { name, .. }) => { fill_me.insert(name.clone()); }
+		}
+	}
+
 	pub fn insert_variant_definitions(&self, fill_me: &mut Vec<VariantDefinition>) {
 		if let Type::Definition(def) = self {
 			def.insert_variant_definitions(fill_me);
@@ -4,7 +4,18 @@ use super::*;
 pub struct JObject {
 	pub description: Option<String>,
 	// If properties is empty, then the object is a Dictionary with any number of key/value pairs
-	pub properties: BTreeMap<String, Type>,
+	pub properties: IndexMap<String, Type>,
+	/// Properties that may be absent from the input, the others are `required`.
+	pub optional: BTreeSet<String>,
+	/// Property name -> the properties that must be present whenever it is (`dependentRequired`).
+	pub dependent_required: BTreeMap<String, BTreeSet<String>>,
+	/// The only value accepted (`const`), see [`Definition::value_keywords()`].
+	pub const_value: Option<Value>,
+	/// The value of this object when it is an absent optional property (`default`).
+	pub default: Option<Value>,
+	/// If set (and `properties` is empty), the object is a Dictionary whose keys are strings and values are all of this type
+	/// (`additionalProperties`).
+	pub values_ty: Option<Box<Type>>,
 }
 
 impl JObject {
@@ -22,13 +33,35 @@ impl JObject {
 			properties: properties
 				.map(|(k, v)| (k.into(), v.into()))
 				.collect(),
+			optional: BTreeSet::new(),
+			dependent_required: BTreeMap::new(),
+			const_value: None,
+			default: None,
+			values_ty: None,
 		}
 	}
 
+	/// A Dictionary of string keys to values of type `values_ty`, e.g. an "id => entity" collection.
+	pub fn map_of(values_ty: impl Into<Type>) -> Self {
+		Self {
+			values_ty: Some(Box::new(values_ty.into())),
+			..Self::default()
+		}
+	}
+
+	/// The names of the properties that must be present in the input.
+	pub fn required(&self) -> Vec<&String> {
+		self.properties.keys().filter(|name| !self.optional.contains(*name)).collect()
+	}
+
 	pub fn insert_variant_definitions(&self, fill_me: &mut Vec<VariantDefinition>) {
 		for ty in self.properties.values() {
 			ty.insert_variant_definitions(fill_me);
 		}
+
+		if let Some(values_ty) = &self.values_ty {
+			values_ty.insert_variant_definitions(fill_me);
+		}
 	}
 }
 
@@ -38,11 +71,21 @@ impl SerializeFields for JObject {
 
 		if !self.properties.is_empty() {
 			map.serialize_entry("properties", &self.properties)?;
-			map.serialize_entry("required", &self.properties.keys().collect::<Vec<_>>())?;
-			map.serialize_entry("additionalProperties", &false)?;
+			map.serialize_entry("required", &self.required())?;
+
+			// Conditions only test some of the properties, the others are checked by the branches.
+			if !JConditional::in_condition() {
+				map.serialize_entry("additionalProperties", &false)?;
+			}
+		} else if let Some(values_ty) = &self.values_ty {
+			map.serialize_entry("additionalProperties", values_ty)?;
+		}
+
+		if !self.dependent_required.is_empty() {
+			map.serialize_entry("dependentRequired", &self.dependent_required)?;
 		}
 
-		Ok(())
+		serialize_value_keywords(map, &self.const_value, &self.default)
 	}
 }
 
@@ -0,0 +1,50 @@
+use super::*;
+
+/// `oneOf`: values must match exactly one of [`Self::options`], e.g. one of several classes.
+///
+/// Instantiation uses the first option the value matches.
+#[derive(Clone, Debug)]
+pub struct JOneOf {
+	pub description: Option<String>,
+	pub options: Vec<Type>,
+}
+
+impl JOneOf {
+	pub fn new(options: impl IntoIterator<Item = impl Into<Type>>) -> Self {
+		Self {
+			description: None,
+			options: options.into_iter().map(Into::into).collect(),
+		}
+	}
+
+	/// The first option `value` matches, if any.
+	pub fn matching_option(&self, value: &Value, defs: &BTreeMap<String, Definition>) -> Option<&Type> {
+		self.options.iter().find(|ty| matches_type(ty, value, defs))
+	}
+
+	/// Converts `variant` with the first option whose JSON matches that option.
+	pub fn json_from_variant(&self, variant: &Variant, defs: &BTreeMap<String, Definition>) -> Result<Value> {
+		for ty in &self.options {
+			if let Ok(value) = ty.resolve(defs).and_then(|def| def.json_from_variant(variant, defs))
+				&& matches_type(ty, &value, defs) {
+				return Ok(value);
+			}
+		}
+
+		bail!("Expected the value to match one of the {} options.\nGot: {variant}", self.options.len())
+	}
+
+	pub fn insert_variant_definitions(&self, fill_me: &mut Vec<VariantDefinition>) {
+		for ty in &self.options {
+			ty.insert_variant_definitions(fill_me);
+		}
+	}
+}
+
+impl SerializeFields for JOneOf {
+	fn serialize_fields<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+		map.serialize_entry("oneOf", &self.options)
+	}
+}
+
+all_shared_impls!(JOneOf);
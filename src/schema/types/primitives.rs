@@ -3,56 +3,108 @@ use super::*;
 #[derive(Clone, Debug, Default)]
 pub struct Null {
 	pub description: Option<String>,
+	pub const_value: Option<Value>,
+	pub default: Option<Value>,
 }
 
 #[derive(Clone, Debug, Default)]
 pub struct Boolean {
 	pub description: Option<String>,
+	pub const_value: Option<Value>,
+	pub default: Option<Value>,
 }
 
 #[derive(Clone, Debug, Default)]
 pub struct Integer {
 	pub description: Option<String>,
+	pub const_value: Option<Value>,
+	pub default: Option<Value>,
 }
 
 #[derive(Clone, Debug, Default)]
 pub struct Number {
 	pub description: Option<String>,
+	pub const_value: Option<Value>,
+	pub default: Option<Value>,
 }
 
 #[derive(Clone, Debug, Default)]
 pub struct JString {
 	pub description: Option<String>,
+	/// A regular expression the string must match, only enforced by the JSON Schema validator.
+	pub pattern: Option<String>,
+	pub const_value: Option<Value>,
+	pub default: Option<Value>,
+}
+
+/// Any JSON value, converted as in untyped contexts (see [`raw_variant_from_json()`]).
+#[derive(Clone, Debug, Default)]
+pub struct JAny {
+	pub description: Option<String>,
 }
 
 impl SerializeFields for Null {
 	fn serialize_fields<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
-		map.serialize_entry("type", "null")
+		map.serialize_entry("type", "null")?;
+		serialize_value_keywords(map, &self.const_value, &self.default)
 	}
 }
 
 impl SerializeFields for Boolean {
 	fn serialize_fields<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
-		map.serialize_entry("type", "boolean")
+		map.serialize_entry("type", "boolean")?;
+		serialize_value_keywords(map, &self.const_value, &self.default)
 	}
 }
 
 impl SerializeFields for Integer {
 	fn serialize_fields<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
-		map.serialize_entry("type", "integer")
+		map.serialize_entry("type", "integer")?;
+		serialize_value_keywords(map, &self.const_value, &self.default)
 	}
 }
 
 impl SerializeFields for Number {
 	fn serialize_fields<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
-		map.serialize_entry("type", "number")
+		map.serialize_entry("type", "number")?;
+		serialize_value_keywords(map, &self.const_value, &self.default)
 	}
 }
 
 impl SerializeFields for JString {
 	fn serialize_fields<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
-		map.serialize_entry("type", "string")
+		map.serialize_entry("type", "string")?;
+
+		if let Some(pattern) = &self.pattern {
+			map.serialize_entry("pattern", pattern)?;
+		}
+
+		serialize_value_keywords(map, &self.const_value, &self.default)
+	}
+}
+
+/// Emits the `const` and `default` keywords of primitive and object definitions, see [`Definition::value_keywords()`].
+pub(crate) fn serialize_value_keywords<M: SerializeMap>(map: &mut M, const_value: &Option<Value>, default: &Option<Value>) -> Result<(), M::Error> {
+	if let Some(const_value) = const_value {
+		map.serialize_entry("const", const_value)?;
+	}
+
+	if let Some(default) = default {
+		map.serialize_entry("default", default)?;
+	}
+
+	Ok(())
+}
+
+/// The types listed by [`JAny`]'s `anyOf`, together they accept any JSON value.
+pub const ANY_TYPES: [&str; 7] = ["null", "boolean", "integer", "number", "string", "array", "object"];
+
+impl SerializeFields for JAny {
+	fn serialize_fields<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+		// Equivalent to the empty schema, but structured output APIs often reject schemas without a type.
+		let types = ANY_TYPES.map(|ty| serde_json::json!({ "type": ty }));
+		map.serialize_entry("anyOf", &types)
 	}
 }
 
-all_shared_impls!(Null, Boolean, Integer, Number, JString);
\ No newline at end of file
+all_shared_impls!(Null, Boolean, Integer, Number, JString, JAny);
\ No newline at end of file
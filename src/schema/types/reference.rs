@@ -13,11 +13,30 @@ impl JRef {
 			name: name.into(),
 		}
 	}
+
+	/// The URI the serialization running on this thread emits instead of referencing definition `name` in `$defs`, if any.
+	pub fn external_uri(name: &str) -> Option<String> {
+		SchemaOptions::read_current(|options| options.external_refs.get(name).cloned())
+	}
+
+	/// Emits the `$ref` keyword alone.
+	pub fn serialize_ref<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+		match Self::external_uri(&self.name) {
+			Some(uri) => map.serialize_entry("$ref", &uri),
+			None => map.serialize_entry("$ref", &format!("#/$defs/{}", self.name)),
+		}
+	}
 }
 
 impl SerializeFields for JRef {
 	fn serialize_fields<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
-		map.serialize_entry("$ref", &format!("#/$defs/{}", self.name))
+		self.serialize_ref(map)?;
+
+		if JClass::is_open_class(&self.name) && !JConditional::in_condition() {
+			map.serialize_entry("unevaluatedProperties", &false)?;
+		}
+
+		Ok(())
 	}
 }
 
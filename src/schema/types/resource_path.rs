@@ -0,0 +1,258 @@
+use super::*;
+
+/// The `format` of resource path strings.
+pub const RESOURCE_PATH_FORMAT: &str = "godot-resource-path";
+/// Annotates resource paths with the class loaded resources must be an instance of.
+pub const GODOT_RESOURCE_CLASS_KEY: &str = "x-godot-resource-class";
+/// Annotates resource paths with the directories they must be inside of.
+pub const GODOT_RESOURCE_DIRS_KEY: &str = "x-godot-resource-dirs";
+/// Annotates resource paths with the file extensions they must have.
+pub const GODOT_RESOURCE_EXTENSIONS_KEY: &str = "x-godot-resource-extensions";
+/// Annotates scene paths that are instantiated into their root node, see [`JResourcePath::instance_scene`].
+pub const GODOT_SCENE_INSTANCE_KEY: &str = "x-godot-scene-instance";
+/// Extensions of scene files, text and binary.
+pub const SCENE_EXTENSIONS: [&str; 2] = ["tscn", "scn"];
+
+/// A `Resource` referenced by its path (e.g. "res://textures/grass.png"), loaded with `ResourceLoader` during instantiation.
+#[derive(Clone, Debug)]
+pub struct JResourcePath {
+	pub description: Option<String>,
+	/// Loaded resources must be instances of this class (or of a class inheriting it).
+	pub source: ClassSource,
+	/// Paths must be inside one of these directories (e.g. "res://textures/"), empty allows any "res://" path.
+	pub allowed_dirs: Vec<String>,
+	/// Paths must have one of these extensions (without the dot), empty allows any extension.
+	pub extensions: Vec<String>,
+	/// If set, paths are loaded as a `PackedScene` and instantiated, [`Self::source`] is the class of the scene's root node.
+	pub instance_scene: bool,
+}
+
+impl JResourcePath {
+	pub fn new(source: ClassSource, allowed_dirs: impl IntoIterator<Item = impl Into<String>>) -> Self {
+		let allowed_dirs = allowed_dirs
+			.into_iter()
+			.map(|dir| {
+				let dir = dir.into();
+				if dir.ends_with('/') { dir } else { dir + "/" }
+			})
+			.collect();
+
+		Self {
+			description: None,
+			source,
+			allowed_dirs,
+			extensions: Vec::new(),
+			instance_scene: false,
+		}
+	}
+
+	/// A path to a scene file, loaded as a `PackedScene`.
+	pub fn scene(allowed_dirs: impl IntoIterator<Item = impl Into<String>>) -> Self {
+		Self {
+			extensions: SCENE_EXTENSIONS.map(str::to_owned).to_vec(),
+			..Self::new(ClassSource::Engine("PackedScene".into()), allowed_dirs)
+		}
+	}
+
+	/// A path to a scene file, instantiated into its root node, which must be an instance of `root_source`.
+	pub fn scene_instance(root_source: ClassSource, allowed_dirs: impl IntoIterator<Item = impl Into<String>>) -> Self {
+		Self {
+			extensions: SCENE_EXTENSIONS.map(str::to_owned).to_vec(),
+			instance_scene: true,
+			..Self::new(root_source, allowed_dirs)
+		}
+	}
+
+	/// Checks that `path` is inside one of [`Self::allowed_dirs`] and has one of [`Self::extensions`], without loading it.
+	pub fn check_path(&self, path: &str) -> Result<()> {
+		if path.split('/').any(|segment| segment == "..") {
+			bail!("Expected resource path without \"..\" segments.\nGot: {path}");
+		}
+
+		let allowed = if self.allowed_dirs.is_empty() {
+			path.starts_with("res://")
+		} else {
+			self.allowed_dirs.iter().any(|dir| path.starts_with(dir.as_str()))
+		};
+
+		if !allowed {
+			let dirs = if self.allowed_dirs.is_empty() { "res://".to_owned() } else { self.allowed_dirs.join("\", \"") };
+			bail!("Expected resource path inside one of \"{dirs}\".\nGot: {path}");
+		}
+
+		let has_extension = path
+			.rsplit_once('.')
+			.is_some_and(|(_, extension)| self.extensions.iter().any(|allowed| allowed == extension));
+
+		if !self.extensions.is_empty() && !has_extension {
+			bail!("Expected resource path with one of the extensions \"{}\".\nGot: {path}", self.extensions.join("\", \""));
+		}
+
+		Ok(())
+	}
+
+	/// Loads the resource at `path`, checking that it is allowed and that its type matches [`Self::source`].
+	///
+	/// If [`Self::instance_scene`] is set, returns the scene's root node instead.
+	pub fn load(&self, path: &str) -> Result<Variant> {
+		self.check_path(path)?;
+
+		let resource = ResourceLoader::singleton()
+			.load(path)
+			.ok_or_else(|| SchemaError::Io { path: path.to_owned(), message: "Could not load resource.".to_owned() })?;
+
+		let loaded = if self.instance_scene {
+			resource
+				.try_cast::<PackedScene>()
+				.map_err(|resource| anyhow!("Expected resource \"{path}\" to be a PackedScene.\nGot: {}", resource.get_class()))?
+				.instantiate()
+				.ok_or_else(|| anyhow!("Could not instantiate scene \"{path}\"."))?
+				.upcast::<Object>()
+		} else {
+			resource.upcast::<Object>()
+		};
+
+		if !self.source.is_instance_of(&loaded) {
+			let got = loaded.get_class();
+
+			// Instantiated nodes are not in the tree, they would leak.
+			if let Ok(node) = loaded.try_cast::<Node>() {
+				node.free();
+			}
+
+			bail!("Expected resource \"{path}\" to be a {}.\nGot: {got}", self.source.definition_name());
+		}
+
+		Ok(loaded.to_variant())
+	}
+
+	/// The paths of the existing files [`Self::load()`] accepts, found by listing [`Self::allowed_dirs`] recursively.
+	///
+	/// Each candidate is loaded (scenes are instantiated, then freed), this is meant for tests, not for every instantiation.
+	pub fn loadable_paths(&self) -> Vec<String> {
+		let mut files = Vec::new();
+
+		if self.allowed_dirs.is_empty() {
+			list_files("res://", &mut files);
+		} else {
+			for dir in &self.allowed_dirs {
+				list_files(dir, &mut files);
+			}
+		}
+
+		files.retain(|path| {
+			if self.check_path(path).is_err() || !ResourceLoader::singleton().exists(path.as_str()) {
+				return false;
+			}
+
+			match self.load(path) {
+				Ok(loaded) => {
+					if let Ok(node) = loaded.try_to::<Gd<Node>>() {
+						node.free();
+					}
+
+					true
+				}
+				Err(_) => false,
+			}
+		});
+
+		files
+	}
+
+	/// The reverse of [`Self::load()`]: the path of the resource (or of the scene the node was instantiated from)
+	/// held by `variant`, `null` if it holds none.
+	pub fn json_from_variant(&self, variant: &Variant) -> Result<Value> {
+		if variant.is_nil() {
+			return Ok(Value::Null);
+		}
+
+		let path = if self.instance_scene {
+			variant
+				.try_to::<Gd<Node>>()
+				.map_err(|err| anyhow!("{err:?}"))?
+				.get_scene_file_path()
+				.to_string()
+		} else {
+			variant
+				.try_to::<Gd<Resource>>()
+				.map_err(|err| anyhow!("{err:?}"))?
+				.get_path()
+				.to_string()
+		};
+
+		// Resources embedded in scenes or other resources have no path of their own.
+		if path.is_empty() || path.contains("::") {
+			bail!("Expected resource to be saved to its own file.\nGot: {}", if path.is_empty() { "an unsaved resource" } else { &path });
+		}
+
+		Ok(Value::String(path))
+	}
+
+	fn pattern(&self) -> String {
+		let dirs = if self.allowed_dirs.is_empty() {
+			vec![regex_escape("res://")]
+		} else {
+			self.allowed_dirs.iter().map(|dir| regex_escape(dir)).collect()
+		};
+
+		if self.extensions.is_empty() {
+			format!("^({})", dirs.join("|"))
+		} else {
+			let extensions = self.extensions.iter().map(|extension| regex_escape(extension)).join("|");
+			format!("^({}).*\\.({extensions})$", dirs.join("|"))
+		}
+	}
+}
+
+/// Pushes the paths of the files in `dir` (ending with "/") and its subdirectories to `files`, skipping hidden directories
+/// (e.g. "res://.godot/").
+fn list_files(dir: &str, files: &mut Vec<String>) {
+	for file in DirAccess::get_files_at(dir).as_slice() {
+		files.push(format!("{dir}{file}"));
+	}
+
+	for subdir in DirAccess::get_directories_at(dir).as_slice() {
+		let subdir = subdir.to_string();
+
+		if !subdir.starts_with('.') {
+			list_files(&format!("{dir}{subdir}/"), files);
+		}
+	}
+}
+
+fn regex_escape(text: &str) -> String {
+	let mut escaped = String::with_capacity(text.len());
+
+	for char in text.chars() {
+		if r"\.+*?()|[]{}^$".contains(char) {
+			escaped.push('\\');
+		}
+
+		escaped.push(char);
+	}
+
+	escaped
+}
+
+impl SerializeFields for JResourcePath {
+	fn serialize_fields<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+		map.serialize_entry("type", "string")?;
+		map.serialize_entry("format", RESOURCE_PATH_FORMAT)?;
+		map.serialize_entry("pattern", &self.pattern())?;
+		map.serialize_entry(GODOT_RESOURCE_CLASS_KEY, &self.source.definition_name())?;
+		map.serialize_entry(GODOT_RESOURCE_DIRS_KEY, &self.allowed_dirs)?;
+
+		if !self.extensions.is_empty() {
+			map.serialize_entry(GODOT_RESOURCE_EXTENSIONS_KEY, &self.extensions)?;
+		}
+
+		if self.instance_scene {
+			map.serialize_entry(GODOT_SCENE_INSTANCE_KEY, &true)?;
+		}
+
+		Ok(())
+	}
+}
+
+all_shared_impls!(JResourcePath);
@@ -1,27 +1,195 @@
 use super::*;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::sync::Arc;
 
 #[derive(Clone, Debug)]
 pub struct RootSchema {
 	pub defs: BTreeMap<String, Definition>,
 	pub base: Definition,
+	pub options: SchemaOptions,
+	/// Raised while generating the schema, e.g. properties skipped by the [`UnsupportedPropertyPolicy`].
+	pub warnings: Vec<GenerationIssue>,
+	/// The result of [`Self::to_annotated_names()`], built by [`Self::rebuild_name_map()`]
+	/// so that translating documents does not serialize the schema each time.
+	pub name_map: Option<Arc<Value>>,
+}
+
+/// Per-schema settings that are not part of the definitions themselves.
+#[derive(Clone, Debug, Default)]
+pub struct SchemaOptions {
+	/// If set, every root property may be accompanied by a sibling `<name><suffix>` number,
+	/// see [`RootSchema::with_confidence()`].
+	pub confidence_suffix: Option<String>,
+	/// How values of untyped contexts are serialized, see [`VariantEncoding`].
+	pub variant_encoding: VariantEncoding,
+	/// If set, class properties are annotated with their Godot type, see [`GodotTypeInfo`].
+	pub type_annotations: bool,
+	/// If set, classes, enums, wrapped roots and the encodings above are annotated with the vendor keys
+	/// [`RootSchema::from_value()`] reads to reconstruct the exact definitions, e.g. [`GODOT_CLASS_KEY`].
+	pub round_trip_annotations: bool,
+	/// If set, enums are serialized as their integer values (`{"type": "integer", "enum": [0, 1, 2]}`) instead of their names.
+	///
+	/// Instantiation accepts either.
+	pub integer_enums: bool,
+	/// Alternative encodings of Godot's built-in types, e.g. vectors as `[x, y]` or colors as hex strings.
+	pub representation: VariantRepresentation,
+	/// Emitted as the schema's `$id`, e.g. a versioned URL that other schemas reference it by.
+	pub id: Option<String>,
+	/// If set, the `$schema` keyword is not emitted, for consumers that reject it (e.g. OpenAI's strict mode).
+	pub omit_meta_schema: bool,
+	/// Definitions referenced by the `$id` of another schema instead of being repeated in `$defs`, keyed by definition name,
+	/// see [`SchemaLibrary::link_shared_definitions()`].
+	pub external_refs: BTreeMap<String, String>,
+	/// The schemas that external references resolve to when compiling validators, keyed by `$id`.
+	pub external_documents: Arc<BTreeMap<String, Value>>,
+	/// How class and object properties are named in the schema and in documents, see [`PropertyNaming`].
+	pub property_naming: PropertyNaming,
+	/// If set, inputs may also name renamed (or aliased) properties by their Godot name.
+	pub accept_godot_names: bool,
+	/// The property non-class roots are wrapped in, [`DEFAULT_WRAPPER_KEY`] if `None`.
+	pub wrapper_key: Option<String>,
+	/// If set, non-class roots are emitted as is (e.g. `{"type": "array", ..}`) instead of being wrapped in an object,
+	/// for consumers that accept other root types.
+	pub unwrapped_root: bool,
+	/// If set, classes whose script extends another script are generated as `allOf: [{"$ref": "#/$defs/BaseClass"}, {own properties}]`,
+	/// registering the base class in `$defs` instead of repeating its properties. Read by the generation, see [`Self::scope()`].
+	///
+	/// Base classes are emitted without `additionalProperties: false` (JSON Schema cannot extend a closed schema): inheriting classes
+	/// close themselves with `unevaluatedProperties: false` instead, and so do the other references to the base class.
+	pub class_inheritance: bool,
+	/// If set, properties prefixed with `_` are left out of generated classes, keeping internal bookkeeping variables out of prompts.
+	/// Read by the generation.
+	pub skip_private_properties: bool,
+	/// How properties of types that cannot be represented by a schema are generated. Read by the generation.
+	pub unsupported_property_policy: UnsupportedPropertyPolicy,
+	/// If `Some`, `Resource` properties of generated classes are referenced by path (see [`JResourcePath`]) instead of generated as classes,
+	/// paths must be inside one of the directories (an empty list allows any `res://` path). Read by the generation.
+	pub resource_path_dirs: Option<Vec<String>>,
+	/// If set, `Node` properties of generated classes are described as scene paths, instantiation instantiates the scene
+	/// and assigns its root node. Read by the generation.
+	pub scene_instancing: bool,
+	/// If set, primitive (and `Dictionary`) properties of generated classes declare their initial value in the script as `default`,
+	/// giving models context about the expected values. Read by the generation.
+	pub script_defaults: bool,
+	/// How the `Jsonschema` backend compiles the schema's validator, e.g. whether `format` is asserted.
+	pub validator: ValidatorOptions,
+}
+
+/// The property non-class roots are wrapped in by default, since most consumers require an object at the root.
+pub const DEFAULT_WRAPPER_KEY: &str = "value";
+
+thread_local! {
+	static ACTIVE_OPTIONS: RefCell<Option<SchemaOptions>> = const { RefCell::new(None) };
+}
+
+impl SchemaOptions {
+	/// Runs `f` with these options as the ones read by the serialization (or conversion, or generation) running on this thread,
+	/// restoring the previous ones afterward.
+	pub fn scope<T>(&self, f: impl FnOnce() -> T) -> T {
+		let previous = ACTIVE_OPTIONS.replace(Some(self.clone()));
+		let result = f();
+		ACTIVE_OPTIONS.set(previous);
+		result
+	}
+
+	/// The options of a schema generated on this thread: the defaults, along with the generation settings of the current options.
+	pub fn generated() -> SchemaOptions {
+		Self::read_current(|options| SchemaOptions::default().with_generation_settings(options))
+	}
+
+	/// These options, with the settings read by the generation (e.g. [`Self::class_inheritance`]) taken from `generation`.
+	pub fn with_generation_settings(self, generation: &SchemaOptions) -> SchemaOptions {
+		SchemaOptions {
+			class_inheritance: generation.class_inheritance,
+			skip_private_properties: generation.skip_private_properties,
+			unsupported_property_policy: generation.unsupported_property_policy,
+			resource_path_dirs: generation.resource_path_dirs.clone(),
+			scene_instancing: generation.scene_instancing,
+			script_defaults: generation.script_defaults,
+			..self
+		}
+	}
+
+	/// Reads the options of the serialization running on this thread, the defaults outside of [`Self::scope()`].
+	pub fn read_current<T>(f: impl FnOnce(&SchemaOptions) -> T) -> T {
+		ACTIVE_OPTIONS.with_borrow(|options| match options {
+			Some(options) => f(options),
+			None => f(&SchemaOptions::default()),
+		})
+	}
 }
 
 impl RootSchema {
 	pub fn from_class(source: ClassSource) -> Result<RootSchema> {
 		let mut defs = BTreeMap::new();
-		let base = Definition::from_class(source, &mut defs)?;
+		let (base, warnings) = collect_generation_issues(|| Definition::from_class(source, &mut defs));
+
+		Ok(RootSchema {
+			defs,
+			base: base?,
+			options: SchemaOptions::generated(),
+			warnings,
+			name_map: None,
+		})
+	}
+
+	/// Same as [`Self::from_class()`], but generates dependencies into `shared_defs`,
+	/// skipping the ones already generated for previous schemas.
+	///
+	/// The issues raised by the generation are added to `shared_issues`, the schema's warnings include the ones
+	/// raised by previous schemas while generating the definitions it references.
+	///
+	/// The schema's `$defs` only contain the definitions it references.
+	pub fn from_class_shared(
+		source: ClassSource,
+		shared_defs: &mut BTreeMap<String, Definition>,
+		shared_issues: &mut Vec<GenerationIssue>,
+	) -> Result<RootSchema> {
+		let (base, mut warnings) = collect_generation_issues(|| Definition::from_class(source, shared_defs));
+		let base = base?;
+
+		let mut pending = BTreeSet::new();
+		base.insert_references(&mut pending);
+
+		let mut defs = BTreeMap::new();
+
+		while let Some(name) = pending.pop_first() {
+			if defs.contains_key(&name) {
+				continue;
+			}
+
+			let def = shared_defs
+				.get(&name)
+				.ok_or_else(|| anyhow!("Expected definition \"{name}\" to be in `$defs` map."))?;
+
+			def.insert_references(&mut pending);
+			defs.insert(name, def.clone());
+		}
+
+		let inherited = shared_issues
+			.iter()
+			.filter(|issue| issue.class_name.as_ref().is_some_and(|name| defs.contains_key(name)) && !warnings.contains(issue))
+			.cloned()
+			.collect_vec();
+
+		shared_issues.extend(warnings.iter().cloned());
+		warnings.splice(0..0, inherited);
 
 		Ok(RootSchema {
 			defs,
 			base,
+			options: SchemaOptions::generated(),
+			warnings,
+			name_map: None,
 		})
 	}
 
 	pub fn from_type_info(property: PropertyTypeInfo) -> Result<Self> {
 		let mut defs = BTreeMap::new();
-		let base_ty = property.eval_type(&mut defs)?;
+		let (base_ty, warnings) = collect_generation_issues(|| property.eval_type(&mut defs));
 
-		let base = match base_ty {
+		let base = match base_ty? {
 			Type::Definition(Definition::Variant(var_def)) => var_def.source_definition(),
 			Type::Definition(base) => base,
 			Type::Ref(JRef { name, .. }) => defs
@@ -32,6 +200,9 @@ impl RootSchema {
 		Ok(RootSchema {
 			defs,
 			base,
+			options: SchemaOptions::default(),
+			warnings,
+			name_map: None,
 		})
 	}
 
@@ -47,6 +218,122 @@ impl RootSchema {
 		self.base.instantiate(value, &self.defs)
 	}
 
+	/// The reverse of [`Self::instantiate()`]: converts a Godot value of this schema's type into JSON that is valid according to the schema.
+	pub fn json_from_instance(&self, instance: &Variant) -> Result<Value> {
+		let value = self.options.scope(|| self.base.json_from_variant(instance, &self.defs))?;
+
+		self.to_json_names(self.wrap_value(value))
+	}
+
+	/// The property a non-class root is wrapped in, see [`SchemaOptions::wrapper_key`].
+	///
+	/// `None` for class and object roots, or if [`SchemaOptions::unwrapped_root`] is set.
+	pub fn wrapper_key(&self) -> Option<&str> {
+		match &self.base {
+			| Definition::Class(_)
+			| Definition::Object(_) => None,
+			_ if self.options.unwrapped_root => None,
+			_ => Some(self.options.wrapper_key.as_deref().unwrap_or(DEFAULT_WRAPPER_KEY)),
+		}
+	}
+
+	/// Converts `value`, of the root's type, into a document: non-class types are wrapped in an object with a single property,
+	/// see [`Self::wrapper_key()`].
+	pub fn wrap_value(&self, value: Value) -> Value {
+		match self.wrapper_key() {
+			Some(key) => Value::Object(Map::from_iter([(key.to_owned(), value)])),
+			None => value,
+		}
+	}
+
+	/// The reverse of [`Self::wrap_value()`]. Documents that are not wrapped are returned as is.
+	pub fn unwrap_document<'a>(&self, document: &'a Value) -> &'a Value {
+		if let Some(key) = self.wrapper_key()
+			&& let Value::Object(properties) = document
+			&& properties.len() == 1
+			&& let Some(inner) = properties.get(key) {
+			inner
+		} else {
+			document
+		}
+	}
+
+	/// Renames the properties of `document` to their names in JSON, see [`SchemaOptions::property_naming`] and [`JClass::aliases`].
+	/// `document` may use either name, e.g. it was written with the Godot names by [`ExampleGenerator`].
+	pub fn to_json_names(&self, document: Value) -> Result<Value> {
+		if !self.renames_properties() {
+			return Ok(document);
+		}
+
+		Ok(to_json_names(&document, &*self.name_map()?))
+	}
+
+	/// Same as [`Self::to_json_names()`], borrowing `document` if no property is renamed.
+	pub fn json_names_of<'a>(&self, document: &'a Value) -> Result<Cow<'a, Value>> {
+		if !self.renames_properties() {
+			return Ok(Cow::Borrowed(document));
+		}
+
+		Ok(Cow::Owned(to_json_names(document, &*self.name_map()?)))
+	}
+
+	/// The reverse of [`Self::to_json_names()`], for documents that are about to be instantiated.
+	pub fn to_godot_names<'a>(&self, document: &'a Value) -> Result<Cow<'a, Value>> {
+		if !self.renames_properties() {
+			return Ok(Cow::Borrowed(document));
+		}
+
+		Ok(Cow::Owned(to_godot_names(document, &*self.name_map()?)))
+	}
+
+	/// Builds [`Self::name_map`] from the current definitions and options, must be called again after changing either.
+	pub fn rebuild_name_map(&mut self) -> Result<()> {
+		self.name_map = None;
+
+		if self.renames_properties() {
+			self.name_map = Some(Arc::new(self.to_annotated_names()?));
+		}
+
+		Ok(())
+	}
+
+	/// [`Self::name_map`], or the same built on the fly if it was not (e.g. the schema is not owned by a [`GodotSchema`]).
+	fn name_map(&self) -> Result<Cow<'_, Value>> {
+		match &self.name_map {
+			Some(map) => Ok(Cow::Borrowed(map.as_ref())),
+			None => Ok(Cow::Owned(self.to_annotated_names()?)),
+		}
+	}
+
+	/// Whether any property has a different name in JSON than in Godot.
+	pub fn renames_properties(&self) -> bool {
+		!self.options.property_naming.is_as_is()
+			|| self.defs
+				.values()
+				.chain([&self.base])
+				.any(|def| matches!(def, Definition::Class(class) if !class.aliases.is_empty()))
+	}
+
+	/// Serializes this schema ignoring [`SchemaOptions::property_naming`], properties are named as in Godot or by their alias.
+	pub fn to_plain_json(&self) -> serde_json::Result<Value> {
+		self.options.scope(|| self.serialize_annotated(serde_json::value::Serializer))
+	}
+
+	/// Serializes this schema with its properties named as in JSON, each renamed (or aliased) one annotated with its Godot name
+	/// under [`GODOT_PROPERTY_KEY`], to translate documents between both names.
+	///
+	/// Unlike the schema's JSON, the annotations are kept regardless of [`SchemaOptions::round_trip_annotations`].
+	pub fn to_annotated_names(&self) -> Result<Value> {
+		let options = SchemaOptions {
+			round_trip_annotations: true,
+			..self.options.clone()
+		};
+
+		let mut schema = options.scope(|| self.serialize_annotated(serde_json::value::Serializer))?;
+		self.options.property_naming.rename_schema(&mut schema)?;
+		Ok(schema)
+	}
+
 	pub fn to_json_compact(&self) -> serde_json::Result<String> {
 		serde_json::to_string(self)
 	}
@@ -54,17 +341,133 @@ impl RootSchema {
 	pub fn to_json_pretty(&self) -> serde_json::Result<String> {
 		serde_json::to_string_pretty(self)
 	}
+
+	/// Serializes this schema, optionally without `description`s and vendor keys (`x-godot-*`),
+	/// to produce the smallest schema for token-sensitive contexts.
+	///
+	/// Schemas without vendor keys can no longer be loaded back into the same classes and enums.
+	pub fn to_json_ex(&self, pretty: bool, include_descriptions: bool, include_vendor_keys: bool) -> serde_json::Result<String> {
+		let mut value = serde_json::to_value(self)?;
+
+		if !include_descriptions || !include_vendor_keys {
+			strip_keywords(&mut value, include_descriptions, include_vendor_keys);
+		}
+
+		if pretty {
+			serde_json::to_string_pretty(&value)
+		} else {
+			serde_json::to_string(&value)
+		}
+	}
+}
+
+impl RootSchema {
+	/// Serializes this schema for the structured outputs and tool parameters of LLM providers:
+	/// without `$schema` and vendor keys (`x-*`), which strict modes (e.g. OpenAI's) reject.
+	///
+	/// Providers cannot resolve external references (see [`SchemaOptions::external_refs`]),
+	/// the definitions they point to are bundled in `$defs` instead. Strict modes do not support `allOf` nor `unevaluatedProperties`
+	/// either, classes emit their inherited properties themselves (see [`SchemaOptions::class_inheritance`]).
+	///
+	/// Every provider-facing output is built from this, e.g. [`GodotSchema::open_ai_response_format()`].
+	pub fn to_provider_value(&self) -> serde_json::Result<Value> {
+		let inherits = self.defs
+			.values()
+			.chain([&self.base])
+			.any(|def| matches!(def, Definition::Class(JClass { base: Some(_), .. })));
+
+		let mut value = if self.options.external_refs.is_empty() && !inherits {
+			serde_json::to_value(self)?
+		} else {
+			let mut provider = self.clone();
+			provider.options.external_refs.clear();
+			provider.flatten_inheritance();
+			provider.prune_defs();
+			serde_json::to_value(&provider)?
+		};
+
+		strip_keywords(&mut value, true, false);
+
+		if let Value::Object(keywords) = &mut value {
+			keywords.remove("$schema");
+		}
+
+		Ok(value)
+	}
+}
+
+fn strip_keywords(schema: &mut Value, descriptions: bool, vendor_keys: bool) {
+	match schema {
+		Value::Object(keywords) => {
+			keywords.retain(|key, _| (descriptions || key != "description") && (vendor_keys || !key.starts_with("x-")));
+
+			for (key, value) in keywords.iter_mut() {
+				match key.as_str() {
+					// Maps of names to schemas, the names are not keywords.
+					"properties" | "patternProperties" | "dependentSchemas" | "$defs" => {
+						if let Value::Object(schemas) = value {
+							for schema in schemas.values_mut() {
+								strip_keywords(schema, descriptions, vendor_keys);
+							}
+						}
+					}
+					// Instance values, not schemas.
+					"const" | "default" | "enum" | "examples" | "required" | "dependentRequired" => {}
+					_ => strip_keywords(value, descriptions, vendor_keys),
+				}
+			}
+		}
+		Value::Array(schemas) => {
+			for schema in schemas {
+				strip_keywords(schema, descriptions, vendor_keys);
+			}
+		}
+		_ => {}
+	}
 }
 
 impl Serialize for RootSchema {
 	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		if self.options.property_naming.is_as_is() {
+			return self.options.scope(|| self.serialize_annotated(serializer));
+		}
+
+		use serde::ser::Error;
+
+		let mut schema = self.to_plain_json().map_err(S::Error::custom)?;
+
+		self.options.property_naming
+			.rename_schema(&mut schema)
+			.map_err(S::Error::custom)?;
+
+		// The Godot names are only needed to translate documents, see `Self::to_annotated_names()`.
+		if !self.options.round_trip_annotations {
+			strip_property_annotations(&mut schema);
+		}
+
+		schema.serialize(serializer)
+	}
+}
+
+impl RootSchema {
+	fn serialize_annotated<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		JClass::scope_open_classes(&self.defs, || self.serialize_keywords(serializer))
+	}
+
+	fn serialize_keywords<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
 		let mut map = serializer.serialize_map(None)?;
 
 		if let Some(description) = self.base.description() {
 			map.serialize_entry("description", description)?;
 		}
 
-		map.serialize_entry("$schema", "https://json-schema.org/draft/2020-12/schema")?;
+		if let Some(id) = &self.options.id {
+			map.serialize_entry("$id", id)?;
+		}
+
+		if !self.options.omit_meta_schema {
+			map.serialize_entry("$schema", "https://json-schema.org/draft/2020-12/schema")?;
+		}
 
 		let var_defs = {
 			let mut vec = Vec::new();
@@ -86,21 +489,64 @@ impl Serialize for RootSchema {
 		};
 
 		map.serialize_entry("$defs", &all_defs)?;
+
+		if self.options.round_trip_annotations {
+			self.serialize_encodings(&mut map)?;
+		}
 		
 		match &self.base {
 			Definition::Class(class) => class.serialize_fields(&mut map)?,
 			Definition::Object(obj) => obj.serialize_fields(&mut map)?,
-			not_class => {
-				let class = Builder::object()
-					.property("value", not_class.clone())
-					.done();
-				
-				class.serialize_fields(&mut map)?;
-			}
+			not_class => match self.wrapper_key() {
+				Some(key) => {
+					let class = Builder::object()
+						.property(key, not_class.clone())
+						.done();
+
+					if self.options.round_trip_annotations {
+						map.serialize_entry(GODOT_WRAPPER_KEY, key)?;
+					}
+
+					class.serialize_fields(&mut map)?;
+				}
+				None => not_class.serialize_fields(&mut map)?,
+			},
 		}
 		
 		map.end()
 	}
+
+	fn serialize_encodings<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
+		if let Some(suffix) = &self.options.confidence_suffix {
+			map.serialize_entry(GODOT_CONFIDENCE_SUFFIX_KEY, suffix)?;
+		}
+
+		if self.options.variant_encoding == VariantEncoding::Tagged {
+			map.serialize_entry(GODOT_VARIANT_ENCODING_KEY, "tagged")?;
+		}
+
+		if self.options.integer_enums {
+			map.serialize_entry(GODOT_ENUM_ENCODING_KEY, "integer")?;
+		}
+
+		if self.options.representation.compact_math {
+			map.serialize_entry(GODOT_MATH_ENCODING_KEY, "compact")?;
+		}
+
+		if self.options.representation.hex_colors {
+			map.serialize_entry(GODOT_COLOR_ENCODING_KEY, "hex")?;
+		}
+
+		if self.options.representation.friendly_transforms {
+			map.serialize_entry(GODOT_TRANSFORM_ENCODING_KEY, "components")?;
+		}
+
+		if self.options.representation.euler_quaternions {
+			map.serialize_entry(GODOT_QUATERNION_ENCODING_KEY, "euler")?;
+		}
+
+		Ok(())
+	}
 }
 
 struct AllDefs<'a> {
@@ -113,7 +559,9 @@ impl Serialize for AllDefs<'_> {
 		let mut map = serializer.serialize_map(None)?;
 
 		for (name, def) in self.base_defs {
-			map.serialize_entry(name, def)?;
+			if JRef::external_uri(name).is_none() {
+				map.serialize_entry(name, def)?;
+			}
 		}
 
 		for var_def in &self.var_defs {
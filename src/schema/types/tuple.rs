@@ -9,7 +9,10 @@ pub struct JTuple {
 impl SerializeFields for JTuple {
 	fn serialize_fields<M: SerializeMap>(&self, map: &mut M) -> Result<(), M::Error> {
 		map.serialize_entry("type", "array")?;
-		map.serialize_entry("prefixItems ", &self.items)
+		map.serialize_entry("prefixItems", &self.items)?;
+		map.serialize_entry("items", &false)?;
+		map.serialize_entry("minItems", &self.items.len())?;
+		map.serialize_entry("maxItems", &self.items.len())
 	}
 }
 
@@ -0,0 +1,99 @@
+use super::*;
+
+/// The previous values of the properties assigned by [`GodotSchema::apply_to()`], allowing the change to be reverted.
+///
+/// ```gdscript
+/// var record = schema.apply_to(npc, llm_output, true)
+/// if not player_accepted:
+/// 	record.revert()
+/// ```
+#[derive(GodotClass, Default)]
+#[class(no_init, base = RefCounted)]
+pub struct UndoRecord {
+	pub changes: Vec<PropertyChange>,
+}
+
+#[derive(Clone, Debug)]
+pub struct PropertyChange {
+	pub target: Gd<Object>,
+	pub property: StringName,
+	pub before: Variant,
+	pub after: Variant,
+}
+
+#[godot_api]
+impl UndoRecord {
+	/// Assigns the recorded previous values back, in reverse order. Targets that were freed are skipped.
+	///
+	/// # Returns
+	/// The amount of properties reverted.
+	#[func]
+	pub fn revert(&self) -> i64 {
+		self.assign_all(self.changes.iter().rev().map(|change| (change, &change.before)))
+	}
+
+	/// Assigns the recorded new values again, undoing [`Self::revert()`].
+	///
+	/// # Returns
+	/// The amount of properties re-applied.
+	#[func]
+	pub fn reapply(&self) -> i64 {
+		self.assign_all(self.changes.iter().map(|change| (change, &change.after)))
+	}
+
+	/// Returns one `Dictionary` per assigned property, with the keys "target", "property", "before" and "after".
+	#[func]
+	pub fn get_changes(&self) -> Array<Dictionary> {
+		self.changes
+			.iter()
+			.map(|change| {
+				let mut dict = Dictionary::new();
+				dict.set("target", change.target.clone());
+				dict.set("property", change.property.clone());
+				dict.set("before", change.before.clone());
+				dict.set("after", change.after.clone());
+				dict
+			})
+			.collect()
+	}
+
+	/// Registers the change as an already performed action of `undo_redo`,
+	/// so that it can be undone with the rest of the editor's (or game's) history.
+	///
+	/// # Input
+	/// - `undo_redo`: Either an `UndoRedo` or an `EditorUndoRedoManager` (e.g. `EditorPlugin.get_undo_redo()`).
+	/// - `action_name`: The name displayed in the history.
+	#[func]
+	pub fn add_to_undo_redo(&self, mut undo_redo: Gd<Object>, action_name: String) {
+		undo_redo.call("create_action", &[action_name.to_variant()]);
+
+		for change in &self.changes {
+			let target = change.target.to_variant();
+			let property = change.property.to_variant();
+
+			undo_redo.call("add_do_property", &[target.clone(), property.clone(), change.after.clone()]);
+			undo_redo.call("add_undo_property", &[target, property, change.before.clone()]);
+		}
+
+		// The properties were already assigned, don't assign them again.
+		undo_redo.call("commit_action", &[false.to_variant()]);
+	}
+}
+
+impl UndoRecord {
+	fn assign_all<'a>(&self, values: impl Iterator<Item = (&'a PropertyChange, &'a Variant)>) -> i64 {
+		let mut count = 0;
+
+		for (change, value) in values {
+			if !change.target.is_instance_valid() {
+				continue;
+			}
+
+			let mut target = change.target.clone();
+			assign_property(&mut target, &change.property.to_string(), value.clone());
+			count += 1;
+		}
+
+		count
+	}
+}
@@ -0,0 +1,235 @@
+use super::*;
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static NEXT_REGISTRY_ID: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+	// `Callable` is not thread-safe, validators are only called on the thread that registered them.
+	static CALLABLES: RefCell<HashMap<(u64, CallableKind, String), Callable>> = RefCell::new(HashMap::new());
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CallableKind {
+	/// Called with the string, for schemas with `"format": name`.
+	Format,
+	/// Called with the value and the keyword's argument, for schemas with keyword `name`.
+	Keyword,
+}
+
+impl CallableKind {
+	fn noun(self) -> &'static str {
+		match self {
+			CallableKind::Format => "format",
+			CallableKind::Keyword => "keyword",
+		}
+	}
+}
+
+/// The custom formats and keywords of a [`SchemaLibrary`], checked by the `Jsonschema` validators of the schemas it is given to,
+/// see [`ValidatorOptions::callables`].
+///
+/// Only the names are stored here, so the registry can be sent to the threads compiling validators.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CallableRegistry {
+	/// Keys this registry's callables, clones share them.
+	id: u64,
+	formats: BTreeSet<String>,
+	keywords: BTreeSet<String>,
+}
+
+impl Default for CallableRegistry {
+	fn default() -> Self {
+		Self {
+			id: NEXT_REGISTRY_ID.fetch_add(1, Ordering::Relaxed),
+			formats: BTreeSet::new(),
+			keywords: BTreeSet::new(),
+		}
+	}
+}
+
+impl CallableRegistry {
+	/// Makes validators compiled afterward check the values matching `name` (see [`CallableKind`]) by calling `validator`,
+	/// which returns whether the value is valid. Replaces the previous validator of `name`, if any.
+	pub fn register(&mut self, kind: CallableKind, name: &str, validator: Callable) -> Result<()> {
+		if name.is_empty() {
+			bail!("Expected the {}'s name to not be empty.", kind.noun());
+		}
+
+		self.names_mut(kind).insert(name.to_owned());
+		CALLABLES.with_borrow_mut(|callables| callables.insert((self.id, kind, name.to_owned()), validator));
+		Ok(())
+	}
+
+	/// Stops checking `name` in validators compiled afterward.
+	///
+	/// # Returns
+	/// Whether `name` was registered.
+	pub fn unregister(&mut self, kind: CallableKind, name: &str) -> bool {
+		CALLABLES.with_borrow_mut(|callables| callables.remove(&(self.id, kind, name.to_owned())));
+		self.names_mut(kind).remove(name)
+	}
+
+	pub fn names(&self, kind: CallableKind) -> &BTreeSet<String> {
+		match kind {
+			CallableKind::Format => &self.formats,
+			CallableKind::Keyword => &self.keywords,
+		}
+	}
+
+	fn names_mut(&mut self, kind: CallableKind) -> &mut BTreeSet<String> {
+		match kind {
+			CallableKind::Format => &mut self.formats,
+			CallableKind::Keyword => &mut self.keywords,
+		}
+	}
+
+	/// Drops the callables registered on this thread, validators compiled with the registry reject the values they check afterward.
+	pub fn clear(&mut self) {
+		CALLABLES.with_borrow_mut(|callables| callables.retain(|(id, ..), _| *id != self.id));
+		self.formats.clear();
+		self.keywords.clear();
+	}
+
+	/// Calls the validator registered as `name` with `args`.
+	///
+	/// # Returns
+	/// What the validator returned, `false` if it cannot be called: it was registered on another thread, its object was freed,
+	/// or it does not return a `bool`.
+	#[cfg(feature = "jsonschema")]
+	fn call(&self, kind: CallableKind, name: &str, args: &VariantArray) -> bool {
+		let validator = CALLABLES.with_borrow(|callables| callables.get(&(self.id, kind, name.to_owned())).cloned());
+
+		let result = match validator {
+			None => Err("it was not registered on this thread".to_owned()),
+			Some(validator) if !validator.is_valid() => Err("its object was freed".to_owned()),
+			Some(validator) => validator
+				.callv(args)
+				.try_to::<bool>()
+				.map_err(|err| format!("it did not return a bool: {err:?}")),
+		};
+
+		result.unwrap_or_else(|err| {
+			log(LogLevel::Warn, || format!("Rejected a value checked by {} \"{name}\", since {err}.", kind.noun()));
+			false
+		})
+	}
+
+	/// Adds the registered formats and keywords to `options`.
+	///
+	/// Custom formats are always checked, standard ones (e.g. `date-time`) only if `assert_standard`.
+	#[cfg(feature = "jsonschema")]
+	pub fn with_callables(&self, mut options: jsonschema::ValidationOptions, assert_standard: bool) -> jsonschema::ValidationOptions {
+		for name in &self.keywords {
+			let registry = self.clone();
+			let keyword = name.clone();
+
+			options = options.with_keyword(name.clone(), move |_: &Map<String, Value>, argument: &Value, location: jsonschema::paths::Location| {
+				Ok(Box::new(CustomKeyword {
+					registry: registry.clone(),
+					name: keyword.clone(),
+					argument: argument.clone(),
+					location,
+				}) as Box<dyn jsonschema::Keyword>)
+			});
+		}
+
+		if self.formats.is_empty() {
+			return options;
+		}
+
+		if assert_standard {
+			for name in &self.formats {
+				let (registry, format) = (self.clone(), name.clone());
+				options = options.with_format(name.clone(), move |value: &str| registry.call(CallableKind::Format, &format, &varray![value]));
+			}
+
+			return options;
+		}
+
+		// Replaces the `format` keyword, so only the custom formats are asserted.
+		let registry = self.clone();
+
+		options.with_keyword("format", move |_: &Map<String, Value>, argument: &Value, location: jsonschema::paths::Location| {
+			let format = argument
+				.as_str()
+				.filter(|name| registry.formats.contains(*name))
+				.map(str::to_owned);
+
+			Ok(Box::new(CustomFormat { registry: registry.clone(), format, location }) as Box<dyn jsonschema::Keyword>)
+		})
+	}
+}
+
+/// A `format` keyword, as it appears in one place of a schema, when standard formats are annotations.
+#[cfg(feature = "jsonschema")]
+struct CustomFormat {
+	registry: CallableRegistry,
+	/// `None` if the format is not a custom one, it accepts everything.
+	format: Option<String>,
+	location: jsonschema::paths::Location,
+}
+
+#[cfg(feature = "jsonschema")]
+impl jsonschema::Keyword for CustomFormat {
+	fn validate<'i>(
+		&self,
+		instance: &'i Value,
+		location: &jsonschema::paths::LazyLocation,
+	) -> std::result::Result<(), jsonschema::ValidationError<'i>> {
+		if self.is_valid(instance) {
+			return Ok(());
+		}
+
+		Err(jsonschema::ValidationError::custom(
+			self.location.clone(),
+			location.into(),
+			instance,
+			format!("{instance} is not a \"{}\"", self.format.as_deref().unwrap_or_default()),
+		))
+	}
+
+	fn is_valid(&self, instance: &Value) -> bool {
+		match (&self.format, instance) {
+			(Some(format), Value::String(value)) => self.registry.call(CallableKind::Format, format, &varray![value.as_str()]),
+			_ => true,
+		}
+	}
+}
+
+/// A custom keyword, as it appears in one place of a schema.
+#[cfg(feature = "jsonschema")]
+struct CustomKeyword {
+	registry: CallableRegistry,
+	name: String,
+	argument: Value,
+	location: jsonschema::paths::Location,
+}
+
+#[cfg(feature = "jsonschema")]
+impl jsonschema::Keyword for CustomKeyword {
+	fn validate<'i>(
+		&self,
+		instance: &'i Value,
+		location: &jsonschema::paths::LazyLocation,
+	) -> std::result::Result<(), jsonschema::ValidationError<'i>> {
+		if self.is_valid(instance) {
+			return Ok(());
+		}
+
+		Err(jsonschema::ValidationError::custom(
+			self.location.clone(),
+			location.into(),
+			instance,
+			format!("{instance} does not satisfy keyword \"{}\": {}", self.name, self.argument),
+		))
+	}
+
+	/// Values and arguments that cannot be converted to Godot are rejected.
+	fn is_valid(&self, instance: &Value) -> bool {
+		let (Ok(value), Ok(argument)) = (raw_variant_from_json(instance), raw_variant_from_json(&self.argument))
+		else { return false };
+
+		self.registry.call(CallableKind::Keyword, &self.name, &varray![value, argument])
+	}
+}
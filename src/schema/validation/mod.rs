@@ -0,0 +1,297 @@
+use super::*;
+
+pub use structural::*;
+pub use callables::*;
+
+pub mod structural;
+pub mod callables;
+
+/// Selects which validator a [`GodotSchema`] compiles to check JSON input before instantiating it.
+#[derive(GodotConvert, Var, Export, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[godot(via = i64)]
+pub enum ValidatorBackend {
+	/// Full JSON Schema (draft 2020-12) validation, powered by the `jsonschema` crate.
+	///
+	/// Requires the `jsonschema` feature (enabled by default).
+	#[cfg_attr(feature = "jsonschema", default)]
+	Jsonschema = 0,
+	/// A lightweight checker built into this crate, it walks the schema's definitions and only verifies
+	/// the structure of the input: types, required/additional properties, tuple lengths and enum variants.
+	#[cfg_attr(not(feature = "jsonschema"), default)]
+	Structural = 1,
+	/// No validation at all, the input is trusted to match the schema.
+	///
+	/// Instantiation still fails if the input cannot be converted into the schema's type.
+	Trust = 2,
+}
+
+impl ValidatorBackend {
+	pub fn build(self, schema: &RootSchema, json: &Value) -> Result<Box<dyn SchemaValidator>> {
+		Ok(match self {
+			#[cfg(feature = "jsonschema")]
+			ValidatorBackend::Jsonschema => Box::new(CompileOptions::of(schema).build_jsonschema(json)?),
+			#[cfg(not(feature = "jsonschema"))]
+			ValidatorBackend::Jsonschema => {
+				let _ = json;
+				bail!("The `Jsonschema` validator backend requires the `jsonschema` feature to be enabled.")
+			}
+			ValidatorBackend::Structural => Box::new(StructuralValidator::new(schema.clone())),
+			ValidatorBackend::Trust => Box::new(TrustValidator),
+		})
+	}
+}
+
+/// How the `Jsonschema` backend compiles a schema's validator, see [`SchemaOptions::validator`].
+///
+/// Parsing LLM output usually wants lenient validators, validating network input strict ones.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ValidatorOptions {
+	/// Whether strings not matching their standard `format` (e.g. `date-time`) are rejected (`true`),
+	/// or `format` is only an annotation (`false` or `None`, as in draft 2020-12).
+	///
+	/// Custom formats are checked either way, see [`Self::callables`].
+	pub format_assertion: Option<bool>,
+	/// Schemas with a longer `pattern` (or `patternProperties` regex), in characters, fail to compile,
+	/// e.g. to bound the cost of compiling schemas received at runtime.
+	///
+	/// The regex engine of the `jsonschema` version in use does not expose its own size limits, so the pattern's source is measured.
+	pub max_pattern_length: Option<usize>,
+	/// The custom formats and keywords of the library the schema belongs to, see [`SchemaLibrary::register_format()`]
+	/// and [`SchemaLibrary::register_keyword()`].
+	pub callables: Option<CallableRegistry>,
+}
+
+impl ValidatorOptions {
+	/// # Errors
+	/// If `json` (a schema) has a regex longer than [`Self::max_pattern_length`].
+	pub fn check_patterns(&self, json: &Value) -> Result<()> {
+		let Some(max_length) = self.max_pattern_length
+		else { return Ok(()) };
+
+		let Value::Object(keywords) = json
+		else { return Ok(()) };
+
+		let check = |pattern: &str| {
+			let length = pattern.chars().count();
+
+			if length > max_length {
+				bail!("Expected regexes to be at most {max_length} characters long.\nGot {length}: {pattern}");
+			}
+
+			Ok(())
+		};
+
+		for (key, value) in keywords {
+			match (key.as_str(), value) {
+				("pattern", Value::String(pattern)) => check(pattern)?,
+				("patternProperties", Value::Object(schemas)) => {
+					for (pattern, schema) in schemas {
+						check(pattern)?;
+						self.check_patterns(schema)?;
+					}
+				}
+				// Maps of names to schemas, the names are not keywords.
+				("properties" | "$defs" | "dependentSchemas", Value::Object(schemas)) => {
+					for schema in schemas.values() {
+						self.check_patterns(schema)?;
+					}
+				}
+				// Instance values, not schemas.
+				("const" | "default" | "enum" | "examples", _) => {}
+				(_, Value::Array(schemas)) => {
+					for schema in schemas {
+						self.check_patterns(schema)?;
+					}
+				}
+				(_, schema) => self.check_patterns(schema)?,
+			}
+		}
+
+		Ok(())
+	}
+}
+
+/// The options of a [`RootSchema`] its `Jsonschema` validator is compiled with, which can be sent to the threads
+/// compiling validators (see [`compile_in_background()`]).
+#[derive(Clone, Debug)]
+pub struct CompileOptions {
+	pub validator: ValidatorOptions,
+	/// See [`SchemaOptions::id`].
+	pub id: Option<String>,
+	/// See [`SchemaOptions::external_documents`].
+	pub external_documents: std::sync::Arc<BTreeMap<String, Value>>,
+}
+
+impl CompileOptions {
+	pub fn of(schema: &RootSchema) -> Self {
+		Self {
+			validator: schema.options.validator.clone(),
+			id: schema.options.id.clone(),
+			external_documents: schema.options.external_documents.clone(),
+		}
+	}
+
+	/// Compiles `json` (a schema) with [`jsonschema_options()`], resolving its external references to [`Self::external_documents`].
+	///
+	/// # Errors
+	/// If `json` is not a valid schema, or has a regex longer than [`ValidatorOptions::max_pattern_length`].
+	#[cfg(feature = "jsonschema")]
+	pub fn build_jsonschema(&self, json: &Value) -> Result<jsonschema::Validator> {
+		self.validator.check_patterns(json)?;
+		let options = jsonschema_options(&self.validator);
+
+		let validator = if self.external_documents.is_empty() {
+			options.build(json)?
+		} else {
+			options.with_retriever(DocumentRetriever::new(self)).build(json)?
+		};
+
+		Ok(validator)
+	}
+}
+
+/// The options `Jsonschema` validators are compiled with: draft 2020-12, `validator`, along with its
+/// [custom formats and keywords](CallableRegistry).
+#[cfg(feature = "jsonschema")]
+pub fn jsonschema_options(validator: &ValidatorOptions) -> jsonschema::ValidationOptions {
+	let mut options = jsonschema::options().with_draft(jsonschema::Draft::Draft202012);
+
+	if let Some(callables) = &validator.callables {
+		options = callables.with_callables(options, validator.format_assertion == Some(true));
+	}
+
+	match validator.format_assertion {
+		Some(assert) => options.should_validate_formats(assert),
+		None => options,
+	}
+}
+
+/// A single violation found by a [`SchemaValidator`].
+#[derive(Debug, Clone)]
+pub struct ValidationIssue {
+	/// JSON pointer to the offending value, e.g. "/facts/0".
+	pub instance_path: String,
+	/// The schema keyword that was violated, e.g. "type" or "required".
+	pub keyword: String,
+	pub message: String,
+}
+
+impl ValidationIssue {
+	/// A `Dictionary` with the keys "instance_path", "keyword" and "message".
+	pub fn to_dictionary(&self) -> Dictionary {
+		let mut dict = Dictionary::new();
+		dict.set("instance_path", self.instance_path.as_str());
+		dict.set("keyword", self.keyword.as_str());
+		dict.set("message", self.message.as_str());
+		dict
+	}
+}
+
+impl std::fmt::Display for ValidationIssue {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(&self.message)
+	}
+}
+
+pub trait SchemaValidator: Send + Sync {
+	/// Checks `value` against the schema, returning one issue per violation found.
+	fn validate(&self, value: &Value) -> std::result::Result<(), Vec<ValidationIssue>>;
+}
+
+#[cfg(feature = "jsonschema")]
+impl SchemaValidator for jsonschema::Validator {
+	fn validate(&self, value: &Value) -> std::result::Result<(), Vec<ValidationIssue>> {
+		jsonschema::Validator::validate(self, value)
+			.map_err(|errors| {
+				errors
+					.map(|err| {
+						let schema_path = err.schema_path.to_string();
+
+						ValidationIssue {
+							instance_path: err.instance_path.to_string(),
+							keyword: schema_path.rsplit('/').next().unwrap_or_default().to_owned(),
+							message: format!("{err:?}"),
+						}
+					})
+					.collect()
+			})
+	}
+}
+
+/// The base URI of schemas without an `$id`, the one the `jsonschema` crate resolves their references against.
+pub const DEFAULT_BASE_URI: &str = "json-schema:///";
+
+/// Resolves the external references of schemas linked by [`SchemaLibrary::link_shared_definitions()`] to the library's schemas.
+///
+/// Documents are keyed by their `$id`, which may be relative: both it and the requested URI are resolved against
+/// the referencing schema's base URI before being compared.
+#[cfg(feature = "jsonschema")]
+struct DocumentRetriever {
+	base: String,
+	documents: std::sync::Arc<BTreeMap<String, Value>>,
+}
+
+#[cfg(feature = "jsonschema")]
+impl DocumentRetriever {
+	fn new(options: &CompileOptions) -> Self {
+		Self {
+			base: resolve_uri(DEFAULT_BASE_URI, options.id.as_deref().unwrap_or_default()),
+			documents: options.external_documents.clone(),
+		}
+	}
+}
+
+#[cfg(feature = "jsonschema")]
+impl jsonschema::Retrieve for DocumentRetriever {
+	fn retrieve(&self, uri: &jsonschema::Uri<&str>) -> std::result::Result<Value, Box<dyn std::error::Error + Send + Sync>> {
+		let requested = resolve_uri(&self.base, uri.as_str());
+
+		self.documents
+			.iter()
+			.find(|(id, _)| resolve_uri(&self.base, id) == requested)
+			.map(|(_, document)| document.clone())
+			.ok_or_else(|| format!("Expected schema \"{requested}\" to be in the library.").into())
+	}
+}
+
+/// Resolves `reference` (absolute or relative, e.g. `person.json`) against `base`, ignoring fragments.
+///
+/// Dot segments (`./`, `../`) are kept as is, `$id`s generated by this crate do not have them.
+pub fn resolve_uri(base: &str, reference: &str) -> String {
+	let base = base.split('#').next().unwrap_or_default();
+	let reference = reference.split('#').next().unwrap_or_default();
+
+	if reference.is_empty() {
+		return base.to_owned();
+	}
+
+	let has_scheme = reference
+		.split_once(':')
+		.is_some_and(|(scheme, _)| !scheme.is_empty() && scheme.chars().all(|ch| ch.is_ascii_alphanumeric() || "+-.".contains(ch)));
+
+	if has_scheme {
+		return reference.to_owned();
+	}
+
+	let (scheme, rest) = base.split_once("://").unwrap_or(("", base));
+
+	if let Some(network_path) = reference.strip_prefix("//") {
+		format!("{scheme}://{network_path}")
+	} else if reference.starts_with('/') {
+		let authority = rest.split('/').next().unwrap_or_default();
+		format!("{scheme}://{authority}{reference}")
+	} else {
+		match base.rfind('/') {
+			Some(idx) => format!("{}{reference}", &base[..=idx]),
+			None => reference.to_owned(),
+		}
+	}
+}
+
+pub struct TrustValidator;
+
+impl SchemaValidator for TrustValidator {
+	fn validate(&self, _: &Value) -> std::result::Result<(), Vec<ValidationIssue>> {
+		Ok(())
+	}
+}
@@ -0,0 +1,232 @@
+use super::*;
+
+/// Verifies the shape of JSON input by walking a [`RootSchema`]'s definitions, without compiling a full JSON Schema validator.
+pub struct StructuralValidator {
+	schema: RootSchema,
+}
+
+impl StructuralValidator {
+	pub const fn new(schema: RootSchema) -> Self {
+		Self { schema }
+	}
+}
+
+impl SchemaValidator for StructuralValidator {
+	fn validate(&self, value: &Value) -> std::result::Result<(), Vec<ValidationIssue>> {
+		let mut errors = Vec::new();
+		let defs = &self.schema.defs;
+
+		// Definitions have the original property names.
+		let value = match self.schema.to_godot_names(value) {
+			Ok(value) => value,
+			Err(err) => return Err(vec![issue("", "properties", format!("{err:#}"))]),
+		};
+		let value = value.as_ref();
+
+		// Built-in types are checked against the representation the schema declares.
+		self.schema.options.scope(|| match self.schema.wrapper_key() {
+			None => check_definition(&self.schema.base, value, defs, "", &mut errors),
+			// Non-class types are wrapped in an object with a single property.
+			Some(key) => match value {
+				Value::Object(properties) if properties.len() == 1 && properties.contains_key(key) => {
+					check_definition(&self.schema.base, &properties[key], defs, &format!("/{key}"), &mut errors);
+				}
+				_ => errors.push(issue("", "type", format!("expected an object with a single property \"{key}\", got: {value}"))),
+			},
+		});
+
+		if errors.is_empty() {
+			Ok(())
+		} else {
+			Err(errors)
+		}
+	}
+}
+
+/// Whether `value` has the structure of `ty`.
+pub fn matches_type(ty: &Type, value: &Value, defs: &BTreeMap<String, Definition>) -> bool {
+	let mut errors = Vec::new();
+	check_type(ty, value, defs, "", &mut errors);
+	errors.is_empty()
+}
+
+/// Whether `a` and `b` are the same JSON value, comparing numbers by value (`1` equals `1.0`) as JSON Schema does.
+pub fn json_equal(a: &Value, b: &Value) -> bool {
+	match (a, b) {
+		// Integers are compared exactly, floats could not represent the large ones.
+		(Value::Number(a), Value::Number(b)) if a.is_f64() || b.is_f64() => a.as_f64() == b.as_f64(),
+		(Value::Array(a), Value::Array(b)) => a.len() == b.len() && a.iter().zip(b).all(|(a, b)| json_equal(a, b)),
+		(Value::Object(a), Value::Object(b)) => {
+			a.len() == b.len() && a.iter().all(|(key, a)| b.get(key).is_some_and(|b| json_equal(a, b)))
+		}
+		_ => a == b,
+	}
+}
+
+fn check_type(ty: &Type, value: &Value, defs: &BTreeMap<String, Definition>, path: &str, errors: &mut Vec<ValidationIssue>) {
+	match ty.resolve(defs) {
+		// Only extensible classes are left open in `$defs`, references to the others are closed even inside conditions.
+		Ok(def @ (Definition::Object(_) | Definition::Class(JClass { extensible: false, .. }))) if matches!(ty, Type::Ref(_)) => {
+			JConditional::scope_outside_condition(|| check_definition(def, value, defs, path, errors))
+		}
+		Ok(def) => check_definition(def, value, defs, path, errors),
+		Err(err) => errors.push(issue(path, "$ref", format!("{err}"))),
+	}
+}
+
+fn check_properties(
+	properties: &IndexMap<String, Type>,
+	optional: &BTreeSet<String>,
+	input: &Map<String, Value>,
+	defs: &BTreeMap<String, Definition>,
+	path: &str,
+	errors: &mut Vec<ValidationIssue>,
+) {
+	for (name, ty) in properties {
+		match input.get(name) {
+			Some(value) => check_type(ty, value, defs, &format!("{path}/{}", escape_pointer_token(name)), errors),
+			None if optional.contains(name) => {}
+			None => errors.push(issue(path, "required", format!("missing required property \"{name}\""))),
+		}
+	}
+
+	// Conditions only test some of the properties, see `JConditional::in_condition()`.
+	if JConditional::in_condition() {
+		return;
+	}
+
+	for name in input.keys() {
+		if !properties.contains_key(name) {
+			errors.push(issue(path, "additionalProperties", format!("additional property \"{name}\" is not allowed")));
+		}
+	}
+}
+
+fn check_definition(def: &Definition, value: &Value, defs: &BTreeMap<String, Definition>, path: &str, errors: &mut Vec<ValidationIssue>) {
+	if let Some(const_value) = def.const_value()
+		&& !json_equal(value, const_value) {
+		errors.push(issue(path, "const", format!("expected {const_value}, got: {value}")));
+		return;
+	}
+
+	match (def, value) {
+		(Definition::Null(_), Value::Null) => {}
+		(Definition::Boolean(_), Value::Bool(_)) => {}
+		// Whole floats (e.g. `2.0`) are integers in JSON Schema.
+		(Definition::Integer(_), Value::Number(number))
+			if number.is_i64() || number.is_u64() || number.as_f64().is_some_and(|float| float.fract() == 0.0) => {}
+		(Definition::Number(_), Value::Number(_)) => {}
+		(Definition::String(_), Value::String(_)) => {}
+		(Definition::Object(object), Value::Object(input)) => {
+			// An object without properties is a Dictionary, which accepts any key/value pairs (of `values_ty`, if set).
+			if !object.properties.is_empty() {
+				check_properties(&object.properties, &object.optional, input, defs, path, errors);
+			} else if let Some(values_ty) = &object.values_ty {
+				for (key, value) in input {
+					check_type(values_ty, value, defs, &format!("{path}/{}", escape_pointer_token(key)), errors);
+				}
+			}
+
+			for (name, dependencies) in &object.dependent_required {
+				if input.contains_key(name) {
+					for dependency in dependencies.iter().filter(|dependency| !input.contains_key(*dependency)) {
+						errors.push(issue(path, "dependentRequired", format!("property \"{name}\" requires property \"{dependency}\"")));
+					}
+				}
+			}
+		}
+		(Definition::Class(class), Value::Object(input)) => {
+			check_properties(&class.properties, &class.optional, input, defs, path, errors);
+		}
+		(Definition::Array(array @ JArray { items_ty, .. }), Value::Array(vec)) => {
+			if let Err(err) = array.check_length(vec.len()) {
+				let keyword = if array.min_items.is_some_and(|min_items| vec.len() < min_items) { "minItems" } else { "maxItems" };
+				errors.push(issue(path, keyword, err.to_string().replace('\n', " ")));
+			}
+
+			if array.unique_items && vec.iter().enumerate().any(|(idx, item)| vec[..idx].iter().any(|other| json_equal(other, item))) {
+				errors.push(issue(path, "uniqueItems", "expected array elements to be unique".to_owned()));
+			}
+
+			if let Some(contains) = &array.contains
+				&& !vec.iter().any(|item| matches_type(contains, item, defs)) {
+				errors.push(issue(path, "contains", "expected at least one element to match `contains`".to_owned()));
+			}
+
+			if let Some(ty) = items_ty {
+				for (idx, item) in vec.iter().enumerate() {
+					check_type(ty, item, defs, &format!("{path}/{idx}"), errors);
+				}
+			}
+		}
+		(Definition::Tuple(JTuple { items, .. }), Value::Array(vec)) => {
+			if items.len() != vec.len() {
+				errors.push(issue(path, "items", format!("expected {} elements, got: {}", items.len(), vec.len())));
+			} else {
+				for (idx, (ty, item)) in items.iter().zip(vec).enumerate() {
+					check_type(ty, item, defs, &format!("{path}/{idx}"), errors);
+				}
+			}
+		}
+		(Definition::Enum(j_enum), Value::String(_) | Value::Number(_)) => {
+			if j_enum.variant_value(value).is_none() {
+				errors.push(issue(path, "enum", format!("expected one of \"{}\", got: {value}", j_enum.variants.keys().join(", "))));
+			}
+		}
+		(Definition::Variant(VariantDefinition::Color), Value::String(html)) if VariantRepresentation::current().hex_colors => {
+			if Color::from_html(html.as_str()).is_none() {
+				errors.push(issue(path, "pattern", format!("expected a hex color (e.g. \"#ff8000\"), got: {value}")));
+			}
+		}
+		(Definition::Variant(var_def), value) => {
+			check_definition(&var_def.source_definition(), value, defs, path, errors);
+		}
+		(Definition::Any(_), _) => {}
+		(Definition::Conditional(conditional), value) => {
+			if let Some(ty) = conditional.branch(value, defs) {
+				check_type(ty, value, defs, path, errors);
+			}
+		}
+		(Definition::OneOf(one_of), value) => {
+			let matches = one_of.options.iter().filter(|ty| matches_type(ty, value, defs)).count();
+
+			if matches != 1 {
+				errors.push(issue(path, "oneOf", format!("expected exactly one of {} options to match, {matches} did: {value}", one_of.options.len())));
+			}
+		}
+		(Definition::ResourcePath(resource_path), Value::String(string)) => {
+			if let Err(err) = resource_path.check_path(string) {
+				errors.push(issue(path, "pattern", err.to_string().replace('\n', " ")));
+			}
+		}
+		(def, value) => {
+			let expected = match def {
+				Definition::Null(_) => "null",
+				Definition::Boolean(_) => "boolean",
+				Definition::Integer(_) => "integer",
+				Definition::Number(_) => "number",
+				Definition::String(_) => "string",
+				| Definition::Object(_)
+				| Definition::Class(_) => "object",
+				| Definition::Array(_)
+				| Definition::Tuple(_) => "array",
+				Definition::Enum(_) => "enum variant",
+				Definition::Variant(var_def) => var_def.name(),
+				Definition::Any(_) => "any value",
+				Definition::ResourcePath(_) => "resource path",
+				Definition::Conditional(_) => "conditional value",
+				Definition::OneOf(_) => "one of the options",
+			};
+
+			errors.push(issue(path, "type", format!("expected {expected}, got: {value}")));
+		}
+	}
+}
+
+fn issue(path: &str, keyword: &str, message: String) -> ValidationIssue {
+	ValidationIssue {
+		instance_path: path.to_owned(),
+		keyword: keyword.to_owned(),
+		message: format!("at \"{path}\": {message}"),
+	}
+}
@@ -0,0 +1,66 @@
+use super::*;
+
+/// How values of untyped contexts (`Dictionary`, untyped `Array`) are converted into JSON.
+///
+/// Parsing always accepts both forms.
+#[derive(GodotConvert, Var, Export, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[godot(via = i64)]
+pub enum VariantEncoding {
+	/// Values are converted into their JSON representation, e.g. a `Vector2` becomes `{"x": 1.0, "y": 2.0}`.
+	///
+	/// Types that share a representation cannot be told apart when parsed back,
+	/// the example above is parsed into a `Dictionary`.
+	#[default]
+	Plain = 0,
+	/// Values without a JSON equivalent are wrapped in an object naming their type,
+	/// e.g. `{"$type": "Vector2", "value": {"x": 1.0, "y": 2.0}}`, preserving the exact type through round-trips.
+	Tagged = 1,
+}
+
+/// The key naming the type of a tagged value.
+pub const TYPE_TAG: &str = "$type";
+
+impl VariantEncoding {
+	/// The encoding of the conversion running on this thread, [`VariantEncoding::Plain`] if none is, see [`SchemaOptions::scope()`].
+	pub fn current() -> Self {
+		SchemaOptions::read_current(|options| options.variant_encoding)
+	}
+}
+
+/// Wraps `value` (the JSON representation of `variant`) in a tagged object, if the current encoding is
+/// [`VariantEncoding::Tagged`] and `variant` has no JSON equivalent.
+pub fn tag_value(variant_type: VariantType, value: Value) -> Value {
+	if VariantEncoding::current() != VariantEncoding::Tagged {
+		return value;
+	}
+
+	let type_name = match variant_type {
+		VariantType::STRING_NAME => "StringName",
+		VariantType::NODE_PATH => "NodePath",
+		other => match VariantDefinition::try_from(other) {
+			Ok(var_def) => var_def.name(),
+			Err(()) => return value,
+		},
+	};
+
+	serde_json::json!({ TYPE_TAG: type_name, "value": value })
+}
+
+/// If `properties` is a tagged value (`{"$type": <type name>, "value": <value>}`), converts it into its type.
+///
+/// # Returns
+/// `None` if `properties` is not a tagged value, in which case it should be parsed as a plain object.
+pub fn untag_value(properties: &Map<String, Value>) -> Option<Result<Variant>> {
+	if properties.len() != 2 {
+		return None;
+	}
+
+	let type_name = properties.get(TYPE_TAG)?.as_str()?;
+	let value = properties.get("value")?;
+
+	match type_name {
+		"StringName" => Some(StringName::try_from_json(value).map(|name| name.to_variant())),
+		"NodePath" => Some(NodePath::try_from_json(value).map(|path| path.to_variant())),
+		_ => VariantDefinition::try_from_name(type_name).map(|var_def| var_def.var_from_json(value)),
+	}
+}
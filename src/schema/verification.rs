@@ -0,0 +1,203 @@
+use super::*;
+
+/// Compares the classes of `cached` against the same classes in `current` (usually `cached` regenerated from its sources).
+///
+/// # Returns
+/// One `Dictionary` per mismatched property, with the keys:
+/// - "class": The name of the class definition.
+/// - "property": The property's name.
+/// - "change": Either "added", "removed" or "type_changed".
+/// - "cached"/"current": The property's schema (as JSON) in each side, empty if the property is missing from that side.
+pub fn schema_mismatches(cached: &RootSchema, current: &RootSchema) -> Result<Vec<Dictionary>> {
+	let mut mismatches = Vec::new();
+
+	if let (Definition::Class(cached_class), Definition::Class(current_class)) = (&cached.base, &current.base) {
+		class_mismatches(cached_class, current_class, &mut mismatches)?;
+	}
+
+	for (name, cached_def) in &cached.defs {
+		if let Definition::Class(cached_class) = cached_def
+			&& let Some(Definition::Class(current_class)) = current.defs.get(name) {
+			class_mismatches(cached_class, current_class, &mut mismatches)?;
+		}
+	}
+
+	Ok(mismatches)
+}
+
+fn class_mismatches(cached: &JClass, current: &JClass, fill_me: &mut Vec<Dictionary>) -> Result<()> {
+	let class_name = cached.source.definition_name();
+	let names = cached.properties.keys().chain(current.properties.keys()).collect::<BTreeSet<_>>();
+
+	for name in names {
+		let cached_ty = cached.properties.get(name);
+		let current_ty = current.properties.get(name);
+
+		let change = match (cached_ty, current_ty) {
+			(Some(_), None) => "removed",
+			(None, Some(_)) => "added",
+			(Some(cached_ty), Some(current_ty)) if cached_ty.to_persisted()? != current_ty.to_persisted()? => "type_changed",
+			_ => continue,
+		};
+
+		let to_json = |ty: Option<&Type>| -> Result<String> {
+			Ok(ty.map(Type::to_json_compact).transpose()?.unwrap_or_default())
+		};
+
+		let mut dict = Dictionary::new();
+		dict.set("class", class_name.as_str());
+		dict.set("property", name.as_str());
+		dict.set("change", change);
+		dict.set("cached", to_json(cached_ty)?);
+		dict.set("current", to_json(current_ty)?);
+		fill_me.push(dict);
+	}
+
+	Ok(())
+}
+
+/// Lists the changes from `old` to `new` that can make data valid according to `old` invalid according to `new`,
+/// e.g. save files written before a class changed.
+///
+/// # Returns
+/// One `Dictionary` per breaking change, with the keys:
+/// - "class": The name of the class (or object) definition, empty for the root of non-class schemas.
+/// - "property": The property's (or enum variant's) name, empty if the change is not about a property.
+/// - "change": One of "removed", "type_changed", "required_added", "became_required", "variant_removed",
+///   "definition_removed", "root_changed" or "encoding_changed".
+/// - "message": A human-readable description of the change.
+///
+/// Description changes are not breaking.
+pub fn breaking_changes(old: &RootSchema, new: &RootSchema) -> Result<Vec<Dictionary>> {
+	let mut changes = Vec::new();
+
+	if old.options.variant_encoding != new.options.variant_encoding
+		|| old.options.representation != new.options.representation
+		|| old.options.integer_enums != new.options.integer_enums {
+		push_change(&mut changes, "", "", "encoding_changed", "The encoding of Godot types changed, e.g. compact math, tagged variants or integer enums.");
+	}
+
+	match (root_properties(&old.base), root_properties(&new.base)) {
+		(Some(old_root), Some(new_root)) => {
+			let class_name = match &new.base {
+				Definition::Class(class) => class.source.definition_name(),
+				_ => String::new(),
+			};
+
+			properties_breaking_changes(&class_name, old_root, new_root, &mut changes)?;
+		}
+		_ if type_differs(old.base.to_persisted()?, new.base.to_persisted()?) => {
+			push_change(&mut changes, "", "", "root_changed", "The root type of the schema changed.");
+		}
+		_ => {}
+	}
+
+	for (name, old_def) in &old.defs {
+		let Some(new_def) = new.defs.get(name)
+		else {
+			push_change(&mut changes, name, "", "definition_removed", &format!("Definition \"{name}\" was removed."));
+			continue;
+		};
+
+		match (old_def, new_def) {
+			(Definition::Enum(old_enum), Definition::Enum(new_enum)) => {
+				for variant in old_enum.variants.keys().filter(|variant| !new_enum.variants.contains_key(*variant)) {
+					push_change(&mut changes, name, variant, "variant_removed", &format!("Variant \"{variant}\" of enum \"{name}\" was removed."));
+				}
+			}
+			_ => match (root_properties(old_def), root_properties(new_def)) {
+				(Some(old_props), Some(new_props)) => properties_breaking_changes(name, old_props, new_props, &mut changes)?,
+				_ if type_differs(old_def.to_persisted()?, new_def.to_persisted()?) => {
+					push_change(&mut changes, name, "", "type_changed", &format!("Definition \"{name}\" changed type."));
+				}
+				_ => {}
+			},
+		}
+	}
+
+	Ok(changes)
+}
+
+type RootProperties<'a> = (&'a IndexMap<String, Type>, &'a BTreeSet<String>);
+
+fn root_properties(def: &Definition) -> Option<RootProperties<'_>> {
+	match def {
+		| Definition::Class(JClass { properties, optional, .. })
+		| Definition::Object(JObject { properties, optional, .. }) => Some((properties, optional)),
+		_ => None,
+	}
+}
+
+fn properties_breaking_changes(
+	class_name: &str,
+	(old_properties, old_optional): RootProperties,
+	(new_properties, new_optional): RootProperties,
+	fill_me: &mut Vec<Dictionary>,
+) -> Result<()> {
+	for (name, old_ty) in old_properties {
+		let Some(new_ty) = new_properties.get(name)
+		else {
+			push_change(fill_me, class_name, name, "removed", &format!("Property \"{name}\" was removed."));
+			continue;
+		};
+
+		if type_differs(old_ty.to_persisted()?, new_ty.to_persisted()?) {
+			push_change(fill_me, class_name, name, "type_changed", &format!("Property \"{name}\" changed type."));
+		} else if old_optional.contains(name) && !new_optional.contains(name) {
+			push_change(fill_me, class_name, name, "became_required", &format!("Property \"{name}\" is no longer optional."));
+		}
+	}
+
+	for name in new_properties.keys() {
+		if !old_properties.contains_key(name) && !new_optional.contains(name) {
+			push_change(fill_me, class_name, name, "required_added", &format!("Required property \"{name}\" was added."));
+		}
+	}
+
+	Ok(())
+}
+
+/// The keys of persisted definitions (see [`Definition::to_persisted()`]) that describe their type,
+/// as opposed to their constraints (e.g. `min_items`, `pattern` or `const`) and descriptions.
+const TYPE_KEYS: [&str; 13] = [
+	"kind", "name", "source", "items", "values", "properties", "options", "if", "then", "else", "variants", "base", "instance_scene",
+];
+
+/// Whether `old` and `new` (persisted) describe different types, constraints and descriptions are ignored.
+fn type_differs(old: Value, new: Value) -> bool {
+	type_keys(&old) != type_keys(&new)
+}
+
+/// The [`TYPE_KEYS`] of the persisted type `value`, and of the types nested in it.
+fn type_keys(value: &Value) -> Value {
+	let Value::Object(map) = value
+	else { return value.clone() };
+
+	map.iter()
+		.filter(|(key, _)| TYPE_KEYS.contains(&key.as_str()))
+		.map(|(key, value)| {
+			let value = match (key.as_str(), value) {
+				("properties", Value::Object(properties)) => properties
+					.iter()
+					.map(|(name, ty)| (name.clone(), type_keys(ty)))
+					.collect::<Map<_, _>>()
+					.into(),
+				("items" | "values" | "if" | "then" | "else" | "options", Value::Array(types)) => types.iter().map(type_keys).collect(),
+				("items" | "values" | "if" | "then" | "else", ty) => type_keys(ty),
+				(_, other) => other.clone(),
+			};
+
+			(key.clone(), value)
+		})
+		.collect::<Map<_, _>>()
+		.into()
+}
+
+fn push_change(fill_me: &mut Vec<Dictionary>, class_name: &str, property: &str, change: &str, message: &str) {
+	let mut dict = Dictionary::new();
+	dict.set("class", class_name);
+	dict.set("property", property);
+	dict.set("change", change);
+	dict.set("message", message);
+	fill_me.push(dict);
+}
@@ -0,0 +1,87 @@
+use super::*;
+use std::sync::mpsc::Receiver;
+
+/// The thread-safe part of a [`GodotSchema`], produced from its serialized JSON.
+pub struct CompiledSchema {
+	pub json_value: Value,
+	/// `None` for backends that are cheap enough to build lazily on the main thread.
+	pub validator: Option<Box<dyn SchemaValidator>>,
+}
+
+/// A schema to compile with [`compile_schema()`]: its serialized JSON, its backend and the options of its schema.
+pub type CompileJob = (String, ValidatorBackend, CompileOptions);
+
+/// Parses `json` and compiles its validator with `options`, this is the expensive part of creating a [`GodotSchema`].
+///
+/// Does not touch any Godot object, so it can run on any thread.
+pub fn compile_schema(json: &str, backend: ValidatorBackend, options: &CompileOptions) -> Result<CompiledSchema> {
+	let json_value = serde_json::from_str(json)?;
+
+	// Other backends are cheap to build, they are built lazily on the main thread.
+	#[cfg(feature = "jsonschema")]
+	let validator = (backend == ValidatorBackend::Jsonschema)
+		.then(|| options.build_jsonschema(&json_value))
+		.transpose()?
+		.map(|validator| Box::new(validator) as Box<dyn SchemaValidator>);
+
+	#[cfg(not(feature = "jsonschema"))]
+	let validator = {
+		let _ = (backend, options);
+		None
+	};
+
+	Ok(CompiledSchema { json_value, validator })
+}
+
+/// Runs [`compile_schema()`] for each of `jobs` on a pool of worker threads, sized after the available parallelism.
+///
+/// # Returns
+/// One receiver per job, in the same order, each receives exactly one result.
+#[cfg(feature = "threads")]
+pub fn compile_in_background(jobs: Vec<CompileJob>) -> Vec<Receiver<Result<CompiledSchema>>> {
+	use std::collections::VecDeque;
+	use std::sync::{mpsc, Arc, Mutex};
+
+	let mut receivers = Vec::with_capacity(jobs.len());
+	let mut queue = VecDeque::with_capacity(jobs.len());
+
+	for (json, backend, options) in jobs {
+		let (sender, receiver) = mpsc::channel();
+		receivers.push(receiver);
+		queue.push_back((json, backend, options, sender));
+	}
+
+	let worker_count = std::thread::available_parallelism()
+		.map_or(1, |count| count.get())
+		.min(queue.len());
+
+	let queue = Arc::new(Mutex::new(queue));
+
+	for _ in 0..worker_count {
+		let queue = Arc::clone(&queue);
+
+		std::thread::spawn(move || {
+			loop {
+				let Some((json, backend, options, sender)) = queue.lock().ok().and_then(|mut queue| queue.pop_front())
+				else { break };
+
+				// The receiver may have been dropped (e.g. the library was freed), nothing to report then.
+				let _ = sender.send(compile_schema(&json, backend, &options));
+			}
+		});
+	}
+
+	receivers
+}
+
+/// Without threads, jobs are compiled immediately, results are still delivered through the receivers.
+#[cfg(not(feature = "threads"))]
+pub fn compile_in_background(jobs: Vec<CompileJob>) -> Vec<Receiver<Result<CompiledSchema>>> {
+	jobs.into_iter()
+		.map(|(json, backend, options)| {
+			let (sender, receiver) = std::sync::mpsc::channel();
+			let _ = sender.send(compile_schema(&json, backend, &options));
+			receiver
+		})
+		.collect()
+}